@@ -27,14 +27,26 @@ CREATE TABLE files (
     source_url TEXT,
     summary TEXT,
     
-    -- Simplified three-level categories (supports "cat1/cat2/cat3" format)
+    -- Source file information recorded at import
+    original_path TEXT,
+    size INTEGER,
+    mime_type TEXT,
+    file_mtime TEXT,
+    
+    -- Categories of any depth: category_path holds the full path and is the source of
+    -- truth; category1/category2 and category3 (third and deeper levels joined with "/")
+    -- are derived from it by triggers
     category1 TEXT,
     category2 TEXT,
     category3 TEXT,
+    category_path TEXT,
     
     -- Full text content (for search)
     full_text TEXT,
     
+    -- Page-level full-text index status (pending/complete/truncated/failed)
+    page_index_status TEXT,
+    
     -- System fields
     created_at TEXT NOT NULL,
     updated_at TEXT NOT NULL,
@@ -48,6 +60,54 @@ CREATE TABLE files (
 
 -- Note: Categories table removed - now using simplified category1/category2/category3 fields
 
+-- category_path is the source of truth; derive the category columns from it
+CREATE TRIGGER IF NOT EXISTS files_category_columns_ai
+AFTER INSERT ON files WHEN new.category_path IS NOT NULL BEGIN
+    UPDATE files SET
+        category1 = CASE WHEN instr(new.category_path, '/') = 0 THEN new.category_path
+            ELSE substr(new.category_path, 1, instr(new.category_path, '/') - 1) END,
+        category2 = CASE WHEN instr(new.category_path, '/') = 0 THEN NULL
+            WHEN instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') = 0 THEN substr(new.category_path, instr(new.category_path, '/') + 1)
+            ELSE substr(substr(new.category_path, instr(new.category_path, '/') + 1), 1, instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') - 1) END,
+        category3 = CASE WHEN instr(new.category_path, '/') = 0 OR instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') = 0 THEN NULL
+            ELSE substr(substr(new.category_path, instr(new.category_path, '/') + 1), instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') + 1) END
+    WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS files_category_columns_au
+AFTER UPDATE OF category_path ON files WHEN new.category_path IS NOT NULL BEGIN
+    UPDATE files SET
+        category1 = CASE WHEN instr(new.category_path, '/') = 0 THEN new.category_path
+            ELSE substr(new.category_path, 1, instr(new.category_path, '/') - 1) END,
+        category2 = CASE WHEN instr(new.category_path, '/') = 0 THEN NULL
+            WHEN instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') = 0 THEN substr(new.category_path, instr(new.category_path, '/') + 1)
+            ELSE substr(substr(new.category_path, instr(new.category_path, '/') + 1), 1, instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') - 1) END,
+        category3 = CASE WHEN instr(new.category_path, '/') = 0 OR instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') = 0 THEN NULL
+            ELSE substr(substr(new.category_path, instr(new.category_path, '/') + 1), instr(substr(new.category_path, instr(new.category_path, '/') + 1), '/') + 1) END
+    WHERE rowid = new.rowid;
+END;
+
+-- Writers that only set the category columns get category_path rebuilt from them
+CREATE TRIGGER IF NOT EXISTS files_category_path_from_columns_ai
+AFTER INSERT ON files WHEN new.category_path IS NULL BEGIN
+    UPDATE files SET category_path = TRIM(COALESCE(new.category1, '')
+        || COALESCE('/' || NULLIF(new.category2, ''), '')
+        || COALESCE('/' || NULLIF(new.category3, ''), ''), '/')
+    WHERE rowid = new.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS files_category_path_from_columns_au
+AFTER UPDATE OF category1, category2, category3 ON files
+WHEN new.category_path IS old.category_path AND new.category_path IS NOT TRIM(COALESCE(new.category1, '')
+        || COALESCE('/' || NULLIF(new.category2, ''), '')
+        || COALESCE('/' || NULLIF(new.category3, ''), ''), '/')
+BEGIN
+    UPDATE files SET category_path = TRIM(COALESCE(new.category1, '')
+        || COALESCE('/' || NULLIF(new.category2, ''), '')
+        || COALESCE('/' || NULLIF(new.category3, ''), ''), '/')
+    WHERE rowid = new.rowid;
+END;
+
 -- Tags table with hierarchical support
 DROP TABLE IF EXISTS tags;
 CREATE TABLE tags (
@@ -69,6 +129,7 @@ CREATE TABLE authors (
     aliases TEXT,  -- JSON array
     bio TEXT,
     homepage TEXT,
+    orcid TEXT,  -- normalized ORCID iD
     created_at TEXT NOT NULL,
     updated_at TEXT NOT NULL,
     is_deleted INTEGER NOT NULL DEFAULT 0
@@ -82,7 +143,9 @@ CREATE TABLE authors (
 CREATE TABLE IF NOT EXISTS file_authors (
     file_id TEXT NOT NULL,
     author_id TEXT NOT NULL,
-    PRIMARY KEY (file_id, author_id),
+    role TEXT NOT NULL DEFAULT 'author',  -- author, editor, translator, illustrator
+    position INTEGER NOT NULL DEFAULT 0,  -- order within the role
+    PRIMARY KEY (file_id, author_id, role),
     FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE,
     FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
 );
@@ -97,6 +160,14 @@ CREATE TABLE IF NOT EXISTS file_tags (
     FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
 );
 
+-- Tag aliases (old tag paths resolving to an existing tag)
+CREATE TABLE IF NOT EXISTS tag_aliases (
+    alias TEXT PRIMARY KEY,  -- alias tag path
+    tag_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
 -- Author aliases for name normalization
 CREATE TABLE IF NOT EXISTS author_aliases (
     alias_id TEXT NOT NULL,  -- alias author ID
@@ -139,9 +210,7 @@ CREATE VIRTUAL TABLE IF NOT EXISTS files_fts USING fts5(
     authors,
     summary, 
     tags,
-    full_text,
-    content='files', 
-    content_rowid='rowid'
+    full_text
 );
 
 -- ========================================
@@ -169,7 +238,7 @@ CREATE TABLE IF NOT EXISTS system_config (
 CREATE TABLE IF NOT EXISTS file_history (
     id TEXT PRIMARY KEY,
     file_id TEXT NOT NULL,
-    operation TEXT NOT NULL,  -- create, update, move, delete, access
+    operation TEXT NOT NULL,  -- create, update, move, delete, access, patch
     old_hash TEXT,
     new_hash TEXT,
     old_path TEXT,
@@ -179,6 +248,30 @@ CREATE TABLE IF NOT EXISTS file_history (
     changed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
     changed_by TEXT,
     reason TEXT,
+    batch_id TEXT,  -- bulk edit batch, see edit_batches
+    FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+);
+
+-- Bulk edit batches, each can be undone as a whole
+CREATE TABLE IF NOT EXISTS edit_batches (
+    id TEXT PRIMARY KEY,
+    description TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    undone_at TEXT
+);
+
+-- Per-file state before and after a batch (tags as JSON arrays, paths relative to storage_dir)
+CREATE TABLE IF NOT EXISTS edit_batch_files (
+    batch_id TEXT NOT NULL,
+    file_id TEXT NOT NULL,
+    old_tags TEXT NOT NULL,
+    new_tags TEXT NOT NULL,
+    old_category TEXT NOT NULL,
+    new_category TEXT NOT NULL,
+    old_path TEXT NOT NULL,
+    new_path TEXT NOT NULL,
+    PRIMARY KEY (batch_id, file_id),
+    FOREIGN KEY (batch_id) REFERENCES edit_batches(id) ON DELETE CASCADE,
     FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
 );
 
@@ -200,6 +293,7 @@ CREATE TABLE IF NOT EXISTS file_access_stats (
 CREATE INDEX IF NOT EXISTS idx_files_category1 ON files(category1);
 CREATE INDEX IF NOT EXISTS idx_files_category2 ON files(category2);
 CREATE INDEX IF NOT EXISTS idx_files_category3 ON files(category3);
+CREATE INDEX IF NOT EXISTS idx_files_category_path ON files(category_path);
 CREATE INDEX IF NOT EXISTS idx_files_year ON files(year);
 CREATE INDEX IF NOT EXISTS idx_files_current_hash ON files(current_hash);
 CREATE INDEX IF NOT EXISTS idx_files_initial_hash ON files(initial_hash);
@@ -226,6 +320,7 @@ CREATE INDEX IF NOT EXISTS idx_file_tags_tag_id ON file_tags(tag_id);
 CREATE INDEX IF NOT EXISTS idx_file_history_file_id ON file_history(file_id);
 CREATE INDEX IF NOT EXISTS idx_file_history_changed_at ON file_history(changed_at);
 CREATE INDEX IF NOT EXISTS idx_file_history_operation ON file_history(operation);
+CREATE INDEX IF NOT EXISTS idx_file_history_batch_id ON file_history(batch_id);
 CREATE INDEX IF NOT EXISTS idx_file_access_stats_access_count ON file_access_stats(access_count);
 CREATE INDEX IF NOT EXISTS idx_file_access_log_file_id ON file_access_log(file_id);
 CREATE INDEX IF NOT EXISTS idx_file_access_log_accessed_at ON file_access_log(accessed_at);
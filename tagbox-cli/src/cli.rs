@@ -41,7 +41,7 @@ pub enum Commands {
         #[arg(long)]
         title: Option<String>,
 
-        /// Specify the authors (comma-separated)
+        /// Specify the authors (separated by commas, ';' or 'and')
         #[arg(long)]
         authors: Option<String>,
 
@@ -72,6 +72,14 @@ pub enum Commands {
         /// Interactive mode - prompt for metadata after extraction
         #[arg(short, long)]
         interactive: bool,
+
+        /// What to do when the file is already imported (skip, merge-tags, overwrite-empty, prompt)
+        #[arg(long, value_name = "POLICY")]
+        on_duplicate: Option<String>,
+
+        /// Also import every file inside ZIP / tar archives, linked to the archive as "contained_in"
+        #[arg(long)]
+        explode: bool,
     },
 
     /// Download and import a file from a URL
@@ -95,7 +103,7 @@ pub enum Commands {
         #[arg(long)]
         title: Option<String>,
 
-        /// Specify the authors (comma-separated)
+        /// Specify the authors (separated by commas, ';' or 'and')
         #[arg(long)]
         authors: Option<String>,
 
@@ -122,6 +130,24 @@ pub enum Commands {
         /// JSON file to set file attributes
         #[arg(long)]
         meta_file: Option<PathBuf>,
+
+        /// What to do when the file is already imported (skip, merge-tags, overwrite-empty, prompt)
+        #[arg(long, value_name = "POLICY")]
+        on_duplicate: Option<String>,
+    },
+
+    /// Import the entries of a BibTeX file, matching each entry to a file by its `file` field or title
+    ImportBib {
+        /// Path to the .bib file
+        bib: PathBuf,
+
+        /// Directory containing the referenced files (defaults to the .bib file's directory)
+        #[arg(long)]
+        files_dir: Option<PathBuf>,
+
+        /// What to do when the file is already imported (skip, merge-tags, overwrite-empty)
+        #[arg(long, value_name = "POLICY")]
+        on_duplicate: Option<String>,
     },
 
     /// Search files using DSL or free text
@@ -189,6 +215,10 @@ pub enum Commands {
         /// Print path to containing folder
         #[arg(long)]
         cd: bool,
+
+        /// Page to jump to when opening a PDF (see search page hits)
+        #[arg(long, requires = "open")]
+        page: Option<u32>,
     },
 
     /// Link two files as semantically related
@@ -233,6 +263,18 @@ pub enum Commands {
         command: Option<AuthorCommands>,
     },
 
+    /// Browse and manage hierarchical tags
+    Tag {
+        #[command(subcommand)]
+        command: Option<TagCommands>,
+    },
+
+    /// Rename, move or merge categories and relocate their files
+    Category {
+        #[command(subcommand)]
+        command: CategoryCommands,
+    },
+
     /// Edit runtime parameters
     Config {
         /// Print path to config file directory (for shell integration)
@@ -243,17 +285,32 @@ pub enum Commands {
         command: Option<ConfigCommands>,
     },
 
-    /// Dump files in JSON or CSV format
+    /// Dump files in JSON, CSV, BibTeX, RIS or CSL-JSON format
     Export {
-        /// Output as JSON
-        #[arg(long)]
+        /// Output as JSON (same as --format json)
+        #[arg(long, conflicts_with = "format")]
         json: bool,
 
+        /// Output format: csv, json, bibtex, ris or csl-json
+        #[arg(short, long, default_value = "csv")]
+        format: String,
+
         /// Output file path
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
 
+    /// Print formatted references for files
+    Cite {
+        /// File IDs
+        #[arg(required = true)]
+        ids: Vec<String>,
+
+        /// Citation style: apa, ieee or chicago
+        #[arg(short, long, default_value = "apa")]
+        style: String,
+    },
+
     /// Show tag usage, top authors, access heatmap
     Stats,
 
@@ -288,10 +345,11 @@ pub enum Commands {
         command: DbCommands,
     },
 
-    /// Edit file metadata
+    /// Edit file metadata, or patch every file matching a query with --query
     Edit {
         /// File ID to edit
-        id: String,
+        #[arg(required_unless_present_any = ["query", "undo"])]
+        id: Option<String>,
 
         /// Interactive mode - prompt for each field
         #[arg(short, long)]
@@ -305,10 +363,14 @@ pub enum Commands {
         #[arg(short, long)]
         title: Option<String>,
 
-        /// New authors (comma-separated)
+        /// New authors (separated by commas, ';' or 'and')
         #[arg(short, long)]
         authors: Option<String>,
 
+        /// Replace editors, translators and illustrators, as ROLE:NAMES (e.g. "editor:Donald Knuth"); repeatable
+        #[arg(long = "contributor", value_name = "ROLE:NAMES")]
+        contributors: Vec<String>,
+
         /// New category (e.g., "Tech/Programming/Rust")
         #[arg(long)]
         category: Option<String>,
@@ -332,6 +394,73 @@ pub enum Commands {
         /// New source
         #[arg(long)]
         source: Option<String>,
+
+        /// Edit every file matching a search query in one undoable batch
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "id", "interactive", "title", "authors", "contributors", "category", "tags",
+                "summary", "year", "publisher", "source",
+            ]
+        )]
+        query: Option<String>,
+
+        /// Tags to add to each matching file (comma-separated)
+        #[arg(long, requires = "query")]
+        add_tags: Option<String>,
+
+        /// Tags to remove from each matching file, with their child tags (comma-separated)
+        #[arg(long, requires = "query")]
+        remove_tags: Option<String>,
+
+        /// New category for each matching file (e.g., "Archive/2025")
+        #[arg(long, requires = "query")]
+        set_category: Option<String>,
+
+        /// Show the changes for each file without applying them
+        #[arg(long, requires = "query")]
+        dry_run: bool,
+
+        /// Undo a bulk edit by its batch ID
+        #[arg(long, conflicts_with_all = ["id", "query"])]
+        undo: Option<String>,
+    },
+
+    /// Extract PDF text page by page for full-text search
+    IndexPages {
+        /// Specific file ID to re-index (default: files queued during import)
+        id: Option<String>,
+
+        /// Re-index every PDF in the library
+        #[arg(long, conflicts_with = "id")]
+        all: bool,
+    },
+
+    /// Recognize text in images and scanned PDF pages (offline OCR)
+    #[cfg(feature = "ocr")]
+    Ocr {
+        /// Specific file ID to recognize
+        #[arg(required_unless_present = "all_missing")]
+        id: Option<String>,
+
+        /// Recognize every image and PDF that has no text and was never OCR'd
+        #[arg(long, conflicts_with = "id")]
+        all_missing: bool,
+    },
+
+    /// Fetch full metadata (authors, venue, abstract, date) from Crossref/arXiv
+    Enrich {
+        /// File ID to enrich
+        #[arg(required_unless_present = "query")]
+        id: Option<String>,
+
+        /// Enrich every file matching a search query
+        #[arg(long, conflicts_with = "id")]
+        query: Option<String>,
+
+        /// Apply changes without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Rebuild file storage paths according to current configuration
@@ -357,20 +486,225 @@ pub enum AuthorCommands {
         name: String,
     },
 
-    /// Remove an author
+    /// Remove an author that has no files
     Remove {
-        /// Author ID
+        /// Author ID or name
         id: String,
     },
 
     /// Merge authors
     Merge {
-        /// Source author ID
+        /// Source author ID or name
         from: String,
 
-        /// Target author ID
+        /// Target author ID or name
         to: String,
     },
+
+    /// List authors with file counts
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show an author's profile and aliases
+    Show {
+        /// Author ID or name
+        id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Edit an author's profile (pass an empty string to clear a field)
+    Edit {
+        /// Author ID or name
+        id: String,
+
+        /// New display name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Real name
+        #[arg(long)]
+        real_name: Option<String>,
+
+        /// Short biography
+        #[arg(long)]
+        bio: Option<String>,
+
+        /// Homepage URL
+        #[arg(long)]
+        homepage: Option<String>,
+
+        /// ORCID iD, e.g. 0000-0002-1825-0097
+        #[arg(long)]
+        orcid: Option<String>,
+    },
+
+    /// Manage author aliases
+    Alias {
+        #[command(subcommand)]
+        command: AuthorAliasCommands,
+    },
+
+    /// List files by an author, including files under its aliases
+    Files {
+        /// Author ID or name
+        id: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Columns to display (comma-separated)
+        #[arg(long)]
+        columns: Option<String>,
+    },
+
+    /// Find likely duplicate authors and merge them interactively
+    Dedupe,
+}
+
+#[derive(Subcommand)]
+pub enum AuthorAliasCommands {
+    /// Record another name as an alias of an author
+    Add {
+        /// Author ID or name
+        id: String,
+
+        /// Alias name or author ID
+        alias: String,
+    },
+
+    /// Remove an alias so it becomes a separate author again
+    Remove {
+        /// Author ID or name
+        id: String,
+
+        /// Alias name or author ID
+        alias: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Show the tag hierarchy with file counts (including child tags)
+    Tree {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List all tags with their file counts and aliases
+    List {
+        /// Only show tags without files
+        #[arg(long)]
+        unused: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Rename a tag across all files; child tags move with it
+    Rename {
+        /// Current tag path
+        old: String,
+
+        /// New tag path
+        new: String,
+    },
+
+    /// Merge a tag into another one and keep the old path as an alias
+    Merge {
+        /// Tag to merge away
+        from: String,
+
+        /// Tag to merge into
+        into: String,
+    },
+
+    /// Delete a tag and its child tags
+    Delete {
+        /// Tag path
+        tag: String,
+
+        /// Also remove the tag from files that still use it
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Delete all tags that no file uses
+    Prune,
+
+    /// Manage tag aliases
+    Alias {
+        #[command(subcommand)]
+        command: TagAliasCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CategoryCommands {
+    /// Rename a category; subcategories are renamed with it
+    Rename {
+        /// Current category path (e.g. Work/Old)
+        old: String,
+
+        /// New category path (e.g. Work/New)
+        new: String,
+
+        /// Actually update files (default: dry run)
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Move a category under another parent ("/" for top level)
+    Move {
+        /// Category path
+        path: String,
+
+        /// New parent category path
+        parent: String,
+
+        /// Actually update files (default: dry run)
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Merge a category into an existing one
+    Merge {
+        /// Category to merge away
+        from: String,
+
+        /// Category to merge into
+        into: String,
+
+        /// Actually update files (default: dry run)
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagAliasCommands {
+    /// Resolve another path to a tag on import and search
+    Add {
+        /// Tag path
+        tag: String,
+
+        /// Alias path
+        alias: String,
+    },
+
+    /// Remove a tag alias
+    Remove {
+        /// Alias path
+        alias: String,
+    },
 }
 
 #[derive(Subcommand)]
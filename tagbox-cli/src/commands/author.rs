@@ -1,13 +1,37 @@
-use crate::utils::error::Result;
+use crate::output::{json, table};
+use crate::utils::error::{CliError, Result};
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
+use tabled::{builder::Builder, settings::Style};
 use tagbox_core::config::AppConfig;
+use tagbox_core::types::{Author, AuthorUpdate};
 use tagbox_core::AuthorManager;
 
+async fn author_manager(config: &AppConfig) -> Result<AuthorManager> {
+    let db = tagbox_core::schema::Database::new(&config.database.path).await?;
+    Ok(AuthorManager::new(db.pool().clone()))
+}
+
+/// Look up an author by ID, then by name (following aliases to the canonical author)
+async fn find_author(manager: &AuthorManager, id_or_name: &str) -> Result<Author> {
+    if let Ok(author) = manager.get_author(id_or_name).await {
+        return Ok(author);
+    }
+
+    match manager.resolve_author(id_or_name).await? {
+        Some((id, _)) => Ok(manager.get_author(&id).await?),
+        None => Err(CliError::InvalidArgument(format!(
+            "Author not found: {}",
+            id_or_name
+        ))),
+    }
+}
+
 /// Handle author commands
 pub async fn handle_author_add(name: &str, config: &AppConfig) -> Result<()> {
     log::debug!("Adding author: {}", name);
 
-    let db = tagbox_core::schema::Database::new(&config.database.path).await?;
-    let author_manager = AuthorManager::new(db.pool().clone());
+    let author_manager = author_manager(config).await?;
 
     let author = author_manager.create_author(name, &[]).await?;
     println!("Author added with ID: {}", author.id);
@@ -19,14 +43,11 @@ pub async fn handle_author_add(name: &str, config: &AppConfig) -> Result<()> {
 pub async fn handle_author_remove(id: &str, config: &AppConfig) -> Result<()> {
     log::debug!("Removing author: {}", id);
 
-    let db = tagbox_core::schema::Database::new(&config.database.path).await?;
-    let _author_manager = AuthorManager::new(db.pool().clone());
-
-    // Get author details before removal for confirmation
-    // let author = author_manager.get_author(id).await?;
+    let author_manager = author_manager(config).await?;
+    let author = find_author(&author_manager, id).await?;
 
-    // TODO: Implement delete_author in core
-    println!("Author removal not yet implemented in core: {}", id);
+    author_manager.delete_author(&author.id).await?;
+    println!("Removed author '{}' ({})", author.name, author.id);
 
     Ok(())
 }
@@ -35,33 +56,279 @@ pub async fn handle_author_remove(id: &str, config: &AppConfig) -> Result<()> {
 pub async fn handle_author_merge(from_id: &str, to_id: &str, config: &AppConfig) -> Result<()> {
     log::debug!("Merging author {} into {}", from_id, to_id);
 
-    let db = tagbox_core::schema::Database::new(&config.database.path).await?;
-    let author_manager = AuthorManager::new(db.pool().clone());
+    let author_manager = author_manager(config).await?;
 
     // Get author details for confirmation
-    let from_author = author_manager.get_author(from_id).await?;
-    let to_author = author_manager.get_author(to_id).await?;
+    let from_author = find_author(&author_manager, from_id).await?;
+    let to_author = find_author(&author_manager, to_id).await?;
 
-    author_manager.merge_authors(from_id, to_id).await?;
+    author_manager
+        .merge_authors(&from_author.id, &to_author.id)
+        .await?;
 
     println!(
         "Successfully merged '{}' ({}) into '{}' ({})",
-        from_author.name, from_id, to_author.name, to_id
+        from_author.name, from_author.id, to_author.name, to_author.id
     );
 
     Ok(())
 }
 
 /// List all authors
-pub async fn handle_author_list(config: &AppConfig) -> Result<()> {
+pub async fn handle_author_list(json_output: bool, config: &AppConfig) -> Result<()> {
     log::info!("Listing all authors");
 
-    let db = tagbox_core::schema::Database::new(&config.database.path).await?;
-    let _author_manager = AuthorManager::new(db.pool().clone());
+    let author_manager = author_manager(config).await?;
+    let authors = author_manager.list_authors().await?;
+
+    if json_output {
+        return json::print_json(&authors);
+    }
+
+    if authors.is_empty() {
+        println!("No authors found.");
+        return Ok(());
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record(["ID", "Name", "Files", "Aliases", "ORCID"]);
+    for author in &authors {
+        builder.push_record([
+            author.id.clone(),
+            author.name.clone(),
+            author.file_count.to_string(),
+            author.alias_count.to_string(),
+            author.orcid.clone().unwrap_or_default(),
+        ]);
+    }
 
-    // TODO: Implement list_authors in core
-    println!("Author listing not yet implemented in core.");
-    println!("Use SQL query directly: SELECT * FROM authors;");
+    println!("{}", builder.build().with(Style::rounded()));
+    println!("{} authors", authors.len());
 
     Ok(())
 }
+
+/// Show an author's profile
+pub async fn handle_author_show(id: &str, json_output: bool, config: &AppConfig) -> Result<()> {
+    let author_manager = author_manager(config).await?;
+    let author = find_author(&author_manager, id).await?;
+
+    if json_output {
+        return json::print_json(&author);
+    }
+
+    let file_count = author_manager.get_author_files(&author.id).await?.len();
+
+    let mut builder = Builder::default();
+    builder.push_record(["Property", "Value"]);
+    builder.push_record(["ID".to_string(), author.id.clone()]);
+    builder.push_record(["Name".to_string(), author.name.clone()]);
+    for (label, value) in [
+        ("Real name", &author.real_name),
+        ("ORCID", &author.orcid),
+        ("Homepage", &author.homepage),
+        ("Bio", &author.bio),
+    ] {
+        if let Some(value) = value {
+            builder.push_record([label.to_string(), value.clone()]);
+        }
+    }
+    if !author.aliases.is_empty() {
+        builder.push_record(["Aliases".to_string(), author.aliases.join(", ")]);
+    }
+    builder.push_record(["Files".to_string(), file_count.to_string()]);
+
+    println!("{}", builder.build().with(Style::rounded()));
+
+    Ok(())
+}
+
+/// Edit an author's profile
+pub async fn handle_author_edit(id: &str, update: AuthorUpdate, config: &AppConfig) -> Result<()> {
+    let author_manager = author_manager(config).await?;
+    let author = find_author(&author_manager, id).await?;
+
+    if update.name.is_none()
+        && update.real_name.is_none()
+        && update.bio.is_none()
+        && update.homepage.is_none()
+        && update.orcid.is_none()
+    {
+        return Err(CliError::InvalidArgument(
+            "Nothing to update, pass at least one of --name, --real-name, --bio, --homepage, --orcid"
+                .to_string(),
+        ));
+    }
+
+    let updated = author_manager.update_author(&author.id, update).await?;
+    println!("Updated author '{}' ({})", updated.name, updated.id);
+
+    Ok(())
+}
+
+/// Record a name (or another author) as an alias of an author
+pub async fn handle_author_alias_add(id: &str, alias: &str, config: &AppConfig) -> Result<()> {
+    let author_manager = author_manager(config).await?;
+    let author = find_author(&author_manager, id).await?;
+
+    let alias_id = match author_manager.get_author(alias).await {
+        Ok(existing) => existing.id,
+        Err(_) => match author_manager.find_author_by_name(alias).await? {
+            Some((alias_id, _)) => alias_id,
+            None => author_manager.find_or_create_author(alias).await?.0,
+        },
+    };
+    if alias_id == author.id {
+        println!("'{}' already resolves to '{}'", alias, author.name);
+        return Ok(());
+    }
+
+    author_manager
+        .add_author_alias(&author.id, &alias_id)
+        .await?;
+    println!(
+        "Added alias '{}' to '{}' ({})",
+        alias, author.name, author.id
+    );
+
+    Ok(())
+}
+
+/// Remove an alias from an author
+pub async fn handle_author_alias_remove(id: &str, alias: &str, config: &AppConfig) -> Result<()> {
+    let author_manager = author_manager(config).await?;
+    let author = find_author(&author_manager, id).await?;
+
+    let alias_id = match author_manager.get_author(alias).await {
+        Ok(existing) => existing.id,
+        Err(_) => author_manager
+            .find_author_by_name(alias)
+            .await?
+            .map(|(alias_id, _)| alias_id)
+            .ok_or_else(|| CliError::InvalidArgument(format!("Author not found: {}", alias)))?,
+    };
+
+    author_manager
+        .remove_author_alias(&author.id, &alias_id)
+        .await?;
+    println!(
+        "Removed alias '{}' from '{}' ({})",
+        alias, author.name, author.id
+    );
+
+    Ok(())
+}
+
+/// List files by an author
+pub async fn handle_author_files(
+    id: &str,
+    json_output: bool,
+    columns: Option<String>,
+    config: &AppConfig,
+) -> Result<()> {
+    let author_manager = author_manager(config).await?;
+    let author = find_author(&author_manager, id).await?;
+
+    let mut entries = Vec::new();
+    for file_id in author_manager.get_author_files(&author.id).await? {
+        entries.push(tagbox_core::get_file(&file_id, config).await?);
+    }
+
+    if json_output {
+        json::print_json(&entries)?;
+    } else {
+        println!("{} files by '{}'", entries.len(), author.name);
+        table::print_file_table(&entries, columns.as_deref())?;
+    }
+
+    Ok(())
+}
+
+/// Walk through likely duplicate authors and merge the ones the user confirms
+pub async fn handle_author_dedupe(config: &AppConfig) -> Result<()> {
+    let author_manager = author_manager(config).await?;
+    let candidates = author_manager.find_duplicate_authors().await?;
+
+    if candidates.is_empty() {
+        println!("No duplicate authors found.");
+        return Ok(());
+    }
+
+    // Never read answers from a pipe (e.g. stdio mode), only list candidates
+    let interactive = io::stdin().is_terminal();
+    let mut merged: HashSet<String> = HashSet::new();
+    let mut merge_count = 0;
+
+    for (first_id, second_id, similarity) in candidates {
+        // An earlier merge in this session may have consumed one side
+        if merged.contains(&first_id) || merged.contains(&second_id) {
+            continue;
+        }
+
+        let first = author_manager.get_author(&first_id).await?;
+        let second = author_manager.get_author(&second_id).await?;
+        let first_files = author_manager.get_author_files(&first_id).await?.len();
+        let second_files = author_manager.get_author_files(&second_id).await?.len();
+
+        println!();
+        println!("Possible duplicate (similarity {:.2}):", similarity);
+        println!("  [1] {} ({} files) {}", first.name, first_files, first.id);
+        println!(
+            "  [2] {} ({} files) {}",
+            second.name, second_files, second.id
+        );
+
+        if !interactive {
+            continue;
+        }
+
+        let (keep, drop) = match prompt_keep_choice()? {
+            KeepChoice::First => (&first, &second),
+            KeepChoice::Second => (&second, &first),
+            KeepChoice::Skip => continue,
+            KeepChoice::Quit => break,
+        };
+
+        author_manager.merge_authors(&drop.id, &keep.id).await?;
+        merged.insert(drop.id.clone());
+        merge_count += 1;
+        println!("Merged '{}' into '{}'", drop.name, keep.name);
+    }
+
+    println!();
+    if interactive {
+        println!("Merged {} authors", merge_count);
+    } else {
+        println!(
+            "Run `tagbox author dedupe` in a terminal or use `tagbox author merge` to merge them."
+        );
+    }
+
+    Ok(())
+}
+
+enum KeepChoice {
+    First,
+    Second,
+    Skip,
+    Quit,
+}
+
+/// Ask which author of a duplicate pair to keep
+fn prompt_keep_choice() -> Result<KeepChoice> {
+    loop {
+        print!("Keep [1], keep [2], [s]kip or [q]uit (1/2/S/q): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        match input.trim().to_lowercase().as_str() {
+            "1" => return Ok(KeepChoice::First),
+            "2" => return Ok(KeepChoice::Second),
+            "" | "s" | "skip" => return Ok(KeepChoice::Skip),
+            "q" | "quit" => return Ok(KeepChoice::Quit),
+            _ => println!("Please enter '1', '2', 's' or 'q'"),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::utils::error::Result;
+use tagbox_core::config::AppConfig;
+use tagbox_core::types::CategoryMove;
+
+/// Rename a category and relocate its files
+pub async fn handle_category_rename(
+    old: &str,
+    new: &str,
+    apply: bool,
+    config: &AppConfig,
+) -> Result<()> {
+    log::debug!("Renaming category {} to {}", old, new);
+
+    print_mode(apply);
+    let moves = tagbox_core::rename_category(old, new, !apply, config).await?;
+    print_moves(&moves, apply);
+
+    Ok(())
+}
+
+/// Move a category under another parent and relocate its files
+pub async fn handle_category_move(
+    path: &str,
+    parent: &str,
+    apply: bool,
+    config: &AppConfig,
+) -> Result<()> {
+    log::debug!("Moving category {} under {}", path, parent);
+
+    print_mode(apply);
+    let moves = tagbox_core::move_category(path, parent, !apply, config).await?;
+    print_moves(&moves, apply);
+
+    Ok(())
+}
+
+/// Merge a category into another and relocate its files
+pub async fn handle_category_merge(
+    from: &str,
+    into: &str,
+    apply: bool,
+    config: &AppConfig,
+) -> Result<()> {
+    log::debug!("Merging category {} into {}", from, into);
+
+    print_mode(apply);
+    let moves = tagbox_core::merge_category(from, into, !apply, config).await?;
+    print_moves(&moves, apply);
+
+    Ok(())
+}
+
+fn print_mode(apply: bool) {
+    if apply {
+        println!("⚠️  APPLY MODE - Categories will be updated and files moved!");
+    } else {
+        println!("🔍 DRY RUN MODE - Nothing will be changed (use --apply to update files)");
+    }
+    println!();
+}
+
+/// Print per-file category and path changes followed by a summary
+fn print_moves(moves: &[CategoryMove], apply: bool) {
+    for change in moves {
+        println!("  [{}] {}", change.file_id, change.title);
+        println!(
+            "    Category: {} → {}",
+            change.old_category, change.new_category
+        );
+        if change.old_path != change.new_path {
+            println!("    From: {}", change.old_path.display());
+            println!("    To:   {}", change.new_path.display());
+        }
+        if apply && !change.moved {
+            println!("    ✗ File could not be moved, run `tagbox rebuild` to retry");
+        }
+        println!();
+    }
+
+    let relocated = moves.iter().filter(|m| m.old_path != m.new_path).count();
+    println!("Summary:");
+    println!("  Files in category: {}", moves.len());
+    if apply {
+        let failed = moves.iter().filter(|m| !m.moved).count();
+        println!("  Files moved: {}", relocated.saturating_sub(failed));
+        if failed > 0 {
+            println!("  Files that failed to move: {}", failed);
+        }
+    } else {
+        println!("  Files that would be moved: {}", relocated);
+        println!();
+        println!("Use --apply to update these files");
+    }
+}
@@ -0,0 +1,26 @@
+use crate::utils::error::{CliError, Result};
+use tagbox_core::bibliography::CitationStyle;
+use tagbox_core::config::AppConfig;
+
+/// Handle cite command
+pub async fn handle_cite(ids: &[String], style: &str, config: &AppConfig) -> Result<()> {
+    let style = style.parse::<CitationStyle>().map_err(|_| {
+        CliError::InvalidArgument(format!(
+            "Unknown citation style: {} (expected apa, ieee or chicago)",
+            style
+        ))
+    })?;
+
+    let citations = tagbox_core::format_citations(ids, style, config).await?;
+
+    for (index, citation) in citations.iter().enumerate() {
+        // IEEE references are numbered in citation order
+        if style == CitationStyle::Ieee {
+            println!("[{}] {}", index + 1, citation);
+        } else {
+            println!("{}", citation);
+        }
+    }
+
+    Ok(())
+}
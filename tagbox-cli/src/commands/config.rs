@@ -51,6 +51,19 @@ fn get_config_value(config: &AppConfig, key: &str) -> Result<String> {
         ["import", "metadata", "default_category"] => {
             Ok(config.import.metadata.default_category.to_string())
         }
+        ["import", "metadata", "on_duplicate"] => {
+            Ok(config.import.metadata.on_duplicate.to_string())
+        }
+        ["import", "metadata", "index_pages"] => Ok(config.import.metadata.index_pages.to_string()),
+        ["import", "metadata", "page_text_budget"] => {
+            Ok(config.import.metadata.page_text_budget.to_string())
+        }
+        ["import", "metadata", "background_extract_size"] => {
+            Ok(config.import.metadata.background_extract_size.to_string())
+        }
+        ["import", "metadata", "explode_archives"] => {
+            Ok(config.import.metadata.explode_archives.to_string())
+        }
 
         ["search", "default_limit"] => Ok(config.search.default_limit.to_string()),
         ["search", "enable_fts"] => Ok(config.search.enable_fts.to_string()),
@@ -59,6 +72,26 @@ fn get_config_value(config: &AppConfig, key: &str) -> Result<String> {
         ["hash", "algorithm"] => Ok(config.hash.algorithm.to_string()),
         ["hash", "verify_on_import"] => Ok(config.hash.verify_on_import.to_string()),
 
+        ["ocr", "on_import"] => Ok(config.ocr.on_import.to_string()),
+        ["ocr", "detection_model"] => Ok(config.ocr.detection_model.to_string_lossy().to_string()),
+        ["ocr", "recognition_model"] => {
+            Ok(config.ocr.recognition_model.to_string_lossy().to_string())
+        }
+        ["ocr", "max_pages"] => Ok(config.ocr.max_pages.to_string()),
+
+        ["enrich", "providers"] => Ok(config.enrich.providers.join(",")),
+        ["enrich", "crossref_url"] => Ok(config.enrich.crossref_url.to_string()),
+        ["enrich", "arxiv_url"] => Ok(config.enrich.arxiv_url.to_string()),
+        ["enrich", "mailto"] => Ok(config.enrich.mailto.clone().unwrap_or_default()),
+        ["enrich", "timeout_secs"] => Ok(config.enrich.timeout_secs.to_string()),
+        ["enrich", "cache_days"] => Ok(config.enrich.cache_days.to_string()),
+        ["enrich", "fixture_dir"] => Ok(config
+            .enrich
+            .fixture_dir
+            .as_ref()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default()),
+
         _ => {
             // Try to find partial matches and suggest
             let available_keys = get_available_config_keys();
@@ -93,11 +126,27 @@ fn get_available_config_keys() -> Vec<String> {
         "import.metadata.prefer_json".to_string(),
         "import.metadata.fallback_pdf".to_string(),
         "import.metadata.default_category".to_string(),
+        "import.metadata.on_duplicate".to_string(),
+        "import.metadata.index_pages".to_string(),
+        "import.metadata.page_text_budget".to_string(),
+        "import.metadata.background_extract_size".to_string(),
+        "import.metadata.explode_archives".to_string(),
         "search.default_limit".to_string(),
         "search.enable_fts".to_string(),
         "search.fts_language".to_string(),
         "hash.algorithm".to_string(),
         "hash.verify_on_import".to_string(),
+        "ocr.on_import".to_string(),
+        "ocr.detection_model".to_string(),
+        "ocr.recognition_model".to_string(),
+        "ocr.max_pages".to_string(),
+        "enrich.providers".to_string(),
+        "enrich.crossref_url".to_string(),
+        "enrich.arxiv_url".to_string(),
+        "enrich.mailto".to_string(),
+        "enrich.timeout_secs".to_string(),
+        "enrich.cache_days".to_string(),
+        "enrich.fixture_dir".to_string(),
     ]
 }
 
@@ -148,6 +197,20 @@ pub async fn handle_config_list(config: &AppConfig) -> Result<()> {
         "default_category = {}",
         config.import.metadata.default_category
     );
+    println!("on_duplicate = {}", config.import.metadata.on_duplicate);
+    println!("index_pages = {}", config.import.metadata.index_pages);
+    println!(
+        "page_text_budget = {}",
+        config.import.metadata.page_text_budget
+    );
+    println!(
+        "background_extract_size = {}",
+        config.import.metadata.background_extract_size
+    );
+    println!(
+        "explode_archives = {}",
+        config.import.metadata.explode_archives
+    );
     println!();
 
     println!("[search]");
@@ -159,6 +222,30 @@ pub async fn handle_config_list(config: &AppConfig) -> Result<()> {
     println!("[hash]");
     println!("algorithm = {}", config.hash.algorithm);
     println!("verify_on_import = {}", config.hash.verify_on_import);
+    println!();
+
+    println!("[ocr]");
+    println!("on_import = {}", config.ocr.on_import);
+    println!("detection_model = {}", config.ocr.detection_model.display());
+    println!(
+        "recognition_model = {}",
+        config.ocr.recognition_model.display()
+    );
+    println!("max_pages = {}", config.ocr.max_pages);
+    println!();
+
+    println!("[enrich]");
+    println!("providers = {:?}", config.enrich.providers);
+    println!("crossref_url = {}", config.enrich.crossref_url);
+    println!("arxiv_url = {}", config.enrich.arxiv_url);
+    if let Some(mailto) = &config.enrich.mailto {
+        println!("mailto = {}", mailto);
+    }
+    println!("timeout_secs = {}", config.enrich.timeout_secs);
+    println!("cache_days = {}", config.enrich.cache_days);
+    if let Some(fixture_dir) = &config.enrich.fixture_dir {
+        println!("fixture_dir = {}", fixture_dir.display());
+    }
 
     Ok(())
 }
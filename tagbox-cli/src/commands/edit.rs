@@ -1,7 +1,12 @@
 use std::io::{self, Write};
 use tagbox_core::{
-    config::AppConfig, errors::Result, schema::Database, types::FileUpdateRequest,
-    utils::parse_category_string, Editor,
+    config::AppConfig,
+    errors::{Result, TagboxError},
+    schema::Database,
+    split_author_list,
+    types::{AuthorRole, Contributor, EditBatch, FilePatch, FileUpdateRequest},
+    utils::parse_category_string,
+    Editor,
 };
 
 /// Handle file edit command
@@ -11,6 +16,7 @@ pub async fn handle_edit(
     mv: bool,
     title: Option<String>,
     authors: Option<String>,
+    contributors: Vec<String>,
     category: Option<String>,
     tags: Option<String>,
     summary: Option<String>,
@@ -33,7 +39,15 @@ pub async fn handle_edit(
         interactive_edit(&current_file).await?
     } else {
         build_update_request(
-            title, authors, category, tags, summary, year, publisher, source,
+            title,
+            authors,
+            contributors,
+            category,
+            tags,
+            summary,
+            year,
+            publisher,
+            source,
         )?
     };
 
@@ -66,6 +80,115 @@ pub async fn handle_edit(
     Ok(())
 }
 
+/// Apply a tag/category patch to every file matching a query
+pub async fn handle_bulk_edit(
+    query: &str,
+    add_tags: Option<String>,
+    remove_tags: Option<String>,
+    set_category: Option<String>,
+    mv: bool,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Result<()> {
+    log::debug!("Bulk editing files matching {}", query);
+
+    let patch = FilePatch {
+        add_tags: split_tags(add_tags.as_deref()),
+        remove_tags: split_tags(remove_tags.as_deref()),
+        set_category,
+        move_files: mv,
+    };
+    if patch.is_empty() {
+        return Err(TagboxError::Config(
+            "Nothing to change, use --add-tags, --remove-tags or --set-category".to_string(),
+        ));
+    }
+
+    if dry_run {
+        println!("🔍 DRY RUN MODE - Nothing will be changed (remove --dry-run to apply)");
+    } else {
+        println!("⚠️  APPLY MODE - Matching files will be updated!");
+    }
+    println!();
+
+    let batch = tagbox_core::bulk_edit(query, &patch, dry_run, config).await?;
+    print_batch(&batch, !dry_run);
+
+    Ok(())
+}
+
+/// Undo a bulk edit, restoring tags, categories and file locations
+pub async fn handle_edit_undo(batch_id: &str, config: &AppConfig) -> Result<()> {
+    log::debug!("Undoing edit batch {}", batch_id);
+
+    let batch = tagbox_core::undo_edit_batch(batch_id, config).await?;
+    print_batch(&batch, true);
+
+    Ok(())
+}
+
+/// Print the per-file changes of a bulk edit followed by a summary
+fn print_batch(batch: &EditBatch, applied: bool) {
+    for change in &batch.changes {
+        println!("  [{}] {}", change.file_id, change.title);
+
+        let mut tag_changes: Vec<String> = change
+            .new_tags
+            .iter()
+            .filter(|tag| !change.old_tags.contains(tag))
+            .map(|tag| format!("+{}", tag))
+            .collect();
+        tag_changes.extend(
+            change
+                .old_tags
+                .iter()
+                .filter(|tag| !change.new_tags.contains(tag))
+                .map(|tag| format!("-{}", tag)),
+        );
+        if !tag_changes.is_empty() {
+            println!("    Tags: {}", tag_changes.join(" "));
+        }
+        if change.old_category != change.new_category {
+            println!(
+                "    Category: {} → {}",
+                change.old_category, change.new_category
+            );
+        }
+        if change.old_path != change.new_path {
+            println!("    From: {}", change.old_path.display());
+            println!("    To:   {}", change.new_path.display());
+        }
+        if applied && change.old_path != change.new_path && !change.moved {
+            println!("    ✗ File could not be moved, run `tagbox rebuild` to retry");
+        }
+        println!();
+    }
+
+    println!("Summary:");
+    println!("  Files matched: {}", batch.matched);
+    if applied {
+        println!("  Files changed: {}", batch.changes.len());
+        if let Some(batch_id) = &batch.batch_id {
+            println!("  Batch: {}", batch_id);
+            println!();
+            println!("Undo with `tagbox edit --undo {}`", batch_id);
+        }
+    } else {
+        println!("  Files that would change: {}", batch.changes.len());
+    }
+}
+
+/// Split a comma-separated tag list
+fn split_tags(tags: Option<&str>) -> Vec<String> {
+    tags.map(|tags| {
+        tags.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
 /// Interactive edit mode
 async fn interactive_edit(
     current_file: &tagbox_core::types::FileEntry,
@@ -76,6 +199,7 @@ async fn interactive_edit(
     let mut update = FileUpdateRequest {
         title: None,
         authors: None,
+        contributors: None,
         year: None,
         publisher: None,
         source: None,
@@ -99,11 +223,7 @@ async fn interactive_edit(
     // Authors
     println!("Authors (current: {:?}): ", current_file.authors);
     if let Some(authors_str) = prompt_optional_string()? {
-        let authors: Vec<String> = authors_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let authors = split_author_list(&authors_str);
         if !authors.is_empty() {
             update.authors = Some(authors);
         }
@@ -176,6 +296,7 @@ async fn interactive_edit(
 fn build_update_request(
     title: Option<String>,
     authors: Option<String>,
+    contributors: Vec<String>,
     category: Option<String>,
     tags: Option<String>,
     summary: Option<String>,
@@ -186,6 +307,7 @@ fn build_update_request(
     let mut update = FileUpdateRequest {
         title,
         authors: None,
+        contributors: None,
         year,
         publisher,
         source,
@@ -202,16 +324,35 @@ fn build_update_request(
 
     // Parse authors
     if let Some(authors_str) = authors {
-        let authors_vec: Vec<String> = authors_str
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let authors_vec = split_author_list(&authors_str);
         if !authors_vec.is_empty() {
             update.authors = Some(authors_vec);
         }
     }
 
+    // Parse contributors: each "role:names" entry, all given roles replace the current ones
+    if !contributors.is_empty() {
+        let mut parsed = Vec::new();
+        for entry in &contributors {
+            let (role, names) = entry
+                .split_once(':')
+                .and_then(|(role, names)| {
+                    let role = AuthorRole::from_name(role.trim())?;
+                    (role != AuthorRole::Author).then_some((role, names))
+                })
+                .ok_or_else(|| {
+                    TagboxError::Config(format!(
+                        "Invalid contributor '{}', expected ROLE:NAMES with role editor, translator or illustrator",
+                        entry
+                    ))
+                })?;
+            for name in split_author_list(names) {
+                parsed.push(Contributor::new(name, role));
+            }
+        }
+        update.contributors = Some(parsed);
+    }
+
     // Parse category
     if let Some(category_str) = category {
         let (cat1, cat2, cat3) = parse_category_string(&category_str)?;
@@ -252,7 +393,7 @@ fn prompt_optional_string() -> io::Result<Option<String>> {
 }
 
 /// Prompt for yes/no confirmation
-fn prompt_confirm(message: &str) -> io::Result<bool> {
+pub(crate) fn prompt_confirm(message: &str) -> io::Result<bool> {
     loop {
         print!("{} (y/N): ", message);
         io::stdout().flush()?;
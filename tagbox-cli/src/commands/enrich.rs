@@ -0,0 +1,96 @@
+use crate::commands::edit::prompt_confirm;
+use crate::output::progress::create_spinner;
+use crate::utils::error::{CliError, Result};
+use tagbox_core::config::AppConfig;
+use tagbox_core::schema::Database;
+use tagbox_core::types::SearchOptions;
+use tagbox_core::Enricher;
+
+/// Handle enrich command
+pub async fn handle_enrich(
+    id: Option<String>,
+    query: Option<String>,
+    yes: bool,
+    config: &AppConfig,
+) -> Result<()> {
+    let file_ids = match (id, query) {
+        (Some(id), _) => vec![id],
+        (None, Some(query)) => {
+            let search_options = Some(SearchOptions {
+                offset: 0,
+                limit: 1000000,
+                sort_by: Some("created_at".to_string()),
+                sort_direction: Some("asc".to_string()),
+                include_deleted: false,
+            });
+            let result = tagbox_core::search_files_advanced(&query, search_options, config).await?;
+            result.entries.into_iter().map(|entry| entry.id).collect()
+        }
+        (None, None) => {
+            return Err(CliError::InvalidArgument(
+                "Provide a file ID or --query".to_string(),
+            ))
+        }
+    };
+
+    if file_ids.is_empty() {
+        println!("No files match the query");
+        return Ok(());
+    }
+
+    let db = Database::new(&config.database.path).await?;
+    let enricher = Enricher::from_config(db.pool().clone(), &config.enrich)?;
+    let mut applied = 0;
+
+    for file_id in &file_ids {
+        let spinner = create_spinner(&format!("Looking up metadata for {}", file_id));
+        let result = enricher.propose(file_id).await;
+        spinner.finish_and_clear();
+
+        let proposal = match result {
+            Ok(Some(proposal)) => proposal,
+            Ok(None) => {
+                println!("No metadata found for {}", file_id);
+                continue;
+            }
+            // 批量时单个文件失败不影响其余文件
+            Err(e) if file_ids.len() > 1 => {
+                eprintln!("✗ {}: {}", file_id, e);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let changes = enricher.preview(&proposal);
+        if changes.is_empty() {
+            println!(
+                "\"{}\" is already up to date ({})",
+                proposal.entry.title, proposal.provider
+            );
+            continue;
+        }
+
+        println!(
+            "Proposed changes for \"{}\" ({}) from {}:",
+            proposal.entry.title, file_id, proposal.provider
+        );
+        for change in &changes {
+            println!("  - {}", change);
+        }
+        println!();
+
+        if yes || prompt_confirm("Apply these changes?")? {
+            enricher.apply(&proposal).await?;
+            println!("✓ File updated successfully");
+            applied += 1;
+        } else {
+            println!("Changes skipped");
+        }
+    }
+
+    if file_ids.len() > 1 {
+        println!("Enriched {} of {} file(s)", applied, file_ids.len());
+    }
+
+    Ok(())
+}
@@ -1,16 +1,17 @@
 use crate::output::json;
-use crate::utils::error::Result;
+use crate::utils::error::{CliError, Result};
 use std::path::Path;
+use tagbox_core::bibliography::BibFormat;
 use tagbox_core::config::AppConfig;
 use tagbox_core::types::{FileEntry, SearchOptions};
 
 /// Handle export command
 pub async fn handle_export(
-    json_output: bool,
+    format: &str,
     output_file: Option<&Path>,
     config: &AppConfig,
 ) -> Result<()> {
-    log::info!("Exporting all files");
+    log::info!("Exporting all files as {}", format);
 
     // Get all files (with a large limit)
     let search_options = Some(SearchOptions {
@@ -23,36 +24,35 @@ pub async fn handle_export(
 
     let result = tagbox_core::search_files_advanced("*", search_options, config).await?;
 
-    if json_output {
-        let output = if let Some(_path) = output_file {
+    let content = match format.to_ascii_lowercase().as_str() {
+        "json" => {
+            if output_file.is_none() {
+                json::print_json(&result.entries)?;
+                return Ok(());
+            }
             json::to_json_string(&result.entries)?
-        } else {
-            json::print_json(&result.entries)?;
-            return Ok(());
-        };
-
-        if let Some(path) = output_file {
-            std::fs::write(path, output)?;
-            println!(
-                "Exported {} files to {}",
-                result.entries.len(),
-                path.display()
-            );
         }
-    } else {
-        // CSV format
-        let csv_content = generate_csv(&result.entries)?;
-
-        if let Some(path) = output_file {
-            std::fs::write(path, csv_content)?;
-            println!(
-                "Exported {} files to {}",
-                result.entries.len(),
-                path.display()
-            );
-        } else {
-            print!("{}", csv_content);
+        "csv" => generate_csv(&result.entries)?,
+        other => {
+            let bib_format = other.parse::<BibFormat>().map_err(|_| {
+                CliError::InvalidArgument(format!(
+                    "Unknown export format: {} (expected csv, json, bibtex, ris or csl-json)",
+                    format
+                ))
+            })?;
+            tagbox_core::export_bibliography(&result.entries, bib_format, config).await?
         }
+    };
+
+    if let Some(path) = output_file {
+        std::fs::write(path, content)?;
+        println!(
+            "Exported {} files to {}",
+            result.entries.len(),
+            path.display()
+        );
+    } else {
+        print!("{}", content);
     }
 
     Ok(())
@@ -63,12 +63,12 @@ fn generate_csv(entries: &[FileEntry]) -> Result<String> {
     let mut csv = String::new();
 
     // CSV header
-    csv.push_str("id,title,authors,year,publisher,source,category1,category2,category3,tags,path,original_filename,hash,created_at,updated_at,is_deleted\n");
+    csv.push_str("id,title,authors,year,publisher,source,category1,category2,category3,tags,path,original_filename,hash,created_at,updated_at,is_deleted,size,mime_type,original_path\n");
 
     // CSV rows
     for entry in entries {
         csv.push_str(&format!(
-            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
             escape_csv_field(&entry.id),
             escape_csv_field(&entry.title),
             escape_csv_field(&entry.authors.join("; ")),
@@ -84,7 +84,16 @@ fn generate_csv(entries: &[FileEntry]) -> Result<String> {
             escape_csv_field(&entry.hash),
             entry.created_at.format("%Y-%m-%d %H:%M:%S"),
             entry.updated_at.format("%Y-%m-%d %H:%M:%S"),
-            entry.is_deleted
+            entry.is_deleted,
+            entry.size.map_or_else(String::new, |s| s.to_string()),
+            escape_csv_field(&entry.mime_type.clone().unwrap_or_default()),
+            escape_csv_field(
+                &entry
+                    .original_path
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            )
         ));
     }
 
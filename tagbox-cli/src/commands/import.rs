@@ -1,8 +1,11 @@
 use crate::output::progress::{create_progress_bar, create_spinner};
 use crate::utils::error::{CliError, Result};
+use indicatif::ProgressBar;
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use tagbox_core::config::AppConfig;
-use tagbox_core::types::{FileEntry, ImportMetadata};
+use tagbox_core::config::{AppConfig, DuplicatePolicy};
+use tagbox_core::split_author_list;
+use tagbox_core::types::{ImportMetadata, ImportOutcome, ImportResult};
 use url::Url;
 
 /// Handle file import command
@@ -19,6 +22,8 @@ pub async fn handle_import(
     summary: Option<String>,
     meta_file: Option<PathBuf>,
     interactive: bool,
+    on_duplicate: Option<String>,
+    explode: bool,
     config: &AppConfig,
 ) -> Result<()> {
     log::info!("Starting import of: {}", path.display());
@@ -27,33 +32,184 @@ pub async fn handle_import(
         return Err(CliError::FileNotFound(path.to_string_lossy().to_string()));
     }
 
-    let entries = if path.is_file() {
+    let policy = resolve_duplicate_policy(on_duplicate, config)?;
+
+    let results = if path.is_file() {
         import_single_file(
             path, delete, category, title, authors, year, publisher, source, tags, summary,
-            meta_file, config,
+            meta_file, policy, config,
         )
         .await?
     } else {
         import_directory(
             path, delete, category, title, authors, year, publisher, source, tags, summary,
-            meta_file, config,
+            meta_file, policy, config,
         )
         .await?
     };
 
     // Return the entries for stdio mode
-    if entries.is_empty() {
+    if results.is_empty() {
         return Err(CliError::CommandFailed("No files imported".to_string()));
     }
 
-    println!("Successfully imported {} file(s)", entries.len());
-    for entry in &entries {
-        println!("  {} -> {}", entry.original_filename, entry.id);
+    let imported = results
+        .iter()
+        .filter(|(_, r)| r.outcome == ImportOutcome::Imported)
+        .count();
+    println!(
+        "Successfully imported {} file(s), {} duplicate(s)",
+        imported,
+        results.len() - imported
+    );
+    for (file_path, result) in &results {
+        let filename = file_path.file_name().unwrap_or_default().to_string_lossy();
+        println!("  {} -> {} ({})", filename, result.entry.id, result.outcome);
+    }
+
+    // 大文件的逐页文本不在导入时提取，在后台处理，同时展开压缩包
+    let pending = tagbox_core::pending_page_count(config).await?;
+    let indexing = (pending > 0).then(|| tagbox_core::spawn_page_indexing(config.clone()));
+
+    if explode || config.import.metadata.explode_archives {
+        explode_archives(&results, policy, config).await?;
+    }
+
+    if let Some(indexing) = indexing {
+        let spinner = create_spinner(&format!("Indexing pages of {} large file(s)", pending));
+        match indexing.await {
+            Ok(Ok(indexed)) => {
+                spinner.finish_with_message(format!("Indexed pages of {} file(s)", indexed))
+            }
+            Ok(Err(e)) => spinner.finish_with_message(format!("Page indexing failed: {}", e)),
+            Err(e) => spinner.finish_with_message(format!("Page indexing failed: {}", e)),
+        }
+    }
+
+    // 压缩包中的大文件或未完成的索引留给 index-pages
+    let pending = tagbox_core::pending_page_count(config).await?;
+    if pending > 0 {
+        println!(
+            "{} large file(s) queued for page text indexing, run `tagbox index-pages` to process them",
+            pending
+        );
+    }
+
+    Ok(())
+}
+
+/// Import the files inside imported ZIP / tar archives, linked to the archive as `contained_in`
+async fn explode_archives(
+    results: &[(PathBuf, ImportResult)],
+    policy: DuplicatePolicy,
+    config: &AppConfig,
+) -> Result<()> {
+    // Archive members are imported without asking about each duplicate
+    let policy = match policy {
+        DuplicatePolicy::Prompt => DuplicatePolicy::Skip,
+        other => other,
+    };
+
+    for (_, result) in results {
+        if !tagbox_core::metainfo::is_archive(&result.entry.path) {
+            continue;
+        }
+
+        let spinner = create_spinner(&format!("Unpacking {}...", result.entry.original_filename));
+        let members = tagbox_core::explode_archive(&result.entry.id, policy, config).await?;
+        spinner.finish_and_clear();
+
+        println!(
+            "Imported {} file(s) from {}",
+            members.len(),
+            result.entry.original_filename
+        );
+        for member in &members {
+            println!(
+                "  {} -> {} ({})",
+                member.entry.original_filename, member.entry.id, member.outcome
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle BibTeX import command
+pub async fn handle_import_bib(
+    bib: &Path,
+    files_dir: Option<&Path>,
+    on_duplicate: Option<String>,
+    config: &AppConfig,
+) -> Result<()> {
+    if !bib.is_file() {
+        return Err(CliError::InvalidArgument(format!(
+            "BibTeX file not found: {}",
+            bib.display()
+        )));
+    }
+
+    // Entries are imported without asking about each duplicate
+    let policy = match resolve_duplicate_policy(on_duplicate, config)? {
+        DuplicatePolicy::Prompt => DuplicatePolicy::Skip,
+        other => other,
+    };
+    let files_dir = files_dir
+        .map(Path::to_path_buf)
+        .or_else(|| bib.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let spinner = create_spinner(&format!("Importing entries from {}...", bib.display()));
+    let results = tagbox_core::import_bibtex(bib, &files_dir, policy, config).await?;
+    spinner.finish_and_clear();
+
+    let mut imported = 0;
+    let mut missing = 0;
+    for entry in &results {
+        match (&entry.file, &entry.result, &entry.error) {
+            (Some(file), Some(result), _) => {
+                imported += 1;
+                println!(
+                    "✓ {} -> {} ({}, {})",
+                    entry.key,
+                    result.entry.id,
+                    file.display(),
+                    result.outcome
+                );
+            }
+            (Some(file), None, Some(error)) => {
+                eprintln!("✗ {} ({}): {}", entry.key, file.display(), error);
+            }
+            _ => {
+                missing += 1;
+                println!("- {}: no file found for \"{}\"", entry.key, entry.title);
+            }
+        }
     }
 
+    println!(
+        "Imported {} of {} entries ({} without a matching file)",
+        imported,
+        results.len(),
+        missing
+    );
+
     Ok(())
 }
 
+/// Resolve the duplicate policy from the command line or the config file
+fn resolve_duplicate_policy(
+    on_duplicate: Option<String>,
+    config: &AppConfig,
+) -> Result<DuplicatePolicy> {
+    match on_duplicate {
+        Some(value) => value
+            .parse::<DuplicatePolicy>()
+            .map_err(|e| CliError::InvalidArgument(e.to_string())),
+        None => Ok(config.import.metadata.on_duplicate),
+    }
+}
+
 /// Handle URL import command
 pub async fn handle_import_url(
     url: &str,
@@ -68,17 +224,23 @@ pub async fn handle_import_url(
     tags: Option<String>,
     summary: Option<String>,
     meta_file: Option<PathBuf>,
+    on_duplicate: Option<String>,
     config: &AppConfig,
 ) -> Result<()> {
     log::info!("Downloading from URL: {}", url);
 
+    let policy = resolve_duplicate_policy(on_duplicate, config)?;
+
+    // Remember where the file came from unless --source overrides it
+    let source = source.or_else(|| Some(url.to_string()));
+
     // Download file
     let temp_path = download_file(url, rename).await?;
 
     // Import the downloaded file
     let result = import_single_file(
         &temp_path, delete, category, title, authors, year, publisher, source, tags, summary,
-        meta_file, config,
+        meta_file, policy, config,
     )
     .await;
 
@@ -92,8 +254,12 @@ pub async fn handle_import_url(
     }
 
     match result {
-        Ok(entries) => {
-            println!("Successfully imported from URL: {}", entries[0].id);
+        Ok(results) => {
+            let (_, result) = &results[0];
+            println!(
+                "Successfully imported from URL: {} ({})",
+                result.entry.id, result.outcome
+            );
             Ok(())
         }
         Err(e) => Err(e),
@@ -113,8 +279,9 @@ async fn import_single_file(
     tags: Option<String>,
     summary: Option<String>,
     meta_file: Option<PathBuf>,
+    policy: DuplicatePolicy,
     config: &AppConfig,
-) -> Result<Vec<FileEntry>> {
+) -> Result<Vec<(PathBuf, ImportResult)>> {
     let spinner = create_spinner("Extracting metadata...");
 
     // Load metadata from file if provided
@@ -138,7 +305,7 @@ async fn import_single_file(
     )?;
 
     spinner.set_message("Importing file...");
-    let entry = tagbox_core::import_file(path, metadata, config).await?;
+    let result = import_with_policy(path, metadata, policy, &spinner, config).await?;
 
     // Handle delete option
     if delete {
@@ -148,7 +315,7 @@ async fn import_single_file(
     }
 
     spinner.finish_with_message("Import completed");
-    Ok(vec![entry])
+    Ok(vec![(path.to_path_buf(), result)])
 }
 
 /// Import a directory of files
@@ -164,8 +331,9 @@ async fn import_directory(
     _tags: Option<String>,
     _summary: Option<String>,
     _meta_file: Option<PathBuf>,
+    policy: DuplicatePolicy,
     config: &AppConfig,
-) -> Result<Vec<FileEntry>> {
+) -> Result<Vec<(PathBuf, ImportResult)>> {
     // Collect all files in directory
     let files = collect_files(path)?;
 
@@ -242,7 +410,7 @@ async fn import_directory(
     // 阶段2：串行导入到数据库
     let import_progress = create_progress_bar(metadata_pairs.len() as u64, "Importing to database");
 
-    let mut entries = Vec::new();
+    let mut results = Vec::new();
     let mut import_errors = Vec::new();
 
     for (file_path, metadata) in metadata_pairs {
@@ -250,9 +418,9 @@ async fn import_directory(
         let import_msg = format!("Importing {}", filename);
         import_progress.set_message(import_msg);
 
-        match tagbox_core::import_file(&file_path, metadata, config).await {
-            Ok(entry) => {
-                entries.push(entry);
+        match import_with_policy(&file_path, metadata, policy, &import_progress, config).await {
+            Ok(result) => {
+                results.push((file_path, result));
                 import_progress.inc(1);
             }
             Err(e) => {
@@ -265,23 +433,19 @@ async fn import_directory(
 
     let import_finish_msg = format!(
         "Import completed: {} succeeded, {} failed",
-        entries.len(),
+        results.len(),
         import_errors.len()
     );
     import_progress.finish_with_message(import_finish_msg);
 
     // Handle delete option for successfully imported files
-    if delete && !entries.is_empty() {
-        let delete_progress = create_progress_bar(entries.len() as u64, "Deleting original files");
-
-        for entry in &entries {
-            // 找到对应的原始文件路径来删除
-            if let Some(original_file) = files.iter().find(|f| {
-                f.file_name().unwrap_or_default().to_string_lossy() == entry.original_filename
-            }) {
-                if let Err(e) = std::fs::remove_file(original_file) {
-                    log::warn!("Failed to delete {}: {}", original_file.display(), e);
-                }
+    // 重复文件的内容已在库中，同样可以删除原文件
+    if delete && !results.is_empty() {
+        let delete_progress = create_progress_bar(results.len() as u64, "Deleting original files");
+
+        for (original_file, _) in &results {
+            if let Err(e) = std::fs::remove_file(original_file) {
+                log::warn!("Failed to delete {}: {}", original_file.display(), e);
             }
             delete_progress.inc(1);
         }
@@ -289,7 +453,7 @@ async fn import_directory(
         delete_progress.finish_with_message("File deletion completed");
 
         // Remove empty directories if all files were deleted
-        if entries.len() == files.len() {
+        if results.len() == files.len() {
             if let Err(e) = std::fs::remove_dir_all(path) {
                 log::warn!("Failed to remove directory {}: {}", path.display(), e);
             }
@@ -304,9 +468,87 @@ async fn import_directory(
         extraction_errors.len()
     );
     println!("  Import failures: {}", import_errors.len());
-    println!("  Successfully imported: {}", entries.len());
+    println!(
+        "  Successfully imported: {}",
+        results
+            .iter()
+            .filter(|(_, r)| r.outcome == ImportOutcome::Imported)
+            .count()
+    );
+    println!(
+        "  Duplicates skipped: {}",
+        results
+            .iter()
+            .filter(|(_, r)| r.outcome == ImportOutcome::Skipped)
+            .count()
+    );
+    println!(
+        "  Duplicates merged: {}",
+        results
+            .iter()
+            .filter(|(_, r)| matches!(
+                r.outcome,
+                ImportOutcome::MergedTags { .. } | ImportOutcome::FilledFields { .. }
+            ))
+            .count()
+    );
+
+    Ok(results)
+}
+
+/// Import one file, asking the user how to handle a duplicate when the policy is `prompt`
+async fn import_with_policy(
+    path: &Path,
+    metadata: ImportMetadata,
+    policy: DuplicatePolicy,
+    progress: &ProgressBar,
+    config: &AppConfig,
+) -> Result<ImportResult> {
+    let result =
+        tagbox_core::import_file_with_policy(path, metadata.clone(), policy, config).await?;
+
+    if result.outcome != ImportOutcome::Pending {
+        return Ok(result);
+    }
+
+    // Never read answers from a pipe (e.g. stdio mode), fall back to skip
+    let chosen = if io::stdin().is_terminal() {
+        progress.suspend(|| prompt_duplicate_policy(path, &result))?
+    } else {
+        log::warn!(
+            "Cannot prompt for duplicate {} without a terminal, skipping",
+            path.display()
+        );
+        DuplicatePolicy::Skip
+    };
+
+    let resolved = tagbox_core::resolve_duplicate(result.entry, &metadata, chosen, config).await?;
+    Ok(resolved)
+}
+
+/// Ask how to handle a duplicate file
+fn prompt_duplicate_policy(path: &Path, result: &ImportResult) -> Result<DuplicatePolicy> {
+    println!(
+        "{} is already in the library as \"{}\" ({})",
+        path.display(),
+        result.entry.title,
+        result.entry.id
+    );
+
+    loop {
+        print!("[s]kip, [m]erge tags, [o]verwrite empty fields (S/m/o): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
 
-    Ok(entries)
+        match input.trim().to_lowercase().as_str() {
+            "" | "s" | "skip" => return Ok(DuplicatePolicy::Skip),
+            "m" | "merge" => return Ok(DuplicatePolicy::MergeTags),
+            "o" | "overwrite" => return Ok(DuplicatePolicy::OverwriteEmpty),
+            _ => println!("Please enter 's', 'm' or 'o'"),
+        }
+    }
 }
 
 /// Collect all files in a directory recursively
@@ -384,7 +626,7 @@ fn apply_metadata_overrides(
     }
 
     if let Some(authors) = authors {
-        metadata.authors = authors.split(',').map(|s| s.trim().to_string()).collect();
+        metadata.authors = split_author_list(&authors);
     }
 
     if let Some(year) = year {
@@ -0,0 +1,36 @@
+use crate::output::progress::create_spinner;
+use crate::utils::error::Result;
+use tagbox_core::config::AppConfig;
+
+/// Handle index-pages command
+pub async fn handle_index_pages(id: Option<String>, all: bool, config: &AppConfig) -> Result<()> {
+    if let Some(id) = id {
+        let status = tagbox_core::reindex_file_pages(&id, config).await?;
+        println!("Page index for {}: {}", id, status);
+        return Ok(());
+    }
+
+    if all {
+        let queued = tagbox_core::queue_all_pages(config).await?;
+        println!("Queued {} file(s) for page indexing", queued);
+    }
+
+    let pending = tagbox_core::pending_page_count(config).await?;
+    if pending == 0 {
+        println!("No files waiting for page indexing");
+        return Ok(());
+    }
+
+    let spinner = create_spinner(&format!("Indexing pages of {} file(s)", pending));
+    let indexed = tagbox_core::index_pending_pages(config).await?;
+    spinner.finish_with_message(format!("Indexed {} of {} file(s)", indexed, pending));
+
+    if indexed < pending {
+        println!(
+            "{} file(s) could not be indexed (encrypted or unreadable PDF)",
+            pending - indexed
+        );
+    }
+
+    Ok(())
+}
@@ -86,7 +86,7 @@ storage_dir = "{}"
 rename_template = "{{title}}_{{year}}"
 
 # Template for organizing files into subdirectories
-# Available variables: {{category1}}, {{category2}}, {{author}}, {{year}}, {{filename}}
+# Available variables: {{category1}}, {{category2}}, {{author}}, {{year}}, {{filename}}, {{ext}}
 classify_template = "{{category1}}/{{filename}}"
 
 [import.metadata]
@@ -99,6 +99,24 @@ fallback_pdf = true
 # Default category for files without explicit category
 default_category = "uncategorized"
 
+# What to do when an imported file is already in the library (same hash):
+# "skip", "merge-tags", "overwrite-empty" or "prompt"
+on_duplicate = "skip"
+
+# Index PDF text page by page (EPUB chapter by chapter) so search hits report page numbers
+index_pages = true
+
+# Maximum bytes of page text stored per file (later pages are skipped)
+page_text_budget = 33554432
+
+# Files larger than this many bytes get their pages indexed in the background after
+# the import (run `tagbox index-pages` to process files left in the queue)
+background_extract_size = 52428800
+
+# Also import every file inside imported ZIP / tar archives, linked to the archive
+# as "contained_in" (same as `tagbox import --explode`)
+explode_archives = false
+
 [search]
 # Default number of search results to return
 default_limit = 50
@@ -125,6 +143,33 @@ algorithm = "xxh3_128"
 
 # Verify file hashes on import (slower but safer)
 verify_on_import = true
+
+[ocr]
+# Recognize text in images and scanned PDF pages during import
+# (requires a build with the `ocr` feature; `tagbox ocr` works on demand)
+on_import = false
+
+# ocrs models, download text-detection.rten and text-recognition.rten from the ocrs project
+detection_model = "./tagbox_data/models/text-detection.rten"
+recognition_model = "./tagbox_data/models/text-recognition.rten"
+
+# Maximum number of pages recognized per PDF
+max_pages = 200
+
+[enrich]
+# Metadata sources for `tagbox enrich`, tried in order (crossref, arxiv, fixture)
+providers = ["arxiv", "crossref"]
+crossref_url = "https://api.crossref.org"
+arxiv_url = "https://export.arxiv.org"
+
+# Contact address sent to Crossref (recommended by their API etiquette)
+# mailto = "you@example.com"
+
+# Request timeout in seconds
+timeout_secs = 20
+
+# Days to keep lookups cached in the database (0 disables the cache)
+cache_days = 30
 "#,
         storage_dir.display(),
         database_path.display()
@@ -1,15 +1,22 @@
 pub mod author;
+pub mod category;
+pub mod cite;
 pub mod config;
 pub mod db;
 pub mod edit;
+pub mod enrich;
 pub mod export;
 pub mod import;
+pub mod index_pages;
 pub mod init_config;
 pub mod link;
 pub mod list;
+#[cfg(feature = "ocr")]
+pub mod ocr;
 pub mod preview;
 pub mod rebuild;
 pub mod search;
 pub mod serve;
 pub mod stats;
 pub mod stdio;
+pub mod tag;
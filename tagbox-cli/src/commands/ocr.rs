@@ -0,0 +1,28 @@
+use crate::output::progress::create_spinner;
+use crate::utils::error::Result;
+use tagbox_core::config::AppConfig;
+
+/// Handle ocr command
+pub async fn handle_ocr(id: Option<String>, all_missing: bool, config: &AppConfig) -> Result<()> {
+    if let Some(id) = id {
+        let spinner = create_spinner(&format!("Recognizing text in {}", id));
+        let pages = tagbox_core::ocr_file(&id, config).await?;
+        spinner.finish_with_message(format!("Recognized text on {} page(s) of {}", pages, id));
+        return Ok(());
+    }
+
+    if all_missing {
+        let spinner = create_spinner("Recognizing text in files without text");
+        let (processed, recognized) = tagbox_core::ocr_missing_files(config).await?;
+        spinner.finish_with_message(format!(
+            "Recognized text in {} of {} file(s)",
+            recognized, processed
+        ));
+
+        if processed == 0 {
+            println!("No images or PDFs are missing text");
+        }
+    }
+
+    Ok(())
+}
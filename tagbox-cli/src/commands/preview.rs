@@ -1,7 +1,9 @@
 use crate::output::{json, table};
 use crate::utils::error::{CliError, Result};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tagbox_core::config::AppConfig;
+use tagbox_core::types::{FileEntry, OutlineEntry};
 
 /// Handle preview command
 pub async fn handle_preview(
@@ -9,6 +11,7 @@ pub async fn handle_preview(
     only_meta: bool,
     open: bool,
     cd: bool,
+    page: Option<u32>,
     config: &AppConfig,
 ) -> Result<()> {
     log::debug!("Previewing file: {}", id);
@@ -26,9 +29,18 @@ pub async fn handle_preview(
     }
 
     if open {
-        // Open the file with system default program
-        open_file(&file_entry.path)?;
-        println!("Opened file: {}", file_entry.path.display());
+        let is_pdf = file_entry.mime_type.as_deref() == Some("application/pdf");
+        match page {
+            Some(page) if is_pdf => {
+                open_file_at_page(&file_entry.path, page)?;
+                println!("Opened file: {} (page {})", file_entry.path.display(), page);
+            }
+            _ => {
+                // Open the file with system default program
+                open_file(&file_entry.path)?;
+                println!("Opened file: {}", file_entry.path.display());
+            }
+        }
         return Ok(());
     }
 
@@ -38,13 +50,176 @@ pub async fn handle_preview(
     } else {
         // Show formatted preview using table format
         table::print_preview_table(&file_entry)?;
+        print_identifiers(&file_entry.id, config).await?;
+        print_outline(&file_entry);
+        print_annotations(&file_entry.id, config).await?;
+    }
+
+    Ok(())
+}
+
+/// Print DOI, arXiv, ISBN and ISSN identifiers detected at import
+async fn print_identifiers(file_id: &str, config: &AppConfig) -> Result<()> {
+    let identifiers = tagbox_core::get_file_identifiers(file_id, config).await?;
+    if identifiers.is_empty() {
+        return Ok(());
+    }
+
+    println!("\nIdentifiers:");
+    for identifier in &identifiers {
+        println!("  {}", identifier);
+    }
+
+    Ok(())
+}
+
+/// Print the document outline (PDF bookmarks or EPUB table of contents)
+fn print_outline(entry: &FileEntry) {
+    let Some(meta) = entry.type_metadata.as_ref() else {
+        return;
+    };
+
+    if let Some(outline) = meta
+        .pointer("/document/outline")
+        .and_then(|v| serde_json::from_value::<Vec<OutlineEntry>>(v.clone()).ok())
+        .filter(|outline| !outline.is_empty())
+    {
+        println!("\nSections:");
+        print_outline_entries(&outline, 1);
+    } else if let Some(toc) = meta.pointer("/book/toc").and_then(|v| v.as_array()) {
+        if !toc.is_empty() {
+            println!("\nContents:");
+            print_toc_entries(toc, 1);
+        }
+    }
+}
+
+fn print_outline_entries(entries: &[OutlineEntry], depth: usize) {
+    for entry in entries {
+        let page = match (&entry.page_label, entry.page) {
+            (Some(label), Some(page)) => format!("p.{} ({})", page, label),
+            (None, Some(page)) => format!("p.{}", page),
+            _ => String::new(),
+        };
+        println!("{}{}  {}", "  ".repeat(depth), entry.title, page);
+        print_outline_entries(&entry.children, depth + 1);
+    }
+}
+
+fn print_toc_entries(entries: &[serde_json::Value], depth: usize) {
+    for entry in entries {
+        println!(
+            "{}{}",
+            "  ".repeat(depth),
+            entry["label"].as_str().unwrap_or_default()
+        );
+        if let Some(children) = entry["children"].as_array() {
+            print_toc_entries(children, depth + 1);
+        }
+    }
+}
+
+/// Print highlights and notes stored for the file
+async fn print_annotations(file_id: &str, config: &AppConfig) -> Result<()> {
+    let annotations = tagbox_core::get_file_annotations(file_id, config).await?;
+    if annotations.is_empty() {
+        return Ok(());
+    }
+
+    println!("\nAnnotations:");
+    for annotation in &annotations {
+        let author = annotation
+            .author
+            .as_deref()
+            .map(|a| format!(" {}", a))
+            .unwrap_or_default();
+        println!("  p.{} [{}]{}", annotation.page, annotation.kind, author);
+        if let Some(quote) = &annotation.quoted_text {
+            println!("    \"{}\"", quote);
+        }
+        if let Some(contents) = &annotation.contents {
+            println!(
+                "    {}",
+                contents.split_whitespace().collect::<Vec<_>>().join(" ")
+            );
+        }
     }
 
     Ok(())
 }
 
+/// Open a PDF at the given page
+///
+/// Uses the command in `TAGBOX_PDF_VIEWER` when set (e.g. `zathura --page={page} {path}`),
+/// otherwise the first known viewer found on PATH, and finally the default program.
+fn open_file_at_page(path: &Path, page: u32) -> Result<()> {
+    let page_str = page.to_string();
+    let path_str = path.to_string_lossy().to_string();
+
+    if let Ok(template) = std::env::var("TAGBOX_PDF_VIEWER") {
+        let args: Vec<String> = template
+            .split_whitespace()
+            .map(|arg| {
+                arg.replace("{page}", &page_str)
+                    .replace("{path}", &path_str)
+            })
+            .collect();
+        if let Some((program, rest)) = args.split_first() {
+            Command::new(program)
+                .args(rest)
+                .spawn()
+                .map_err(CliError::Io)?;
+            return Ok(());
+        }
+    }
+
+    let viewers: &[(&str, &[&str])] = if cfg!(target_os = "windows") {
+        &[("SumatraPDF.exe", &["-page", "{page}", "{path}"])]
+    } else if cfg!(target_os = "macos") {
+        &[]
+    } else {
+        &[
+            ("zathura", &["--page={page}", "{path}"]),
+            ("evince", &["-i", "{page}", "{path}"]),
+            ("okular", &["-p", "{page}", "{path}"]),
+            ("qpdfview", &["{path}#{page}"]),
+        ]
+    };
+
+    for (program, args) in viewers {
+        if let Some(program) = find_in_path(program) {
+            let args: Vec<String> = args
+                .iter()
+                .map(|arg| {
+                    arg.replace("{page}", &page_str)
+                        .replace("{path}", &path_str)
+                })
+                .collect();
+            Command::new(program)
+                .args(&args)
+                .spawn()
+                .map_err(CliError::Io)?;
+            return Ok(());
+        }
+    }
+
+    log::warn!(
+        "No PDF viewer with page support found, set TAGBOX_PDF_VIEWER to jump to page {}",
+        page
+    );
+    open_file(path)
+}
+
+/// Find an executable on PATH
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
 /// Open file with system default program
-fn open_file(path: &std::path::Path) -> Result<()> {
+fn open_file(path: &Path) -> Result<()> {
     let result = if cfg!(target_os = "macos") {
         Command::new("open").arg(path).status()
     } else if cfg!(target_os = "windows") {
@@ -36,6 +36,36 @@ pub async fn handle_search(
         );
 
         table::print_file_table(&result.entries, columns.as_deref())?;
+
+        if !result.page_hits.is_empty() {
+            println!("\nPage matches (open with `tagbox preview <id> --open --page <n>`):");
+            for hit in &result.page_hits {
+                println!(
+                    "  {} p.{}: {}",
+                    hit.file_id,
+                    hit.page,
+                    hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ")
+                );
+            }
+        }
+
+        if !result.annotation_hits.is_empty() {
+            println!("\nAnnotation matches:");
+            for hit in &result.annotation_hits {
+                let annotation = &hit.annotation;
+                let text = annotation
+                    .quoted_text
+                    .iter()
+                    .map(|quote| format!("\"{}\"", quote))
+                    .chain(annotation.contents.clone())
+                    .collect::<Vec<_>>()
+                    .join(" — ");
+                println!(
+                    "  {} p.{} [{}]: {}",
+                    hit.file_id, annotation.page, annotation.kind, text
+                );
+            }
+        }
     }
 
     Ok(())
@@ -145,11 +145,26 @@ async fn execute_command(
             let source = get_optional_string_arg(args, "source");
             let tags = get_optional_string_arg(args, "tags");
             let summary = get_optional_string_arg(args, "summary");
+            let on_duplicate = get_optional_string_arg(args, "on_duplicate");
+            let explode = get_bool_arg(args, "explode").unwrap_or(false);
 
             // For stdio mode, we need to capture the import result
             import::handle_import(
-                path, delete, category, title, authors, year, publisher, source, tags, summary,
-                None, false, config,
+                path,
+                delete,
+                category,
+                title,
+                authors,
+                year,
+                publisher,
+                source,
+                tags,
+                summary,
+                None,
+                false,
+                on_duplicate,
+                explode,
+                config,
             )
             .await?;
 
@@ -184,6 +199,78 @@ async fn execute_command(
             }))
         }
 
+        "tag_tree" => {
+            let tree = tagbox_core::get_tag_tree(config).await?;
+            Ok(serde_json::to_value(tree)?)
+        }
+
+        "tag_list" => {
+            let tags = tagbox_core::list_tags(config).await?;
+            Ok(serde_json::to_value(tags)?)
+        }
+
+        "tag_rename" => {
+            let old = get_string_arg(args, "old")?;
+            let new = get_string_arg(args, "new")?;
+
+            let files = tagbox_core::rename_tag(&old, &new, config).await?;
+
+            Ok(serde_json::json!({
+                "success": true,
+                "files": files,
+                "message": format!("Renamed tag {} -> {}", old, new)
+            }))
+        }
+
+        "tag_merge" => {
+            let from = get_string_arg(args, "from")?;
+            let into = get_string_arg(args, "into")?;
+
+            let files = tagbox_core::merge_tags(&from, &into, config).await?;
+
+            Ok(serde_json::json!({
+                "success": true,
+                "files": files,
+                "message": format!("Merged tag {} into {}", from, into)
+            }))
+        }
+
+        "tag_delete" => {
+            let tag = get_string_arg(args, "tag")?;
+            let force = get_bool_arg(args, "force").unwrap_or(false);
+
+            let files = tagbox_core::delete_tag(&tag, force, config).await?;
+
+            Ok(serde_json::json!({
+                "success": true,
+                "files": files,
+                "message": format!("Deleted tag {}", tag)
+            }))
+        }
+
+        "tag_alias_add" => {
+            let tag = get_string_arg(args, "tag")?;
+            let alias = get_string_arg(args, "alias")?;
+
+            tagbox_core::add_tag_alias(&tag, &alias, config).await?;
+
+            Ok(serde_json::json!({
+                "success": true,
+                "message": format!("Added alias {} for tag {}", alias, tag)
+            }))
+        }
+
+        "tag_alias_remove" => {
+            let alias = get_string_arg(args, "alias")?;
+
+            tagbox_core::remove_tag_alias(&alias, config).await?;
+
+            Ok(serde_json::json!({
+                "success": true,
+                "message": format!("Removed tag alias {}", alias)
+            }))
+        }
+
         "export" => {
             let _json_output = get_bool_arg(args, "json").unwrap_or(true);
 
@@ -0,0 +1,179 @@
+use crate::output::json;
+use crate::utils::error::Result;
+use tabled::{builder::Builder, settings::Style};
+use tagbox_core::config::AppConfig;
+use tagbox_core::types::TagNode;
+
+/// Print the tag hierarchy with file counts
+pub async fn handle_tag_tree(json_output: bool, config: &AppConfig) -> Result<()> {
+    log::info!("Listing tag tree");
+
+    let tree = tagbox_core::get_tag_tree(config).await?;
+
+    if json_output {
+        return json::print_json(&tree);
+    }
+
+    if tree.is_empty() {
+        println!("No tags found.");
+        return Ok(());
+    }
+
+    for (index, node) in tree.iter().enumerate() {
+        print_tag_node(node, "", index + 1 == tree.len(), true);
+    }
+
+    Ok(())
+}
+
+/// List tags with their usage
+pub async fn handle_tag_list(unused: bool, json_output: bool, config: &AppConfig) -> Result<()> {
+    log::info!("Listing tags");
+
+    let mut tags = tagbox_core::list_tags(config).await?;
+    if unused {
+        // Parents of used tags have no direct files but are still in use
+        let tree = tagbox_core::get_tag_tree(config).await?;
+        let mut used = Vec::new();
+        collect_used_paths(&tree, &mut used);
+        tags.retain(|tag| !used.contains(&tag.path));
+    }
+
+    if json_output {
+        return json::print_json(&tags);
+    }
+
+    if tags.is_empty() {
+        println!("No tags found.");
+        return Ok(());
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record(["Tag", "Files", "Aliases"]);
+    for tag in &tags {
+        builder.push_record([
+            tag.path.clone(),
+            tag.file_count.to_string(),
+            tag.aliases.join(", "),
+        ]);
+    }
+
+    println!("{}", builder.build().with(Style::rounded()));
+    println!("{} tags", tags.len());
+
+    Ok(())
+}
+
+/// Rename a tag across all files
+pub async fn handle_tag_rename(old: &str, new: &str, config: &AppConfig) -> Result<()> {
+    log::debug!("Renaming tag {} to {}", old, new);
+
+    let files = tagbox_core::rename_tag(old, new, config).await?;
+    println!("Renamed tag '{}' to '{}' ({} files)", old, new, files.len());
+
+    Ok(())
+}
+
+/// Merge one tag into another
+pub async fn handle_tag_merge(from: &str, into: &str, config: &AppConfig) -> Result<()> {
+    log::debug!("Merging tag {} into {}", from, into);
+
+    let files = tagbox_core::merge_tags(from, into, config).await?;
+    println!(
+        "Merged tag '{}' into '{}' ({} files), '{}' is now an alias",
+        from,
+        into,
+        files.len(),
+        from
+    );
+
+    Ok(())
+}
+
+/// Delete a tag and its child tags
+pub async fn handle_tag_delete(tag: &str, force: bool, config: &AppConfig) -> Result<()> {
+    log::debug!("Deleting tag {}", tag);
+
+    let files = tagbox_core::delete_tag(tag, force, config).await?;
+    if files.is_empty() {
+        println!("Deleted tag '{}'", tag);
+    } else {
+        println!(
+            "Deleted tag '{}' and removed it from {} files",
+            tag,
+            files.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Delete every tag no file uses
+pub async fn handle_tag_prune(config: &AppConfig) -> Result<()> {
+    let removed = tagbox_core::prune_unused_tags(config).await?;
+
+    for path in &removed {
+        println!("Deleted {}", path);
+    }
+    println!("Deleted {} unused tags", removed.len());
+
+    Ok(())
+}
+
+/// Add an alias that resolves to a tag
+pub async fn handle_tag_alias_add(tag: &str, alias: &str, config: &AppConfig) -> Result<()> {
+    tagbox_core::add_tag_alias(tag, alias, config).await?;
+    println!("Added alias '{}' for tag '{}'", alias, tag);
+
+    Ok(())
+}
+
+/// Remove a tag alias
+pub async fn handle_tag_alias_remove(alias: &str, config: &AppConfig) -> Result<()> {
+    tagbox_core::remove_tag_alias(alias, config).await?;
+    println!("Removed tag alias '{}'", alias);
+
+    Ok(())
+}
+
+/// Collect the paths of tags that have files directly or below them
+fn collect_used_paths(nodes: &[TagNode], used: &mut Vec<String>) {
+    for node in nodes {
+        if node.total_count > 0 {
+            used.push(node.path.clone());
+        }
+        collect_used_paths(&node.children, used);
+    }
+}
+
+/// Print a node and its children with box-drawing connectors
+fn print_tag_node(node: &TagNode, prefix: &str, is_last: bool, is_root: bool) {
+    let count = if node.file_count != node.total_count && node.file_count > 0 {
+        format!("{}, {} direct", node.total_count, node.file_count)
+    } else {
+        node.total_count.to_string()
+    };
+
+    if is_root {
+        println!("{} ({})", node.name, count);
+    } else {
+        let connector = if is_last { "└── " } else { "├── " };
+        println!("{}{}{} ({})", prefix, connector, node.name, count);
+    }
+
+    let child_prefix = if is_root {
+        String::new()
+    } else if is_last {
+        format!("{}    ", prefix)
+    } else {
+        format!("{}│   ", prefix)
+    };
+    for (index, child) in node.children.iter().enumerate() {
+        print_tag_node(
+            child,
+            &child_prefix,
+            index + 1 == node.children.len(),
+            false,
+        );
+    }
+}
@@ -8,7 +8,10 @@ mod commands;
 mod output;
 mod utils;
 
-use cli::{AuthorCommands, Cli, Commands, ConfigCommands};
+use cli::{
+    AuthorAliasCommands, AuthorCommands, CategoryCommands, Cli, Commands, ConfigCommands,
+    TagAliasCommands, TagCommands,
+};
 use utils::{config, error::CliError};
 
 #[tokio::main]
@@ -144,6 +147,8 @@ async fn execute_command(
             summary,
             meta_file,
             interactive,
+            on_duplicate,
+            explode,
         } => {
             commands::import::handle_import(
                 &path,
@@ -158,6 +163,8 @@ async fn execute_command(
                 summary,
                 meta_file,
                 interactive,
+                on_duplicate,
+                explode,
                 config,
             )
             .await
@@ -176,14 +183,36 @@ async fn execute_command(
             tags,
             summary,
             meta_file,
+            on_duplicate,
         } => {
             commands::import::handle_import_url(
-                &url, rename, delete, category, title, authors, year, publisher, source, tags,
-                summary, meta_file, config,
+                &url,
+                rename,
+                delete,
+                category,
+                title,
+                authors,
+                year,
+                publisher,
+                source,
+                tags,
+                summary,
+                meta_file,
+                on_duplicate,
+                config,
             )
             .await
         }
 
+        Commands::ImportBib {
+            bib,
+            files_dir,
+            on_duplicate,
+        } => {
+            commands::import::handle_import_bib(&bib, files_dir.as_deref(), on_duplicate, config)
+                .await
+        }
+
         Commands::Search {
             query,
             json,
@@ -208,7 +237,8 @@ async fn execute_command(
             only_meta,
             open,
             cd,
-        } => commands::preview::handle_preview(&id, only_meta, open, cd, config).await,
+            page,
+        } => commands::preview::handle_preview(&id, only_meta, open, cd, page, config).await,
 
         Commands::Link { id1, id2, relation } => {
             commands::link::handle_link(&id1, &id2, relation, config).await
@@ -233,7 +263,82 @@ async fn execute_command(
             Some(AuthorCommands::Merge { from, to }) => {
                 commands::author::handle_author_merge(&from, &to, config).await
             }
-            None => commands::author::handle_author_list(config).await,
+            Some(AuthorCommands::List { json }) => {
+                commands::author::handle_author_list(json, config).await
+            }
+            Some(AuthorCommands::Show { id, json }) => {
+                commands::author::handle_author_show(&id, json, config).await
+            }
+            Some(AuthorCommands::Edit {
+                id,
+                name,
+                real_name,
+                bio,
+                homepage,
+                orcid,
+            }) => {
+                let update = tagbox_core::types::AuthorUpdate {
+                    name,
+                    real_name,
+                    bio,
+                    homepage,
+                    orcid,
+                };
+                commands::author::handle_author_edit(&id, update, config).await
+            }
+            Some(AuthorCommands::Alias { command }) => match command {
+                AuthorAliasCommands::Add { id, alias } => {
+                    commands::author::handle_author_alias_add(&id, &alias, config).await
+                }
+                AuthorAliasCommands::Remove { id, alias } => {
+                    commands::author::handle_author_alias_remove(&id, &alias, config).await
+                }
+            },
+            Some(AuthorCommands::Files { id, json, columns }) => {
+                commands::author::handle_author_files(&id, json, columns, config).await
+            }
+            Some(AuthorCommands::Dedupe) => commands::author::handle_author_dedupe(config).await,
+            None => commands::author::handle_author_list(false, config).await,
+        },
+
+        Commands::Tag { command } => match command {
+            Some(TagCommands::Tree { json }) => commands::tag::handle_tag_tree(json, config).await,
+            Some(TagCommands::List { unused, json }) => {
+                commands::tag::handle_tag_list(unused, json, config).await
+            }
+            Some(TagCommands::Rename { old, new }) => {
+                commands::tag::handle_tag_rename(&old, &new, config).await
+            }
+            Some(TagCommands::Merge { from, into }) => {
+                commands::tag::handle_tag_merge(&from, &into, config).await
+            }
+            Some(TagCommands::Delete { tag, force }) => {
+                commands::tag::handle_tag_delete(&tag, force, config).await
+            }
+            Some(TagCommands::Prune) => commands::tag::handle_tag_prune(config).await,
+            Some(TagCommands::Alias { command }) => match command {
+                TagAliasCommands::Add { tag, alias } => {
+                    commands::tag::handle_tag_alias_add(&tag, &alias, config).await
+                }
+                TagAliasCommands::Remove { alias } => {
+                    commands::tag::handle_tag_alias_remove(&alias, config).await
+                }
+            },
+            None => commands::tag::handle_tag_tree(false, config).await,
+        },
+
+        Commands::Category { command } => match command {
+            CategoryCommands::Rename { old, new, apply } => {
+                commands::category::handle_category_rename(&old, &new, apply, config).await
+            }
+            CategoryCommands::Move {
+                path,
+                parent,
+                apply,
+            } => commands::category::handle_category_move(&path, &parent, apply, config).await,
+            CategoryCommands::Merge { from, into, apply } => {
+                commands::category::handle_category_merge(&from, &into, apply, config).await
+            }
         },
 
         Commands::Config { cd, command } => {
@@ -256,10 +361,17 @@ async fn execute_command(
             }
         }
 
-        Commands::Export { json, output } => {
-            commands::export::handle_export(json, output.as_deref(), config).await
+        Commands::Export {
+            json,
+            format,
+            output,
+        } => {
+            let format = if json { "json".to_string() } else { format };
+            commands::export::handle_export(&format, output.as_deref(), config).await
         }
 
+        Commands::Cite { ids, style } => commands::cite::handle_cite(&ids, &style, config).await,
+
         Commands::Stats => commands::stats::handle_stats(config).await,
 
         Commands::Serve { port, host } => commands::serve::handle_serve(port, &host, config).await,
@@ -282,28 +394,68 @@ async fn execute_command(
             mv,
             title,
             authors,
+            contributors,
             category,
             tags,
             summary,
             year,
             publisher,
             source,
-        } => commands::edit::handle_edit(
-            &id,
-            interactive,
-            mv,
-            title,
-            authors,
-            category,
-            tags,
-            summary,
-            year,
-            publisher,
-            source,
-            config,
-        )
-        .await
-        .map_err(CliError::Core),
+            query,
+            add_tags,
+            remove_tags,
+            set_category,
+            dry_run,
+            undo,
+        } => match (id, query, undo) {
+            (_, _, Some(batch_id)) => commands::edit::handle_edit_undo(&batch_id, config)
+                .await
+                .map_err(CliError::Core),
+            (_, Some(query), None) => commands::edit::handle_bulk_edit(
+                &query,
+                add_tags,
+                remove_tags,
+                set_category,
+                mv,
+                dry_run,
+                config,
+            )
+            .await
+            .map_err(CliError::Core),
+            (Some(id), None, None) => commands::edit::handle_edit(
+                &id,
+                interactive,
+                mv,
+                title,
+                authors,
+                contributors,
+                category,
+                tags,
+                summary,
+                year,
+                publisher,
+                source,
+                config,
+            )
+            .await
+            .map_err(CliError::Core),
+            (None, None, None) => Err(CliError::InvalidArgument(
+                "Provide a file ID, --query or --undo".to_string(),
+            )),
+        },
+
+        Commands::IndexPages { id, all } => {
+            commands::index_pages::handle_index_pages(id, all, config).await
+        }
+
+        #[cfg(feature = "ocr")]
+        Commands::Ocr { id, all_missing } => {
+            commands::ocr::handle_ocr(id, all_missing, config).await
+        }
+
+        Commands::Enrich { id, query, yes } => {
+            commands::enrich::handle_enrich(id, query, yes, config).await
+        }
 
         Commands::Rebuild { id, apply, workers } => {
             commands::rebuild::handle_rebuild(id, apply, workers, config)
@@ -4,7 +4,7 @@ use tabled::{
     builder::Builder,
     settings::{object::Columns, Modify, Style, Width},
 };
-use tagbox_core::types::FileEntry;
+use tagbox_core::types::{AuthorRole, FileEntry};
 
 /// Print file entries as a formatted table
 pub fn print_file_table(entries: &[FileEntry], columns: Option<&str>) -> Result<()> {
@@ -64,6 +64,9 @@ fn column_header(column: &str) -> String {
         "publisher" => "Publisher".to_string(),
         "source" => "Source".to_string(),
         "path" => "Path".to_string(),
+        "original_path" => "Original path".to_string(),
+        "size" => "Size".to_string(),
+        "mime_type" => "Type".to_string(),
         "category1" => "Category".to_string(),
         "tags" => "Tags".to_string(),
         "created_at" => "Created".to_string(),
@@ -90,6 +93,22 @@ pub fn print_preview_table(entry: &FileEntry) -> Result<()> {
         rows.push(["Authors".to_string(), entry.authors.join(", ")]);
     }
 
+    for (role, label) in [
+        (AuthorRole::Editor, "Editors"),
+        (AuthorRole::Translator, "Translators"),
+        (AuthorRole::Illustrator, "Illustrators"),
+    ] {
+        let names: Vec<&str> = entry
+            .contributors
+            .iter()
+            .filter(|c| c.role == role)
+            .map(|c| c.name.as_str())
+            .collect();
+        if !names.is_empty() {
+            rows.push([label.to_string(), names.join(", ")]);
+        }
+    }
+
     if let Some(year) = entry.year {
         rows.push(["Year".to_string(), year.to_string()]);
     }
@@ -124,6 +143,28 @@ pub fn print_preview_table(entry: &FileEntry) -> Result<()> {
         "Original filename".to_string(),
         entry.original_filename.clone(),
     ]);
+
+    if let Some(original_path) = &entry.original_path {
+        rows.push([
+            "Original path".to_string(),
+            original_path.to_string_lossy().to_string(),
+        ]);
+    }
+
+    if let Some(size) = entry.size {
+        rows.push(["Size".to_string(), format_size(size)]);
+    }
+
+    if let Some(mime_type) = &entry.mime_type {
+        rows.push(["Type".to_string(), mime_type.clone()]);
+    }
+
+    if let Some(mtime) = entry.mtime {
+        rows.push([
+            "File modified".to_string(),
+            mtime.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ]);
+    }
     rows.push(["Hash".to_string(), entry.hash.clone()]);
 
     if let Some(current_hash) = &entry.current_hash {
@@ -307,6 +348,12 @@ fn format_column_value(entry: &FileEntry, column: &str) -> String {
         "publisher" => entry.publisher.clone().unwrap_or_else(|| "-".to_string()),
         "source" => entry.source.clone().unwrap_or_else(|| "-".to_string()),
         "path" => entry.path.to_string_lossy().to_string(),
+        "original_path" => entry
+            .original_path
+            .as_ref()
+            .map_or_else(|| "-".to_string(), |p| p.to_string_lossy().to_string()),
+        "size" => entry.size.map_or_else(|| "-".to_string(), format_size),
+        "mime_type" => entry.mime_type.clone().unwrap_or_else(|| "-".to_string()),
         "category1" => combine_categories(entry),
         "tags" => entry.tags.join(", "),
         "created_at" => entry.created_at.format("%Y-%m-%d %H:%M").to_string(),
@@ -315,6 +362,24 @@ fn format_column_value(entry: &FileEntry, column: &str) -> String {
     }
 }
 
+/// Format a byte count for display (e.g. "1.50 MB")
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", bytes, UNITS[unit_index])
+    } else {
+        format!("{:.2} {}", size, UNITS[unit_index])
+    }
+}
+
 fn combine_categories(entry: &FileEntry) -> String {
     let mut categories = vec![entry.category1.clone()];
     if let Some(category2) = &entry.category2 {
@@ -0,0 +1,148 @@
+use crate::errors::{Result, TagboxError};
+use crate::types::{Annotation, AnnotationHit, FileEntry};
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+use tracing::debug;
+
+/// PDF 批注管理器，批注保存在 `file_annotations` 表中供全文搜索
+pub struct AnnotationManager {
+    db_pool: SqlitePool,
+}
+
+impl AnnotationManager {
+    /// 创建一个新的批注管理器
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 批注全文索引表是否可用
+    pub async fn is_available(&self) -> bool {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'file_annotations_fts'",
+        )
+        .fetch_one(&self.db_pool)
+        .await
+        .map(|count| count > 0)
+        .unwrap_or(false)
+    }
+
+    /// 用给定批注替换文件的全部批注
+    pub async fn replace(&self, file_id: &str, annotations: &[Annotation]) -> Result<()> {
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        sqlx::query("DELETE FROM file_annotations WHERE file_id = ?")
+            .bind(file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        for annotation in annotations {
+            sqlx::query(
+                "INSERT INTO file_annotations
+                 (file_id, page, kind, author, contents, quoted_text, modified_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(file_id)
+            .bind(annotation.page as i64)
+            .bind(&annotation.kind)
+            .bind(&annotation.author)
+            .bind(&annotation.contents)
+            .bind(&annotation.quoted_text)
+            .bind(&annotation.modified_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+        }
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        debug!("文件 {} 保存了 {} 条批注", file_id, annotations.len());
+        Ok(())
+    }
+
+    /// 按 `type_metadata.document.annotations` 更新文件的批注，返回批注数量
+    pub async fn index_entry(&self, entry: &FileEntry) -> Result<usize> {
+        let annotations = annotations_from_metadata(entry);
+        self.replace(&entry.id, &annotations).await?;
+        Ok(annotations.len())
+    }
+
+    /// 文件的全部批注，按页码排列
+    pub async fn list(&self, file_id: &str) -> Result<Vec<Annotation>> {
+        let rows = sqlx::query(
+            "SELECT page, kind, author, contents, quoted_text, modified_at
+             FROM file_annotations WHERE file_id = ? ORDER BY page, id",
+        )
+        .bind(file_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(rows.iter().map(row_to_annotation).collect())
+    }
+
+    /// 在给定文件的批注中查找命中，每个文件最多返回 `per_file` 条
+    pub async fn find_hits(
+        &self,
+        fts_query: &str,
+        file_ids: &[String],
+        per_file: usize,
+    ) -> Result<Vec<AnnotationHit>> {
+        if file_ids.is_empty() || fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = format!(
+            "SELECT a.file_id, a.page, a.kind, a.author, a.contents, a.quoted_text, a.modified_at
+             FROM file_annotations_fts
+             JOIN file_annotations a ON a.id = file_annotations_fts.rowid
+             WHERE file_annotations_fts MATCH ? AND a.file_id IN ({})
+             ORDER BY a.file_id, rank",
+            vec!["?"; file_ids.len()].join(", ")
+        );
+
+        let mut query = sqlx::query(&sql).bind(fts_query);
+        for file_id in file_ids {
+            query = query.bind(file_id);
+        }
+
+        let rows = query
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        let mut hits: Vec<AnnotationHit> = Vec::new();
+        for row in rows {
+            let file_id: String = row.get("file_id");
+            if hits.iter().filter(|h| h.file_id == file_id).count() >= per_file {
+                continue;
+            }
+            hits.push(AnnotationHit {
+                file_id,
+                annotation: row_to_annotation(&row),
+            });
+        }
+
+        Ok(hits)
+    }
+}
+
+/// 读取提取器写入 `type_metadata.document.annotations` 的批注
+pub fn annotations_from_metadata(entry: &FileEntry) -> Vec<Annotation> {
+    entry
+        .type_metadata
+        .as_ref()
+        .and_then(|meta| meta.pointer("/document/annotations"))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+fn row_to_annotation(row: &SqliteRow) -> Annotation {
+    Annotation {
+        page: row.get::<i64, _>("page") as u32,
+        kind: row.get("kind"),
+        author: row.get("author"),
+        contents: row.get("contents"),
+        quoted_text: row.get("quoted_text"),
+        modified_at: row.get("modified_at"),
+    }
+}
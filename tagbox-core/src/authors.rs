@@ -1,7 +1,8 @@
+use crate::bibliography::{fold_accent, NAME_PARTICLES};
 use crate::errors::{Result, TagboxError};
-use crate::types::Author;
-use crate::utils::{current_time, generate_uuid, require_field};
-use sqlx::SqlitePool;
+use crate::types::{Author, AuthorRole, AuthorSummary, AuthorUpdate, Contributor};
+use crate::utils::{current_time, generate_uuid};
+use sqlx::{Row, SqlitePool};
 use std::collections::HashMap;
 use tracing::info;
 
@@ -18,236 +19,587 @@ impl AuthorManager {
 
     /// 获取作者信息
     pub async fn get_author(&self, author_id_param: &str) -> Result<Author> {
-        let author_row = sqlx::query!(
-            r#"
-            SELECT id, name, created_at, updated_at
-            FROM authors
-            WHERE id = ?
-            "#,
-            author_id_param
+        let author_row = sqlx::query(
+            "SELECT id, name, real_name, bio, homepage, orcid FROM authors WHERE id = ?",
         )
+        .bind(author_id_param)
         .fetch_optional(&self.db_pool)
         .await
         .map_err(TagboxError::Database)?
         .ok_or_else(|| TagboxError::Config(format!("作者ID不存在: {}", author_id_param)))?;
 
-        // 获取别名. The author_aliases table uses (alias_id, canonical_id).
-        // alias_id is the ID of an author entry that is an alias.
-        // canonical_id is the ID of the main author entry it points to.
-        // To get all names that are aliases FOR `author_id_param` (which is a canonical_id):
-        // we need to select `authors.name` where `authors.id` is in `author_aliases.alias_id`
-        // and `author_aliases.canonical_id` is `author_id_param`.
-        // OR, if `authors.aliases` JSON field is the primary source for alias strings, use that.
-        // The current query `SELECT alias FROM author_aliases WHERE author_id = ?` is problematic
-        // because `author_aliases` doesn't have an `alias` text column nor a simple `author_id` column.
-        // It has `alias_id` (FK to authors) and `canonical_id` (FK to authors).
-        // Assuming we want to list names of other Author entries that are marked as aliases for this one:
-        let alias_names = sqlx::query!(
+        // 别名是 author_aliases 中指向该作者的其他作者条目
+        let alias_names = sqlx::query_scalar(
             r#"
             SELECT a.name FROM authors a JOIN author_aliases aa ON a.id = aa.alias_id
             WHERE aa.canonical_id = ?
+            ORDER BY a.name
             "#,
-            author_id_param
         )
+        .bind(author_id_param)
         .fetch_all(&self.db_pool)
         .await
-        .map_err(TagboxError::Database)?
-        .into_iter()
-        .map(|row| row.name) // row.name is String (NOT NULL in db)
-        .collect();
+        .map_err(TagboxError::Database)?;
 
         let metadata = self.get_author_metadata(author_id_param).await?;
 
         Ok(Author {
-            id: require_field(author_row.id, "authors.id")?,
-            name: author_row.name,
-            aliases: alias_names, // Use the fetched alias names
+            id: author_row.get("id"),
+            name: author_row.get("name"),
+            aliases: alias_names,
             metadata: Some(metadata),
+            real_name: author_row.get("real_name"),
+            bio: author_row.get("bio"),
+            homepage: author_row.get("homepage"),
+            orcid: author_row.get("orcid"),
         })
     }
 
-    /// 创建新作者
+    /// 创建新作者，`aliases` 中的名字作为该作者的别名
     pub async fn create_author(&self, name: &str, aliases: &[String]) -> Result<Author> {
-        // 检查是否已存在同名作者
-        let existing = sqlx::query!(
+        let (id, _) = self.find_or_create_author(name).await?;
+
+        for alias in aliases {
+            let (alias_id, _) = self.find_or_create_author(alias).await?;
+            if alias_id != id {
+                self.add_author_alias(&id, &alias_id).await?;
+            }
+        }
+
+        self.get_author(&id).await
+    }
+
+    /// 更新作者资料
+    pub async fn update_author(&self, author_id: &str, update: AuthorUpdate) -> Result<Author> {
+        let mut author = self.get_author(author_id).await?;
+
+        if let Some(name) = &update.name {
+            let name = normalize_author_name(name);
+            if name.is_empty() {
+                return Err(TagboxError::Config("作者名不能为空".to_string()));
+            }
+            let existing: Option<String> =
+                sqlx::query_scalar("SELECT id FROM authors WHERE name = ? AND id != ?")
+                    .bind(&name)
+                    .bind(author_id)
+                    .fetch_optional(&self.db_pool)
+                    .await
+                    .map_err(TagboxError::Database)?;
+            if let Some(existing) = existing {
+                return Err(TagboxError::Config(format!(
+                    "作者名 {} 已被 {} 使用，请改用合并",
+                    name, existing
+                )));
+            }
+            author.name = name;
+        }
+        if let Some(orcid) = &update.orcid {
+            author.orcid = match orcid.trim() {
+                "" => None,
+                raw => Some(
+                    normalize_orcid(raw)
+                        .ok_or_else(|| TagboxError::Config(format!("无效的 ORCID: {}", raw)))?,
+                ),
+            };
+        }
+        for (value, field) in [
+            (&update.real_name, &mut author.real_name),
+            (&update.bio, &mut author.bio),
+            (&update.homepage, &mut author.homepage),
+        ] {
+            if let Some(value) = value {
+                let value = value.trim();
+                *field = (!value.is_empty()).then(|| value.to_string());
+            }
+        }
+
+        sqlx::query(
             r#"
-            SELECT id FROM authors WHERE name = ?
+            UPDATE authors
+            SET name = ?, real_name = ?, bio = ?, homepage = ?, orcid = ?, updated_at = ?
+            WHERE id = ?
             "#,
-            name
         )
-        .fetch_optional(&self.db_pool)
+        .bind(&author.name)
+        .bind(&author.real_name)
+        .bind(&author.bio)
+        .bind(&author.homepage)
+        .bind(&author.orcid)
+        .bind(current_time().to_rfc3339())
+        .bind(author_id)
+        .execute(&self.db_pool)
         .await
         .map_err(TagboxError::Database)?;
 
-        if let Some(author) = existing {
-            if let Some(id) = &author.id {
-                return self.get_author(id).await;
+        Ok(author)
+    }
+
+    /// 删除作者；仍有关联文件时拒绝，应先合并到其他作者
+    pub async fn delete_author(&self, author_id: &str) -> Result<()> {
+        let author = self.get_author(author_id).await?;
+        let files = self.get_author_files(author_id).await?;
+        if !files.is_empty() {
+            return Err(TagboxError::Config(format!(
+                "作者 {} 仍关联 {} 个文件，请先合并到其他作者",
+                author.name,
+                files.len()
+            )));
+        }
+
+        sqlx::query("DELETE FROM authors WHERE id = ?")
+            .bind(author_id)
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        info!("作者 {} 已删除", author_id);
+        Ok(())
+    }
+
+    /// 列出主作者（不含别名和已合并的作者）及其文件数，按文件数降序
+    pub async fn list_authors(&self) -> Result<Vec<AuthorSummary>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.id, a.name, a.orcid,
+                (SELECT COUNT(DISTINCT fa.file_id)
+                 FROM file_authors fa JOIN files f ON f.id = fa.file_id
+                 WHERE f.is_deleted = 0
+                   AND (fa.author_id = a.id OR fa.author_id IN
+                        (SELECT alias_id FROM author_aliases WHERE canonical_id = a.id))
+                ) AS file_count,
+                (SELECT COUNT(*) FROM author_aliases WHERE canonical_id = a.id) AS alias_count
+            FROM authors a
+            WHERE a.is_deleted = 0
+              AND a.id NOT IN (SELECT alias_id FROM author_aliases)
+            ORDER BY file_count DESC, a.name COLLATE NOCASE
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AuthorSummary {
+                id: row.get("id"),
+                name: row.get("name"),
+                orcid: row.get("orcid"),
+                file_count: row.get("file_count"),
+                alias_count: row.get("alias_count"),
+            })
+            .collect())
+    }
+
+    /// 获取作者（含其别名）关联的未删除文件 ID，按标题排序
+    pub async fn get_author_files(&self, author_id: &str) -> Result<Vec<String>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT f.id FROM files f
+            WHERE f.is_deleted = 0
+              AND f.id IN (
+                  SELECT fa.file_id FROM file_authors fa
+                  WHERE fa.author_id = ? OR fa.author_id IN
+                      (SELECT alias_id FROM author_aliases WHERE canonical_id = ?))
+            ORDER BY f.title COLLATE NOCASE
+            "#,
+        )
+        .bind(author_id)
+        .bind(author_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)
+    }
+
+    /// 按名称查找作者条目，不解析别名，返回 (id, name)
+    ///
+    /// 先精确匹配，再按 [`author_key`] 忽略大小写、标点、重音和"姓, 名"顺序匹配。
+    pub async fn find_author_by_name(&self, name: &str) -> Result<Option<(String, String)>> {
+        let exact = sqlx::query("SELECT id, name FROM authors WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+        if let Some(row) = exact {
+            return Ok(Some((row.get("id"), row.get("name"))));
+        }
+
+        let key = author_key(name);
+        if key.is_empty() {
+            return Ok(None);
+        }
+        Ok(
+            sqlx::query("SELECT id, name FROM authors ORDER BY created_at")
+                .fetch_all(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?
+                .into_iter()
+                .map(|row| (row.get::<String, _>("id"), row.get::<String, _>("name")))
+                .find(|(_, existing)| author_key(existing) == key),
+        )
+    }
+
+    /// 按名称查找作者，并通过 author_aliases 解析到主作者，返回 (id, name)
+    pub async fn resolve_author(&self, name: &str) -> Result<Option<(String, String)>> {
+        let Some(mut author) = self.find_author_by_name(name).await? else {
+            return Ok(None);
+        };
+
+        // 沿别名链找到主作者，遇到环时停止
+        let mut seen = vec![author.0.clone()];
+        while let Some(row) = sqlx::query(
+            r#"
+            SELECT a.id, a.name FROM author_aliases aa
+            JOIN authors a ON a.id = aa.canonical_id
+            WHERE aa.alias_id = ?
+            "#,
+        )
+        .bind(&author.0)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?
+        {
+            let id: String = row.get("id");
+            if seen.contains(&id) {
+                break;
             }
+            seen.push(id.clone());
+            author = (id, row.get("name"));
         }
 
-        // 创建新作者
-        let id = generate_uuid();
+        Ok(Some(author))
+    }
+
+    /// 查找或创建作者，返回 (id, name)；名称先经过 [`normalize_author_name`] 规范化
+    pub async fn find_or_create_author(&self, name: &str) -> Result<(String, String)> {
+        let name = normalize_author_name(name);
+        if name.is_empty() {
+            return Err(TagboxError::Config("作者名不能为空".to_string()));
+        }
+        if let Some(author) = self.resolve_author(&name).await? {
+            return Ok(author);
+        }
+
+        // 并行导入时可能同时创建同名作者，插入冲突后按名称取回
         let now = current_time().to_rfc3339();
+        sqlx::query(
+            "INSERT OR IGNORE INTO authors (id, name, created_at, updated_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(generate_uuid())
+        .bind(&name)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        let id: String = sqlx::query_scalar("SELECT id FROM authors WHERE name = ?")
+            .bind(&name)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+        Ok((id, name))
+    }
 
-        sqlx::query!(
+    /// 按署名顺序读取文件的作者和其他署名（编者、译者、插画）
+    pub async fn get_file_authors(&self, file_id: &str) -> Result<(Vec<String>, Vec<Contributor>)> {
+        let rows = sqlx::query(
             r#"
-            INSERT INTO authors (id, name, created_at, updated_at)
-            VALUES (?, ?, ?, ?)
+            SELECT a.name, fa.role FROM file_authors fa
+            JOIN authors a ON a.id = fa.author_id
+            WHERE fa.file_id = ?
+            ORDER BY fa.position, fa.rowid
             "#,
-            id,
-            name,
-            now,
-            now
         )
-        .execute(&self.db_pool)
+        .bind(file_id)
+        .fetch_all(&self.db_pool)
         .await
         .map_err(TagboxError::Database)?;
 
-        // 添加别名
-        for alias in aliases {
-            self.add_author_alias(&id, alias).await?;
+        let mut authors = Vec::new();
+        let mut contributors = Vec::new();
+        for row in rows {
+            let name: String = row.get("name");
+            match AuthorRole::from_name(row.get("role")).unwrap_or_default() {
+                AuthorRole::Author => authors.push(name),
+                role => contributors.push(Contributor::new(name, role)),
+            }
         }
+        // 其他署名按角色分组，组内保持署名顺序
+        contributors.sort_by_key(|c: &Contributor| c.role);
 
-        self.get_author(&id).await
+        Ok((authors, contributors))
     }
 
-    /// 添加作者别名
+    /// 按顺序设置文件某一角色的署名，替换该角色原有的关联
+    ///
+    /// 名称经拆分、规范化和别名解析，返回实际关联的作者名（同一作者只保留首次出现）。
+    pub async fn set_file_authors(
+        &self,
+        file_id: &str,
+        role: AuthorRole,
+        names: &[String],
+    ) -> Result<Vec<String>> {
+        sqlx::query("DELETE FROM file_authors WHERE file_id = ? AND role = ?")
+            .bind(file_id)
+            .bind(role.as_str())
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        let mut linked: Vec<String> = Vec::new();
+        for name in names.iter().flat_map(|n| split_author_list(n)) {
+            let (author_id, name) = self.find_or_create_author(&name).await?;
+            if linked.contains(&name) {
+                continue;
+            }
+            sqlx::query(
+                "INSERT OR IGNORE INTO file_authors (file_id, author_id, role, position) VALUES (?, ?, ?, ?)",
+            )
+            .bind(file_id)
+            .bind(&author_id)
+            .bind(role.as_str())
+            .bind(linked.len() as i64)
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+            linked.push(name);
+        }
+
+        Ok(linked)
+    }
+
+    /// 设置文件的全部非作者署名，未出现的角色被清空
+    pub async fn set_file_contributors(
+        &self,
+        file_id: &str,
+        contributors: &[Contributor],
+    ) -> Result<Vec<Contributor>> {
+        let mut linked = Vec::new();
+        for role in AuthorRole::ALL {
+            if role == AuthorRole::Author {
+                continue;
+            }
+            let names: Vec<String> = contributors
+                .iter()
+                .filter(|c| c.role == role)
+                .map(|c| c.name.clone())
+                .collect();
+            for name in self.set_file_authors(file_id, role, &names).await? {
+                linked.push(Contributor::new(name, role));
+            }
+        }
+        Ok(linked)
+    }
+
+    /// 把 `alias_author_id` 标记为 `canonical_author_id` 的别名
+    ///
+    /// 原先指向别名作者的别名一并改为指向主作者，避免形成别名链。
     pub async fn add_author_alias(
         &self,
         canonical_author_id: &str,
         alias_author_id: &str,
     ) -> Result<()> {
-        // This function now should take two author IDs: the main one (canonical) and the one to be marked as alias.
-        // The `alias` string parameter is removed.
-        // We need to ensure both authors exist.
-        // Then, insert into author_aliases (alias_id, canonical_id).
-
-        // Check if alias_author_id is already an alias for someone, or if it's already a canonical_id for another alias.
-        let existing_alias_mapping = sqlx::query!(
-            "SELECT canonical_id FROM author_aliases WHERE alias_id = ?",
-            alias_author_id
-        )
-        .fetch_optional(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-
-        if let Some(existing_map) = existing_alias_mapping {
-            // existing_map.canonical_id is String (NOT NULL in db)
-            if existing_map.canonical_id == canonical_author_id {
-                // Direct comparison for String
-                return Ok(()); // Already correctly mapped
-            } else {
-                return Err(TagboxError::Config(format!(
-                    "Author {} is already an alias for {}.",
-                    alias_author_id, existing_map.canonical_id
-                )));
+        if canonical_author_id == alias_author_id {
+            return Err(TagboxError::Config("作者不能是自己的别名".to_string()));
+        }
+        self.get_author(canonical_author_id).await?;
+        self.get_author(alias_author_id).await?;
+
+        let existing: Option<String> =
+            sqlx::query_scalar("SELECT canonical_id FROM author_aliases WHERE alias_id = ?")
+                .bind(alias_author_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        if let Some(existing) = existing {
+            if existing == canonical_author_id {
+                return Ok(());
             }
+            return Err(TagboxError::Config(format!(
+                "Author {} is already an alias for {}.",
+                alias_author_id, existing
+            )));
         }
 
-        let now = current_time().to_rfc3339();
-        let note_val = "Manually added alias".to_string(); // Bind to a variable
-        sqlx::query!(
+        let canonical_is_alias: Option<String> =
+            sqlx::query_scalar("SELECT canonical_id FROM author_aliases WHERE alias_id = ?")
+                .bind(canonical_author_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        if let Some(owner) = canonical_is_alias {
+            return Err(TagboxError::Config(format!(
+                "Author {} is itself an alias for {}.",
+                canonical_author_id, owner
+            )));
+        }
+
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        sqlx::query("UPDATE author_aliases SET canonical_id = ? WHERE canonical_id = ?")
+            .bind(canonical_author_id)
+            .bind(alias_author_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        sqlx::query(
             r#"
             INSERT INTO author_aliases (alias_id, canonical_id, merged_at, note)
             VALUES (?, ?, ?, ?)
             "#,
-            alias_author_id,
-            canonical_author_id,
-            now,
-            note_val // Use the bound variable directly (not wrapped in Some)
         )
-        .execute(&self.db_pool)
+        .bind(alias_author_id)
+        .bind(canonical_author_id)
+        .bind(current_time().to_rfc3339())
+        .bind("Manually added alias")
+        .execute(&mut *tx)
         .await
         .map_err(TagboxError::Database)?;
 
+        tx.commit().await.map_err(TagboxError::Database)?;
         Ok(())
     }
 
-    /// 合并两个作者
-    pub async fn merge_authors(&self, source_id: &str, target_id: &str) -> Result<()> {
-        // 检查两个作者是否都存在
-        let source_exists = sqlx::query!(
-            r#"SELECT COUNT(*) as count FROM authors WHERE id = ?"#,
-            source_id
-        )
-        .fetch_one(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .count
-            > 0;
+    /// 取消别名关系，别名作者重新成为独立作者
+    pub async fn remove_author_alias(
+        &self,
+        canonical_author_id: &str,
+        alias_author_id: &str,
+    ) -> Result<()> {
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
 
-        let target_exists = sqlx::query!(
-            r#"SELECT COUNT(*) as count FROM authors WHERE id = ?"#,
-            target_id
-        )
-        .fetch_one(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .count
-            > 0;
+        let removed =
+            sqlx::query("DELETE FROM author_aliases WHERE alias_id = ? AND canonical_id = ?")
+                .bind(alias_author_id)
+                .bind(canonical_author_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?
+                .rows_affected();
+        if removed == 0 {
+            return Err(TagboxError::NotFound(format!(
+                "{} 不是 {} 的别名",
+                alias_author_id, canonical_author_id
+            )));
+        }
+
+        // 合并时被标记删除的作者恢复为独立作者
+        sqlx::query("UPDATE authors SET is_deleted = 0, updated_at = ? WHERE id = ?")
+            .bind(current_time().to_rfc3339())
+            .bind(alias_author_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+        Ok(())
+    }
 
-        if !source_exists || !target_exists {
+    /// 合并两个作者
+    ///
+    /// 源作者的文件关联和别名转到目标作者，目标作者缺少的资料字段从源作者补齐。
+    /// 源作者保留为目标作者的别名并标记删除，以后按旧名字导入时仍能解析到目标作者。
+    pub async fn merge_authors(&self, source_id: &str, target_id: &str) -> Result<()> {
+        if source_id == target_id {
+            return Err(TagboxError::Config("不能把作者合并到自己".to_string()));
+        }
+        let source = self.get_author(source_id).await;
+        let target = self.get_author(target_id).await;
+        if source.is_err() || target.is_err() {
             return Err(TagboxError::Config("源作者或目标作者不存在".to_string()));
         }
 
+        let target_owner: Option<String> =
+            sqlx::query_scalar("SELECT canonical_id FROM author_aliases WHERE alias_id = ?")
+                .bind(target_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        if let Some(owner) = target_owner.filter(|owner| owner != source_id) {
+            return Err(TagboxError::Config(format!(
+                "目标作者 {} 是 {} 的别名，请合并到主作者",
+                target_id, owner
+            )));
+        }
+
         // 开始事务
         let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
 
         // 1. 移动文件-作者关联
-        sqlx::query!(
+        sqlx::query(
             r#"
-            INSERT OR IGNORE INTO file_authors (file_id, author_id)
-            SELECT file_id, ? FROM file_authors WHERE author_id = ?
+            INSERT OR IGNORE INTO file_authors (file_id, author_id, role, position)
+            SELECT file_id, ?, role, position FROM file_authors WHERE author_id = ?
             "#,
-            target_id,
-            source_id
         )
+        .bind(target_id)
+        .bind(source_id)
         .execute(&mut *tx)
         .await
         .map_err(TagboxError::Database)?;
 
-        // 2. 移动作者别名: Update existing entries in `author_aliases` where `alias_id` or `canonical_id` is `source_id`.
-        //    - If `alias_id` = `source_id`, update it to `target_id` (if `target_id` isn't already an alias itself or the canonical_id).
-        //      This is complex because an alias cannot become an alias for another alias directly through merge like this.
-        //      Usually, you re-point things that pointed TO source_id, or aliases OF source_id.
-        //    - Point all aliases of `source_id` to `target_id`:
-        //      UPDATE author_aliases SET canonical_id = ? WHERE canonical_id = ?
-        //    - Mark `source_id` itself as an alias of `target_id`.
-        sqlx::query!(
+        sqlx::query("DELETE FROM file_authors WHERE author_id = ?")
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        // 2. 源作者的别名改为指向目标作者；源作者若是目标作者的别名，先解除
+        sqlx::query(
+            "DELETE FROM author_aliases WHERE alias_id = ? OR (alias_id = ? AND canonical_id = ?)",
+        )
+        .bind(source_id)
+        .bind(target_id)
+        .bind(source_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        sqlx::query("UPDATE author_aliases SET canonical_id = ? WHERE canonical_id = ?")
+            .bind(target_id)
+            .bind(source_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        // 3. 记录合并：源作者成为目标作者的别名
+        sqlx::query(
             r#"
-            UPDATE author_aliases SET canonical_id = ? WHERE canonical_id = ?
+            INSERT INTO author_aliases (alias_id, canonical_id, merged_at, note)
+            VALUES (?, ?, ?, ?)
             "#,
-            target_id, // New canonical_id
-            source_id  // Old canonical_id
         )
+        .bind(source_id)
+        .bind(target_id)
+        .bind(current_time().to_rfc3339())
+        .bind(format!("Merged from {}", source_id))
         .execute(&mut *tx)
         .await
         .map_err(TagboxError::Database)?;
 
-        // 3. 记录合并操作: Mark source_id as an alias of target_id.
-        //    The old query `INSERT INTO author_aliases (alias, author_id) SELECT name, ? FROM authors WHERE id = ?`
-        //    was trying to use `authors.name` as an alias string, which is not how `author_aliases` works.
-        let now = current_time().to_rfc3339();
-        let note_string = format!("Merged from {}", source_id);
-        sqlx::query!(
+        // 4. 补齐目标作者资料，标记源作者删除（保留行以便别名解析）
+        sqlx::query(
             r#"
-            INSERT OR IGNORE INTO author_aliases (alias_id, canonical_id, merged_at, note)
-            VALUES (?, ?, ?, ?)
+            UPDATE authors SET
+                real_name = COALESCE(real_name, (SELECT real_name FROM authors WHERE id = ?1)),
+                bio = COALESCE(bio, (SELECT bio FROM authors WHERE id = ?1)),
+                homepage = COALESCE(homepage, (SELECT homepage FROM authors WHERE id = ?1)),
+                orcid = COALESCE(orcid, (SELECT orcid FROM authors WHERE id = ?1)),
+                updated_at = ?2
+            WHERE id = ?3
             "#,
-            source_id,
-            target_id,
-            now,
-            note_string // Use the bound variable directly (not wrapped in Some)
         )
+        .bind(source_id)
+        .bind(current_time().to_rfc3339())
+        .bind(target_id)
         .execute(&mut *tx)
         .await
         .map_err(TagboxError::Database)?;
 
-        // 4. 删除源作者 (authors.is_deleted should be set to true instead of DELETE)
-        // Or, if authors table `aliases` JSON field was the source of truth for alias strings, that needs update.
-        // For now, assuming we are logically deleting by marking source_id as an alias and potentially deactivating it.
-        // The original authors.delete is fine if that's the desired behavior after merging.
-        sqlx::query!(r#"DELETE FROM authors WHERE id = ?"#, source_id)
+        sqlx::query("UPDATE authors SET is_deleted = 1, updated_at = ? WHERE id = ?")
+            .bind(current_time().to_rfc3339())
+            .bind(source_id)
             .execute(&mut *tx)
             .await
             .map_err(TagboxError::Database)?;
@@ -266,13 +618,16 @@ impl AuthorManager {
         Ok(HashMap::new())
     }
 
-    /// 查找可能的重复作者
+    /// 查找可能的重复作者，返回 (作者1, 作者2, 相似度)，按相似度降序
+    ///
+    /// 只比较主作者，已是别名或已合并的作者不参与。
     pub async fn find_duplicate_authors(&self) -> Result<Vec<(String, String, f32)>> {
-        // 简单实现：查找名称相似的作者
-        let authors_rows = sqlx::query!(
+        let authors_rows: Vec<(String, String)> = sqlx::query_as(
             r#"
-            SELECT id as "id!", name FROM authors
-            "#
+            SELECT id, name FROM authors
+            WHERE is_deleted = 0 AND id NOT IN (SELECT alias_id FROM author_aliases)
+            ORDER BY created_at
+            "#,
         )
         .fetch_all(&self.db_pool)
         .await
@@ -280,20 +635,21 @@ impl AuthorManager {
 
         let mut potential_matches = Vec::new();
 
-        for (i, author1) in authors_rows.iter().enumerate() {
-            for author2 in authors_rows.iter().skip(i + 1) {
-                let similarity = self.compute_name_similarity(&author1.name, &author2.name);
+        for (i, (id1, name1)) in authors_rows.iter().enumerate() {
+            for (id2, name2) in authors_rows.iter().skip(i + 1) {
+                let similarity = if author_key(name1) == author_key(name2) {
+                    0.95
+                } else {
+                    self.compute_name_similarity(name1, name2)
+                };
 
                 if similarity > 0.8 {
-                    potential_matches.push((
-                        author1.id.clone(), // id is now String due to "id!"
-                        author2.id.clone(), // id is now String
-                        similarity,
-                    ));
+                    potential_matches.push((id1.clone(), id2.clone(), similarity));
                 }
             }
         }
 
+        potential_matches.sort_by(|a, b| b.2.total_cmp(&a.2));
         Ok(potential_matches)
     }
 
@@ -363,3 +719,236 @@ impl AuthorManager {
         common_chars as f32 * 2.0 / (name1_norm.len() + name2_norm.len()) as f32
     }
 }
+
+/// 规范化 ORCID，格式或校验位不正确时返回 `None`
+///
+/// 接受 `https://orcid.org/` 前缀和不带连字符的写法，输出 `0000-0002-1825-0097` 形式。
+pub fn normalize_orcid(raw: &str) -> Option<String> {
+    let raw = raw.trim().trim_end_matches('/');
+    let raw = raw.rsplit('/').next().unwrap_or(raw);
+    let chars: Vec<char> = raw
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if chars.len() != 16 {
+        return None;
+    }
+
+    // ISO 7064 MOD 11-2
+    let digits = chars[..15]
+        .iter()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<u32>>>()?;
+    let total = digits.iter().fold(0, |total, d| (total + d) * 2);
+    let check = match (12 - total % 11) % 11 {
+        10 => 'X',
+        d => char::from_digit(d, 10)?,
+    };
+    if chars[15] != check {
+        return None;
+    }
+
+    let orcid: String = chars.iter().collect();
+    Some(format!(
+        "{}-{}-{}-{}",
+        &orcid[..4],
+        &orcid[4..8],
+        &orcid[8..12],
+        &orcid[12..]
+    ))
+}
+
+/// 作者之间的分隔符（逗号另行判断）
+const AUTHOR_SEPARATORS: &[char] = &[';', '；', '、', '&', '＆', '，'];
+
+/// 名字后缀，`Doe, Jr., John` 中的 `Jr.`
+const NAME_SUFFIXES: &[&str] = &["jr", "jr.", "sr", "sr.", "ii", "iii", "iv"];
+
+/// 把元数据中的作者字段拆成单个作者
+///
+/// 按 `;`、`and`、`&`（以及中文的 `、`、`；`、`，`）分隔。逗号既可能分隔作者，也可能是
+/// `姓, 名` 写法：逗号两边能两两配成"单个姓 + 名"时按后者处理。
+/// 拆出的名字经过 [`normalize_author_name`] 规范化，`et al.` 和 `others` 被丢弃。
+pub fn split_author_list(raw: &str) -> Vec<String> {
+    let raw = match raw.find(" et al").or_else(|| raw.find(" Et al")) {
+        Some(index) => &raw[..index],
+        None => raw,
+    };
+
+    let mut names: Vec<String> = Vec::new();
+    for segment in raw.split(AUTHOR_SEPARATORS).flat_map(split_on_and) {
+        for name in split_commas(&segment) {
+            let name = normalize_author_name(&name);
+            if !name.is_empty() && !name.eq_ignore_ascii_case("others") && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// 规范化单个作者名
+///
+/// 合并空白，`姓, 名` 和 `姓, Jr., 名` 转为 `名 姓` 和 `名 姓 Jr.`；
+/// 中日韩姓名去掉姓与名之间的空格（`张, 三` 和 `张 三` 都得到 `张三`）。
+pub fn normalize_author_name(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name = name.trim_matches(|c: char| c == ',' || c == ';');
+    let parts: Vec<&str> = name
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let ordered = match parts.as_slice() {
+        [family, suffix, given] if is_suffix(suffix) => {
+            format!("{} {} {}", given, family, suffix)
+        }
+        [name, suffix] if is_suffix(suffix) => format!("{} {}", name, suffix),
+        [family, given] if family.chars().any(is_cjk) => format!("{}{}", family, given),
+        [family, given] => format!("{} {}", given, family),
+        _ => parts.join(", "),
+    };
+
+    if ordered.chars().any(is_cjk) && ordered.chars().all(|c| is_cjk(c) || c == ' ' || c == '·') {
+        return ordered.replace(' ', "");
+    }
+    ordered
+}
+
+/// 作者名的比较键：规范化后去掉重音、标点和大小写差异
+pub(crate) fn author_key(name: &str) -> String {
+    let mut key = String::new();
+    for c in normalize_author_name(name).chars() {
+        match fold_accent(c) {
+            Some(folded) => key.push_str(folded),
+            None if c.is_alphanumeric() => key.extend(c.to_lowercase()),
+            None => key.push(' '),
+        }
+    }
+    key.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 按独立的单词 `and` 拆分
+fn split_on_and(segment: &str) -> Vec<String> {
+    let mut parts = vec![Vec::new()];
+    for word in segment.split_whitespace() {
+        if word.eq_ignore_ascii_case("and") {
+            parts.push(Vec::new());
+        } else {
+            parts.last_mut().unwrap().push(word);
+        }
+    }
+    parts
+        .into_iter()
+        .filter(|words| !words.is_empty())
+        .map(|words| words.join(" "))
+        .collect()
+}
+
+/// 拆分逗号：`姓, 名` 两两配对时保留为一个名字，否则每段是一个作者
+fn split_commas(segment: &str) -> Vec<String> {
+    let parts: Vec<&str> = segment
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    match parts.as_slice() {
+        [] => Vec::new(),
+        [_] => vec![segment.to_string()],
+        [family, suffix, _] if is_family_name(family) && is_suffix(suffix) => {
+            vec![parts.join(", ")]
+        }
+        _ if parts.len().is_multiple_of(2)
+            && parts
+                .chunks(2)
+                .all(|pair| is_family_name(pair[0]) && !is_suffix(pair[1])) =>
+        {
+            parts.chunks(2).map(|pair| pair.join(", ")).collect()
+        }
+        _ => parts.iter().map(|p| p.to_string()).collect(),
+    }
+}
+
+/// 是否像单独的姓：去掉 `van`、`de` 等前缀后只剩一个词，且不是缩写
+fn is_family_name(part: &str) -> bool {
+    let words: Vec<&str> = part
+        .split_whitespace()
+        .filter(|w| !NAME_PARTICLES.contains(&w.to_lowercase().as_str()))
+        .collect();
+    matches!(words.as_slice(), [word] if !word.ends_with('.'))
+}
+
+fn is_suffix(part: &str) -> bool {
+    NAME_SUFFIXES.contains(&part.to_lowercase().as_str())
+}
+
+/// 中日韩文字（汉字、假名、谚文）
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2A6DF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_author_list() {
+        assert_eq!(
+            split_author_list("Vaswani, A.; Shazeer, N. and Parmar, N."),
+            vec!["A. Vaswani", "N. Shazeer", "N. Parmar"]
+        );
+        assert_eq!(
+            split_author_list("Ashish Vaswani, Noam Shazeer, and Niki Parmar"),
+            vec!["Ashish Vaswani", "Noam Shazeer", "Niki Parmar"]
+        );
+        assert_eq!(
+            split_author_list("Vaswani, A., Shazeer, N., van der Berg, J."),
+            vec!["A. Vaswani", "N. Shazeer", "J. van der Berg"]
+        );
+        assert_eq!(
+            split_author_list("Knuth, Donald E. & Doe, Jr., John"),
+            vec!["Donald E. Knuth", "John Doe Jr."]
+        );
+        assert_eq!(
+            split_author_list("张三、李 四，王, 五"),
+            vec!["张三", "李四", "王五"]
+        );
+        assert_eq!(split_author_list("Jane Doe et al."), vec!["Jane Doe"]);
+        assert_eq!(
+            split_author_list("Alice Smith and others"),
+            vec!["Alice Smith"]
+        );
+        assert!(split_author_list("  ").is_empty());
+    }
+
+    #[test]
+    fn test_author_key() {
+        assert_eq!(author_key("Müller, Jürgen"), author_key("jurgen muller"));
+        assert_eq!(author_key("A.B. Smith"), author_key("A. B. Smith"));
+        assert_ne!(author_key("A. Smith"), author_key("Alice Smith"));
+        assert_eq!(author_key("陣内 美勇"), "陣内美勇");
+    }
+
+    #[test]
+    fn test_normalize_orcid() {
+        assert_eq!(
+            normalize_orcid("https://orcid.org/0000-0002-1825-0097").as_deref(),
+            Some("0000-0002-1825-0097")
+        );
+        assert_eq!(
+            normalize_orcid("000000021694233x").as_deref(),
+            Some("0000-0002-1694-233X")
+        );
+        assert_eq!(normalize_orcid("0000-0002-1825-0098"), None);
+        assert_eq!(normalize_orcid("0000-0002-1825"), None);
+    }
+}
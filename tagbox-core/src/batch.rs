@@ -0,0 +1,501 @@
+use crate::categories::CategoryPath;
+use crate::config::AppConfig;
+use crate::editor::Editor;
+use crate::errors::{Result, TagboxError};
+use crate::history::{FileHistoryManager, FileOperation};
+use crate::tags::{create_tag_chain, is_in_subtree, TagManager};
+use crate::types::{EditBatch, FilePatch, FilePatchChange};
+use crate::utils::{current_time, generate_uuid};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::path::Path;
+
+/// 一次批量编辑最多处理的文件数
+pub(crate) const MAX_BATCH_FILES: usize = 10_000;
+
+/// 批量编辑器
+///
+/// 把 [`FilePatch`] 应用到一组文件：数据库修改在一个事务中完成，并作为一个批次记录在
+/// `edit_batches`、`edit_batch_files` 和 `file_history` 中，之后可用
+/// [`BatchEditor::undo_batch`] 撤销。文件移动无法放进事务，在提交后逐个进行。
+pub struct BatchEditor {
+    db_pool: SqlitePool,
+}
+
+impl BatchEditor {
+    /// 创建一个新的批量编辑器
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 把补丁应用到给定文件，返回每个有变化的文件的修改前后状态
+    ///
+    /// 先移除 `remove_tags`（含子标签）再加入 `add_tags`，别名解析为目标标签。
+    /// 预演时只计算变化，不修改数据库和文件；`description` 记录在批次上，通常是搜索查询。
+    pub async fn apply_patch(
+        &self,
+        file_ids: &[String],
+        patch: &FilePatch,
+        description: &str,
+        config: &AppConfig,
+        dry_run: bool,
+    ) -> Result<EditBatch> {
+        if patch.is_empty() {
+            return Err(TagboxError::Config(
+                "补丁没有任何修改（需要添加/移除标签或设置分类）".to_string(),
+            ));
+        }
+
+        let tag_manager = TagManager::new(self.db_pool.clone());
+        let add_tags = self.resolve_tags(&tag_manager, &patch.add_tags).await?;
+        let remove_tags = self.resolve_tags(&tag_manager, &patch.remove_tags).await?;
+        let set_category = patch.set_category.as_deref().map(CategoryPath::parse);
+        if set_category.as_ref().is_some_and(CategoryPath::is_empty) {
+            return Err(TagboxError::Config("分类不能为空".to_string()));
+        }
+
+        let mut unique_ids: Vec<&String> = Vec::new();
+        for file_id in file_ids {
+            if !unique_ids.contains(&file_id) {
+                unique_ids.push(file_id);
+            }
+        }
+
+        let editor = Editor::new(self.db_pool.clone());
+        let storage_dir = &config.import.paths.storage_dir;
+        let mut changes = Vec::new();
+        for file_id in &unique_ids {
+            let mut file = editor.get_file(file_id).await?;
+            let old_tags = tag_manager.get_file_tags(file_id).await?;
+            let new_tags = patch_tags(&old_tags, &add_tags, &remove_tags);
+            let old_category = CategoryPath::of_file(&file);
+            let new_category = set_category.clone().unwrap_or_else(|| old_category.clone());
+            if new_tags == old_tags && new_category == old_category {
+                continue;
+            }
+
+            let old_path = storage_dir.join(&file.path);
+            let new_path = if patch.move_files && new_category != old_category {
+                (file.category1, file.category2, file.category3) = new_category.to_columns();
+                editor.expected_path(&file, config)?
+            } else {
+                old_path.clone()
+            };
+            changes.push(FilePatchChange {
+                file_id: file.id,
+                title: file.title,
+                old_tags,
+                new_tags,
+                old_category: old_category.to_string(),
+                new_category: new_category.to_string(),
+                old_path,
+                new_path,
+                moved: false,
+            });
+        }
+
+        let matched = unique_ids.len();
+        if dry_run || changes.is_empty() {
+            return Ok(EditBatch {
+                batch_id: None,
+                matched,
+                changes,
+            });
+        }
+
+        let batch_id = self
+            .commit_changes(&changes, description, None, storage_dir)
+            .await?;
+        self.move_files(&batch_id, &mut changes, config).await?;
+
+        Ok(EditBatch {
+            batch_id: Some(batch_id),
+            matched,
+            changes,
+        })
+    }
+
+    /// 撤销一个批次：只回退该批次本身的修改，批次之后的编辑保留
+    ///
+    /// 移除批次加入的标签、加回批次移除的标签；分类仍是批次设置的值时才恢复原分类，
+    /// 此时批次移动过的文件也移回原位置。撤销本身记录为一个新批次，返回的变化是
+    /// 从当前状态到撤销后的状态，没有需要回退内容的文件不计入。
+    pub async fn undo_batch(&self, batch_id: &str, config: &AppConfig) -> Result<EditBatch> {
+        let undone_at: Option<Option<String>> =
+            sqlx::query_scalar("SELECT undone_at FROM edit_batches WHERE id = ?")
+                .bind(batch_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        match undone_at {
+            None => {
+                return Err(TagboxError::NotFound(format!(
+                    "批量编辑不存在: {}",
+                    batch_id
+                )))
+            }
+            Some(Some(undone_at)) => {
+                return Err(TagboxError::Config(format!(
+                    "批量编辑 {} 已于 {} 撤销",
+                    batch_id, undone_at
+                )))
+            }
+            Some(None) => {}
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT b.file_id, f.title, f.relative_path, b.old_tags, b.new_tags,
+                   b.old_category, b.new_category, b.old_path, b.new_path
+            FROM edit_batch_files b JOIN files f ON f.id = b.file_id
+            WHERE b.batch_id = ?
+            ORDER BY f.title
+            "#,
+        )
+        .bind(batch_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        let tag_manager = TagManager::new(self.db_pool.clone());
+        let editor = Editor::new(self.db_pool.clone());
+        let storage_dir = &config.import.paths.storage_dir;
+        let mut changes = Vec::new();
+        for row in &rows {
+            let file_id: String = row.get("file_id");
+            let batch_old_tags: Vec<String> =
+                serde_json::from_str(&row.get::<String, _>("old_tags"))?;
+            let batch_new_tags: Vec<String> =
+                serde_json::from_str(&row.get::<String, _>("new_tags"))?;
+            let current_tags = tag_manager.get_file_tags(&file_id).await?;
+            let new_tags = unpatch_tags(&current_tags, &batch_old_tags, &batch_new_tags);
+
+            // 分类在批次之后又被修改过时保留当前分类，文件也留在当前位置
+            let file = editor.get_file(&file_id).await?;
+            let current_category = CategoryPath::of_file(&file).to_string();
+            let batch_new_category: String = row.get("new_category");
+            let batch_old_category: String = row.get("old_category");
+            let restore_category =
+                batch_old_category != batch_new_category && current_category == batch_new_category;
+            let new_category = if restore_category {
+                batch_old_category
+            } else {
+                current_category.clone()
+            };
+
+            let current_path: String = row.get("relative_path");
+            let batch_new_path: String = row.get("new_path");
+            let batch_old_path: String = row.get("old_path");
+            let old_path = storage_dir.join(&current_path);
+            let new_path = if restore_category
+                && batch_old_path != batch_new_path
+                && current_path == batch_new_path
+            {
+                storage_dir.join(batch_old_path)
+            } else {
+                old_path.clone()
+            };
+
+            if new_tags == current_tags && new_category == current_category && new_path == old_path
+            {
+                continue;
+            }
+            changes.push(FilePatchChange {
+                file_id,
+                title: row.get("title"),
+                old_tags: current_tags,
+                new_tags,
+                old_category: current_category,
+                new_category,
+                old_path,
+                new_path,
+                moved: false,
+            });
+        }
+
+        let matched = rows.len();
+        let undo_id = self
+            .commit_changes(
+                &changes,
+                &format!("撤销批量编辑 {}", batch_id),
+                Some(batch_id),
+                storage_dir,
+            )
+            .await?;
+        self.move_files(&undo_id, &mut changes, config).await?;
+
+        Ok(EditBatch {
+            batch_id: Some(undo_id),
+            matched,
+            changes,
+        })
+    }
+
+    /// 在一个事务中写入所有文件的新标签和分类，并记录批次和历史，返回批次 ID
+    ///
+    /// `undoes` 为被撤销的批次，会同时标记为已撤销。
+    async fn commit_changes(
+        &self,
+        changes: &[FilePatchChange],
+        description: &str,
+        undoes: Option<&str>,
+        storage_dir: &Path,
+    ) -> Result<String> {
+        let batch_id = generate_uuid();
+        let now = current_time().to_rfc3339();
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        sqlx::query("INSERT INTO edit_batches (id, description, created_at) VALUES (?, ?, ?)")
+            .bind(&batch_id)
+            .bind(description)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        for change in changes {
+            write_file_state(&mut *tx, change, &now).await?;
+
+            // 路径先记为移动前的位置，移动成功后再更新
+            let old_path = stored_path(&change.old_path, storage_dir);
+            sqlx::query(
+                r#"
+                INSERT INTO edit_batch_files
+                    (batch_id, file_id, old_tags, new_tags, old_category, new_category, old_path, new_path)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&batch_id)
+            .bind(&change.file_id)
+            .bind(serde_json::to_string(&change.old_tags)?)
+            .bind(serde_json::to_string(&change.new_tags)?)
+            .bind(&change.old_category)
+            .bind(&change.new_category)
+            .bind(&old_path)
+            .bind(&old_path)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+            FileHistoryManager::record_batch_history(
+                &mut *tx,
+                &batch_id,
+                &change.file_id,
+                FileOperation::Patch,
+                Some(&describe_change(change)),
+            )
+            .await?;
+        }
+
+        if let Some(undoes) = undoes {
+            sqlx::query("UPDATE edit_batches SET undone_at = ? WHERE id = ?")
+                .bind(&now)
+                .bind(undoes)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+        Ok(batch_id)
+    }
+
+    /// 把需要移动的文件移到 `new_path`，成功的记入批次；失败时保留原位置并标记 `moved = false`
+    async fn move_files(
+        &self,
+        batch_id: &str,
+        changes: &mut [FilePatchChange],
+        config: &AppConfig,
+    ) -> Result<()> {
+        let editor = Editor::new(self.db_pool.clone());
+        let storage_dir = &config.import.paths.storage_dir;
+        for change in changes.iter_mut().filter(|c| c.old_path != c.new_path) {
+            if let Err(e) = editor
+                .move_to(&change.file_id, &change.old_path, &change.new_path, config)
+                .await
+            {
+                tracing::warn!("移动文件 {} 失败: {}", change.file_id, e);
+                continue;
+            }
+            change.moved = true;
+
+            let old_path = stored_path(&change.old_path, storage_dir);
+            let new_path = stored_path(&change.new_path, storage_dir);
+            let mut conn = self
+                .db_pool
+                .acquire()
+                .await
+                .map_err(TagboxError::Database)?;
+            sqlx::query(
+                "UPDATE edit_batch_files SET new_path = ? WHERE batch_id = ? AND file_id = ?",
+            )
+            .bind(&new_path)
+            .bind(batch_id)
+            .bind(&change.file_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(TagboxError::Database)?;
+            FileHistoryManager::record_batch_history(
+                &mut *conn,
+                batch_id,
+                &change.file_id,
+                FileOperation::Move { old_path, new_path },
+                None,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// 解析标签别名并去重，忽略空标签
+    async fn resolve_tags(&self, tag_manager: &TagManager, tags: &[String]) -> Result<Vec<String>> {
+        let mut resolved = Vec::new();
+        for tag in tags {
+            let path = tag_manager.resolve_tag_path(tag).await?;
+            if !path.is_empty() && !resolved.contains(&path) {
+                resolved.push(path);
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// 在事务中把文件的标签、分类和 FTS 标签列改为 `change` 的新值
+async fn write_file_state(
+    conn: &mut SqliteConnection,
+    change: &FilePatchChange,
+    now: &str,
+) -> Result<()> {
+    sqlx::query("DELETE FROM file_tags WHERE file_id = ?")
+        .bind(&change.file_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(TagboxError::Database)?;
+    for tag in &change.new_tags {
+        let tag_id = create_tag_chain(&mut *conn, tag).await?;
+        sqlx::query("INSERT OR IGNORE INTO file_tags (file_id, tag_id) VALUES (?, ?)")
+            .bind(&change.file_id)
+            .bind(&tag_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(TagboxError::Database)?;
+    }
+
+    // 分类列由触发器从 category_path 派生
+    sqlx::query("UPDATE files SET category_path = ?, updated_at = ? WHERE id = ?")
+        .bind(CategoryPath::parse(&change.new_category).to_string())
+        .bind(now)
+        .bind(&change.file_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(TagboxError::Database)?;
+
+    sqlx::query(
+        r#"
+        UPDATE files_fts SET tags = ?
+        WHERE rowid = (SELECT rowid FROM files WHERE id = ?)
+        "#,
+    )
+    .bind(change.new_tags.join(" "))
+    .bind(&change.file_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(TagboxError::Database)?;
+
+    Ok(())
+}
+
+/// 先移除 `remove`（含子标签）再加入 `add`，结果按路径排序
+fn patch_tags(old: &[String], add: &[String], remove: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = old
+        .iter()
+        .filter(|tag| !remove.iter().any(|root| is_in_subtree(tag, root)))
+        .cloned()
+        .collect();
+    for tag in add {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    tags.sort();
+    tags
+}
+
+/// 在当前标签上回退一次修改（`before` → `after`）：移除当时加入的标签，加回当时移除的标签
+fn unpatch_tags(current: &[String], before: &[String], after: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = current
+        .iter()
+        .filter(|tag| !after.contains(tag) || before.contains(tag))
+        .cloned()
+        .collect();
+    for tag in before.iter().filter(|tag| !after.contains(tag)) {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    tags.sort();
+    tags
+}
+
+/// 历史记录中的变更说明，如 `+reviewed, -draft, 分类 Inbox → Archive/2025`
+fn describe_change(change: &FilePatchChange) -> String {
+    let mut parts: Vec<String> = change
+        .new_tags
+        .iter()
+        .filter(|tag| !change.old_tags.contains(tag))
+        .map(|tag| format!("+{}", tag))
+        .collect();
+    parts.extend(
+        change
+            .old_tags
+            .iter()
+            .filter(|tag| !change.new_tags.contains(tag))
+            .map(|tag| format!("-{}", tag)),
+    );
+    if change.old_category != change.new_category {
+        parts.push(format!(
+            "分类 {} → {}",
+            change.old_category, change.new_category
+        ));
+    }
+    parts.join(", ")
+}
+
+/// 记入批次的路径：存储目录下的文件记为相对路径，存储目录外的文件保留原路径
+fn stored_path(path: &Path, storage_dir: &Path) -> String {
+    path.strip_prefix(storage_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_tags() {
+        let old = vec![
+            "draft".to_string(),
+            "draft/v1".to_string(),
+            "drafting".to_string(),
+        ];
+        let tags = patch_tags(&old, &["reviewed".to_string()], &["draft".to_string()]);
+        assert_eq!(tags, vec!["drafting", "reviewed"]);
+
+        // 已有的标签不重复添加
+        let tags = patch_tags(&tags, &["reviewed".to_string()], &[]);
+        assert_eq!(tags, vec!["drafting", "reviewed"]);
+    }
+
+    #[test]
+    fn test_unpatch_tags() {
+        let before = vec!["draft".to_string(), "rust".to_string()];
+        let after = vec!["reviewed".to_string(), "rust".to_string()];
+        // 修改之后又加了 urgent、删了 rust
+        let current = vec!["reviewed".to_string(), "urgent".to_string()];
+        assert_eq!(
+            unpatch_tags(&current, &before, &after),
+            vec!["draft", "urgent"]
+        );
+    }
+}
@@ -0,0 +1,591 @@
+use super::BibEntry;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// 按原样读写、不做 LaTeX 转换的字段
+const VERBATIM_FIELDS: &[&str] = &["url", "doi", "file", "eprint", "isbn", "issn"];
+
+/// 人名列表字段，以 ` and ` 分隔
+const NAME_FIELDS: &[&str] = &["author", "editor", "translator", "illustrator"];
+
+/// 重音命令：(命令, 基本字母, 组合后的字符)
+const ACCENTS: &[(char, &str, &str)] = &[
+    ('\'', "aeiouyAEIOUYcnszCNSZ", "áéíóúýÁÉÍÓÚÝćńśźĆŃŚŹ"),
+    ('`', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+    ('^', "aeiouAEIOU", "âêîôûÂÊÎÔÛ"),
+    ('"', "aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+    ('~', "anoANO", "ãñõÃÑÕ"),
+    ('c', "cscS", "çşÇŞ"),
+    ('v', "cszrnCSZRNe", "čšžřňČŠŽŘŇě"),
+    ('H', "oOuU", "őŐűŰ"),
+    ('=', "aeiouAEIOU", "āēīōūĀĒĪŌŪ"),
+    ('.', "zZ", "żŻ"),
+    ('u', "agAG", "ăğĂĞ"),
+    ('r', "uU", "ůŮ"),
+];
+
+/// 不带参数的特殊字母命令
+const LETTERS: &[(&str, &str)] = &[
+    ("ss", "ß"),
+    ("o", "ø"),
+    ("O", "Ø"),
+    ("ae", "æ"),
+    ("AE", "Æ"),
+    ("oe", "œ"),
+    ("OE", "Œ"),
+    ("aa", "å"),
+    ("AA", "Å"),
+    ("l", "ł"),
+    ("L", "Ł"),
+    ("i", "ı"),
+];
+
+/// 转义字符的文本命令
+const SYMBOLS: &[(&str, &str)] = &[
+    ("textbackslash", "\\"),
+    ("textasciitilde", "~"),
+    ("textasciicircum", "^"),
+    ("textendash", "–"),
+    ("textemdash", "—"),
+];
+
+lazy_static! {
+    // \'e、\'{e}、{\'e}、\"{o}、\c{c}、\v s 等
+    static ref ACCENT_RE: Regex =
+        Regex::new(r#"\{?\\([`'^"~=.]|[cvHur](?:\s+|\b))\s*\{?([A-Za-z]|\\i)\}?\}?"#).unwrap();
+    static ref LETTER_RE: Regex =
+        Regex::new(r"\{?\\(ss|ae|AE|oe|OE|aa|AA|o|O|l|L|i)\b\s*\}?").unwrap();
+}
+
+/// 解析 BibTeX 文本
+///
+/// 支持 `@string` 宏、`#` 拼接和 `@type(...)` 写法，跳过 `@comment` 和 `@preamble`；
+/// 无法解析的条目会被跳过，不影响其余条目。
+pub fn parse_bibtex(input: &str) -> Vec<BibEntry> {
+    let mut parser = Parser {
+        chars: input.chars().collect(),
+        pos: 0,
+        strings: month_macros(),
+    };
+
+    let mut entries = Vec::new();
+    while parser.skip_to('@') {
+        match parser.entry() {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => {}
+            Err(message) => debug!("跳过无法解析的 BibTeX 条目: {}", message),
+        }
+    }
+
+    entries
+}
+
+/// 生成 BibTeX 文本
+pub fn write_bibtex(entries: &[BibEntry]) -> String {
+    let mut out = String::new();
+
+    for entry in entries {
+        out.push_str(&format!("@{}{{{},\n", entry.entry_type, entry.key));
+        for (name, value) in &entry.fields {
+            out.push_str(&format!(
+                "  {} = {{{}}},\n",
+                name,
+                encode_field(name, value)
+            ));
+        }
+        out.push_str("}\n\n");
+    }
+
+    out
+}
+
+fn month_macros() -> HashMap<String, String> {
+    [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, month)| (month.to_string(), (i + 1).to_string()))
+    .collect()
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    strings: HashMap<String, String>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// 跳到指定字符之后，没有找到时返回 false
+    fn skip_to(&mut self, target: char) -> bool {
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == target {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_ws();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("第 {} 个字符处缺少 '{}'", self.pos, expected))
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(&predicate) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// 条目类型、字段名和宏名
+    fn name(&mut self) -> String {
+        self.take_while(|c| c.is_alphanumeric() || "_-:.+".contains(c))
+    }
+
+    /// 解析 `@` 之后的一个条目；`@string`、`@comment`、`@preamble` 返回 None
+    fn entry(&mut self) -> Result<Option<BibEntry>, String> {
+        self.skip_ws();
+        let entry_type = self.name().to_lowercase();
+        self.skip_ws();
+
+        let close = match self.peek() {
+            Some('{') => '}',
+            Some('(') => ')',
+            _ => return Err(format!("@{} 后缺少 {{", entry_type)),
+        };
+
+        match entry_type.as_str() {
+            "comment" | "preamble" => {
+                self.group(close)?;
+                return Ok(None);
+            }
+            "string" => {
+                self.pos += 1;
+                self.skip_ws();
+                let name = self.name().to_lowercase();
+                self.expect('=')?;
+                let value = self.value()?;
+                self.strings.insert(name, value);
+                self.expect(close)?;
+                return Ok(None);
+            }
+            _ => self.pos += 1,
+        }
+
+        self.skip_ws();
+        let key = self.take_while(|c| c != ',' && c != close && !c.is_whitespace());
+        let mut entry = BibEntry::new(&entry_type, &key);
+
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                    continue;
+                }
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                None => return Err(format!("条目 {} 没有结束", entry.key)),
+                _ => {}
+            }
+
+            let name = self.name().to_lowercase();
+            if name.is_empty() {
+                return Err(format!("条目 {} 中有无效字段", entry.key));
+            }
+            self.expect('=')?;
+            let raw = self.value()?;
+            entry.set(&name, &field_text(&name, &raw));
+        }
+
+        Ok(Some(entry))
+    }
+
+    /// 字段值：花括号、引号、数字或宏，可用 `#` 拼接
+    fn value(&mut self) -> Result<String, String> {
+        let mut value = String::new();
+
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some('{') => value.push_str(&self.group('}')?),
+                Some('"') => value.push_str(&self.quoted()?),
+                Some(c) if c.is_alphanumeric() => {
+                    let word = self.name();
+                    match self.strings.get(&word.to_lowercase()) {
+                        Some(expanded) => value.push_str(expanded),
+                        None => value.push_str(&word),
+                    }
+                }
+                _ => return Err(format!("第 {} 个字符处缺少字段值", self.pos)),
+            }
+
+            self.skip_ws();
+            if self.peek() == Some('#') {
+                self.pos += 1;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// 括号包围的内容（不含最外层括号），内部括号和 `\{` 原样保留
+    fn group(&mut self, close: char) -> Result<String, String> {
+        let open = self.peek().unwrap_or_default();
+        self.pos += 1;
+        let start = self.pos;
+        let mut depth = 1;
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '\\' => self.pos += 1,
+                c if c == open => depth += 1,
+                c if c == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(self.chars[start..self.pos - 1].iter().collect());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err("括号不匹配".to_string())
+    }
+
+    /// 引号包围的内容，花括号内的引号不结束字段
+    fn quoted(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let start = self.pos;
+        let mut depth = 0;
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '\\' => self.pos += 1,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '"' if depth == 0 => {
+                    return Ok(self.chars[start..self.pos - 1].iter().collect());
+                }
+                _ => {}
+            }
+        }
+
+        Err("引号不匹配".to_string())
+    }
+}
+
+/// 把字段的原始值转为纯文本
+fn field_text(name: &str, raw: &str) -> String {
+    if name == "file" {
+        raw.trim().to_string()
+    } else if VERBATIM_FIELDS.contains(&name) {
+        raw.split_whitespace()
+            .collect::<String>()
+            .replace(['{', '}'], "")
+    } else if NAME_FIELDS.contains(&name) {
+        split_names(raw)
+            .iter()
+            .map(|name| decode_latex(&normalize_name(name)))
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    } else {
+        decode_latex(raw)
+    }
+}
+
+/// 在顶层（不在花括号内）的 `and` 处切分人名列表
+fn split_names(raw: &str) -> Vec<String> {
+    let mut names = vec![Vec::new()];
+    let mut word = String::new();
+    let mut depth = 0;
+
+    let flush = |word: &mut String, names: &mut Vec<Vec<String>>| {
+        if word.eq_ignore_ascii_case("and") {
+            names.push(Vec::new());
+        } else if !word.is_empty() {
+            names.last_mut().unwrap().push(word.clone());
+        }
+        word.clear();
+    };
+
+    for c in raw.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                flush(&mut word, &mut names);
+                continue;
+            }
+            _ => {}
+        }
+        word.push(c);
+    }
+    flush(&mut word, &mut names);
+
+    names
+        .into_iter()
+        .filter(|words| !words.is_empty())
+        .map(|words| words.join(" "))
+        .collect()
+}
+
+/// `Last, First` 和 `Last, Jr., First` 转为 `First Last [Jr.]`
+fn normalize_name(name: &str) -> String {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for c in name.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current.trim().to_string());
+
+    let ordered = match parts.as_slice() {
+        [last, first] => vec![first.as_str(), last.as_str()],
+        [last, jr, first, ..] => vec![first.as_str(), last.as_str(), jr.as_str()],
+        _ => parts.iter().map(String::as_str).collect(),
+    };
+
+    ordered
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 把常见的 LaTeX 写法转为 Unicode 纯文本
+pub(crate) fn decode_latex(text: &str) -> String {
+    let text = ACCENT_RE.replace_all(text, |caps: &regex::Captures| {
+        let command = caps[1].trim().chars().next().unwrap_or_default();
+        let letter = if &caps[2] == "\\i" { "i" } else { &caps[2] };
+        accent(command, letter).unwrap_or_else(|| letter.to_string())
+    });
+    let text = LETTER_RE.replace_all(&text, |caps: &regex::Captures| {
+        LETTERS
+            .iter()
+            .find(|(command, _)| *command == &caps[1])
+            .map(|(_, letter)| letter.to_string())
+            .unwrap_or_default()
+    });
+
+    let text = text
+        .replace("---", "—")
+        .replace("--", "–")
+        .replace("``", "“")
+        .replace("''", "”");
+
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.peek() {
+                Some(&next) if "&%$#_{}\\ ".contains(next) => {
+                    out.push(next);
+                    chars.next();
+                }
+                Some(next) if next.is_ascii_alphabetic() => {
+                    let mut command = String::new();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                        command.push(c);
+                        chars.next();
+                    }
+                    while chars.peek() == Some(&' ') {
+                        chars.next();
+                    }
+                    // 其余命令（\emph、\textbf 等）只保留参数
+                    if let Some((_, symbol)) = SYMBOLS.iter().find(|(name, _)| *name == command) {
+                        out.push_str(symbol);
+                    }
+                }
+                _ => {}
+            },
+            '{' | '}' => {}
+            '~' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn accent(command: char, letter: &str) -> Option<String> {
+    let (_, bases, accented) = ACCENTS.iter().find(|(c, _, _)| *c == command)?;
+    let index = bases.chars().position(|b| letter.starts_with(b))?;
+    accented.chars().nth(index).map(String::from)
+}
+
+/// 去掉重音后的 ASCII 写法，用于生成引用键
+pub(crate) fn fold_accent(c: char) -> Option<&'static str> {
+    const FOLDED: &[(char, &str)] = &[
+        ('ß', "ss"),
+        ('ø', "o"),
+        ('Ø', "o"),
+        ('æ', "ae"),
+        ('Æ', "ae"),
+        ('œ', "oe"),
+        ('Œ', "oe"),
+        ('å', "a"),
+        ('Å', "a"),
+        ('ł', "l"),
+        ('Ł', "l"),
+        ('ı', "i"),
+    ];
+    if let Some((_, folded)) = FOLDED.iter().find(|(letter, _)| *letter == c) {
+        return Some(folded);
+    }
+
+    const ASCII: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    ACCENTS.iter().find_map(|(_, bases, accented)| {
+        let index = accented.chars().position(|a| a == c)?;
+        let base = bases.chars().nth(index)?;
+        let offset = ASCII.find(base.to_ascii_lowercase())?;
+        Some(&ASCII[offset..offset + 1])
+    })
+}
+
+/// 把纯文本字段写成 BibTeX 值
+fn encode_field(name: &str, value: &str) -> String {
+    if VERBATIM_FIELDS.contains(&name) {
+        return value.replace(['{', '}'], "");
+    }
+
+    let mut out = String::new();
+    for c in value.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bibtex() {
+        let input = r#"
+% 注释会被忽略
+@comment{jabref-meta: databaseType:bibtex;}
+@string{ neurips = "Advances in Neural Information Processing Systems" }
+
+@inproceedings{vaswani2017attention,
+  author    = {Vaswani, Ashish and Shazeer, Noam and Parmar, Niki and others},
+  title     = {Attention Is {All} You Need},
+  booktitle = neurips # " 30",
+  year      = 2017,
+  month     = jun,
+  pages     = {5998--6008},
+  doi       = {10.48550/arXiv.1706.03762},
+  file      = {:pdfs/attention.pdf:PDF},
+}
+
+@Article(schroedinger,
+  author = "Erwin Schr{\"o}dinger and M{\"{u}}ller, Jr., Hans",
+  title = "Die gegenw{\"a}rtige Situation in der Quantenmechanik \& mehr",
+  journal = {Naturwissenschaften},
+  year = {1935}
+)
+"#;
+
+        let entries = parse_bibtex(input);
+        assert_eq!(entries.len(), 2);
+
+        let attention = &entries[0];
+        assert_eq!(attention.entry_type, "inproceedings");
+        assert_eq!(attention.key, "vaswani2017attention");
+        assert_eq!(
+            attention.authors(),
+            vec!["Ashish Vaswani", "Noam Shazeer", "Niki Parmar"]
+        );
+        assert_eq!(attention.title(), Some("Attention Is All You Need"));
+        assert_eq!(
+            attention.venue(),
+            Some("Advances in Neural Information Processing Systems 30")
+        );
+        assert_eq!(attention.year(), Some(2017));
+        assert_eq!(attention.get("month"), Some("6"));
+        assert_eq!(attention.get("pages"), Some("5998–6008"));
+        assert_eq!(attention.file_paths(), vec!["pdfs/attention.pdf"]);
+
+        let schroedinger = &entries[1];
+        assert_eq!(schroedinger.entry_type, "article");
+        assert_eq!(
+            schroedinger.authors(),
+            vec!["Erwin Schrödinger", "Hans Müller Jr."]
+        );
+        assert_eq!(
+            schroedinger.title(),
+            Some("Die gegenwärtige Situation in der Quantenmechanik & mehr")
+        );
+    }
+
+    #[test]
+    fn test_decode_latex() {
+        assert_eq!(decode_latex(r"Erd\H{o}s and Ko\v{c}i"), "Erdős and Koči");
+        assert_eq!(decode_latex(r"G{\'e}rard {\ss}tra\ss e"), "Gérard ßtraße");
+        assert_eq!(
+            decode_latex(r"\emph{Deep} Learning~Book"),
+            "Deep Learning Book"
+        );
+        assert_eq!(decode_latex(r"50\% of {\$}10"), "50% of $10");
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let mut entry = BibEntry::new("article", "knuth1984literate");
+        entry.set("author", "Donald E. Knuth");
+        entry.set("title", "Literate Programming & 100% {fun}_with #tags");
+        entry.set("url", "https://example.com/a_b?x=1&y=2");
+
+        let written = write_bibtex(std::slice::from_ref(&entry));
+        assert!(written.contains(r"Literate Programming \& 100\% \{fun\}\_with \#tags"));
+        assert!(written.contains("url = {https://example.com/a_b?x=1&y=2}"));
+
+        let parsed = parse_bibtex(&written);
+        assert_eq!(parsed, vec![entry]);
+    }
+}
@@ -0,0 +1,135 @@
+use super::{split_name, BibEntry};
+use serde::{Deserialize, Serialize};
+
+/// CSL-JSON 条目（citeproc、Zotero、Pandoc 使用的数据格式）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CslItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub item_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub author: Vec<CslName>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editor: Vec<CslName>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub translator: Vec<CslName>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub illustrator: Vec<CslName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued: Option<CslDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    #[serde(rename = "DOI", skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(rename = "ISBN", skip_serializing_if = "Option::is_none")]
+    pub isbn: Option<String>,
+    #[serde(rename = "ISSN", skip_serializing_if = "Option::is_none")]
+    pub issn: Option<String>,
+    #[serde(rename = "URL", skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(rename = "abstract", skip_serializing_if = "Option::is_none")]
+    pub abstract_text: Option<String>,
+    /// 逗号分隔的关键词
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+}
+
+/// CSL 人名：个人为 family/given，机构或无法拆分的名字为 literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CslName {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub literal: Option<String>,
+}
+
+impl CslName {
+    /// 拆分为姓和名，无法拆分的名字（单个词、机构）作为 literal
+    pub fn new(name: &str) -> Self {
+        match split_name(name) {
+            (Some(given), family) => CslName {
+                family: Some(family),
+                given: Some(given),
+                literal: None,
+            },
+            (None, family) => CslName {
+                family: None,
+                given: None,
+                literal: Some(family),
+            },
+        }
+    }
+}
+
+/// CSL 日期，`date-parts` 为 `[[年, 月, 日]]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CslDate {
+    #[serde(rename = "date-parts")]
+    pub date_parts: Vec<Vec<i32>>,
+}
+
+impl CslItem {
+    pub fn from_bib(entry: &BibEntry) -> Self {
+        let field = |name: &str| entry.get(name).map(str::to_string);
+
+        let names = |field: &str| entry.names(field).iter().map(|n| CslName::new(n)).collect();
+
+        let issued = entry.year().map(|year| {
+            let mut parts = vec![year];
+            if let Some(month) = entry.get("month").and_then(|m| m.parse().ok()) {
+                parts.push(month);
+            }
+            CslDate {
+                date_parts: vec![parts],
+            }
+        });
+
+        Self {
+            id: entry.key.clone(),
+            item_type: csl_type(&entry.entry_type).to_string(),
+            title: field("title"),
+            author: names("author"),
+            editor: names("editor"),
+            translator: names("translator"),
+            illustrator: names("illustrator"),
+            issued,
+            container_title: entry.venue().map(str::to_string),
+            publisher: field("publisher"),
+            volume: field("volume"),
+            issue: field("number"),
+            page: field("pages"),
+            doi: field("doi"),
+            isbn: field("isbn"),
+            issn: field("issn"),
+            url: field("url"),
+            abstract_text: field("abstract"),
+            keyword: field("keywords"),
+        }
+    }
+}
+
+/// BibTeX 条目类型对应的 CSL 类型
+fn csl_type(entry_type: &str) -> &'static str {
+    match entry_type {
+        "article" => "article-journal",
+        "inproceedings" | "conference" => "paper-conference",
+        "book" | "proceedings" => "book",
+        "incollection" | "inbook" => "chapter",
+        "phdthesis" | "mastersthesis" | "thesis" => "thesis",
+        "techreport" | "report" => "report",
+        _ => "document",
+    }
+}
@@ -0,0 +1,539 @@
+//! 参考文献导入导出：BibTeX 读写、RIS / CSL-JSON 导出、引用格式化、引用键生成，
+//! 以及按 BibTeX 条目查找对应文件
+
+mod bibtex;
+mod csl;
+mod ris;
+mod style;
+
+pub(crate) use bibtex::fold_accent;
+pub use bibtex::{parse_bibtex, write_bibtex};
+pub use csl::{CslDate, CslItem, CslName};
+pub use ris::write_ris;
+pub use style::{format_citation, CitationStyle};
+
+use crate::enrich::titles_match;
+use crate::errors::TagboxError;
+use crate::identifiers::normalize_identifier;
+use crate::types::{
+    AuthorRole, Contributor, FileEntry, Identifier, IdentifierKind, ImportMetadata,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 参考文献导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BibFormat {
+    Bibtex,
+    Ris,
+    #[serde(rename = "csl-json")]
+    CslJson,
+}
+
+impl std::str::FromStr for BibFormat {
+    type Err = TagboxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bibtex" | "bib" => Ok(BibFormat::Bibtex),
+            "ris" => Ok(BibFormat::Ris),
+            "csl-json" | "csljson" | "csl" => Ok(BibFormat::CslJson),
+            other => Err(TagboxError::Config(format!(
+                "未知的参考文献格式: {} (可选: bibtex, ris, csl-json)",
+                other
+            ))),
+        }
+    }
+}
+
+/// 一条参考文献记录
+///
+/// 字段名为小写，字段值为纯文本；LaTeX 转义只在读写 BibTeX 时处理。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibEntry {
+    pub entry_type: String,
+    pub key: String,
+    pub fields: Vec<(String, String)>,
+}
+
+/// 生成引用键时跳过的标题虚词
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "on", "of", "in", "for", "and", "to", "with", "is", "at", "by",
+];
+
+impl BibEntry {
+    pub fn new(entry_type: &str, key: &str) -> Self {
+        Self {
+            entry_type: entry_type.to_lowercase(),
+            key: key.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// 字段值，空字段视为不存在
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// 设置字段，空值不写入
+    pub fn set(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        let name = name.to_lowercase();
+        self.fields.retain(|(field, _)| *field != name);
+        if !value.is_empty() {
+            self.fields.push((name, value.to_string()));
+        }
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.get("title")
+    }
+
+    /// 作者列表，`others`（即 et al.）不计入
+    pub fn authors(&self) -> Vec<String> {
+        self.names("author")
+    }
+
+    /// 人名字段（`author`、`editor`、`translator` 等）中的名字列表
+    pub fn names(&self, field: &str) -> Vec<String> {
+        self.get(field)
+            .map(|names| {
+                names
+                    .split(" and ")
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty() && !name.eq_ignore_ascii_case("others"))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 编者、译者、插画，对应 `editor`、`translator`、`illustrator` 字段
+    pub fn contributors(&self) -> Vec<Contributor> {
+        AuthorRole::ALL
+            .into_iter()
+            .filter(|role| *role != AuthorRole::Author)
+            .flat_map(|role| {
+                self.names(role.as_str())
+                    .into_iter()
+                    .map(move |name| Contributor::new(name, role))
+            })
+            .collect()
+    }
+
+    pub fn year(&self) -> Option<i32> {
+        let year = self.get("year").or_else(|| self.get("date"))?;
+        year.get(..4)?.parse().ok()
+    }
+
+    /// 期刊、会议论文集或丛书名
+    pub fn venue(&self) -> Option<&str> {
+        [
+            "journal",
+            "journaltitle",
+            "booktitle",
+            "series",
+            "howpublished",
+        ]
+        .iter()
+        .find_map(|field| self.get(field))
+    }
+
+    /// 条目中的 DOI、ISBN、ISSN 和 arXiv 编号
+    pub fn identifiers(&self) -> Vec<Identifier> {
+        let mut found = Vec::new();
+        let mut push = |kind: IdentifierKind, raw: &str| {
+            if let Some(value) = normalize_identifier(kind, raw) {
+                let identifier = Identifier { kind, value };
+                if !found.contains(&identifier) {
+                    found.push(identifier);
+                }
+            }
+        };
+
+        if let Some(doi) = self.get("doi") {
+            push(IdentifierKind::Doi, doi);
+        }
+        if let Some(isbn) = self.get("isbn") {
+            push(IdentifierKind::Isbn, isbn);
+        }
+        if let Some(issn) = self.get("issn") {
+            push(IdentifierKind::Issn, issn);
+        }
+
+        let archive = self
+            .get("archiveprefix")
+            .or_else(|| self.get("eprinttype"))
+            .unwrap_or_default();
+        if let Some(eprint) = self.get("eprint") {
+            if archive.eq_ignore_ascii_case("arxiv") {
+                push(IdentifierKind::Arxiv, eprint);
+            }
+        }
+
+        found
+    }
+
+    /// `file` 字段中的路径
+    ///
+    /// 支持 JabRef / Mendeley 的 `描述:路径:类型` 格式（`\:` 为转义的冒号）和多个文件用 `;` 分隔。
+    pub fn file_paths(&self) -> Vec<String> {
+        let Some(value) = self.get("file") else {
+            return Vec::new();
+        };
+
+        value
+            .split(';')
+            .filter_map(|part| {
+                let fields = split_unescaped(part, ':');
+                let path = match fields.len() {
+                    1 => fields[0].clone(),
+                    3 => fields[1].clone(),
+                    // Windows 盘符中未转义的冒号
+                    _ => part.replace("\\:", ":"),
+                };
+                let path = path.replace("\\\\", "\\");
+                let path = path.trim();
+                (!path.is_empty()).then(|| path.to_string())
+            })
+            .collect()
+    }
+
+    /// 转换为导入元数据；期刊/会议和引用键记录在 `file_metadata.bibtex`
+    pub fn to_import_metadata(&self) -> ImportMetadata {
+        let source = self.get("url").map(str::to_string).or_else(|| {
+            self.get("doi")
+                .map(|doi| format!("https://doi.org/{}", doi))
+        });
+
+        let publisher = ["publisher", "institution", "school", "organization"]
+            .iter()
+            .find_map(|field| self.get(field))
+            .map(str::to_string);
+
+        let tags = self
+            .get("keywords")
+            .map(|keywords| {
+                keywords
+                    .split([',', ';'])
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut bibtex = Map::new();
+        bibtex.insert("type".to_string(), json!(self.entry_type));
+        bibtex.insert("key".to_string(), json!(self.key));
+        if let Some(venue) = self.venue() {
+            bibtex.insert("venue".to_string(), json!(venue));
+        }
+
+        ImportMetadata {
+            title: self.title().unwrap_or_default().to_string(),
+            authors: self.authors(),
+            contributors: self.contributors(),
+            year: self.year(),
+            publisher,
+            source,
+            tags,
+            summary: self.get("abstract").map(str::to_string),
+            file_metadata: Some(json!({
+                "bibtex": bibtex,
+                "identifiers": self.identifiers(),
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// 由已入库文件生成参考文献记录
+    ///
+    /// 从 BibTeX 导入的文件沿用原来的条目类型；期刊/会议取自元数据补全或 BibTeX 导入的记录。
+    pub fn from_file(entry: &FileEntry, identifiers: &[Identifier], key: &str) -> Self {
+        let stored = |pointer: &str| {
+            entry
+                .file_metadata
+                .as_ref()
+                .and_then(|m| m.pointer(pointer))
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+        };
+        let identifier = |kind: IdentifierKind| {
+            identifiers
+                .iter()
+                .find(|i| i.kind == kind)
+                .map(|i| i.value.as_str())
+        };
+
+        let venue = stored("/enrichment/venue").or_else(|| stored("/bibtex/venue"));
+        let entry_type = stored("/bibtex/type")
+            .map(str::to_string)
+            .unwrap_or_else(|| guess_type(venue, identifier(IdentifierKind::Isbn).is_some()));
+
+        let mut bib = BibEntry::new(&entry_type, key);
+        bib.set("author", &entry.authors.join(" and "));
+        for role in [
+            AuthorRole::Editor,
+            AuthorRole::Translator,
+            AuthorRole::Illustrator,
+        ] {
+            let names: Vec<&str> = entry
+                .contributors
+                .iter()
+                .filter(|c| c.role == role)
+                .map(|c| c.name.as_str())
+                .collect();
+            bib.set(role.as_str(), &names.join(" and "));
+        }
+        bib.set("title", &entry.title);
+        if let Some(venue) = venue {
+            let field = match entry_type.as_str() {
+                "article" => "journal",
+                "inproceedings" | "incollection" | "proceedings" => "booktitle",
+                "book" => "series",
+                _ => "howpublished",
+            };
+            bib.set(field, venue);
+        }
+        if let Some(year) = entry.year {
+            bib.set("year", &year.to_string());
+        }
+        bib.set("publisher", entry.publisher.as_deref().unwrap_or_default());
+        bib.set("doi", identifier(IdentifierKind::Doi).unwrap_or_default());
+        bib.set("isbn", identifier(IdentifierKind::Isbn).unwrap_or_default());
+        bib.set("issn", identifier(IdentifierKind::Issn).unwrap_or_default());
+        if let Some(arxiv) = identifier(IdentifierKind::Arxiv) {
+            bib.set("eprint", arxiv);
+            bib.set("archiveprefix", "arXiv");
+        }
+
+        let url = stored("/enrichment/url").or_else(|| {
+            entry
+                .source
+                .as_deref()
+                .filter(|s| s.starts_with("http://") || s.starts_with("https://"))
+        });
+        bib.set("url", url.unwrap_or_default());
+        bib.set("abstract", entry.summary.as_deref().unwrap_or_default());
+        bib.set("keywords", &entry.tags.join(", "));
+        bib.set("file", &entry.path.to_string_lossy());
+
+        bib
+    }
+}
+
+fn guess_type(venue: Option<&str>, has_isbn: bool) -> String {
+    let entry_type = match venue.map(str::to_lowercase) {
+        Some(venue)
+            if ["proceedings", "conference", "workshop", "symposium"]
+                .iter()
+                .any(|word| venue.contains(word)) =>
+        {
+            "inproceedings"
+        }
+        Some(_) => "article",
+        None if has_isbn => "book",
+        None => "misc",
+    };
+    entry_type.to_string()
+}
+
+/// 按未转义的分隔符切分，`\` 转义的分隔符还原为原字符
+fn split_unescaped(text: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&separator) => {
+                parts.last_mut().unwrap().push(separator);
+                chars.next();
+            }
+            c if c == separator => parts.push(String::new()),
+            c => parts.last_mut().unwrap().push(c),
+        }
+    }
+
+    parts
+}
+
+/// 文件的引用键：从 BibTeX 导入的文件沿用原键，其余为 `第一作者姓 + 年份 + 标题首个实词`，
+/// 例如 `vaswani2017attention`
+pub fn citation_key(entry: &FileEntry) -> String {
+    let stored = entry
+        .file_metadata
+        .as_ref()
+        .and_then(|m| m.pointer("/bibtex/key"))
+        .and_then(Value::as_str)
+        .filter(|key| !key.is_empty());
+    if let Some(key) = stored {
+        return key.to_string();
+    }
+
+    let author = entry
+        .authors
+        .first()
+        .map(|name| key_part(&split_name(name).1))
+        .unwrap_or_default();
+    let year = entry.year.map(|y| y.to_string()).unwrap_or_default();
+    let word = entry
+        .title
+        .split_whitespace()
+        .map(key_part)
+        .find(|word| !word.is_empty() && !STOP_WORDS.contains(&word.as_str()))
+        .unwrap_or_default();
+
+    let key = format!("{}{}{}", author, year, word);
+    if key.is_empty() {
+        format!("tagbox{}", entry.id.chars().take(8).collect::<String>())
+    } else {
+        key
+    }
+}
+
+/// 为一组文件生成互不相同的引用键
+///
+/// 相同的键按导入时间（其次 ID）依次加上 `a`、`b`、`c` 后缀，与导出顺序无关。
+pub fn citation_keys(entries: &[FileEntry]) -> Vec<String> {
+    let mut keys: Vec<String> = entries.iter().map(citation_key).collect();
+
+    let mut groups: std::collections::HashMap<String, Vec<usize>> = Default::default();
+    for (index, key) in keys.iter().enumerate() {
+        groups.entry(key.clone()).or_default().push(index);
+    }
+
+    for (key, mut indices) in groups {
+        if indices.len() < 2 {
+            continue;
+        }
+        indices.sort_by_key(|&i| (entries[i].created_at, entries[i].id.clone()));
+        for (n, index) in indices.into_iter().enumerate() {
+            keys[index] = format!("{}{}", key, key_suffix(n));
+        }
+    }
+
+    keys
+}
+
+/// 0 → a，25 → z，26 → aa
+fn key_suffix(mut n: usize) -> String {
+    let mut suffix = String::new();
+    loop {
+        suffix.insert(0, (b'a' + (n % 26) as u8) as char);
+        if n < 26 {
+            return suffix;
+        }
+        n = n / 26 - 1;
+    }
+}
+
+/// 姓前的小写前缀，如 `van`、`de`
+pub(crate) const NAME_PARTICLES: &[&str] = &[
+    "van", "von", "de", "der", "den", "di", "da", "du", "del", "della", "le", "la", "ter",
+];
+
+/// 把人名拆为 (名, 姓)
+///
+/// `Last, First` 按逗号拆分；否则最后一个词（连同前面的 `van`、`de` 等前缀）为姓。
+/// 单个词的名字（如中文姓名或机构）整体作为姓。
+pub(crate) fn split_name(name: &str) -> (Option<String>, String) {
+    if let Some((family, given)) = name.split_once(',') {
+        let given = given.trim();
+        return (
+            (!given.is_empty()).then(|| given.to_string()),
+            family.trim().to_string(),
+        );
+    }
+
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let Some((_, rest)) = words.split_last() else {
+        return (None, String::new());
+    };
+    let mut given_len = rest.len();
+    while given_len > 1 && NAME_PARTICLES.contains(&rest[given_len - 1]) {
+        given_len -= 1;
+    }
+
+    let family = words[given_len..].join(" ");
+    let given = rest[..given_len].join(" ");
+    ((!given.is_empty()).then_some(given), family)
+}
+
+/// 引用键片段：去掉重音、只保留字母数字并转为小写
+fn key_part(text: &str) -> String {
+    let mut part = String::new();
+    for c in text.chars() {
+        match fold_accent(c) {
+            Some(folded) => part.push_str(folded),
+            None if c.is_alphanumeric() => part.extend(c.to_lowercase()),
+            None => {}
+        }
+    }
+    part.to_lowercase()
+}
+
+/// 在目录中为 BibTeX 条目查找对应文件
+pub struct FileIndex {
+    files: Vec<PathBuf>,
+}
+
+impl FileIndex {
+    /// 递归收集目录中的文件
+    pub fn scan(dir: &Path) -> Self {
+        let files = WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+        Self { files }
+    }
+
+    /// 依次按 `file` 字段（绝对路径、相对 `base_dirs` 的路径、文件名）、引用键和标题匹配文件
+    pub fn resolve(&self, entry: &BibEntry, base_dirs: &[&Path]) -> Option<PathBuf> {
+        for listed in entry.file_paths() {
+            let path = Path::new(&listed);
+            if path.is_absolute() && path.is_file() {
+                return Some(path.to_path_buf());
+            }
+            for dir in base_dirs {
+                let candidate = dir.join(path);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+
+            // bib 文件来自其他机器时只能按文件名匹配
+            let name = listed.rsplit(['/', '\\']).next().unwrap_or(&listed);
+            let found = self
+                .files
+                .iter()
+                .find(|f| f.file_name().is_some_and(|n| n.to_string_lossy() == name));
+            if let Some(found) = found {
+                return Some(found.clone());
+            }
+        }
+
+        let title = entry.title();
+        self.files
+            .iter()
+            .filter(|f| f.extension().is_none_or(|ext| ext != "bib"))
+            .find(|f| {
+                let stem = f
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                stem.eq_ignore_ascii_case(&entry.key)
+                    || title.is_some_and(|t| titles_match(&stem, t))
+            })
+            .cloned()
+    }
+}
@@ -0,0 +1,68 @@
+use super::BibEntry;
+
+/// 生成 RIS 文本
+pub fn write_ris(entries: &[BibEntry]) -> String {
+    let mut out = String::new();
+
+    for entry in entries {
+        let mut line = |tag: &str, value: &str| {
+            let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !value.is_empty() {
+                out.push_str(&format!("{}  - {}\n", tag, value));
+            }
+        };
+
+        line("TY", ris_type(&entry.entry_type));
+        line("ID", &entry.key);
+        line("TI", entry.title().unwrap_or_default());
+        for author in entry.authors() {
+            line("AU", &author);
+        }
+        for editor in entry.names("editor") {
+            line("ED", &editor);
+        }
+        for translator in entry.names("translator") {
+            line("A4", &translator);
+        }
+        if let Some(year) = entry.year() {
+            line("PY", &year.to_string());
+        }
+        match entry.entry_type.as_str() {
+            "article" => line("JO", entry.venue().unwrap_or_default()),
+            _ => line("T2", entry.venue().unwrap_or_default()),
+        }
+        line("PB", entry.get("publisher").unwrap_or_default());
+        line("DO", entry.get("doi").unwrap_or_default());
+        line(
+            "SN",
+            entry
+                .get("isbn")
+                .or_else(|| entry.get("issn"))
+                .unwrap_or_default(),
+        );
+        line("UR", entry.get("url").unwrap_or_default());
+        line("AB", entry.get("abstract").unwrap_or_default());
+        for keyword in entry.get("keywords").unwrap_or_default().split(',') {
+            line("KW", keyword);
+        }
+        for path in entry.file_paths() {
+            line("L1", &path);
+        }
+        out.push_str("ER  - \n\n");
+    }
+
+    out
+}
+
+/// BibTeX 条目类型对应的 RIS 类型
+fn ris_type(entry_type: &str) -> &'static str {
+    match entry_type {
+        "article" => "JOUR",
+        "inproceedings" | "conference" | "proceedings" => "CONF",
+        "book" => "BOOK",
+        "incollection" | "inbook" => "CHAP",
+        "phdthesis" | "mastersthesis" | "thesis" => "THES",
+        "techreport" | "report" => "RPRT",
+        _ => "GEN",
+    }
+}
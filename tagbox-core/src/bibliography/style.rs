@@ -0,0 +1,396 @@
+use super::{split_name, BibEntry};
+use crate::errors::TagboxError;
+use serde::{Deserialize, Serialize};
+
+/// 内置的引用格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CitationStyle {
+    /// APA 第 7 版
+    Apa,
+    Ieee,
+    /// Chicago author-date
+    Chicago,
+}
+
+impl std::str::FromStr for CitationStyle {
+    type Err = TagboxError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "apa" => Ok(CitationStyle::Apa),
+            "ieee" => Ok(CitationStyle::Ieee),
+            "chicago" | "chicago-author-date" => Ok(CitationStyle::Chicago),
+            other => Err(TagboxError::Config(format!(
+                "未知的引用格式: {} (可选: apa, ieee, chicago)",
+                other
+            ))),
+        }
+    }
+}
+
+/// 按指定格式生成纯文本参考文献条目
+pub fn format_citation(entry: &BibEntry, style: CitationStyle) -> String {
+    let citation = match style {
+        CitationStyle::Apa => apa(entry),
+        CitationStyle::Ieee => ieee(entry),
+        CitationStyle::Chicago => chicago(entry),
+    };
+    citation.join(" ")
+}
+
+fn apa(entry: &BibEntry) -> Vec<String> {
+    let names: Vec<String> = entry
+        .authors()
+        .iter()
+        .map(|name| match split_name(name) {
+            (Some(given), family) => format!("{}, {}", family, initials(&given)),
+            (None, family) => family,
+        })
+        .collect();
+    let year = entry
+        .year()
+        .map(|y| y.to_string())
+        .unwrap_or_else(|| "n.d.".to_string());
+    let title = entry.title().unwrap_or("Untitled");
+
+    let mut parts = Vec::new();
+    if names.is_empty() {
+        parts.push(sentence(title));
+        parts.push(format!("({}).", year));
+    } else {
+        parts.push(format!("{} ({}).", apa_names(&names), year));
+        parts.push(sentence(title));
+    }
+
+    let pages = entry.get("pages");
+    match entry.entry_type.as_str() {
+        "article" => {
+            if let Some(journal) = entry.venue() {
+                let mut source = journal.to_string();
+                if let Some(volume) = entry.get("volume") {
+                    source.push_str(&format!(", {}", volume));
+                    if let Some(number) = entry.get("number") {
+                        source.push_str(&format!("({})", number));
+                    }
+                }
+                if let Some(pages) = pages {
+                    source.push_str(&format!(", {}", pages));
+                }
+                parts.push(sentence(&source));
+            }
+        }
+        "book" => {}
+        _ => {
+            if let Some(venue) = entry.venue() {
+                let source = match pages {
+                    Some(pages) => format!("In {} (pp. {})", venue, pages),
+                    None => format!("In {}", venue),
+                };
+                parts.push(sentence(&source));
+            }
+        }
+    }
+    if entry.entry_type != "article" {
+        if let Some(publisher) = entry.get("publisher") {
+            parts.push(sentence(publisher));
+        }
+    }
+
+    parts.extend(link(entry));
+    parts
+}
+
+fn ieee(entry: &BibEntry) -> Vec<String> {
+    let names: Vec<String> = entry
+        .authors()
+        .iter()
+        .map(|name| match split_name(name) {
+            (Some(given), family) => format!("{} {}", initials(&given), family),
+            (None, family) => family,
+        })
+        .collect();
+    let title = entry.title().unwrap_or("Untitled");
+    let year = entry.year().map(|y| y.to_string());
+    let pages = entry.get("pages").map(|p| format!("pp. {}", p));
+
+    let mut parts = Vec::new();
+    if !names.is_empty() {
+        parts.push(format!("{},", ieee_names(&names)));
+    }
+
+    if entry.entry_type == "book" {
+        let details: Vec<String> = [entry.get("publisher").map(str::to_string), year]
+            .into_iter()
+            .flatten()
+            .collect();
+        parts.push(sentence(title));
+        if !details.is_empty() {
+            parts.push(format!("{}.", details.join(", ")));
+        }
+    } else {
+        let mut details = Vec::new();
+        match entry.entry_type.as_str() {
+            "article" => {
+                details.extend(entry.venue().map(str::to_string));
+                details.extend(entry.get("volume").map(|v| format!("vol. {}", v)));
+                details.extend(entry.get("number").map(|n| format!("no. {}", n)));
+                details.extend(pages);
+                details.extend(year);
+            }
+            _ => {
+                details.extend(entry.venue().map(|v| format!("in {}", v)));
+                details.extend(year);
+                details.extend(pages);
+            }
+        }
+
+        if details.is_empty() {
+            parts.push(format!("\"{}\"", sentence(title)));
+        } else {
+            parts.push(format!("\"{}\"", with_trailing(title, ',')));
+            parts.push(format!("{}.", details.join(", ")));
+        }
+    }
+
+    if let Some(doi) = entry.get("doi") {
+        parts.push(format!("doi: {}.", doi));
+    } else if let Some(url) = entry.get("url") {
+        parts.push(format!("[Online]. Available: {}", url));
+    }
+    parts
+}
+
+fn chicago(entry: &BibEntry) -> Vec<String> {
+    let authors = entry.authors();
+    let names: Vec<String> = authors
+        .iter()
+        .enumerate()
+        .map(|(i, name)| match split_name(name) {
+            (Some(given), family) if i == 0 => format!("{}, {}", family, given),
+            (Some(given), family) => format!("{} {}", given, family),
+            (None, family) => family,
+        })
+        .collect();
+    let year = entry
+        .year()
+        .map(|y| y.to_string())
+        .unwrap_or_else(|| "n.d.".to_string());
+    let title = entry.title().unwrap_or("Untitled");
+    let title = if entry.entry_type == "book" {
+        sentence(title)
+    } else {
+        format!("\"{}\"", sentence(title))
+    };
+
+    let mut parts = Vec::new();
+    if names.is_empty() {
+        parts.push(title);
+        parts.push(sentence(&year));
+    } else {
+        parts.push(sentence(&chicago_names(&names)));
+        parts.push(sentence(&year));
+        parts.push(title);
+    }
+
+    match entry.entry_type.as_str() {
+        "article" => {
+            if let Some(journal) = entry.venue() {
+                let mut source = journal.to_string();
+                if let Some(volume) = entry.get("volume") {
+                    source.push_str(&format!(" {}", volume));
+                }
+                if let Some(number) = entry.get("number") {
+                    source.push_str(&format!(" ({})", number));
+                }
+                if let Some(pages) = entry.get("pages") {
+                    source.push_str(&format!(": {}", pages));
+                }
+                parts.push(sentence(&source));
+            }
+        }
+        "book" => {}
+        _ => {
+            if let Some(venue) = entry.venue() {
+                let source = match entry.get("pages") {
+                    Some(pages) => format!("In {}, {}", venue, pages),
+                    None => format!("In {}", venue),
+                };
+                parts.push(sentence(&source));
+            }
+        }
+    }
+    if entry.entry_type != "article" {
+        if let Some(publisher) = entry.get("publisher") {
+            parts.push(sentence(publisher));
+        }
+    }
+
+    parts.extend(link(entry).map(|link| sentence(&link)));
+    parts
+}
+
+/// DOI 链接，没有 DOI 时为 URL
+fn link(entry: &BibEntry) -> Option<String> {
+    entry
+        .get("doi")
+        .map(|doi| format!("https://doi.org/{}", doi))
+        .or_else(|| entry.get("url").map(str::to_string))
+}
+
+/// `A, B, & C`；超过 20 人时为前 19 人、省略号和最后一人
+fn apa_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first @ .., last] if names.len() <= 20 => format!("{}, & {}", first.join(", "), last),
+        _ => format!(
+            "{}, . . . {}",
+            names[..19].join(", "),
+            names[names.len() - 1]
+        ),
+    }
+}
+
+/// `A and B`、`A, B, and C`；超过 6 人时只列第一作者加 et al.
+fn ieee_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.clone(),
+        [a, b] => format!("{} and {}", a, b),
+        [first @ .., last] if names.len() <= 6 => {
+            format!("{}, and {}", first.join(", "), last)
+        }
+        _ => format!("{} et al.", names[0]),
+    }
+}
+
+/// `A, B, and C`（第一作者姓在前，两人时也有逗号）；超过 10 人时列前 7 人加 et al.
+fn chicago_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first @ .., last] if names.len() <= 10 => {
+            format!("{}, and {}", first.join(", "), last)
+        }
+        _ => format!("{}, et al.", names[..7].join(", ")),
+    }
+}
+
+/// 名的首字母缩写：`Donald E.` → `D. E.`，`Jean-Paul` → `J.-P.`
+fn initials(given: &str) -> String {
+    given
+        .split_whitespace()
+        .map(|word| {
+            word.split('-')
+                .filter_map(|part| part.chars().next())
+                .map(|c| format!("{}.", c))
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 补上句末标点
+fn sentence(text: &str) -> String {
+    with_trailing(text, '.')
+}
+
+/// 末尾没有句末标点时加上指定标点
+fn with_trailing(text: &str, punctuation: char) -> String {
+    let text = text.trim();
+    if text.ends_with(['.', '?', '!']) {
+        text.to_string()
+    } else {
+        format!("{}{}", text, punctuation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attention() -> BibEntry {
+        let mut entry = BibEntry::new("inproceedings", "vaswani2017attention");
+        entry.set("author", "Ashish Vaswani and Noam Shazeer and Niki Parmar");
+        entry.set("title", "Attention Is All You Need");
+        entry.set(
+            "booktitle",
+            "Advances in Neural Information Processing Systems 30",
+        );
+        entry.set("year", "2017");
+        entry.set("pages", "5998–6008");
+        entry.set("publisher", "Curran Associates");
+        entry.set("doi", "10.48550/arxiv.1706.03762");
+        entry
+    }
+
+    #[test]
+    fn test_format_conference_paper() {
+        let entry = attention();
+
+        assert_eq!(
+            format_citation(&entry, CitationStyle::Apa),
+            "Vaswani, A., Shazeer, N., & Parmar, N. (2017). Attention Is All You Need. \
+             In Advances in Neural Information Processing Systems 30 (pp. 5998–6008). \
+             Curran Associates. https://doi.org/10.48550/arxiv.1706.03762"
+        );
+        assert_eq!(
+            format_citation(&entry, CitationStyle::Ieee),
+            "A. Vaswani, N. Shazeer, and N. Parmar, \"Attention Is All You Need,\" \
+             in Advances in Neural Information Processing Systems 30, 2017, pp. 5998–6008. \
+             doi: 10.48550/arxiv.1706.03762."
+        );
+        assert_eq!(
+            format_citation(&entry, CitationStyle::Chicago),
+            "Vaswani, Ashish, Noam Shazeer, and Niki Parmar. 2017. \"Attention Is All You Need.\" \
+             In Advances in Neural Information Processing Systems 30, 5998–6008. \
+             Curran Associates. https://doi.org/10.48550/arxiv.1706.03762."
+        );
+    }
+
+    #[test]
+    fn test_format_article_and_book() {
+        let mut article = BibEntry::new("article", "knuth1984literate");
+        article.set("author", "Donald E. Knuth");
+        article.set("title", "Literate Programming");
+        article.set("journal", "The Computer Journal");
+        article.set("volume", "27");
+        article.set("number", "2");
+        article.set("pages", "97–111");
+        article.set("year", "1984");
+
+        assert_eq!(
+            format_citation(&article, CitationStyle::Apa),
+            "Knuth, D. E. (1984). Literate Programming. The Computer Journal, 27(2), 97–111."
+        );
+        assert_eq!(
+            format_citation(&article, CitationStyle::Ieee),
+            "D. E. Knuth, \"Literate Programming,\" The Computer Journal, vol. 27, no. 2, \
+             pp. 97–111, 1984."
+        );
+        assert_eq!(
+            format_citation(&article, CitationStyle::Chicago),
+            "Knuth, Donald E. 1984. \"Literate Programming.\" The Computer Journal 27 (2): 97–111."
+        );
+
+        let mut book = BibEntry::new("book", "book");
+        book.set("author", "Ludwig van Beethoven and 张三");
+        book.set("title", "Who Wrote This?");
+        book.set("publisher", "Example Press");
+
+        assert_eq!(
+            format_citation(&book, CitationStyle::Apa),
+            "van Beethoven, L., & 张三 (n.d.). Who Wrote This? Example Press."
+        );
+        assert_eq!(
+            format_citation(&book, CitationStyle::Ieee),
+            "L. van Beethoven and 张三, Who Wrote This? Example Press."
+        );
+        assert_eq!(
+            format_citation(&book, CitationStyle::Chicago),
+            "van Beethoven, Ludwig, and 张三. n.d. Who Wrote This? Example Press."
+        );
+    }
+}
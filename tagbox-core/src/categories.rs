@@ -0,0 +1,337 @@
+use crate::config::AppConfig;
+use crate::editor::Editor;
+use crate::errors::{Result, TagboxError};
+use crate::types::{CategoryMove, FileEntry};
+use crate::utils::{current_time, hierarchy_segments, normalize_hierarchy_path};
+use sqlx::{Row, SqlitePool};
+use std::fmt;
+
+/// 匹配分类及其所有子分类：`Work` 命中 `Work` 和 `Work/...`
+///
+/// 子分类用路径范围 `(path/, path0)` 判断（`0` 紧跟在 `/` 之后）。
+pub(crate) const CATEGORY_SUBTREE_CLAUSE: &str =
+    "(f.category_path = ? OR (f.category_path > ? AND f.category_path < ?))";
+
+/// 任意层级的分类路径，如 `Work/Clients/Acme/2026/Contracts`
+///
+/// 完整路径存于 `files.category_path`，是分类的唯一来源；`category1/2/3` 列由触发器派生
+/// （前两级各占一列，第三级及更深层级以 `/` 连接存于 `category3`），供按列读取的旧代码使用。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CategoryPath {
+    segments: Vec<String>,
+}
+
+impl CategoryPath {
+    /// 解析 `a/b/c` 形式的路径，去掉每段首尾空白和空段
+    pub fn parse(raw: &str) -> Self {
+        Self {
+            segments: hierarchy_segments(raw).map(str::to_string).collect(),
+        }
+    }
+
+    /// 从 `category1/2/3` 三列还原完整路径
+    pub fn from_columns(category1: &str, category2: Option<&str>, category3: Option<&str>) -> Self {
+        let mut path = Self::parse(category1);
+        for column in [category2, category3].into_iter().flatten() {
+            path.segments.extend(Self::parse(column).segments);
+        }
+        path
+    }
+
+    /// 文件条目的分类路径
+    pub fn of_file(file: &FileEntry) -> Self {
+        Self::from_columns(
+            &file.category1,
+            file.category2.as_deref(),
+            file.category3.as_deref(),
+        )
+    }
+
+    /// 拆分为 `category1/2/3` 三列，第三级及以下合并到 `category3`
+    pub fn to_columns(&self) -> (String, Option<String>, Option<String>) {
+        let rest = self.segments.get(2..).filter(|rest| !rest.is_empty());
+        (
+            self.segments.first().cloned().unwrap_or_default(),
+            self.segments.get(1).cloned(),
+            rest.map(|rest| rest.join("/")),
+        )
+    }
+
+    /// 各级分类名
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// 第 `level` 级分类名（从 1 开始）
+    pub fn level(&self, level: usize) -> Option<&str> {
+        level
+            .checked_sub(1)
+            .and_then(|index| self.segments.get(index))
+            .map(String::as_str)
+    }
+
+    /// 分类层级数
+    pub fn depth(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// 是否没有任何分类
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// 是否为 `ancestor` 本身或其子分类
+    pub fn starts_with(&self, ancestor: &CategoryPath) -> bool {
+        self.segments.starts_with(&ancestor.segments)
+    }
+}
+
+impl fmt::Display for CategoryPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.segments.join("/"))
+    }
+}
+
+/// 分类管理器
+///
+/// 分类以 `category_path` 列存在于 `files` 上（见 [`CategoryPath`]），调整分类即改写该列的路径前缀，
+/// 并按 `classify_template` 把文件移动到新位置。
+pub struct CategoryManager {
+    db_pool: SqlitePool,
+}
+
+/// 受影响文件的当前分类
+struct CategoryRow {
+    id: String,
+    is_deleted: bool,
+    path: CategoryPath,
+}
+
+impl CategoryManager {
+    /// 创建一个新的分类管理器
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 重命名分类，子分类一起改名（`Work/Old` 改为 `Work/New` 时 `Work/Old/A` 变为 `Work/New/A`）
+    ///
+    /// 目标分类已有文件时报错，应改用 [`CategoryManager::merge_category`]。
+    pub async fn rename_category(
+        &self,
+        old_path: &str,
+        new_path: &str,
+        config: &AppConfig,
+        dry_run: bool,
+    ) -> Result<Vec<CategoryMove>> {
+        let new_path = normalize_category_path(new_path);
+        if !self.category_rows(&new_path).await?.is_empty() {
+            return Err(TagboxError::Config(format!(
+                "分类 {} 已存在，请使用合并",
+                new_path
+            )));
+        }
+
+        self.relocate(old_path, &new_path, config, dry_run).await
+    }
+
+    /// 把分类子树移动到另一个父分类下，`new_parent` 为空时移到顶层
+    pub async fn move_category(
+        &self,
+        path: &str,
+        new_parent: &str,
+        config: &AppConfig,
+        dry_run: bool,
+    ) -> Result<Vec<CategoryMove>> {
+        let path = normalize_category_path(path);
+        let name = path.rsplit('/').next().unwrap_or_default();
+        let new_parent = normalize_category_path(new_parent);
+        let new_path = if new_parent.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", new_parent, name)
+        };
+
+        self.rename_category(&path, &new_path, config, dry_run)
+            .await
+    }
+
+    /// 把分类子树合并到已有分类，子分类按相对路径合并
+    pub async fn merge_category(
+        &self,
+        from_path: &str,
+        into_path: &str,
+        config: &AppConfig,
+        dry_run: bool,
+    ) -> Result<Vec<CategoryMove>> {
+        let into_path = normalize_category_path(into_path);
+        if self.category_rows(&into_path).await?.is_empty() {
+            return Err(TagboxError::NotFound(format!("分类不存在: {}", into_path)));
+        }
+
+        self.relocate(from_path, &into_path, config, dry_run).await
+    }
+
+    /// 把 `from` 子树下的文件改到 `to` 下，返回每个文件的分类和路径变化
+    ///
+    /// `category_path` 在一个事务中更新（分类列由触发器重新派生），之后逐个用 [`Editor::move_file`] 移动文件；
+    /// 预演时只计算新路径，不修改数据库和文件。
+    async fn relocate(
+        &self,
+        from: &str,
+        to: &str,
+        config: &AppConfig,
+        dry_run: bool,
+    ) -> Result<Vec<CategoryMove>> {
+        let from = CategoryPath::parse(from);
+        let to = CategoryPath::parse(to);
+        if from.is_empty() || to.is_empty() {
+            return Err(TagboxError::Config("分类不能为空".to_string()));
+        }
+        if to.starts_with(&from) {
+            return Err(TagboxError::Config(format!(
+                "不能把分类 {} 移动到自己或自己的子分类 {}",
+                from, to
+            )));
+        }
+
+        let rows = self.category_rows(&from.to_string()).await?;
+        if rows.is_empty() {
+            return Err(TagboxError::NotFound(format!("分类不存在: {}", from)));
+        }
+
+        let updates: Vec<(CategoryRow, CategoryPath)> = rows
+            .into_iter()
+            .map(|row| {
+                let mut segments = to.segments().to_vec();
+                segments.extend(row.path.segments()[from.depth()..].iter().cloned());
+                (row, CategoryPath { segments })
+            })
+            .collect();
+
+        let editor = Editor::new(self.db_pool.clone());
+        let mut moves = Vec::new();
+
+        if dry_run {
+            for (row, new_category) in updates.iter().filter(|(row, _)| !row.is_deleted) {
+                let mut file = editor.get_file(&row.id).await?;
+                let old_path = config.import.paths.storage_dir.join(&file.path);
+                (file.category1, file.category2, file.category3) = new_category.to_columns();
+                moves.push(CategoryMove {
+                    file_id: row.id.clone(),
+                    title: file.title.clone(),
+                    old_category: row.path.to_string(),
+                    new_category: new_category.to_string(),
+                    new_path: editor.expected_path(&file, config)?,
+                    old_path,
+                    moved: false,
+                });
+            }
+            return Ok(moves);
+        }
+
+        // 已删除的文件同样改分类，保证恢复后仍在新分类下
+        let now = current_time().to_rfc3339();
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+        for (row, new_category) in &updates {
+            sqlx::query("UPDATE files SET category_path = ?, updated_at = ? WHERE id = ?")
+                .bind(new_category.to_string())
+                .bind(&now)
+                .bind(&row.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        for (row, new_category) in updates.iter().filter(|(row, _)| !row.is_deleted) {
+            let file = editor.get_file(&row.id).await?;
+            let old_path = config.import.paths.storage_dir.join(&file.path);
+            let (new_path, moved) = match editor.move_file(&row.id, config).await {
+                Ok(new_path) => (new_path, true),
+                Err(e) => {
+                    tracing::warn!("移动文件 {} 失败: {}", row.id, e);
+                    (editor.expected_path(&file, config)?, false)
+                }
+            };
+            moves.push(CategoryMove {
+                file_id: row.id.clone(),
+                title: file.title,
+                old_category: row.path.to_string(),
+                new_category: new_category.to_string(),
+                old_path,
+                new_path,
+                moved,
+            });
+        }
+
+        Ok(moves)
+    }
+
+    /// 分类本身或其子分类下的文件（含已删除文件）
+    async fn category_rows(&self, path: &str) -> Result<Vec<CategoryRow>> {
+        if path.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(&format!(
+            "SELECT f.id, f.is_deleted, f.category_path FROM files f
+            WHERE {} ORDER BY f.title",
+            CATEGORY_SUBTREE_CLAUSE
+        ))
+        .bind(path)
+        .bind(format!("{}/", path))
+        .bind(format!("{}0", path))
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CategoryRow {
+                id: row.get("id"),
+                is_deleted: row.get::<i64, _>("is_deleted") != 0,
+                path: CategoryPath::parse(
+                    &row.get::<Option<String>, _>("category_path")
+                        .unwrap_or_default(),
+                ),
+            })
+            .collect())
+    }
+}
+
+/// 规范化分类路径：去掉每段首尾空白和空段，`" Work / Clients/ "` 变为 `Work/Clients`
+pub fn normalize_category_path(raw: &str) -> String {
+    normalize_hierarchy_path(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_category_path() {
+        assert_eq!(normalize_category_path(" Work / Clients/ "), "Work/Clients");
+        assert_eq!(normalize_category_path("/"), "");
+    }
+
+    #[test]
+    fn test_category_path_columns() {
+        let path = CategoryPath::parse("Work/Clients/Acme/2026/Contracts");
+        assert_eq!(path.depth(), 5);
+        assert_eq!(path.level(4), Some("2026"));
+        assert_eq!(path.level(0), None);
+
+        let (category1, category2, category3) = path.to_columns();
+        assert_eq!(category1, "Work");
+        assert_eq!(category2.as_deref(), Some("Clients"));
+        assert_eq!(category3.as_deref(), Some("Acme/2026/Contracts"));
+        assert_eq!(
+            CategoryPath::from_columns(&category1, category2.as_deref(), category3.as_deref()),
+            path
+        );
+
+        assert_eq!(CategoryPath::parse("Work").to_columns().1, None);
+        assert!(path.starts_with(&CategoryPath::parse("Work/Clients")));
+        assert!(!path.starts_with(&CategoryPath::parse("Work/Cli")));
+    }
+}
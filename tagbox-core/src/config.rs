@@ -10,6 +10,10 @@ pub struct AppConfig {
     pub search: SearchConfig,
     pub database: DatabaseConfig,
     pub hash: HashConfig,
+    #[serde(default)]
+    pub ocr: OcrConfig,
+    #[serde(default)]
+    pub enrich: EnrichConfig,
 }
 
 /// 导入相关配置
@@ -33,6 +37,89 @@ pub struct ImportMetadataConfig {
     pub prefer_json: bool,
     pub fallback_pdf: bool,
     pub default_category: String,
+
+    /// 导入重复文件（哈希相同）时的处理策略
+    #[serde(default)]
+    pub on_duplicate: DuplicatePolicy,
+
+    /// 是否为 PDF（逐页）和 EPUB（逐章）建立页级全文索引
+    #[serde(default = "default_index_pages")]
+    pub index_pages: bool,
+
+    /// 单个文件逐页文本的总字节上限，超过后停止提取后续页
+    #[serde(default = "default_page_text_budget")]
+    pub page_text_budget: u64,
+
+    /// 大于该大小（字节）的文件不在导入时提取逐页文本，而是留给后台任务
+    #[serde(default = "default_background_extract_size")]
+    pub background_extract_size: u64,
+
+    /// 导入 ZIP / tar 压缩包时，是否把其中的文件逐个导入并关联到压缩包
+    #[serde(default)]
+    pub explode_archives: bool,
+}
+
+fn default_index_pages() -> bool {
+    true
+}
+
+pub(crate) fn default_page_text_budget() -> u64 {
+    32 * 1024 * 1024
+}
+
+fn default_background_extract_size() -> u64 {
+    50 * 1024 * 1024
+}
+
+/// 重复文件处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DuplicatePolicy {
+    /// 保持已有条目不变（默认）
+    #[default]
+    Skip,
+    /// 将新的标签合并到已有条目
+    MergeTags,
+    /// 仅用新元数据填补已有条目中的空字段
+    OverwriteEmpty,
+    /// 由调用方交互式决定，核心库中等同于 skip
+    Prompt,
+}
+
+impl DuplicatePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuplicatePolicy::Skip => "skip",
+            DuplicatePolicy::MergeTags => "merge-tags",
+            DuplicatePolicy::OverwriteEmpty => "overwrite-empty",
+            DuplicatePolicy::Prompt => "prompt",
+        }
+    }
+}
+
+impl std::fmt::Display for DuplicatePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for DuplicatePolicy {
+    type Err = TagboxError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "skip" => Ok(DuplicatePolicy::Skip),
+            "merge-tags" | "merge" => Ok(DuplicatePolicy::MergeTags),
+            "overwrite-empty" | "overwrite-empty-fields" | "fill" => {
+                Ok(DuplicatePolicy::OverwriteEmpty)
+            }
+            "prompt" | "ask" => Ok(DuplicatePolicy::Prompt),
+            other => Err(TagboxError::Config(format!(
+                "未知的重复文件策略: {} (可选: skip, merge-tags, overwrite-empty, prompt)",
+                other
+            ))),
+        }
+    }
 }
 
 /// 搜索配置
@@ -78,6 +165,115 @@ fn default_verify_on_import() -> bool {
     true
 }
 
+/// OCR 配置，需要启用 `ocr` cargo feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrConfig {
+    /// 导入时识别没有文本的图片和扫描版 PDF 页
+    #[serde(default)]
+    pub on_import: bool,
+
+    /// ocrs 文字检测模型（text-detection.rten）
+    #[serde(default = "default_detection_model")]
+    pub detection_model: PathBuf,
+
+    /// ocrs 文字识别模型（text-recognition.rten）
+    #[serde(default = "default_recognition_model")]
+    pub recognition_model: PathBuf,
+
+    /// 单个 PDF 最多识别的页数
+    #[serde(default = "default_ocr_max_pages")]
+    pub max_pages: usize,
+}
+
+fn default_detection_model() -> PathBuf {
+    PathBuf::from("./tagbox_data/models/text-detection.rten")
+}
+
+fn default_recognition_model() -> PathBuf {
+    PathBuf::from("./tagbox_data/models/text-recognition.rten")
+}
+
+fn default_ocr_max_pages() -> usize {
+    200
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            on_import: false,
+            detection_model: default_detection_model(),
+            recognition_model: default_recognition_model(),
+            max_pages: default_ocr_max_pages(),
+        }
+    }
+}
+
+/// 元数据补全配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichConfig {
+    /// 依次尝试的数据源：`crossref`、`arxiv`、`fixture`
+    #[serde(default = "default_enrich_providers")]
+    pub providers: Vec<String>,
+
+    /// Crossref REST API 地址
+    #[serde(default = "default_crossref_url")]
+    pub crossref_url: String,
+
+    /// arXiv API 地址
+    #[serde(default = "default_arxiv_url")]
+    pub arxiv_url: String,
+
+    /// 随请求发送给 Crossref 的联系邮箱（polite pool）
+    #[serde(default)]
+    pub mailto: Option<String>,
+
+    /// 单次请求超时（秒）
+    #[serde(default = "default_enrich_timeout")]
+    pub timeout_secs: u64,
+
+    /// 查询结果在数据库中缓存的天数，0 表示不缓存
+    #[serde(default = "default_enrich_cache_days")]
+    pub cache_days: u32,
+
+    /// `fixture` 数据源读取 JSON 文件的目录，用于离线测试
+    #[serde(default)]
+    pub fixture_dir: Option<PathBuf>,
+}
+
+fn default_enrich_providers() -> Vec<String> {
+    vec!["arxiv".to_string(), "crossref".to_string()]
+}
+
+fn default_crossref_url() -> String {
+    "https://api.crossref.org".to_string()
+}
+
+fn default_arxiv_url() -> String {
+    "https://export.arxiv.org".to_string()
+}
+
+fn default_enrich_timeout() -> u64 {
+    20
+}
+
+fn default_enrich_cache_days() -> u32 {
+    30
+}
+
+impl Default for EnrichConfig {
+    fn default() -> Self {
+        Self {
+            providers: default_enrich_providers(),
+            crossref_url: default_crossref_url(),
+            arxiv_url: default_arxiv_url(),
+            mailto: None,
+            timeout_secs: default_enrich_timeout(),
+            cache_days: default_enrich_cache_days(),
+            fixture_dir: None,
+        }
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
@@ -91,6 +287,11 @@ impl Default for AppConfig {
                     prefer_json: true,
                     fallback_pdf: true,
                     default_category: "未分类".to_string(),
+                    on_duplicate: DuplicatePolicy::Skip,
+                    index_pages: default_index_pages(),
+                    page_text_budget: default_page_text_budget(),
+                    background_extract_size: default_background_extract_size(),
+                    explode_archives: false,
                 },
             },
             search: SearchConfig {
@@ -107,6 +308,8 @@ impl Default for AppConfig {
                 algorithm: "blake3".to_string(),
                 verify_on_import: true,
             },
+            ocr: OcrConfig::default(),
+            enrich: EnrichConfig::default(),
         }
     }
 }
@@ -1,7 +1,9 @@
 // 在 update_file 方法中添加 FTS 索引更新逻辑
 
+use crate::authors::AuthorManager;
 use crate::errors::{Result, TagboxError}; // Assuming Result and TagboxError are in errors.rs
-use crate::types::{FileUpdateRequest, QueryParam}; // Assuming FileUpdateRequest is in types.rs
+use crate::tags::TagManager;
+use crate::types::{AuthorRole, FileUpdateRequest, QueryParam}; // Assuming FileUpdateRequest is in types.rs
 use crate::utils::{current_time, require_field};
 use sqlx::{sqlite::SqliteArguments, Arguments, SqlitePool}; // Assuming current_time is in utils.rs
 
@@ -73,32 +75,30 @@ impl Editor {
             params.push(QueryParam::Int(if is_deleted { 1 } else { 0 }));
         }
 
-        // Fields below are removed based on "no field" errors for FileUpdateRequest
-        // if let Some(source_url) = &update.source_url {
-        //     updates.push("source_url = ?".to_string());
-        //     params.push(QueryParam::String(source_url.clone()));
-        // }
+        if let Some(year) = update.year {
+            updates.push("year = ?".to_string());
+            params.push(QueryParam::Int(year.into()));
+        }
 
-        // if let Some(thumbnail_url) = &update.thumbnail_url {
-        //     updates.push("thumbnail_url = ?".to_string());
-        //     params.push(QueryParam::String(thumbnail_url.clone()));
-        // }
+        if let Some(publisher) = &update.publisher {
+            updates.push("publisher = ?".to_string());
+            params.push(QueryParam::String(publisher.clone()));
+        }
 
-        // if let Some(file_size) = update.file_size {
-        //     updates.push("file_size = ?".to_string());
-        //     params.push(QueryParam::Int(file_size));
-        // }
+        if let Some(source) = &update.source {
+            updates.push("source_url = ?".to_string());
+            params.push(QueryParam::String(source.clone()));
+        }
 
-        // if let Some(year) = update.year { // year was Option<i32>
-        //     updates.push("year = ?".to_string());
-        //     params.push(QueryParam::Int(year.into())); // Convert i32 to i64 for QueryParam::Int
-        // }
+        if let Some(file_metadata) = &update.file_metadata {
+            updates.push("file_metadata = ?".to_string());
+            params.push(QueryParam::String(file_metadata.to_string()));
+        }
 
-        // if let Some(rating) = update.rating {
-        //     updates.push("rating = ?".to_string());
-        //     // QueryParam::Float variant does not exist
-        //     // params.push(QueryParam::Float(rating));
-        // }
+        if let Some(type_metadata) = &update.type_metadata {
+            updates.push("type_metadata = ?".to_string());
+            params.push(QueryParam::String(type_metadata.to_string()));
+        }
 
         // 添加更新时间
         updates.push("updated_at = ?".to_string());
@@ -132,39 +132,28 @@ impl Editor {
                 .map_err(TagboxError::Database)?;
         }
 
-        // 处理作者更新
-        if let Some(authors) = &update.authors {
-            // 清除旧作者关系
-            sqlx::query!(
-                r#"
-                DELETE FROM file_authors WHERE file_id = ?
-                "#,
-                file_id
-            )
-            .execute(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
-
-            // 添加新作者
-            for author_name in authors {
-                // Assuming authors is Vec<String> of names. Need to get/create author IDs.
-                // This part requires AuthorManager logic or direct insertion if simple name suffices for linking.
-                // For now, let's assume self.add_author_to_file handles finding or creating author by name and linking.
-                // If add_author_to_file is not available in Editor, we need a different approach.
-                // Let's assume for now `add_author_to_file` is a helper method we might need to implement or call from AuthorManager.
-                // For simplicity, if author_name is ID:
-                // self.link_author_to_file(file_id, author_name).await?; // If author_name is ID
-                // If author_name is a name string, we need to find/create and then link:
-                // let author = self.author_manager.get_or_create_author(author_name).await?;
-                // self.link_author_to_file(file_id, &author.id).await?;
-                // This requires Editor to have access to AuthorManager or similar logic.
-
-                // Placeholder: Assuming add_author_to_file is a method on Editor that handles this.
-                self.add_author_to_file(file_id, author_name).await?;
+        // 处理作者和其他署名更新，按署名顺序保存，名称经拆分、规范化和别名解析
+        if update.authors.is_some() || update.contributors.is_some() {
+            let author_manager = AuthorManager::new(self.db_pool.clone());
+            if let Some(authors) = &update.authors {
+                author_manager
+                    .set_file_authors(file_id, AuthorRole::Author, authors)
+                    .await?;
+            }
+            if let Some(contributors) = &update.contributors {
+                author_manager
+                    .set_file_contributors(file_id, contributors)
+                    .await?;
             }
 
-            // 因为作者改变，需要手动更新 FTS 索引的 authors 列
-            let authors_text = authors.join(" ");
+            // 因为署名改变，需要手动更新 FTS 索引的 authors 列
+            let (authors, contributors) = author_manager.get_file_authors(file_id).await?;
+            let authors_text = authors
+                .iter()
+                .chain(contributors.iter().map(|c| &c.name))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ");
             sqlx::query!(
                 r#"
                 UPDATE files_fts SET authors = ?
@@ -180,114 +169,15 @@ impl Editor {
 
         // 处理标签更新
         if let Some(tags) = &update.tags {
-            // 清除旧标签关系
-            sqlx::query!(
-                r#"
-                DELETE FROM file_tags WHERE file_id = ?
-                "#,
-                file_id
-            )
-            .execute(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
-
-            // 添加新标签
-            for tag_name in tags {
-                // Placeholder: Assuming add_tag_to_file is a method on Editor that handles this.
-                self.add_tag_to_file(file_id, tag_name).await?;
-            }
+            let tag_manager = TagManager::new(self.db_pool.clone());
+            tag_manager.set_file_tags(file_id, tags).await?;
 
             // 因为标签改变，需要手动更新 FTS 索引的 tags 列
-            let tags_text = tags.join(" ");
-            sqlx::query!(
-                r#"
-                UPDATE files_fts SET tags = ?
-                WHERE rowid = (SELECT rowid FROM files WHERE id = ?)
-                "#,
-                tags_text,
-                file_id
-            )
-            .execute(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
+            tag_manager.update_fts_tags(file_id).await?;
         }
         Ok(())
     }
 
-    // Helper method placeholders - these would need actual implementation
-    // or calls to respective managers (AuthorManager, TagManager)
-    async fn add_author_to_file(&self, file_id: &str, author_name: &str) -> Result<()> {
-        // 1. Check if author exists by name, get ID. If not, create author, get ID.
-        let author = sqlx::query!("SELECT id FROM authors WHERE name = ?", author_name)
-            .fetch_optional(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
-
-        let author_id = if let Some(auth) = author {
-            auth.id
-        } else {
-            let new_author_id = crate::utils::generate_uuid();
-            let now = current_time().to_rfc3339();
-            sqlx::query!(
-                "INSERT INTO authors (id, name, created_at, updated_at) VALUES (?, ?, ?, ?)",
-                new_author_id,
-                author_name,
-                now,
-                now
-            )
-            .execute(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
-            Some(new_author_id) // Ensure compatible types
-        };
-
-        // 2. Link file_id and author_id in file_authors
-        sqlx::query!(
-            "INSERT OR IGNORE INTO file_authors (file_id, author_id) VALUES (?, ?)",
-            file_id,
-            author_id
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-        Ok(())
-    }
-
-    async fn add_tag_to_file(&self, file_id: &str, tag_name: &str) -> Result<()> {
-        // 1. Check if tag exists by name, get ID. If not, create tag, get ID.
-        let tag = sqlx::query!("SELECT id FROM tags WHERE name = ?", tag_name)
-            .fetch_optional(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
-
-        let tag_id = if let Some(t) = tag {
-            t.id
-        } else {
-            let new_tag_id = crate::utils::generate_uuid();
-            let now = current_time().to_rfc3339();
-            // Tags table has (id, name, created_at)
-            // Removed updated_at based on "table tags has no column named updated_at" error
-            sqlx::query!(
-                "INSERT INTO tags (id, name, path, created_at, is_deleted, parent_id) VALUES (?, ?, ?, ?, ?, NULL)",
-                new_tag_id, tag_name, tag_name, now, 0
-            )
-            .execute(&self.db_pool)
-            .await.map_err(TagboxError::Database)?;
-            Some(new_tag_id) // Ensure compatible types
-        };
-
-        // 2. Link file_id and tag_id in file_tags
-        sqlx::query!(
-            "INSERT OR IGNORE INTO file_tags (file_id, tag_id) VALUES (?, ?)",
-            file_id,
-            tag_id
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-        Ok(())
-    }
-
     /// 获取文件路径
     pub async fn get_file_path(&self, file_id: &str) -> Result<std::path::PathBuf> {
         let file_path = sqlx::query!(
@@ -312,6 +202,7 @@ impl Editor {
             SELECT
                 id, title, initial_hash, current_hash, relative_path, filename,
                 year, publisher, category1, category2, category3, source_url, summary, full_text,
+                original_path, size, mime_type, file_mtime,
                 created_at, updated_at, is_deleted, deleted_at,
                 file_metadata, type_metadata
             FROM files
@@ -324,40 +215,34 @@ impl Editor {
         .map_err(TagboxError::Database)?
         .ok_or_else(|| TagboxError::InvalidFileId(file_id.to_string()))?;
 
-        // 获取作者
-        let authors = sqlx::query!(
-            "SELECT name FROM authors a JOIN file_authors fa ON a.id = fa.author_id WHERE fa.file_id = ?",
-            file_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .into_iter()
-        .map(|row| row.name)
-        .collect();
+        // 获取作者和其他署名
+        let (authors, contributors) = AuthorManager::new(self.db_pool.clone())
+            .get_file_authors(file_id)
+            .await?;
 
         // 获取标签
-        let tags = sqlx::query!(
-            "SELECT name FROM tags t JOIN file_tags ft ON t.id = ft.tag_id WHERE ft.file_id = ?",
-            file_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .into_iter()
-        .map(|row| row.name)
-        .collect();
+        let tags = TagManager::new(self.db_pool.clone())
+            .get_file_tags(file_id)
+            .await?;
 
         Ok(crate::types::FileEntry {
             id: require_field(file_row.id, "files.id")?,
             title: file_row.title,
             authors,
+            contributors,
             year: file_row.year.map(|y| y as i32),
             publisher: file_row.publisher,
             source: file_row.source_url,
             path: std::path::PathBuf::from(file_row.relative_path),
-            original_path: None,
+            original_path: file_row.original_path.map(std::path::PathBuf::from),
             original_filename: file_row.filename,
+            size: file_row.size.map(|s| s as u64),
+            mime_type: file_row.mime_type,
+            mtime: file_row
+                .file_mtime
+                .as_deref()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
             hash: file_row.initial_hash,
             current_hash: file_row.current_hash,
             category1: file_row.category1.unwrap_or_default(),
@@ -417,35 +302,11 @@ impl Editor {
         file_id: &str,
         config: &crate::config::AppConfig,
     ) -> Result<std::path::PathBuf> {
-        use crate::pathgen::PathGenerator;
-        use crate::types::ImportMetadata;
-        use std::fs;
-
         // 获取文件当前信息
         let file = self.get_file(file_id).await?;
 
-        // 构建新的元数据用于路径生成
-        let metadata = ImportMetadata {
-            title: file.title.clone(),
-            authors: file.authors.clone(),
-            year: file.year,
-            publisher: file.publisher.clone(),
-            source: file.source.clone(),
-            category1: file.category1.clone(),
-            category2: file.category2.clone(),
-            category3: file.category3.clone(),
-            tags: file.tags.clone(),
-            summary: file.summary.clone(),
-            full_text: file.full_text.clone(),
-            additional_info: std::collections::HashMap::new(),
-            file_metadata: file.file_metadata.clone(),
-            type_metadata: file.type_metadata.clone(),
-        };
-
         // 生成新路径
-        let path_generator = PathGenerator::new(config.clone());
-        let new_filename = path_generator.generate_filename(&file.original_filename, &metadata)?;
-        let new_path = path_generator.generate_path(&new_filename, &metadata)?;
+        let new_path = self.expected_path(&file, config)?;
 
         // 当前文件的绝对路径
         let old_absolute_path = config.import.paths.storage_dir.join(&file.path);
@@ -455,6 +316,22 @@ impl Editor {
             return Ok(new_path);
         }
 
+        self.move_to(file_id, &old_absolute_path, &new_path, config)
+            .await?;
+
+        Ok(new_path)
+    }
+
+    /// 把文件从 `old_absolute_path` 移动到指定的绝对路径并更新数据库中的相对路径
+    pub(crate) async fn move_to(
+        &self,
+        file_id: &str,
+        old_absolute_path: &std::path::Path,
+        new_path: &std::path::Path,
+        config: &crate::config::AppConfig,
+    ) -> Result<()> {
+        use std::fs;
+
         // 创建目标目录
         if let Some(parent) = new_path.parent() {
             fs::create_dir_all(parent).map_err(|e| {
@@ -463,7 +340,7 @@ impl Editor {
         }
 
         // 移动文件
-        fs::rename(&old_absolute_path, &new_path)
+        fs::rename(old_absolute_path, new_path)
             .map_err(|e| TagboxError::FileSystem(format!("Failed to move file: {}", e)))?;
 
         // 更新数据库中的路径
@@ -484,24 +361,22 @@ impl Editor {
         .await
         .map_err(TagboxError::Database)?;
 
-        Ok(new_path)
+        Ok(())
     }
 
-    /// 检查单个文件路径是否需要重建
-    pub async fn check_file_path(
+    /// 按文件当前的元数据和 `classify_template` 计算文件应在的绝对路径
+    pub fn expected_path(
         &self,
-        file_id: &str,
+        file: &crate::types::FileEntry,
         config: &crate::config::AppConfig,
-    ) -> Result<Option<std::path::PathBuf>> {
+    ) -> Result<std::path::PathBuf> {
         use crate::pathgen::PathGenerator;
         use crate::types::ImportMetadata;
 
-        let file = self.get_file(file_id).await?;
-
-        // 构建元数据
         let metadata = ImportMetadata {
             title: file.title.clone(),
             authors: file.authors.clone(),
+            contributors: file.contributors.clone(),
             year: file.year,
             publisher: file.publisher.clone(),
             source: file.source.clone(),
@@ -514,13 +389,24 @@ impl Editor {
             additional_info: std::collections::HashMap::new(),
             file_metadata: file.file_metadata.clone(),
             type_metadata: file.type_metadata.clone(),
+            mime_type: file.mime_type.clone(),
         };
 
-        // 生成应该的路径
         let path_generator = PathGenerator::new(config.clone());
-        let expected_filename =
-            path_generator.generate_filename(&file.original_filename, &metadata)?;
-        let expected_path = path_generator.generate_path(&expected_filename, &metadata)?;
+        let filename = path_generator.generate_filename(&file.original_filename, &metadata)?;
+        path_generator.generate_path(&filename, &metadata)
+    }
+
+    /// 检查单个文件路径是否需要重建
+    pub async fn check_file_path(
+        &self,
+        file_id: &str,
+        config: &crate::config::AppConfig,
+    ) -> Result<Option<std::path::PathBuf>> {
+        let file = self.get_file(file_id).await?;
+
+        // 生成应该的路径
+        let expected_path = self.expected_path(&file, config)?;
 
         // 当前文件的绝对路径
         let current_absolute_path = config.import.paths.storage_dir.join(&file.path);
@@ -650,6 +536,21 @@ impl Editor {
             }
         }
 
+        if let Some(contributors) = &update.contributors {
+            if contributors != &original.contributors {
+                let names = |list: &[crate::types::Contributor]| {
+                    list.iter()
+                        .map(|c| format!("{} ({})", c.name, c.role))
+                        .collect::<Vec<_>>()
+                };
+                changes.push(format!(
+                    "Contributors: {:?} → {:?}",
+                    names(&original.contributors),
+                    names(contributors)
+                ));
+            }
+        }
+
         if let Some(category1) = &update.category1 {
             if category1 != &original.category1 {
                 changes.push(format!(
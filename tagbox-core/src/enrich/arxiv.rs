@@ -0,0 +1,249 @@
+use super::{collapse_whitespace, http_client, titles_match, EnrichmentProvider};
+use crate::config::EnrichConfig;
+use crate::errors::{Result, TagboxError};
+use crate::identifiers::normalize_identifier;
+use crate::types::{EnrichedMetadata, EnrichmentQuery, Identifier, IdentifierKind};
+use async_trait::async_trait;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use tracing::debug;
+
+/// 按标题搜索时检查的候选数量
+const SEARCH_RESULTS: &str = "5";
+
+/// arXiv API（Atom 格式）数据源，支持 arXiv 编号和标题查询
+pub struct ArxivProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl ArxivProvider {
+    pub fn new(config: &EnrichConfig) -> Result<Self> {
+        Ok(Self {
+            client: http_client(config)?,
+            base_url: config.arxiv_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    async fn query(&self, params: &[(&str, String)]) -> Result<Vec<EnrichedMetadata>> {
+        let url = format!("{}/api/query", self.base_url);
+        debug!("请求 arXiv: {} {:?}", url, params);
+
+        let feed = self
+            .client
+            .get(&url)
+            .query(params)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| TagboxError::Network(e.to_string()))?
+            .text()
+            .await
+            .map_err(|e| TagboxError::Network(e.to_string()))?;
+
+        Ok(parse_feed(&feed))
+    }
+}
+
+#[async_trait]
+impl EnrichmentProvider for ArxivProvider {
+    fn name(&self) -> &str {
+        "arxiv"
+    }
+
+    fn supports(&self, query: &EnrichmentQuery) -> bool {
+        query.title.is_some()
+            || query
+                .identifiers
+                .iter()
+                .any(|i| i.kind == IdentifierKind::Arxiv)
+    }
+
+    async fn lookup(&self, query: &EnrichmentQuery) -> Result<Option<EnrichedMetadata>> {
+        let arxiv_id = query
+            .identifiers
+            .iter()
+            .find(|i| i.kind == IdentifierKind::Arxiv);
+
+        if let Some(id) = arxiv_id {
+            let entries = self
+                .query(&[
+                    ("id_list", id.value.clone()),
+                    ("max_results", "1".to_string()),
+                ])
+                .await?;
+            return Ok(entries.into_iter().next());
+        }
+
+        let Some(title) = query.title.as_deref() else {
+            return Ok(None);
+        };
+
+        // 搜索语法中的引号和冒号会破坏短语查询
+        let phrase: String = title
+            .chars()
+            .map(|c| if c == '"' || c == ':' { ' ' } else { c })
+            .collect();
+        let entries = self
+            .query(&[
+                (
+                    "search_query",
+                    format!("ti:\"{}\"", collapse_whitespace(&phrase)),
+                ),
+                ("max_results", SEARCH_RESULTS.to_string()),
+            ])
+            .await?;
+
+        Ok(entries.into_iter().find(|entry| {
+            entry
+                .title
+                .as_deref()
+                .is_some_and(|t| titles_match(t, title))
+        }))
+    }
+}
+
+/// 解析 Atom feed 中的 `<entry>`，跳过 arXiv 用来报告错误的条目
+fn parse_feed(feed: &str) -> Vec<EnrichedMetadata> {
+    let mut reader = Reader::from_str(feed);
+    let mut entries = Vec::new();
+    let mut entry: Option<EnrichedMetadata> = None;
+    let mut abs_id: Option<String> = None;
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = local_name(e.local_name().into_inner()).to_string();
+                if name == "entry" {
+                    entry = Some(EnrichedMetadata::default());
+                    abs_id = None;
+                }
+                path.push(name);
+                text.clear();
+            }
+            Ok(Event::Text(e)) => {
+                if let Ok(value) = e.unescape() {
+                    text.push_str(&value);
+                }
+            }
+            Ok(Event::CData(e)) => {
+                text.push_str(&String::from_utf8_lossy(&e));
+            }
+            Ok(Event::End(_)) => {
+                let name = path.pop().unwrap_or_default();
+                let parent = path.last().map(String::as_str);
+                let value = collapse_whitespace(&text);
+                text.clear();
+
+                if name == "entry" {
+                    if let (Some(mut done), Some(id)) = (entry.take(), abs_id.take()) {
+                        if let Some(value) = normalize_identifier(IdentifierKind::Arxiv, &id) {
+                            done.url = Some(format!("https://arxiv.org/abs/{}", value));
+                            done.identifiers.insert(
+                                0,
+                                Identifier {
+                                    kind: IdentifierKind::Arxiv,
+                                    value,
+                                },
+                            );
+                            entries.push(done);
+                        }
+                    }
+                    continue;
+                }
+
+                let Some(current) = entry.as_mut() else {
+                    continue;
+                };
+                if value.is_empty() {
+                    continue;
+                }
+
+                match (parent, name.as_str()) {
+                    (Some("entry"), "id") => {
+                        abs_id = value.split_once("/abs/").map(|(_, id)| id.to_string());
+                    }
+                    (Some("entry"), "title") => current.title = Some(value),
+                    (Some("entry"), "summary") => current.abstract_text = Some(value),
+                    (Some("entry"), "published") => {
+                        current.published = value.get(..10).map(str::to_string);
+                    }
+                    (Some("entry"), "journal_ref") => current.venue = Some(value),
+                    (Some("entry"), "doi") => {
+                        if let Some(doi) = normalize_identifier(IdentifierKind::Doi, &value) {
+                            current.identifiers.push(Identifier {
+                                kind: IdentifierKind::Doi,
+                                value: doi,
+                            });
+                        }
+                    }
+                    (Some("author"), "name") => current.authors.push(value),
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                debug!("解析 arXiv 响应失败: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+fn local_name(name: &[u8]) -> &str {
+    std::str::from_utf8(name).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_feed() {
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:arxiv="http://arxiv.org/schemas/atom">
+  <id>http://arxiv.org/api/abc</id>
+  <title type="html">ArXiv Query: id_list=1706.03762</title>
+  <entry>
+    <id>http://arxiv.org/abs/1706.03762v7</id>
+    <published>2017-06-12T17:57:34Z</published>
+    <title>Attention Is All
+      You Need</title>
+    <summary>  The dominant sequence transduction models &amp; more.
+    </summary>
+    <author><name>Ashish Vaswani</name></author>
+    <author><name>Noam Shazeer</name><arxiv:affiliation>Google</arxiv:affiliation></author>
+    <arxiv:doi>10.48550/arXiv.1706.03762</arxiv:doi>
+    <arxiv:journal_ref>NeurIPS 2017</arxiv:journal_ref>
+  </entry>
+  <entry>
+    <id>http://arxiv.org/api/errors#incorrect_id_format_for_1234</id>
+    <title>Error</title>
+  </entry>
+</feed>"#;
+
+        let entries = parse_feed(feed);
+        assert_eq!(entries.len(), 1);
+
+        let entry = &entries[0];
+        assert_eq!(entry.title.as_deref(), Some("Attention Is All You Need"));
+        assert_eq!(entry.authors, vec!["Ashish Vaswani", "Noam Shazeer"]);
+        assert_eq!(
+            entry.abstract_text.as_deref(),
+            Some("The dominant sequence transduction models & more.")
+        );
+        assert_eq!(entry.published.as_deref(), Some("2017-06-12"));
+        assert_eq!(entry.venue.as_deref(), Some("NeurIPS 2017"));
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://arxiv.org/abs/1706.03762")
+        );
+        assert_eq!(entry.identifiers[0].to_string(), "arxiv:1706.03762");
+        assert_eq!(entry.identifiers[1].kind, IdentifierKind::Doi);
+    }
+}
@@ -0,0 +1,259 @@
+use super::{collapse_whitespace, http_client, titles_match, EnrichmentProvider};
+use crate::config::EnrichConfig;
+use crate::errors::{Result, TagboxError};
+use crate::identifiers::normalize_identifier;
+use crate::types::{EnrichedMetadata, EnrichmentQuery, Identifier, IdentifierKind};
+use async_trait::async_trait;
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::StatusCode;
+use serde_json::Value;
+use tracing::debug;
+
+lazy_static! {
+    // Crossref 的摘要是 JATS XML 片段
+    static ref JATS_TAG_RE: Regex = Regex::new(r"<[^>]+>").unwrap();
+}
+
+/// 按标题搜索时检查的候选数量
+const SEARCH_ROWS: &str = "5";
+
+/// Crossref REST API（`/works`）数据源，支持 DOI、ISBN 和标题查询
+pub struct CrossrefProvider {
+    client: reqwest::Client,
+    base_url: String,
+    mailto: Option<String>,
+}
+
+impl CrossrefProvider {
+    pub fn new(config: &EnrichConfig) -> Result<Self> {
+        Ok(Self {
+            client: http_client(config)?,
+            base_url: config.crossref_url.trim_end_matches('/').to_string(),
+            mailto: config.mailto.clone(),
+        })
+    }
+
+    async fn get(&self, url: &str, params: &[(&str, String)]) -> Result<Option<Value>> {
+        let mut request = self.client.get(url).query(params);
+        if let Some(mailto) = &self.mailto {
+            request = request.query(&[("mailto", mailto)]);
+        }
+
+        debug!("请求 Crossref: {}", url);
+        let response = request
+            .send()
+            .await
+            .map_err(|e| TagboxError::Network(e.to_string()))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body = response
+            .error_for_status()
+            .map_err(|e| TagboxError::Network(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| TagboxError::Network(e.to_string()))?;
+        Ok(Some(body))
+    }
+}
+
+#[async_trait]
+impl EnrichmentProvider for CrossrefProvider {
+    fn name(&self) -> &str {
+        "crossref"
+    }
+
+    fn supports(&self, query: &EnrichmentQuery) -> bool {
+        query.title.is_some()
+            || query
+                .identifiers
+                .iter()
+                .any(|i| matches!(i.kind, IdentifierKind::Doi | IdentifierKind::Isbn))
+    }
+
+    async fn lookup(&self, query: &EnrichmentQuery) -> Result<Option<EnrichedMetadata>> {
+        let find = |kind: IdentifierKind| query.identifiers.iter().find(|i| i.kind == kind);
+
+        if let Some(doi) = find(IdentifierKind::Doi) {
+            let url = format!("{}/works/{}", self.base_url, doi.value);
+            let body = self.get(&url, &[]).await?;
+            return Ok(body.map(|b| parse_work(&b["message"])));
+        }
+
+        let url = format!("{}/works", self.base_url);
+        let mut params = vec![("rows", SEARCH_ROWS.to_string())];
+
+        if let Some(isbn) = find(IdentifierKind::Isbn) {
+            params.push(("filter", format!("isbn:{}", isbn.value)));
+            let body = self.get(&url, &params).await?;
+            let first = body.as_ref().and_then(|b| b["message"]["items"].get(0));
+            return Ok(first.map(parse_work));
+        }
+
+        let Some(title) = query.title.as_deref() else {
+            return Ok(None);
+        };
+        params.push(("query.bibliographic", title.to_string()));
+        if !query.authors.is_empty() {
+            params.push(("query.author", query.authors.join(" ")));
+        }
+
+        // 相关度排序的第一条不一定是同一作品，只接受标题一致的结果
+        let Some(body) = self.get(&url, &params).await? else {
+            return Ok(None);
+        };
+        let items = body["message"]["items"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        Ok(items.iter().map(parse_work).find(|work| {
+            work.title
+                .as_deref()
+                .is_some_and(|t| titles_match(t, title))
+        }))
+    }
+}
+
+/// 解析 Crossref 的 work 记录
+fn parse_work(work: &Value) -> EnrichedMetadata {
+    let first_string = |key: &str| {
+        work[key]
+            .as_array()
+            .and_then(|values| values.first())
+            .and_then(Value::as_str)
+            .map(collapse_whitespace)
+            .filter(|s| !s.is_empty())
+    };
+
+    let authors = work["author"]
+        .as_array()
+        .map(|authors| authors.iter().filter_map(author_name).collect())
+        .unwrap_or_default();
+
+    let abstract_text = work["abstract"]
+        .as_str()
+        .map(|a| collapse_whitespace(&JATS_TAG_RE.replace_all(a, " ")))
+        .filter(|a| !a.is_empty());
+
+    let published = ["issued", "published", "published-print", "published-online"]
+        .iter()
+        .find_map(|key| date_parts(&work[*key]));
+
+    let mut identifiers = Vec::new();
+    let mut push = |kind: IdentifierKind, raw: &str| {
+        if let Some(value) = normalize_identifier(kind, raw) {
+            let identifier = Identifier { kind, value };
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+    };
+    if let Some(doi) = work["DOI"].as_str() {
+        push(IdentifierKind::Doi, doi);
+    }
+    for (key, kind) in [
+        ("ISBN", IdentifierKind::Isbn),
+        ("ISSN", IdentifierKind::Issn),
+    ] {
+        for raw in work[key].as_array().into_iter().flatten() {
+            if let Some(raw) = raw.as_str() {
+                push(kind, raw);
+            }
+        }
+    }
+
+    EnrichedMetadata {
+        title: first_string("title"),
+        authors,
+        venue: first_string("container-title"),
+        publisher: work["publisher"].as_str().map(str::to_string),
+        abstract_text,
+        published,
+        identifiers,
+        url: work["URL"].as_str().map(str::to_string),
+    }
+}
+
+/// 作者名：个人为 "given family"，机构作者只有 `name`
+fn author_name(author: &Value) -> Option<String> {
+    let family = author["family"].as_str().map(str::trim);
+    let given = author["given"].as_str().map(str::trim);
+
+    match (given, family) {
+        (Some(given), Some(family)) if !given.is_empty() => Some(format!("{} {}", given, family)),
+        (_, Some(family)) if !family.is_empty() => Some(family.to_string()),
+        _ => author["name"].as_str().map(|n| n.trim().to_string()),
+    }
+}
+
+/// `{"date-parts": [[2017, 6, 12]]}` 转为 `2017-06-12`
+fn date_parts(date: &Value) -> Option<String> {
+    let parts: Vec<i64> = date["date-parts"]
+        .get(0)?
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_i64)
+        .collect();
+
+    match parts.as_slice() {
+        [year] => Some(format!("{:04}", year)),
+        [year, month] => Some(format!("{:04}-{:02}", year, month)),
+        [year, month, day, ..] => Some(format!("{:04}-{:02}-{:02}", year, month, day)),
+        [] => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_work() {
+        let work = json!({
+            "DOI": "10.1145/3292500.3330701",
+            "title": ["Optuna:\n  A Next-generation Hyperparameter Optimization Framework"],
+            "author": [
+                {"given": "Takuya", "family": "Akiba"},
+                {"given": "Shotaro", "family": "Sano"},
+                {"name": "Preferred Networks"}
+            ],
+            "container-title": ["Proceedings of the 25th ACM SIGKDD"],
+            "publisher": "ACM",
+            "abstract": "<jats:p>The purpose of this study is\n to introduce</jats:p>",
+            "issued": {"date-parts": [[2019, 7, 25]]},
+            "ISBN": ["9781450362016"],
+            "URL": "http://dx.doi.org/10.1145/3292500.3330701"
+        });
+
+        let metadata = parse_work(&work);
+        assert_eq!(
+            metadata.title.as_deref(),
+            Some("Optuna: A Next-generation Hyperparameter Optimization Framework")
+        );
+        assert_eq!(
+            metadata.authors,
+            vec!["Takuya Akiba", "Shotaro Sano", "Preferred Networks"]
+        );
+        assert_eq!(
+            metadata.venue.as_deref(),
+            Some("Proceedings of the 25th ACM SIGKDD")
+        );
+        assert_eq!(
+            metadata.abstract_text.as_deref(),
+            Some("The purpose of this study is to introduce")
+        );
+        assert_eq!(metadata.published.as_deref(), Some("2019-07-25"));
+        assert_eq!(metadata.year(), Some(2019));
+        assert!(metadata.identifiers.contains(&Identifier {
+            kind: IdentifierKind::Doi,
+            value: "10.1145/3292500.3330701".to_string(),
+        }));
+        assert!(metadata
+            .identifiers
+            .iter()
+            .any(|i| i.kind == IdentifierKind::Isbn));
+    }
+}
@@ -0,0 +1,94 @@
+use super::{titles_match, EnrichmentProvider};
+use crate::errors::{Result, TagboxError};
+use crate::identifiers::normalize_identifier;
+use crate::types::{EnrichedMetadata, EnrichmentQuery, Identifier};
+use async_trait::async_trait;
+use std::fs;
+use std::path::Path;
+use tracing::debug;
+
+/// 从本地 JSON 文件读取元数据的数据源，用于离线测试
+///
+/// 目录中每个 `*.json` 文件是一条 `EnrichedMetadata` 或它们的数组，
+/// 先按标识符匹配，再按标题匹配。
+pub struct FixtureProvider {
+    records: Vec<EnrichedMetadata>,
+}
+
+impl FixtureProvider {
+    /// 用内存中的记录创建数据源
+    pub fn new(records: Vec<EnrichedMetadata>) -> Self {
+        let records = records.into_iter().map(normalize_record).collect();
+        Self { records }
+    }
+
+    /// 读取目录下的全部 `*.json` 文件
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut records = Vec::new();
+
+        for entry in fs::read_dir(dir).map_err(TagboxError::Io)? {
+            let path = entry.map_err(TagboxError::Io)?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let content = fs::read_to_string(&path).map_err(TagboxError::Io)?;
+            let value: serde_json::Value = serde_json::from_str(&content)?;
+            if value.is_array() {
+                records.extend(serde_json::from_value::<Vec<EnrichedMetadata>>(value)?);
+            } else {
+                records.push(serde_json::from_value(value)?);
+            }
+        }
+
+        debug!("从 {} 读取了 {} 条元数据", dir.display(), records.len());
+        Ok(Self::new(records))
+    }
+}
+
+/// 规范化记录中的标识符，使手写的 fixture 可以用任意常见写法
+fn normalize_record(mut record: EnrichedMetadata) -> EnrichedMetadata {
+    record.identifiers = record
+        .identifiers
+        .into_iter()
+        .filter_map(|i| {
+            normalize_identifier(i.kind, &i.value).map(|value| Identifier {
+                kind: i.kind,
+                value,
+            })
+        })
+        .collect();
+    record
+}
+
+#[async_trait]
+impl EnrichmentProvider for FixtureProvider {
+    fn name(&self) -> &str {
+        "fixture"
+    }
+
+    fn supports(&self, query: &EnrichmentQuery) -> bool {
+        !query.identifiers.is_empty() || query.title.is_some()
+    }
+
+    async fn lookup(&self, query: &EnrichmentQuery) -> Result<Option<EnrichedMetadata>> {
+        let by_identifier = self.records.iter().find(|record| {
+            record
+                .identifiers
+                .iter()
+                .any(|i| i.kind.identifies_work() && query.identifiers.contains(i))
+        });
+
+        let found = by_identifier.or_else(|| {
+            let title = query.title.as_deref()?;
+            self.records.iter().find(|record| {
+                record
+                    .title
+                    .as_deref()
+                    .is_some_and(|t| titles_match(t, title))
+            })
+        });
+
+        Ok(found.cloned())
+    }
+}
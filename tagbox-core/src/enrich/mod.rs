@@ -0,0 +1,390 @@
+//! 元数据补全：按标识符或标题、作者向外部数据源查询完整的文献信息
+//! （作者列表、期刊/会议、摘要、发表日期），结果缓存在数据库中。
+
+#[cfg(feature = "enrich")]
+mod arxiv;
+#[cfg(feature = "enrich")]
+mod crossref;
+mod fixture;
+
+#[cfg(feature = "enrich")]
+pub use arxiv::ArxivProvider;
+#[cfg(feature = "enrich")]
+pub use crossref::CrossrefProvider;
+pub use fixture::FixtureProvider;
+
+use crate::authors::normalize_author_name;
+use crate::config::EnrichConfig;
+use crate::editor::Editor;
+use crate::errors::{Result, TagboxError};
+use crate::identifiers::IdentifierManager;
+use crate::types::{
+    EnrichedMetadata, EnrichmentProposal, EnrichmentQuery, FileEntry, FileUpdateRequest,
+};
+use crate::utils::current_time;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Map, Value};
+use sqlx::{Row, SqlitePool};
+use tracing::{debug, info, warn};
+
+/// 元数据数据源
+#[async_trait]
+pub trait EnrichmentProvider: Send + Sync {
+    /// 数据源名称，同时用作缓存的命名空间
+    fn name(&self) -> &str;
+
+    /// 是否能处理该查询（例如需要特定类型的标识符）
+    fn supports(&self, query: &EnrichmentQuery) -> bool;
+
+    /// 查询元数据，没有匹配的记录时返回 `None`
+    async fn lookup(&self, query: &EnrichmentQuery) -> Result<Option<EnrichedMetadata>>;
+}
+
+/// 元数据补全管理器：依次查询数据源、缓存结果并生成对文件的更新
+pub struct Enricher {
+    db_pool: SqlitePool,
+    providers: Vec<Box<dyn EnrichmentProvider>>,
+    cache_days: u32,
+}
+
+impl Enricher {
+    /// 用给定的数据源创建补全管理器
+    pub fn new(db_pool: SqlitePool, providers: Vec<Box<dyn EnrichmentProvider>>) -> Self {
+        Self {
+            db_pool,
+            providers,
+            cache_days: EnrichConfig::default().cache_days,
+        }
+    }
+
+    /// 按 `[enrich]` 配置创建数据源
+    pub fn from_config(db_pool: SqlitePool, config: &EnrichConfig) -> Result<Self> {
+        let providers = config
+            .providers
+            .iter()
+            .map(|name| build_provider(name, config))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(db_pool, providers).with_cache_days(config.cache_days))
+    }
+
+    /// 设置缓存有效天数，0 表示不使用缓存
+    pub fn with_cache_days(mut self, cache_days: u32) -> Self {
+        self.cache_days = cache_days;
+        self
+    }
+
+    /// 依次查询数据源，返回 (数据源名称, 元数据)
+    ///
+    /// 单个数据源失败时继续尝试下一个；所有数据源都没有结果且有失败时返回最后一个错误。
+    pub async fn lookup(
+        &self,
+        query: &EnrichmentQuery,
+    ) -> Result<Option<(String, EnrichedMetadata)>> {
+        let key = query.cache_key();
+        let mut last_error = None;
+
+        for provider in self.providers.iter().filter(|p| p.supports(query)) {
+            let name = provider.name();
+
+            let result = match self.cached(name, &key).await? {
+                Some(cached) => {
+                    debug!("数据源 {} 命中缓存: {}", name, key);
+                    cached
+                }
+                None => match provider.lookup(query).await {
+                    Ok(result) => {
+                        self.store(name, &key, result.as_ref()).await?;
+                        result
+                    }
+                    Err(e) => {
+                        warn!("数据源 {} 查询失败: {}", name, e);
+                        last_error = Some(e);
+                        continue;
+                    }
+                },
+            };
+
+            if let Some(metadata) = result {
+                return Ok(Some((name.to_string(), metadata)));
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
+
+    /// 为已入库文件查询元数据并生成更新建议，数据源都没有结果时返回 `None`
+    pub async fn propose(&self, file_id: &str) -> Result<Option<EnrichmentProposal>> {
+        let editor = Editor::new(self.db_pool.clone());
+        let entry = editor.get_file(file_id).await?;
+        let identifiers = IdentifierManager::new(self.db_pool.clone())
+            .list(file_id)
+            .await?;
+
+        let query = EnrichmentQuery::from_entry(&entry, identifiers.clone());
+        let Some((provider, metadata)) = self.lookup(&query).await? else {
+            debug!("文件 {} 没有找到补全数据", file_id);
+            return Ok(None);
+        };
+
+        let update = build_update(&entry, &provider, &metadata);
+        let new_identifiers = metadata
+            .identifiers
+            .iter()
+            .filter(|i| !identifiers.contains(i))
+            .cloned()
+            .collect();
+
+        Ok(Some(EnrichmentProposal {
+            entry,
+            provider,
+            metadata,
+            update,
+            new_identifiers,
+        }))
+    }
+
+    /// 列出更新建议会改变的字段，格式与 `Editor::preview_changes` 相同
+    pub fn preview(&self, proposal: &EnrichmentProposal) -> Vec<String> {
+        let editor = Editor::new(self.db_pool.clone());
+        let mut changes = editor.preview_changes(&proposal.entry, &proposal.update);
+
+        if proposal.update.file_metadata.is_some() {
+            let current = proposal
+                .entry
+                .file_metadata
+                .as_ref()
+                .and_then(|m| m.get("enrichment"));
+            let metadata = &proposal.metadata;
+
+            for (label, key, value) in [
+                ("Venue", "venue", &metadata.venue),
+                ("Published", "published", &metadata.published),
+                ("URL", "url", &metadata.url),
+            ] {
+                let old = current.and_then(|c| c.get(key)).and_then(Value::as_str);
+                if let Some(value) = value.as_deref() {
+                    if old != Some(value) {
+                        changes.push(format!("{}: {:?} → {:?}", label, old, value));
+                    }
+                }
+            }
+        }
+
+        for identifier in &proposal.new_identifiers {
+            changes.push(format!("Identifier: + {}", identifier));
+        }
+
+        changes
+    }
+
+    /// 应用更新建议：通过 `Editor::update_file` 写入字段并追加新的标识符
+    pub async fn apply(&self, proposal: &EnrichmentProposal) -> Result<()> {
+        let file_id = &proposal.entry.id;
+
+        Editor::new(self.db_pool.clone())
+            .update_file(file_id, proposal.update.clone())
+            .await?;
+
+        if !proposal.new_identifiers.is_empty() {
+            IdentifierManager::new(self.db_pool.clone())
+                .add(file_id, &proposal.new_identifiers)
+                .await?;
+        }
+
+        info!("文件 {} 已用 {} 的元数据补全", file_id, proposal.provider);
+        Ok(())
+    }
+
+    /// 读取未过期的缓存：外层 `None` 表示没有缓存，内层 `None` 表示数据源没有结果
+    async fn cached(&self, provider: &str, key: &str) -> Result<Option<Option<EnrichedMetadata>>> {
+        if self.cache_days == 0 {
+            return Ok(None);
+        }
+
+        let row = sqlx::query(
+            "SELECT response, fetched_at FROM enrichment_cache WHERE provider = ? AND query_key = ?",
+        )
+        .bind(provider)
+        .bind(key)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let fetched_at: String = row.get("fetched_at");
+        let fresh = DateTime::parse_from_rfc3339(&fetched_at)
+            .map(|t| Utc::now() - t.with_timezone(&Utc) < Duration::days(self.cache_days.into()))
+            .unwrap_or(false);
+        if !fresh {
+            return Ok(None);
+        }
+
+        let response: Option<String> = row.get("response");
+        match response {
+            Some(json) => Ok(Some(Some(serde_json::from_str(&json)?))),
+            None => Ok(Some(None)),
+        }
+    }
+
+    async fn store(
+        &self,
+        provider: &str,
+        key: &str,
+        metadata: Option<&EnrichedMetadata>,
+    ) -> Result<()> {
+        if self.cache_days == 0 {
+            return Ok(());
+        }
+
+        let response = metadata.map(serde_json::to_string).transpose()?;
+
+        sqlx::query(
+            "INSERT OR REPLACE INTO enrichment_cache (provider, query_key, response, fetched_at)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(provider)
+        .bind(key)
+        .bind(response)
+        .bind(current_time().to_rfc3339())
+        .execute(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(())
+    }
+}
+
+fn build_provider(name: &str, config: &EnrichConfig) -> Result<Box<dyn EnrichmentProvider>> {
+    match name.to_ascii_lowercase().as_str() {
+        #[cfg(feature = "enrich")]
+        "crossref" => Ok(Box::new(CrossrefProvider::new(config)?)),
+        #[cfg(feature = "enrich")]
+        "arxiv" => Ok(Box::new(ArxivProvider::new(config)?)),
+        "fixture" => {
+            let dir = config.fixture_dir.as_ref().ok_or_else(|| {
+                TagboxError::Config("fixture 数据源需要设置 enrich.fixture_dir".to_string())
+            })?;
+            Ok(Box::new(FixtureProvider::load(dir)?))
+        }
+        other => Err(TagboxError::Config(format!(
+            "未知或未启用的元数据数据源: {}",
+            other
+        ))),
+    }
+}
+
+/// 对比当前条目和数据源返回的元数据，只保留有变化的字段
+///
+/// 摘要写入 `summary`，期刊/会议、发表日期和链接记录在 `file_metadata.enrichment`。
+fn build_update(
+    entry: &FileEntry,
+    provider: &str,
+    metadata: &EnrichedMetadata,
+) -> FileUpdateRequest {
+    let mut update = FileUpdateRequest::default();
+
+    if let Some(title) = non_empty(&metadata.title) {
+        if title != entry.title {
+            update.title = Some(title.to_string());
+        }
+    }
+
+    // 署名顺序也是元数据的一部分，顺序不同同样更新
+    if !metadata.authors.is_empty() && !same_authors(&metadata.authors, &entry.authors) {
+        update.authors = Some(metadata.authors.clone());
+    }
+
+    if let Some(year) = metadata.year() {
+        if entry.year != Some(year) {
+            update.year = Some(year);
+        }
+    }
+
+    if let Some(publisher) = non_empty(&metadata.publisher) {
+        if entry.publisher.as_deref() != Some(publisher) {
+            update.publisher = Some(publisher.to_string());
+        }
+    }
+
+    if let Some(abstract_text) = non_empty(&metadata.abstract_text) {
+        if entry.summary.as_deref() != Some(abstract_text) {
+            update.summary = Some(abstract_text.to_string());
+        }
+    }
+
+    let record = json!({
+        "provider": provider,
+        "venue": metadata.venue,
+        "published": metadata.published,
+        "url": metadata.url,
+    });
+    let current = entry
+        .file_metadata
+        .as_ref()
+        .and_then(|m| m.get("enrichment"));
+    if current != Some(&record) {
+        let mut file_metadata = match entry.file_metadata.clone() {
+            Some(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        file_metadata.insert("enrichment".to_string(), record);
+        update.file_metadata = Some(Value::Object(file_metadata));
+    }
+
+    update
+}
+
+/// 按顺序比较作者，名字先规范化（`Last, First` 与 `First Last` 视为相同）
+fn same_authors(a: &[String], b: &[String]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| normalize_author_name(a) == normalize_author_name(b))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// 比较两个标题是否指同一作品：忽略大小写、标点和多余空白
+pub(crate) fn titles_match(a: &str, b: &str) -> bool {
+    let a = normalize_title(a);
+    !a.is_empty() && a == normalize_title(b)
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 合并空白，数据源返回的标题和摘要常带有换行和缩进
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 数据源共用的 HTTP 客户端
+#[cfg(feature = "enrich")]
+fn http_client(config: &EnrichConfig) -> Result<reqwest::Client> {
+    let user_agent = match &config.mailto {
+        Some(mailto) => format!("tagbox/{} (mailto:{})", env!("CARGO_PKG_VERSION"), mailto),
+        None => format!("tagbox/{}", env!("CARGO_PKG_VERSION")),
+    };
+
+    reqwest::Client::builder()
+        .user_agent(user_agent)
+        .timeout(std::time::Duration::from_secs(config.timeout_secs))
+        .build()
+        .map_err(|e| TagboxError::Network(e.to_string()))
+}
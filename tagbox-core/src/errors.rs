@@ -54,6 +54,9 @@ pub enum TagboxError {
 
     #[error("文件系统错误: {0}")]
     FileSystem(String),
+
+    #[error("网络请求失败: {0}")]
+    Network(String),
 }
 
 pub type Result<T> = std::result::Result<T, TagboxError>;
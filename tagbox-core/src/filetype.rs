@@ -0,0 +1,336 @@
+use crate::errors::{Result, TagboxError};
+use crate::utils::mime_from_extension;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// 检测时读取的文件头长度（ZIP 子类型需要查看前几个条目名）
+const HEAD_LEN: usize = 64 * 1024;
+
+/// 通过文件内容检测到的文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileType {
+    pub mime: &'static str,
+    /// 规范扩展名（不含点）
+    pub extension: &'static str,
+}
+
+const fn ft(mime: &'static str, extension: &'static str) -> FileType {
+    FileType { mime, extension }
+}
+
+pub const PDF: FileType = ft("application/pdf", "pdf");
+pub const EPUB: FileType = ft("application/epub+zip", "epub");
+pub const DOCX: FileType = ft(
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "docx",
+);
+pub const XLSX: FileType = ft(
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "xlsx",
+);
+pub const PPTX: FileType = ft(
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    "pptx",
+);
+pub const ODT: FileType = ft("application/vnd.oasis.opendocument.text", "odt");
+pub const ODS: FileType = ft("application/vnd.oasis.opendocument.spreadsheet", "ods");
+pub const ODP: FileType = ft("application/vnd.oasis.opendocument.presentation", "odp");
+pub const ZIP: FileType = ft("application/zip", "zip");
+pub const GZIP: FileType = ft("application/gzip", "gz");
+pub const TAR: FileType = ft("application/x-tar", "tar");
+pub const SEVEN_Z: FileType = ft("application/x-7z-compressed", "7z");
+pub const RAR: FileType = ft("application/vnd.rar", "rar");
+pub const PNG: FileType = ft("image/png", "png");
+pub const JPEG: FileType = ft("image/jpeg", "jpg");
+pub const GIF: FileType = ft("image/gif", "gif");
+pub const BMP: FileType = ft("image/bmp", "bmp");
+pub const WEBP: FileType = ft("image/webp", "webp");
+pub const TIFF: FileType = ft("image/tiff", "tiff");
+pub const HEIC: FileType = ft("image/heic", "heic");
+pub const DJVU: FileType = ft("image/vnd.djvu", "djvu");
+pub const MP3: FileType = ft("audio/mpeg", "mp3");
+pub const FLAC: FileType = ft("audio/flac", "flac");
+pub const OGG: FileType = ft("audio/ogg", "ogg");
+pub const WAV: FileType = ft("audio/wav", "wav");
+pub const M4A: FileType = ft("audio/mp4", "m4a");
+pub const MP4: FileType = ft("video/mp4", "mp4");
+pub const MOV: FileType = ft("video/quicktime", "mov");
+pub const AVI: FileType = ft("video/x-msvideo", "avi");
+pub const MKV: FileType = ft("video/x-matroska", "mkv");
+pub const WEBM: FileType = ft("video/webm", "webm");
+pub const HTML: FileType = ft("text/html", "html");
+pub const XML: FileType = ft("application/xml", "xml");
+pub const TEXT: FileType = ft("text/plain", "txt");
+
+const ALL_TYPES: &[FileType] = &[
+    PDF, EPUB, DOCX, XLSX, PPTX, ODT, ODS, ODP, ZIP, GZIP, TAR, SEVEN_Z, RAR, PNG, JPEG, GIF, BMP,
+    WEBP, TIFF, HEIC, DJVU, MP3, FLAC, OGG, WAV, M4A, MP4, MOV, AVI, MKV, WEBM, HTML, XML, TEXT,
+];
+
+/// 同一类型可接受的其他扩展名
+const EXTENSION_ALIASES: &[(&str, &[&str])] = &[
+    ("jpg", &["jpeg", "jpe", "jfif"]),
+    ("tiff", &["tif"]),
+    ("html", &["htm", "xhtml"]),
+    ("mp4", &["m4v"]),
+    ("m4a", &["m4b", "mp4"]),
+    ("ogg", &["oga", "ogv", "opus"]),
+    ("gz", &["tgz"]),
+    ("mkv", &["mka", "mk3d"]),
+    ("heic", &["heif"]),
+    ("djvu", &["djv"]),
+    // 以 ZIP 为容器的其他格式
+    (
+        "zip",
+        &["jar", "apk", "cbz", "xpi", "whl", "kmz", "ipa", "nupkg"],
+    ),
+    ("xml", &["svg", "opf", "ncx", "rss", "atom", "xsl"]),
+];
+
+/// 根据 MIME 类型查找文件类型（仅限可通过内容检测的类型）
+pub fn from_mime(mime: &str) -> Option<FileType> {
+    ALL_TYPES
+        .iter()
+        .find(|t| t.mime.eq_ignore_ascii_case(mime))
+        .copied()
+}
+
+/// 根据 MIME 类型查找规范扩展名（仅限可通过内容检测的类型）
+pub fn extension_for_mime(mime: &str) -> Option<&'static str> {
+    from_mime(mime).map(|t| t.extension)
+}
+
+/// 读取文件头并检测文件类型
+pub fn detect_file(path: &Path) -> Result<Option<FileType>> {
+    let mut file = File::open(path).map_err(TagboxError::Io)?;
+    let mut head = Vec::with_capacity(HEAD_LEN);
+    file.by_ref()
+        .take(HEAD_LEN as u64)
+        .read_to_end(&mut head)
+        .map_err(TagboxError::Io)?;
+    Ok(detect_bytes(&head))
+}
+
+/// 检测文件的 MIME 类型，内容无法识别时回退到扩展名
+pub fn detect_mime(path: &Path) -> Option<String> {
+    match detect_file(path) {
+        Ok(Some(file_type)) => Some(file_type.mime.to_string()),
+        _ => mime_from_extension(path).map(|m| m.to_string()),
+    }
+}
+
+/// 文件扩展名是否与检测到的类型一致
+pub fn extension_matches(path: &Path, file_type: &FileType) -> bool {
+    // 纯文本可以是任意扩展名（md、csv、rs ...）
+    if *file_type == TEXT {
+        return true;
+    }
+
+    let ext = match path.extension() {
+        Some(ext) => ext.to_string_lossy().to_lowercase(),
+        None => return false,
+    };
+
+    if ext == file_type.extension {
+        return true;
+    }
+
+    if EXTENSION_ALIASES
+        .iter()
+        .any(|(canonical, aliases)| *canonical == file_type.extension && aliases.contains(&&*ext))
+    {
+        return true;
+    }
+
+    mime_from_extension(path) == Some(file_type.mime)
+}
+
+/// 根据文件头字节检测文件类型
+pub fn detect_bytes(buf: &[u8]) -> Option<FileType> {
+    if buf.starts_with(b"%PDF-") {
+        return Some(PDF);
+    }
+    if buf.starts_with(b"PK\x03\x04") {
+        return Some(detect_zip_subtype(buf));
+    }
+    if buf.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some(PNG);
+    }
+    if buf.starts_with(b"\xFF\xD8\xFF") {
+        return Some(JPEG);
+    }
+    if buf.starts_with(b"GIF87a") || buf.starts_with(b"GIF89a") {
+        return Some(GIF);
+    }
+    if buf.starts_with(b"II*\0") || buf.starts_with(b"MM\0*") {
+        return Some(TIFF);
+    }
+    if buf.starts_with(b"RIFF") && buf.len() >= 12 {
+        match &buf[8..12] {
+            b"WEBP" => return Some(WEBP),
+            b"WAVE" => return Some(WAV),
+            b"AVI " => return Some(AVI),
+            _ => {}
+        }
+    }
+    if buf.len() >= 12 && &buf[4..8] == b"ftyp" {
+        return Some(match &buf[8..12] {
+            b"M4A " | b"M4B " => M4A,
+            b"qt  " => MOV,
+            b"heic" | b"heix" | b"mif1" | b"msf1" => HEIC,
+            _ => MP4,
+        });
+    }
+    if buf.starts_with(b"\x1A\x45\xDF\xA3") {
+        return Some(if contains(buf, b"webm") { WEBM } else { MKV });
+    }
+    if buf.starts_with(b"ID3") {
+        return Some(MP3);
+    }
+    if buf.starts_with(b"fLaC") {
+        return Some(FLAC);
+    }
+    if buf.starts_with(b"OggS") {
+        return Some(OGG);
+    }
+    if buf.starts_with(b"AT&TFORM") {
+        return Some(DJVU);
+    }
+    if buf.starts_with(b"\x1F\x8B") {
+        return Some(GZIP);
+    }
+    if buf.starts_with(b"7z\xBC\xAF\x27\x1C") {
+        return Some(SEVEN_Z);
+    }
+    if buf.starts_with(b"Rar!\x1A\x07") {
+        return Some(RAR);
+    }
+    if buf.len() >= 262 && &buf[257..262] == b"ustar" {
+        return Some(TAR);
+    }
+    // MPEG 音频帧同步（无 ID3 标签的 MP3）
+    if buf.len() >= 2 && buf[0] == 0xFF && (buf[1] & 0xE0) == 0xE0 && (buf[1] & 0x06) != 0 {
+        return Some(MP3);
+    }
+    if buf.starts_with(b"BM") && buf.len() >= 26 && buf[6..10] == [0, 0, 0, 0] {
+        return Some(BMP);
+    }
+
+    detect_text(buf)
+}
+
+/// 区分 EPUB / OpenDocument / Office Open XML / 普通 ZIP
+fn detect_zip_subtype(buf: &[u8]) -> FileType {
+    // EPUB 和 ODF 要求第一个条目是未压缩的 "mimetype" 文件
+    if buf.len() > 38 && &buf[30..38] == b"mimetype" {
+        let compressed_size = u32::from_le_bytes([buf[18], buf[19], buf[20], buf[21]]) as usize;
+        let name_len = u16::from_le_bytes([buf[26], buf[27]]) as usize;
+        let extra_len = u16::from_le_bytes([buf[28], buf[29]]) as usize;
+        let start = 30 + name_len + extra_len;
+        let end = (start + compressed_size).min(buf.len());
+        if start < end {
+            let mimetype = String::from_utf8_lossy(&buf[start..end]);
+            let mimetype = mimetype.trim();
+            if let Some(known) = ALL_TYPES.iter().find(|t| t.mime == mimetype) {
+                return *known;
+            }
+        }
+    }
+
+    if contains(buf, b"[Content_Types].xml") || contains(buf, b"_rels/.rels") {
+        if contains(buf, b"word/") {
+            return DOCX;
+        }
+        if contains(buf, b"xl/") {
+            return XLSX;
+        }
+        if contains(buf, b"ppt/") {
+            return PPTX;
+        }
+    }
+
+    ZIP
+}
+
+/// 识别 HTML / XML / 纯文本
+fn detect_text(buf: &[u8]) -> Option<FileType> {
+    if buf.is_empty() || buf.contains(&0) {
+        return None;
+    }
+
+    let text = match std::str::from_utf8(buf) {
+        Ok(text) => text,
+        // 文件头可能在多字节字符中间被截断
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&buf[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+
+    let start = text
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .chars()
+        .take(64)
+        .collect::<String>()
+        .to_lowercase();
+
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Some(HTML)
+    } else if start.starts_with("<?xml") {
+        // XHTML 也以 <?xml 开头
+        if text.contains("<html") {
+            Some(HTML)
+        } else {
+            Some(XML)
+        }
+    } else {
+        Some(TEXT)
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_bytes() {
+        assert_eq!(detect_bytes(b"%PDF-1.7\n..."), Some(PDF));
+        assert_eq!(detect_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some(PNG));
+        assert_eq!(detect_bytes(b"\xFF\xD8\xFF\xE0\0\x10JFIF"), Some(JPEG));
+        assert_eq!(detect_bytes(b"ID3\x04\0\0\0\0\0\0"), Some(MP3));
+        assert_eq!(detect_bytes(b"\0\0\0\x20ftypM4A \0\0\0\0"), Some(M4A));
+        assert_eq!(detect_bytes(b"\0\0\0\x18ftypisom\0\0\0\0"), Some(MP4));
+        assert_eq!(detect_bytes(b"<!DOCTYPE html><html>"), Some(HTML));
+        assert_eq!(detect_bytes(b"# Title\n\nSome text"), Some(TEXT));
+        assert_eq!(detect_bytes(b"\0\x01\x02\x03"), None);
+    }
+
+    #[test]
+    fn test_detect_epub_from_mimetype_entry() {
+        let mimetype = b"application/epub+zip";
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"PK\x03\x04");
+        buf.extend_from_slice(&[0u8; 14]);
+        buf.extend_from_slice(&(mimetype.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(mimetype.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&8u16.to_le_bytes());
+        buf.extend_from_slice(&0u16.to_le_bytes());
+        buf.extend_from_slice(b"mimetype");
+        buf.extend_from_slice(mimetype);
+
+        assert_eq!(detect_bytes(&buf), Some(EPUB));
+    }
+
+    #[test]
+    fn test_extension_matches() {
+        assert!(extension_matches(Path::new("a.pdf"), &PDF));
+        assert!(extension_matches(Path::new("a.JPEG"), &JPEG));
+        assert!(extension_matches(Path::new("notes.md"), &TEXT));
+        assert!(!extension_matches(Path::new("a.pdf"), &ZIP));
+        assert!(!extension_matches(Path::new("noext"), &PDF));
+    }
+}
@@ -2,7 +2,7 @@ use crate::errors::TagboxError;
 use chrono::Utc;
 use sea_query::{Expr, Iden, Query, SqliteQueryBuilder};
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, SqlitePool};
+use sqlx::{Row, SqliteConnection, SqlitePool};
 
 #[derive(Iden)]
 enum FileHistory {
@@ -19,6 +19,7 @@ enum FileHistory {
     ChangedAt,
     ChangedBy,
     Reason,
+    BatchId,
 }
 
 #[derive(Iden)]
@@ -45,6 +46,8 @@ pub struct FileHistoryEntry {
     pub changed_at: String,
     pub changed_by: Option<String>,
     pub reason: Option<String>,
+    /// 所属批量编辑批次，可通过批次整体撤销
+    pub batch_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,74 +76,7 @@ impl FileHistoryManager {
         reason: Option<&str>,
     ) -> Result<String, TagboxError> {
         let history_id = uuid::Uuid::new_v4().to_string();
-
-        let mut columns = vec![FileHistory::Id, FileHistory::FileId, FileHistory::Operation];
-
-        let mut values: Vec<sea_query::SimpleExpr> = vec![
-            history_id.clone().into(),
-            file_id.into(),
-            operation.as_str().into(),
-        ];
-
-        match &operation {
-            FileOperation::Create { hash, path, size } => {
-                columns.extend([
-                    FileHistory::NewHash,
-                    FileHistory::NewPath,
-                    FileHistory::NewSize,
-                ]);
-                values.extend([hash.clone().into(), path.clone().into(), (*size).into()]);
-            }
-            FileOperation::Update {
-                old_hash,
-                new_hash,
-                old_size,
-                new_size,
-            } => {
-                columns.extend([
-                    FileHistory::OldHash,
-                    FileHistory::NewHash,
-                    FileHistory::OldSize,
-                    FileHistory::NewSize,
-                ]);
-                values.extend([
-                    old_hash.clone().into(),
-                    new_hash.clone().into(),
-                    (*old_size).into(),
-                    (*new_size).into(),
-                ]);
-            }
-            FileOperation::Move { old_path, new_path } => {
-                columns.extend([FileHistory::OldPath, FileHistory::NewPath]);
-                values.extend([old_path.clone().into(), new_path.clone().into()]);
-            }
-            FileOperation::Delete { hash, path, size } => {
-                columns.extend([
-                    FileHistory::OldHash,
-                    FileHistory::OldPath,
-                    FileHistory::OldSize,
-                ]);
-                values.extend([hash.clone().into(), path.clone().into(), (*size).into()]);
-            }
-            FileOperation::Access => {}
-        }
-
-        if let Some(by) = changed_by {
-            columns.push(FileHistory::ChangedBy);
-            values.push(by.into());
-        }
-
-        if let Some(r) = reason {
-            columns.push(FileHistory::Reason);
-            values.push(r.into());
-        }
-
-        let query = Query::insert()
-            .into_table(FileHistory::Table)
-            .columns(columns)
-            .values_panic(values)
-            .to_string(SqliteQueryBuilder);
-
+        let query = insert_query(&history_id, file_id, &operation, changed_by, reason, None);
         sqlx::query(&query).execute(&self.pool).await?;
 
         if matches!(operation, FileOperation::Access) {
@@ -150,6 +86,28 @@ impl FileHistoryManager {
         Ok(history_id)
     }
 
+    /// 在给定连接（通常是批量编辑的事务）上记录属于批次 `batch_id` 的历史
+    pub(crate) async fn record_batch_history(
+        conn: &mut SqliteConnection,
+        batch_id: &str,
+        file_id: &str,
+        operation: FileOperation,
+        reason: Option<&str>,
+    ) -> Result<String, TagboxError> {
+        let history_id = uuid::Uuid::new_v4().to_string();
+        let query = insert_query(
+            &history_id,
+            file_id,
+            &operation,
+            None,
+            reason,
+            Some(batch_id),
+        );
+        sqlx::query(&query).execute(&mut *conn).await?;
+
+        Ok(history_id)
+    }
+
     pub async fn get_file_history(
         &self,
         file_id: &str,
@@ -169,6 +127,7 @@ impl FileHistoryManager {
                 FileHistory::ChangedAt,
                 FileHistory::ChangedBy,
                 FileHistory::Reason,
+                FileHistory::BatchId,
             ])
             .from(FileHistory::Table)
             .and_where(Expr::col(FileHistory::FileId).eq(file_id))
@@ -197,6 +156,7 @@ impl FileHistoryManager {
                 changed_at: row.get(9),
                 changed_by: row.get(10),
                 reason: row.get(11),
+                batch_id: row.get(12),
             })
             .collect())
     }
@@ -296,6 +256,85 @@ impl FileHistoryManager {
     }
 }
 
+/// 构造一条历史记录的 INSERT 语句
+fn insert_query(
+    history_id: &str,
+    file_id: &str,
+    operation: &FileOperation,
+    changed_by: Option<&str>,
+    reason: Option<&str>,
+    batch_id: Option<&str>,
+) -> String {
+    let mut columns = vec![FileHistory::Id, FileHistory::FileId, FileHistory::Operation];
+
+    let mut values: Vec<sea_query::SimpleExpr> =
+        vec![history_id.into(), file_id.into(), operation.as_str().into()];
+
+    match operation {
+        FileOperation::Create { hash, path, size } => {
+            columns.extend([
+                FileHistory::NewHash,
+                FileHistory::NewPath,
+                FileHistory::NewSize,
+            ]);
+            values.extend([hash.clone().into(), path.clone().into(), (*size).into()]);
+        }
+        FileOperation::Update {
+            old_hash,
+            new_hash,
+            old_size,
+            new_size,
+        } => {
+            columns.extend([
+                FileHistory::OldHash,
+                FileHistory::NewHash,
+                FileHistory::OldSize,
+                FileHistory::NewSize,
+            ]);
+            values.extend([
+                old_hash.clone().into(),
+                new_hash.clone().into(),
+                (*old_size).into(),
+                (*new_size).into(),
+            ]);
+        }
+        FileOperation::Move { old_path, new_path } => {
+            columns.extend([FileHistory::OldPath, FileHistory::NewPath]);
+            values.extend([old_path.clone().into(), new_path.clone().into()]);
+        }
+        FileOperation::Delete { hash, path, size } => {
+            columns.extend([
+                FileHistory::OldHash,
+                FileHistory::OldPath,
+                FileHistory::OldSize,
+            ]);
+            values.extend([hash.clone().into(), path.clone().into(), (*size).into()]);
+        }
+        FileOperation::Access | FileOperation::Retag | FileOperation::Patch => {}
+    }
+
+    if let Some(by) = changed_by {
+        columns.push(FileHistory::ChangedBy);
+        values.push(by.into());
+    }
+
+    if let Some(r) = reason {
+        columns.push(FileHistory::Reason);
+        values.push(r.into());
+    }
+
+    if let Some(batch_id) = batch_id {
+        columns.push(FileHistory::BatchId);
+        values.push(batch_id.into());
+    }
+
+    Query::insert()
+        .into_table(FileHistory::Table)
+        .columns(columns)
+        .values_panic(values)
+        .to_string(SqliteQueryBuilder)
+}
+
 #[derive(Debug, Clone)]
 pub enum FileOperation {
     Create {
@@ -319,6 +358,10 @@ pub enum FileOperation {
         size: i64,
     },
     Access,
+    /// 标签重命名、合并或删除改变了文件的标签，具体变更写在 reason 中
+    Retag,
+    /// 批量编辑增删了文件的标签或修改了分类，具体变更写在 reason 中
+    Patch,
 }
 
 impl FileOperation {
@@ -329,6 +372,8 @@ impl FileOperation {
             FileOperation::Move { .. } => "move",
             FileOperation::Delete { .. } => "delete",
             FileOperation::Access => "access",
+            FileOperation::Retag => "retag",
+            FileOperation::Patch => "patch",
         }
     }
 }
@@ -363,7 +408,8 @@ mod tests {
                 new_size INTEGER,
                 changed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 changed_by TEXT,
-                reason TEXT
+                reason TEXT,
+                batch_id TEXT
             );
 
             CREATE TABLE IF NOT EXISTS file_access_stats (
@@ -0,0 +1,472 @@
+//! 文献标识符识别与存储
+//!
+//! 从文件名、来源链接、提取器给出的元数据（如 PDF Info/XMP）和正文开头识别 DOI、
+//! arXiv 编号、ISBN 与 ISSN，规范化后保存在 `file_identifiers` 表中供搜索使用。
+//! 正文中的标识符可能属于引用的其他作品，只有正文以外的标识符用于导入去重。
+
+use crate::errors::{Result, TagboxError};
+use crate::types::{Identifier, IdentifierKind, ImportMetadata};
+use lazy_static::lazy_static;
+use regex::Regex;
+use sqlx::{Row, SqlitePool};
+use std::path::Path;
+use tracing::debug;
+
+/// 只扫描正文开头的字符数，后面参考文献中的标识符不属于本文
+const TEXT_SCAN_LIMIT: usize = 10_000;
+
+lazy_static! {
+    static ref DOI_RE: Regex =
+        Regex::new(r#"\b10\.\d{4,9}/[^\s"'<>{}]+"#).expect("failed to compile DOI_RE regex");
+    static ref ARXIV_RE: Regex = Regex::new(
+        r"(?i)(?:arxiv:\s*|arxiv\.org/(?:abs|pdf)/)([a-z][a-z-]*(?:\.[a-z]{2})?/\d{7}|\d{4}\.\d{4,5})(?:v\d+)?"
+    )
+    .expect("failed to compile ARXIV_RE regex");
+    static ref ARXIV_ID_RE: Regex =
+        Regex::new(r"^([a-z][a-z-]*(?:\.[a-z]{2})?/\d{7}|\d{4}\.\d{4,5})(?:v\d+)?$")
+            .expect("failed to compile ARXIV_ID_RE regex");
+    /// 文件名中不带前缀的新式 arXiv 编号，如 `1706.03762v7.pdf`
+    static ref ARXIV_BARE_RE: Regex = Regex::new(r"(?:^|[^\d.])(\d{4}\.\d{4,5})(?:v\d+)?(?:$|[^\d])")
+        .expect("failed to compile ARXIV_BARE_RE regex");
+    static ref ISBN_RE: Regex = Regex::new(
+        r"(?i)\bISBN(?:-1[03])?\s*:?\s*(97[89](?:[\s-]?\d){10}|\d(?:[\s-]?\d){8}[\s-]?[\dX])\b"
+    )
+    .expect("failed to compile ISBN_RE regex");
+    /// 文件名中不带前缀的 ISBN-13
+    static ref ISBN_BARE_RE: Regex = Regex::new(r"(?:^|[^\d])(97[89](?:-?\d){10})(?:$|[^\d])")
+        .expect("failed to compile ISBN_BARE_RE regex");
+    static ref ISSN_RE: Regex = Regex::new(r"(?i)\b(?:[ep]-?)?ISSN\s*:?\s*(\d{4}-?\d{3}[\dX])\b")
+        .expect("failed to compile ISSN_RE regex");
+}
+
+/// 在文本中查找标识符
+///
+/// DOI 按自身格式识别，arXiv 编号需要 `arXiv:` 前缀或 arxiv.org 链接，ISBN 和 ISSN 需要前缀。
+pub fn detect_identifiers(text: &str) -> Vec<Identifier> {
+    let mut found = Vec::new();
+
+    for m in DOI_RE.find_iter(text) {
+        push_normalized(&mut found, IdentifierKind::Doi, m.as_str());
+    }
+    for caps in ARXIV_RE.captures_iter(text) {
+        push_normalized(&mut found, IdentifierKind::Arxiv, &caps[1]);
+    }
+    for caps in ISBN_RE.captures_iter(text) {
+        push_normalized(&mut found, IdentifierKind::Isbn, &caps[1]);
+    }
+    for caps in ISSN_RE.captures_iter(text) {
+        push_normalized(&mut found, IdentifierKind::Issn, &caps[1]);
+    }
+
+    found
+}
+
+/// 在文件名中查找标识符，另外识别不带前缀的 arXiv 编号和 ISBN-13
+pub fn detect_in_filename(name: &str) -> Vec<Identifier> {
+    let mut found = detect_identifiers(name);
+
+    for caps in ARXIV_BARE_RE.captures_iter(name) {
+        push_normalized(&mut found, IdentifierKind::Arxiv, &caps[1]);
+    }
+    for caps in ISBN_BARE_RE.captures_iter(name) {
+        push_normalized(&mut found, IdentifierKind::Isbn, &caps[1]);
+    }
+
+    found
+}
+
+/// 汇总导入文件自身的标识符：提取器或元数据文件给出的 `file_metadata.identifiers`、
+/// 文件名、来源链接和其他元数据字段，可以作为导入去重的依据
+pub fn identifiers_from_metadata(path: &Path, metadata: &ImportMetadata) -> Vec<Identifier> {
+    let mut found = Vec::new();
+
+    if let Some(listed) = metadata
+        .file_metadata
+        .as_ref()
+        .and_then(|m| m.get("identifiers"))
+        .and_then(|v| serde_json::from_value::<Vec<Identifier>>(v.clone()).ok())
+    {
+        for identifier in listed {
+            push_normalized(&mut found, identifier.kind, &identifier.value);
+        }
+    }
+
+    if let Some(stem) = path.file_stem() {
+        merge(&mut found, detect_in_filename(&stem.to_string_lossy()));
+    }
+
+    for text in metadata
+        .source
+        .iter()
+        .chain(metadata.additional_info.values())
+    {
+        merge(&mut found, detect_identifiers(text));
+    }
+
+    debug!("识别到 {} 个标识符: {}", path.display(), found.len());
+    found
+}
+
+/// 正文开头出现的标识符，只保存供搜索，不参与导入去重
+pub fn identifiers_from_text(metadata: &ImportMetadata) -> Vec<Identifier> {
+    let file_metadata = metadata.file_metadata.as_ref();
+
+    // 压缩包的正文是各成员的内容，不代表压缩包本身
+    if file_metadata.is_some_and(|m| m.get("archive").is_some()) {
+        return Vec::new();
+    }
+
+    // PDF 前几页的文本保存在 file_metadata.pdf.full_text 中
+    let body = metadata.full_text.as_deref().or_else(|| {
+        file_metadata
+            .and_then(|m| m.pointer("/pdf/full_text"))
+            .and_then(|v| v.as_str())
+    });
+    body.map(|body| detect_identifiers(head(body, TEXT_SCAN_LIMIT)))
+        .unwrap_or_default()
+}
+
+/// 规范化标识符，格式或校验位不正确时返回 `None`
+///
+/// 接受常见写法：`doi:`/`https://doi.org/` 前缀、带版本号的 arXiv 编号、带连字符的 ISBN/ISSN。
+pub fn normalize_identifier(kind: IdentifierKind, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    match kind {
+        IdentifierKind::Doi => normalize_doi(raw),
+        IdentifierKind::Arxiv => normalize_arxiv(raw),
+        IdentifierKind::Isbn => normalize_isbn(raw),
+        IdentifierKind::Issn => normalize_issn(raw),
+    }
+}
+
+fn normalize_doi(raw: &str) -> Option<String> {
+    let doi = DOI_RE.find(raw)?.as_str();
+    let mut doi = doi.trim_end_matches(['.', ',', ';', ':']);
+
+    // 去掉包住 DOI 的括号，DOI 内部成对的括号保留
+    loop {
+        let trimmed = match doi.chars().last() {
+            Some(')') if doi.matches('(').count() < doi.matches(')').count() => {
+                &doi[..doi.len() - 1]
+            }
+            Some(']') if doi.matches('[').count() < doi.matches(']').count() => {
+                &doi[..doi.len() - 1]
+            }
+            _ => break,
+        };
+        doi = trimmed.trim_end_matches(['.', ',', ';', ':']);
+    }
+
+    // 前缀后面必须还有内容
+    let (_, suffix) = doi.split_once('/')?;
+    (!suffix.is_empty()).then(|| doi.to_lowercase())
+}
+
+fn normalize_arxiv(raw: &str) -> Option<String> {
+    let lower = raw.to_lowercase();
+    let id = lower
+        .strip_prefix("arxiv:")
+        .map(str::trim_start)
+        .unwrap_or(lower.as_str());
+    let id = ARXIV_ID_RE.captures(id)?.get(1)?.as_str();
+
+    // 新式编号以 YYMM 开头
+    if !id.contains('/') {
+        let month: u32 = id.get(2..4)?.parse().ok()?;
+        if !(1..=12).contains(&month) {
+            return None;
+        }
+    }
+
+    Some(id.to_string())
+}
+
+fn normalize_isbn(raw: &str) -> Option<String> {
+    let chars: Vec<char> = raw
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    match chars.len() {
+        10 => {
+            let digits = chars[..9]
+                .iter()
+                .map(|c| c.to_digit(10))
+                .collect::<Option<Vec<u32>>>()?;
+            let check = match chars[9] {
+                'X' => 10,
+                c => c.to_digit(10)?,
+            };
+            let sum: u32 = digits
+                .iter()
+                .enumerate()
+                .map(|(i, d)| d * (10 - i as u32))
+                .sum();
+            if (11 - sum % 11) % 11 != check {
+                return None;
+            }
+
+            // 统一转换为 978 开头的 ISBN-13
+            let mut isbn13: Vec<u32> = vec![9, 7, 8];
+            isbn13.extend(digits);
+            isbn13.push(isbn13_check_digit(&isbn13));
+            Some(isbn13.iter().map(|d| d.to_string()).collect())
+        }
+        13 => {
+            let digits = chars
+                .iter()
+                .map(|c| c.to_digit(10))
+                .collect::<Option<Vec<u32>>>()?;
+            if !matches!(digits[..3], [9, 7, 8] | [9, 7, 9]) {
+                return None;
+            }
+            if isbn13_check_digit(&digits[..12]) != digits[12] {
+                return None;
+            }
+            Some(chars.iter().collect())
+        }
+        _ => None,
+    }
+}
+
+fn isbn13_check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { *d } else { d * 3 })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+fn normalize_issn(raw: &str) -> Option<String> {
+    let chars: Vec<char> = raw
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if chars.len() != 8 {
+        return None;
+    }
+
+    let digits = chars[..7]
+        .iter()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<Vec<u32>>>()?;
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, d)| d * (8 - i as u32))
+        .sum();
+    let check = match (11 - sum % 11) % 11 {
+        10 => 'X',
+        d => char::from_digit(d, 10)?,
+    };
+    if chars[7] != check {
+        return None;
+    }
+
+    let issn: String = chars.iter().collect();
+    Some(format!("{}-{}", &issn[..4], &issn[4..]))
+}
+
+fn push_normalized(found: &mut Vec<Identifier>, kind: IdentifierKind, raw: &str) {
+    if let Some(value) = normalize_identifier(kind, raw) {
+        let identifier = Identifier { kind, value };
+        if !found.contains(&identifier) {
+            found.push(identifier);
+        }
+    }
+}
+
+fn merge(found: &mut Vec<Identifier>, more: Vec<Identifier>) {
+    for identifier in more {
+        if !found.contains(&identifier) {
+            found.push(identifier);
+        }
+    }
+}
+
+fn head(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// 文献标识符管理器，标识符保存在 `file_identifiers` 表中
+pub struct IdentifierManager {
+    db_pool: SqlitePool,
+}
+
+impl IdentifierManager {
+    /// 创建一个新的标识符管理器
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 用给定标识符替换文件的全部标识符
+    pub async fn replace(&self, file_id: &str, identifiers: &[Identifier]) -> Result<()> {
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        sqlx::query("DELETE FROM file_identifiers WHERE file_id = ?")
+            .bind(file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        for identifier in identifiers {
+            sqlx::query(
+                "INSERT OR IGNORE INTO file_identifiers (file_id, kind, value) VALUES (?, ?, ?)",
+            )
+            .bind(file_id)
+            .bind(identifier.kind.as_str())
+            .bind(&identifier.value)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+        }
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        debug!("文件 {} 保存了 {} 个标识符", file_id, identifiers.len());
+        Ok(())
+    }
+
+    /// 为文件追加标识符，已有的保持不变
+    pub async fn add(&self, file_id: &str, identifiers: &[Identifier]) -> Result<()> {
+        for identifier in identifiers {
+            sqlx::query(
+                "INSERT OR IGNORE INTO file_identifiers (file_id, kind, value) VALUES (?, ?, ?)",
+            )
+            .bind(file_id)
+            .bind(identifier.kind.as_str())
+            .bind(&identifier.value)
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+        }
+
+        Ok(())
+    }
+
+    /// 文件的全部标识符
+    pub async fn list(&self, file_id: &str) -> Result<Vec<Identifier>> {
+        let rows = sqlx::query(
+            "SELECT kind, value FROM file_identifiers WHERE file_id = ? ORDER BY kind, value",
+        )
+        .bind(file_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let kind = IdentifierKind::from_name(&row.get::<String, _>("kind"))?;
+                Some(Identifier {
+                    kind,
+                    value: row.get("value"),
+                })
+            })
+            .collect())
+    }
+
+    /// 查找拥有相同 DOI、arXiv 编号或 ISBN 的未删除文件
+    pub async fn find_file(&self, identifiers: &[Identifier]) -> Result<Option<String>> {
+        for identifier in identifiers.iter().filter(|i| i.kind.identifies_work()) {
+            let file_id: Option<String> = sqlx::query_scalar(
+                "SELECT i.file_id FROM file_identifiers i
+                 JOIN files f ON f.id = i.file_id
+                 WHERE i.kind = ? AND i.value = ? AND f.is_deleted = 0
+                 LIMIT 1",
+            )
+            .bind(identifier.kind.as_str())
+            .bind(&identifier.value)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
+            if file_id.is_some() {
+                debug!("标识符 {} 已存在", identifier);
+                return Ok(file_id);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(found: &[Identifier], kind: IdentifierKind) -> Vec<&str> {
+        found
+            .iter()
+            .filter(|i| i.kind == kind)
+            .map(|i| i.value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_doi() {
+        let found = detect_identifiers(
+            "Published as doi:10.1000/XYZ.123. See (https://doi.org/10.1016/S0140-6736(20)30183-5).",
+        );
+        assert_eq!(
+            values(&found, IdentifierKind::Doi),
+            vec!["10.1000/xyz.123", "10.1016/s0140-6736(20)30183-5"]
+        );
+    }
+
+    #[test]
+    fn test_detect_arxiv() {
+        let found = detect_identifiers(
+            "arXiv:1706.03762v7 [cs.CL] and https://arxiv.org/abs/hep-th/9901001v2",
+        );
+        assert_eq!(
+            values(&found, IdentifierKind::Arxiv),
+            vec!["1706.03762", "hep-th/9901001"]
+        );
+
+        // 没有前缀的数字只在文件名中识别，月份必须有效
+        assert!(detect_identifiers("version 1706.03762").is_empty());
+        assert_eq!(
+            values(&detect_in_filename("1706.03762v7"), IdentifierKind::Arxiv),
+            vec!["1706.03762"]
+        );
+        assert!(detect_in_filename("report 2023.1234").is_empty());
+    }
+
+    #[test]
+    fn test_isbn_checksum_and_conversion() {
+        let found = detect_identifiers(
+            "ISBN 0-306-40615-2, ISBN-13: 978-0-13-468599-1, ISBN 0-306-40615-3",
+        );
+        assert_eq!(
+            values(&found, IdentifierKind::Isbn),
+            vec!["9780306406157", "9780134685991"]
+        );
+        assert_eq!(
+            normalize_identifier(IdentifierKind::Isbn, "080442957X"),
+            Some("9780804429573".to_string())
+        );
+        assert_eq!(
+            values(
+                &detect_in_filename("clean-code_9780134685991"),
+                IdentifierKind::Isbn
+            ),
+            vec!["9780134685991"]
+        );
+    }
+
+    #[test]
+    fn test_issn_checksum() {
+        let found = detect_identifiers("ISSN 0317-8471; eISSN: 1050-124x; ISSN 0317-8472");
+        assert_eq!(
+            values(&found, IdentifierKind::Issn),
+            vec!["0317-8471", "1050-124X"]
+        );
+    }
+}
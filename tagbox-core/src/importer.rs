@@ -1,20 +1,38 @@
-use crate::config::AppConfig;
+use crate::annotations::{annotations_from_metadata, AnnotationManager};
+use crate::authors::{split_author_list, AuthorManager};
+use crate::bibliography::{parse_bibtex, FileIndex};
+use crate::config::{AppConfig, DuplicatePolicy};
+use crate::editor::Editor;
 use crate::errors::{Result, TagboxError};
-use crate::metainfo::MetaInfoExtractor;
+use crate::filetype::detect_mime;
+use crate::identifiers::{identifiers_from_metadata, identifiers_from_text, IdentifierManager};
+#[cfg(feature = "archive")]
+use crate::link::LinkManager;
+#[cfg(feature = "archive")]
+use crate::metainfo::unpack_archive;
+use crate::metainfo::{merge_metadata, MetaInfoExtractor};
+#[cfg(feature = "ocr")]
+use crate::ocr::{OcrEngine, OcrManager};
+use crate::pages::PageIndexer;
 use crate::pathgen::PathGenerator;
-use crate::types::{FileEntry, ImportMetadata};
+use crate::tags::{normalize_tag_path, TagManager};
+#[cfg(feature = "archive")]
+use crate::types::RelationType;
+use crate::types::{
+    AuthorRole, BibImportResult, FileEntry, FileUpdateRequest, ImportMetadata, ImportOutcome,
+    ImportResult,
+};
 use crate::utils::{
     calculate_file_hash_with_type, current_time, ensure_dir_exists, generate_uuid, require_field,
     safe_copy_file, HashType,
 };
 use chrono::{DateTime, Utc};
-use sqlx::SqlitePool;
+use sqlx::{Row, SqlitePool};
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 /// 文件导入器
 pub struct Importer {
-    #[allow(dead_code)]
     config: AppConfig,
     db_pool: SqlitePool,
     metainfo_extractor: MetaInfoExtractor,
@@ -37,14 +55,38 @@ impl Importer {
 
     /// 使用已提取的元数据导入文件
     ///
-    /// 这个方法允许批量导入时重用已经提取的元数据，避免重复提取
+    /// 这个方法允许批量导入时重用已经提取的元数据，避免重复提取。
+    /// 遇到重复文件时按配置中的 `import.metadata.on_duplicate` 策略处理
     pub async fn import_with_metadata(
         &self,
         file_path: &Path,
         metadata: ImportMetadata,
     ) -> Result<FileEntry> {
+        let policy = self.config.import.metadata.on_duplicate;
+        Ok(self
+            .import_with_policy(file_path, metadata, policy)
+            .await?
+            .entry)
+    }
+
+    /// 使用已提取的元数据和指定的重复策略导入文件
+    pub async fn import_with_policy(
+        &self,
+        file_path: &Path,
+        mut metadata: ImportMetadata,
+        policy: DuplicatePolicy,
+    ) -> Result<ImportResult> {
         debug!("开始导入文件（使用已提取元数据）: {}", file_path.display());
 
+        // 作者字段可能是 "Last, First; ..." 形式的列表，先拆分并规范化（文件名模板也使用规范化后的作者）
+        let mut authors: Vec<String> = Vec::new();
+        for author in metadata.authors.iter().flat_map(|a| split_author_list(a)) {
+            if !authors.contains(&author) {
+                authors.push(author);
+            }
+        }
+        metadata.authors = authors;
+
         // 1. 检查文件是否存在
         if !file_path.exists() {
             return Err(TagboxError::FileNotFound {
@@ -60,14 +102,38 @@ impl Importer {
         // 3. 检查文件是否已存在（基于哈希）
         if let Some(existing_entry) = self.find_by_hash(&hash).await? {
             warn!(
-                "文件已存在: {} (ID: {})",
+                "文件已存在: {} (ID: {})，重复策略: {}",
                 existing_entry.path.display(),
-                existing_entry.id
+                existing_entry.id,
+                policy
             );
-            return Ok(existing_entry);
+            self.record_alias(&existing_entry.id, file_path).await?;
+            return self
+                .apply_duplicate_policy(existing_entry, &metadata, policy)
+                .await;
         }
 
-        // 4. 生成新的文件名和目标路径
+        // 4. 检查是否已有相同 DOI、arXiv 编号或 ISBN 的文件（同一作品的不同版本或副本），
+        //    只比较文件名、元数据和来源中的标识符
+        let identifiers = identifiers_from_metadata(file_path, &metadata);
+        let identifier_manager = IdentifierManager::new(self.db_pool.clone());
+        if let Some(existing_id) = identifier_manager.find_file(&identifiers).await? {
+            let existing_entry = Editor::new(self.db_pool.clone())
+                .get_file(&existing_id)
+                .await?;
+            warn!(
+                "相同标识符的文件已存在: {} (ID: {})，重复策略: {}",
+                existing_entry.path.display(),
+                existing_entry.id,
+                policy
+            );
+            self.record_alias(&existing_entry.id, file_path).await?;
+            return self
+                .apply_duplicate_policy(existing_entry, &metadata, policy)
+                .await;
+        }
+
+        // 5. 生成新的文件名和目标路径
         let original_filename = file_path
             .file_name()
             .ok_or_else(|| TagboxError::Config(format!("无法获取文件名: {}", file_path.display())))?
@@ -87,13 +153,29 @@ impl Importer {
             ensure_dir_exists(parent)?;
         }
 
-        // 5. 复制文件到目标位置
+        // 6. 复制文件到目标位置
         safe_copy_file(file_path, &dest_path).await?;
 
-        // 6. 创建文件记录
+        // 7. 创建文件记录
         let file_entry = self
             .create_file_entry(file_path, &dest_path, &original_filename, &hash, &metadata)
             .await?;
+        identifier_manager
+            .replace(&file_entry.id, &identifiers)
+            .await?;
+        identifier_manager
+            .add(&file_entry.id, &identifiers_from_text(&metadata))
+            .await?;
+
+        // 8. 建立逐页全文索引，大文件留给后台处理
+        self.index_pages(&file_entry).await;
+
+        // 9. 保存PDF批注供搜索
+        self.index_annotations(&file_entry).await;
+
+        // 10. 按配置对图片和扫描版PDF做OCR
+        #[cfg(feature = "ocr")]
+        let file_entry = self.recognize_text(&file_entry).await.unwrap_or(file_entry);
 
         info!(
             "文件导入完成: {} -> {} (ID: {})",
@@ -102,74 +184,464 @@ impl Importer {
             file_entry.id
         );
 
-        Ok(file_entry)
+        Ok(ImportResult {
+            entry: file_entry,
+            outcome: ImportOutcome::Imported,
+        })
+    }
+
+    /// 为新导入的文件建立逐页全文索引，失败不影响导入结果
+    async fn index_pages(&self, entry: &FileEntry) {
+        let metadata_config = &self.config.import.metadata;
+        if !metadata_config.index_pages || !PageIndexer::supports(entry.mime_type.as_deref()) {
+            return;
+        }
+
+        let indexer = PageIndexer::new(self.db_pool.clone());
+        let result = if entry.size.unwrap_or(0) > metadata_config.background_extract_size {
+            debug!("文件较大，逐页索引留给后台处理: {}", entry.path.display());
+            indexer.mark_pending(&entry.id).await
+        } else {
+            indexer
+                .index_file(&entry.id, &entry.path, metadata_config.page_text_budget)
+                .await
+                .map(|_| ())
+        };
+
+        if let Err(e) = result {
+            warn!("逐页索引失败 {}: {}", entry.path.display(), e);
+        }
+    }
+
+    /// 保存提取到的批注，失败不影响导入结果
+    async fn index_annotations(&self, entry: &FileEntry) {
+        if annotations_from_metadata(entry).is_empty() {
+            return;
+        }
+
+        let manager = AnnotationManager::new(self.db_pool.clone());
+        if let Err(e) = manager.index_entry(entry).await {
+            warn!("保存批注失败 {}: {}", entry.path.display(), e);
+        }
+    }
+
+    /// 导入时运行 OCR，成功后返回更新了全文的条目；失败不影响导入结果
+    #[cfg(feature = "ocr")]
+    async fn recognize_text(&self, entry: &FileEntry) -> Option<FileEntry> {
+        let mime_type = entry.mime_type.as_deref();
+        if !self.config.ocr.on_import || !OcrEngine::supports(mime_type) {
+            return None;
+        }
+        // 已有文本的图片不需要识别；PDF 可能只有部分页是扫描页
+        let has_text = entry
+            .full_text
+            .as_deref()
+            .is_some_and(|text| !text.trim().is_empty());
+        if has_text && mime_type != Some("application/pdf") {
+            return None;
+        }
+
+        let result = match OcrManager::new(self.db_pool.clone(), &self.config.ocr) {
+            Ok(manager) => manager.ocr_file(&entry.id).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(_) => Editor::new(self.db_pool.clone())
+                .get_file(&entry.id)
+                .await
+                .ok(),
+            Err(e) => {
+                warn!("OCR 失败 {}: {}", entry.path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// 将已导入的压缩包中的文件逐个导入，并以 `contained_in` 关联到压缩包
+    ///
+    /// 成员解压到临时目录，导入完成后删除；没有分类的成员沿用压缩包的分类。
+    #[cfg(feature = "archive")]
+    pub async fn explode_archive(
+        &self,
+        archive: &FileEntry,
+        policy: DuplicatePolicy,
+    ) -> Result<Vec<ImportResult>> {
+        let temp_dir = std::env::temp_dir().join(format!("tagbox-archive-{}", generate_uuid()));
+
+        let archive_path = archive.path.clone();
+        let dest = temp_dir.clone();
+        let unpacked = tokio::task::spawn_blocking(move || unpack_archive(&archive_path, &dest))
+            .await
+            .map_err(|e| TagboxError::ImportError(format!("解压任务失败: {}", e)))?;
+
+        let result = match unpacked {
+            Ok(members) => self.import_members(archive, members, policy).await,
+            Err(e) => Err(e),
+        };
+
+        if temp_dir.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&temp_dir) {
+                warn!("删除临时目录失败 {}: {}", temp_dir.display(), e);
+            }
+        }
+
+        result
+    }
+
+    #[cfg(feature = "archive")]
+    async fn import_members(
+        &self,
+        archive: &FileEntry,
+        members: Vec<(String, PathBuf)>,
+        policy: DuplicatePolicy,
+    ) -> Result<Vec<ImportResult>> {
+        let link_manager = LinkManager::new(self.db_pool.clone());
+        let default_category = &self.config.import.metadata.default_category;
+        let mut results = Vec::new();
+
+        for (member, path) in members {
+            let mut metadata = match self.metainfo_extractor.extract(&path).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    warn!("压缩包成员元数据提取失败 {}: {}", member, e);
+                    continue;
+                }
+            };
+
+            if metadata.category1.is_empty() || &metadata.category1 == default_category {
+                metadata.category1 = archive.category1.clone();
+                metadata.category2 = archive.category2.clone();
+                metadata.category3 = archive.category3.clone();
+            }
+
+            // 记录来源，解压出的临时路径在导入后即被删除
+            let origin = ImportMetadata {
+                file_metadata: Some(serde_json::json!({
+                    "archive_member": { "archive_id": archive.id, "path": member }
+                })),
+                ..Default::default()
+            };
+            let metadata = merge_metadata(metadata, origin);
+
+            let result = match self.import_with_policy(&path, metadata, policy).await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!("压缩包成员导入失败 {}: {}", member, e);
+                    continue;
+                }
+            };
+
+            // 与压缩包本身内容相同的成员不建立关联
+            if result.entry.id != archive.id {
+                link_manager
+                    .create_link(
+                        &result.entry.id,
+                        &archive.id,
+                        Some(RelationType::ContainedIn.to_string()),
+                    )
+                    .await?;
+            }
+
+            results.push(result);
+        }
+
+        info!(
+            "压缩包 {} 展开完成，导入 {} 个成员",
+            archive.id,
+            results.len()
+        );
+        Ok(results)
+    }
+
+    /// 导入 BibTeX 文件中的条目
+    ///
+    /// 按 `file` 字段、引用键或标题在 `files_dir` 中查找对应文件，以 BibTeX 中的元数据
+    /// 覆盖从文件提取的元数据后导入；单个条目失败不影响其余条目。
+    pub async fn import_bibtex(
+        &self,
+        bib_path: &Path,
+        files_dir: &Path,
+        policy: DuplicatePolicy,
+    ) -> Result<Vec<BibImportResult>> {
+        let content = std::fs::read_to_string(bib_path)?;
+        let entries = parse_bibtex(&content);
+        let index = FileIndex::scan(files_dir);
+        let bib_dir = bib_path.parent().unwrap_or(Path::new("."));
+        let mut results = Vec::new();
+
+        for entry in entries {
+            let mut result = BibImportResult {
+                key: entry.key.clone(),
+                title: entry.title().unwrap_or_default().to_string(),
+                file: None,
+                result: None,
+                error: None,
+            };
+
+            let Some(path) = index.resolve(&entry, &[files_dir, bib_dir]) else {
+                warn!("没有找到 BibTeX 条目 {} 对应的文件", entry.key);
+                results.push(result);
+                continue;
+            };
+
+            let imported = match self.metainfo_extractor.extract(&path).await {
+                Ok(extracted) => {
+                    let metadata = merge_metadata(extracted, entry.to_import_metadata());
+                    self.import_with_policy(&path, metadata, policy).await
+                }
+                Err(e) => Err(e),
+            };
+            match imported {
+                Ok(imported) => result.result = Some(imported),
+                Err(e) => {
+                    warn!("BibTeX 条目 {} 导入失败: {}", entry.key, e);
+                    result.error = Some(e.to_string());
+                }
+            }
+            result.file = Some(path);
+            results.push(result);
+        }
+
+        info!(
+            "BibTeX 导入完成: {}，共 {} 个条目",
+            bib_path.display(),
+            results.len()
+        );
+        Ok(results)
     }
 
     /// 从文件路径导入文件
     pub async fn import(&self, file_path: &Path) -> Result<FileEntry> {
         debug!("开始导入文件: {}", file_path.display());
 
-        // 1. 检查文件是否存在
         if !file_path.exists() {
             return Err(TagboxError::FileNotFound {
                 path: file_path.to_path_buf(),
             });
         }
 
-        // 2. 计算文件哈希
+        // skip/prompt 策略不使用新元数据，先按哈希查重，避免对重复文件做完整的元数据提取
+        let policy = self.config.import.metadata.on_duplicate;
+        if matches!(policy, DuplicatePolicy::Skip | DuplicatePolicy::Prompt) {
+            if let Some(existing_entry) = self.find_duplicate(file_path).await? {
+                warn!(
+                    "文件已存在: {} (ID: {})，重复策略: {}",
+                    existing_entry.path.display(),
+                    existing_entry.id,
+                    policy
+                );
+                self.record_alias(&existing_entry.id, file_path).await?;
+                return Ok(existing_entry);
+            }
+        }
+
+        let metadata = self.metainfo_extractor.extract(file_path).await?;
+        self.import_with_metadata(file_path, metadata).await
+    }
+
+    /// 查找与给定文件内容相同（哈希一致）的已有条目
+    pub async fn find_duplicate(&self, file_path: &Path) -> Result<Option<FileEntry>> {
         let hash_type = HashType::from_string(&self.config.hash.algorithm)?;
         let hash = calculate_file_hash_with_type(file_path, hash_type).await?;
-        debug!("文件哈希 ({:?}): {}", hash_type, hash);
+        self.find_by_hash(&hash).await
+    }
 
-        // 3. 检查文件是否已存在（基于哈希）
-        if let Some(existing_entry) = self.find_by_hash(&hash).await? {
-            warn!(
-                "文件已存在: {} (ID: {})",
-                existing_entry.path.display(),
-                existing_entry.id
-            );
-            return Ok(existing_entry);
-        }
+    /// 按重复策略将新元数据应用到已有条目
+    ///
+    /// - `Skip`: 不做修改
+    /// - `MergeTags`: 把新标签追加到已有条目
+    /// - `OverwriteEmpty`: 仅填补已有条目中的空字段（分类变化不会移动文件，需要 rebuild）
+    /// - `Prompt`: 不做修改，返回 `Pending` 由调用方决定后再次调用
+    pub async fn apply_duplicate_policy(
+        &self,
+        existing: FileEntry,
+        metadata: &ImportMetadata,
+        policy: DuplicatePolicy,
+    ) -> Result<ImportResult> {
+        let editor = Editor::new(self.db_pool.clone());
+
+        let outcome = match policy {
+            DuplicatePolicy::Skip => ImportOutcome::Skipped,
+            DuplicatePolicy::Prompt => ImportOutcome::Pending,
+            DuplicatePolicy::MergeTags => {
+                let mut added: Vec<String> = Vec::new();
+                for tag in metadata.tags.iter().map(|t| normalize_tag_path(t)) {
+                    if !tag.is_empty() && !existing.tags.contains(&tag) && !added.contains(&tag) {
+                        added.push(tag);
+                    }
+                }
+
+                if !added.is_empty() {
+                    let mut tags = existing.tags.clone();
+                    tags.extend(added.iter().cloned());
+                    let update = FileUpdateRequest {
+                        tags: Some(tags),
+                        ..Default::default()
+                    };
+                    editor.update_file(&existing.id, update).await?;
+                }
+
+                ImportOutcome::MergedTags { added }
+            }
+            DuplicatePolicy::OverwriteEmpty => {
+                let (update, mut fields) = self.fill_empty_fields(&existing, metadata);
+                if !fields.is_empty() {
+                    editor.update_file(&existing.id, update).await?;
+                }
+
+                for (key, value) in &metadata.additional_info {
+                    let inserted = sqlx::query(
+                        "INSERT OR IGNORE INTO file_metadata (file_id, key, value) VALUES (?, ?, ?)",
+                    )
+                    .bind(&existing.id)
+                    .bind(key)
+                    .bind(value)
+                    .execute(&self.db_pool)
+                    .await
+                    .map_err(TagboxError::Database)?
+                    .rows_affected();
+                    if inserted > 0 {
+                        fields.push(key.clone());
+                    }
+                }
+
+                ImportOutcome::FilledFields { fields }
+            }
+        };
+
+        let entry = if matches!(outcome, ImportOutcome::Skipped | ImportOutcome::Pending) {
+            existing
+        } else {
+            editor.get_file(&existing.id).await?
+        };
 
-        // 4. 提取元数据
-        let metadata = self.metainfo_extractor.extract(file_path).await?;
+        Ok(ImportResult { entry, outcome })
+    }
 
-        // 5. 生成新的文件名和目标路径
-        let original_filename = file_path
-            .file_name()
-            .ok_or_else(|| TagboxError::Config(format!("无法获取文件名: {}", file_path.display())))?
-            .to_string_lossy()
-            .to_string();
+    /// 计算 overwrite-empty 策略需要填补的字段
+    fn fill_empty_fields(
+        &self,
+        existing: &FileEntry,
+        metadata: &ImportMetadata,
+    ) -> (FileUpdateRequest, Vec<String>) {
+        fn is_blank(value: &Option<String>) -> bool {
+            !value.as_deref().is_some_and(|v| !v.trim().is_empty())
+        }
 
-        let new_filename = self
-            .path_generator
-            .generate_filename(&original_filename, &metadata)?;
+        let mut update = FileUpdateRequest::default();
+        let mut fields = Vec::new();
 
-        let dest_path = self
-            .path_generator
-            .generate_path(&new_filename, &metadata)?;
+        if existing.title.trim().is_empty() && !metadata.title.trim().is_empty() {
+            update.title = Some(metadata.title.clone());
+            fields.push("title".to_string());
+        }
+        if existing.authors.is_empty() && !metadata.authors.is_empty() {
+            update.authors = Some(metadata.authors.clone());
+            fields.push("authors".to_string());
+        }
+        if existing.contributors.is_empty() && !metadata.contributors.is_empty() {
+            update.contributors = Some(metadata.contributors.clone());
+            fields.push("contributors".to_string());
+        }
+        if existing.year.is_none() && metadata.year.is_some() {
+            update.year = metadata.year;
+            fields.push("year".to_string());
+        }
+        if is_blank(&existing.publisher) && !is_blank(&metadata.publisher) {
+            update.publisher = metadata.publisher.clone();
+            fields.push("publisher".to_string());
+        }
+        if is_blank(&existing.source) && !is_blank(&metadata.source) {
+            update.source = metadata.source.clone();
+            fields.push("source".to_string());
+        }
+        if is_blank(&existing.summary) && !is_blank(&metadata.summary) {
+            update.summary = metadata.summary.clone();
+            fields.push("summary".to_string());
+        }
+        if is_blank(&existing.full_text) && !is_blank(&metadata.full_text) {
+            update.full_text = metadata.full_text.clone();
+            fields.push("full_text".to_string());
+        }
+        if existing.tags.is_empty() && !metadata.tags.is_empty() {
+            update.tags = Some(metadata.tags.clone());
+            fields.push("tags".to_string());
+        }
 
-        // 确保目标目录存在
-        if let Some(parent) = dest_path.parent() {
-            ensure_dir_exists(parent)?;
+        // 已有条目仍在默认分类时才采用新分类
+        let default_category = &self.config.import.metadata.default_category;
+        let existing_uncategorized =
+            existing.category1.trim().is_empty() || &existing.category1 == default_category;
+        let new_categorized =
+            !metadata.category1.trim().is_empty() && &metadata.category1 != default_category;
+        if existing_uncategorized && new_categorized {
+            update.category1 = Some(metadata.category1.clone());
+            update.category2 = metadata.category2.clone();
+            update.category3 = metadata.category3.clone();
+            fields.push("category".to_string());
         }
 
-        // 6. 复制文件到目标位置
-        safe_copy_file(file_path, &dest_path).await?;
+        if existing.file_metadata.is_none() && metadata.file_metadata.is_some() {
+            update.file_metadata = metadata.file_metadata.clone();
+            fields.push("file_metadata".to_string());
+        }
+        if existing.type_metadata.is_none() && metadata.type_metadata.is_some() {
+            update.type_metadata = metadata.type_metadata.clone();
+            fields.push("type_metadata".to_string());
+        }
 
-        // 7. 创建文件记录
-        let file_entry = self
-            .create_file_entry(file_path, &dest_path, &original_filename, &hash, &metadata)
-            .await?;
+        (update, fields)
+    }
 
-        info!(
-            "文件导入完成: {} -> {} (ID: {})",
-            file_path.display(),
-            dest_path.display(),
-            file_entry.id
-        );
+    /// 记录重复文件的原始路径和文件名，作为已有条目的别名
+    async fn record_alias(&self, file_id: &str, file_path: &Path) -> Result<()> {
+        let original_path = file_path
+            .canonicalize()
+            .unwrap_or_else(|_| file_path.to_path_buf())
+            .to_string_lossy()
+            .to_string();
+        let filename = file_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let now = current_time().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO file_aliases (file_id, original_path, filename, added_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(file_id)
+        .bind(&original_path)
+        .bind(&filename)
+        .bind(&now)
+        .execute(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        debug!("记录文件别名: {} -> {}", original_path, file_id);
+        Ok(())
+    }
+
+    /// 获取文件的所有别名（重复导入时记录的原始路径）
+    pub async fn get_aliases(&self, file_id: &str) -> Result<Vec<(PathBuf, String)>> {
+        let rows = sqlx::query(
+            "SELECT original_path, filename FROM file_aliases WHERE file_id = ? ORDER BY added_at",
+        )
+        .bind(file_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
 
-        Ok(file_entry)
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let path: String = row.get("original_path");
+                let filename: String = row.get("filename");
+                (PathBuf::from(path), filename)
+            })
+            .collect())
     }
 
     /// 根据哈希查找文件
@@ -191,6 +663,10 @@ impl Importer {
             source_url: Option<String>,
             summary: Option<String>,
             full_text: Option<String>,
+            original_path: Option<String>,
+            size: Option<i64>,
+            mime_type: Option<String>,
+            file_mtime: Option<String>,
             created_at: String,
             updated_at: String,
             is_deleted: i64,
@@ -205,6 +681,7 @@ impl Importer {
             SELECT 
                 id as "id!", title as "title!", initial_hash, current_hash, 
                 relative_path, filename, year, publisher, category1, category2, category3, source_url, summary, full_text,
+                original_path, size, mime_type, file_mtime,
                 created_at as "created_at!", updated_at as "updated_at!", is_deleted, deleted_at as "_deleted_at",
                 file_metadata, type_metadata
             FROM files
@@ -218,19 +695,31 @@ impl Importer {
         .map_err(TagboxError::Database)?;
 
         if let Some(db_row) = maybe_row {
-            let authors = self.get_file_authors(&db_row.id).await?;
-            let tags = self.get_file_tags(&db_row.id).await?;
+            let (authors, contributors) = AuthorManager::new(self.db_pool.clone())
+                .get_file_authors(&db_row.id)
+                .await?;
+            let tags = TagManager::new(self.db_pool.clone())
+                .get_file_tags(&db_row.id)
+                .await?;
 
             Ok(Some(FileEntry {
                 id: db_row.id,
                 title: db_row.title,
                 authors,
+                contributors,
                 year: db_row.year.map(|y| y as i32),
                 publisher: db_row.publisher,
                 source: db_row.source_url,
                 path: PathBuf::from(require_field(db_row.relative_path, "files.relative_path")?),
-                original_path: None,
+                original_path: db_row.original_path.map(PathBuf::from),
                 original_filename: require_field(db_row.filename, "files.filename")?,
+                size: db_row.size.map(|s| s as u64),
+                mime_type: db_row.mime_type,
+                mtime: db_row
+                    .file_mtime
+                    .as_deref()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc)),
                 hash: require_field(db_row.initial_hash, "files.initial_hash")?,
                 current_hash: db_row.current_hash,
                 category1: db_row.category1.unwrap_or_default(),
@@ -295,15 +784,31 @@ impl Importer {
             .transpose()
             .map_err(TagboxError::from)?;
 
+        // 源文件信息
+        let original_path_for_db = original_path
+            .canonicalize()
+            .unwrap_or_else(|_| original_path.to_path_buf());
+        let original_path_str = original_path_for_db.to_string_lossy().into_owned();
+        let fs_metadata = std::fs::metadata(original_path).map_err(TagboxError::Io)?;
+        let size = fs_metadata.len();
+        let size_for_db = size as i64;
+        let mtime: Option<DateTime<Utc>> = fs_metadata.modified().ok().map(DateTime::from);
+        let mtime_str = mtime.map(|t| t.to_rfc3339());
+        let mime_type = metadata
+            .mime_type
+            .clone()
+            .or_else(|| detect_mime(original_path));
+
         sqlx::query!(
             r#"
             INSERT INTO files (
                 id, title, initial_hash, current_hash, relative_path, filename,
                 year, publisher, category1, category2, category3, source_url, summary, full_text,
+                original_path, size, mime_type, file_mtime,
                 created_at, updated_at, is_deleted, deleted_at,
                 file_metadata, type_metadata
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             id,
             metadata.title,
@@ -319,6 +824,10 @@ impl Importer {
             metadata.source,
             metadata.summary,
             metadata.full_text,
+            original_path_str,
+            size_for_db,
+            mime_type,
+            mtime_str,
             now_str_rfc3339,
             now_str_rfc3339,
             0,
@@ -330,19 +839,19 @@ impl Importer {
         .await
         .map_err(TagboxError::Database)?;
 
-        let mut authors_for_entry = Vec::new();
-        for author_name in &metadata.authors {
-            let author_id = self.find_or_create_author(author_name).await?;
-            self.link_author_to_file(&id, &author_id).await?;
-            authors_for_entry.push(author_name.clone());
-        }
+        // 作者按署名顺序关联，经别名解析到已有作者，不同写法指向同一作者时只关联一次
+        let author_manager = AuthorManager::new(self.db_pool.clone());
+        let authors_for_entry = author_manager
+            .set_file_authors(&id, AuthorRole::Author, &metadata.authors)
+            .await?;
+        let contributors_for_entry = author_manager
+            .set_file_contributors(&id, &metadata.contributors)
+            .await?;
 
-        let mut tags_for_entry = Vec::new();
-        for tag_name in &metadata.tags {
-            let tag_id = self.find_or_create_tag(tag_name).await?;
-            self.link_tag_to_file(&id, &tag_id).await?;
-            tags_for_entry.push(tag_name.clone());
-        }
+        // 层级标签沿路径创建父标签，插入触发器写入的索引不含标签，需要补上
+        let tag_manager = TagManager::new(self.db_pool.clone());
+        let tags_for_entry = tag_manager.add_file_tags(&id, &metadata.tags).await?;
+        tag_manager.update_fts_tags(&id).await?;
 
         for (key, value) in &metadata.additional_info {
             self.add_metadata_to_file(&id, key, value).await?;
@@ -352,12 +861,16 @@ impl Importer {
             id,
             title: metadata.title.clone(),
             authors: authors_for_entry,
+            contributors: contributors_for_entry,
             year: metadata.year,
             publisher: metadata.publisher.clone(),
             source: metadata.source.clone(),
             path: dest_path.to_path_buf(),
-            original_path: Some(original_path.to_path_buf()),
+            original_path: Some(original_path_for_db),
             original_filename: original_filename_str.to_string(),
+            size: Some(size),
+            mime_type,
+            mtime,
             hash: hash_val.to_string(),
             current_hash: Some(hash_val.to_string()),
             category1: metadata.category1.clone(),
@@ -375,91 +888,6 @@ impl Importer {
         })
     }
 
-    /// 添加作者到文件
-    async fn link_author_to_file(&self, file_id: &str, author_id: &str) -> Result<()> {
-        sqlx::query!(
-            "INSERT OR IGNORE INTO file_authors (file_id, author_id) VALUES (?, ?)",
-            file_id,
-            author_id
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-        Ok(())
-    }
-
-    /// 查找或创建作者
-    async fn find_or_create_author(&self, author_name: &str) -> Result<String> {
-        let maybe_author = sqlx::query!(
-            r#"SELECT id as "id!" FROM authors WHERE name = ?"#, // id! ensures String
-            author_name
-        )
-        .fetch_optional(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-
-        if let Some(author) = maybe_author {
-            return Ok(author.id);
-        }
-
-        let author_id = generate_uuid();
-        let now_str = current_time().to_rfc3339();
-        sqlx::query!(
-            "INSERT INTO authors (id, name, created_at, updated_at) VALUES (?, ?, ?, ?)",
-            author_id,
-            author_name,
-            now_str,
-            now_str
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-        Ok(author_id)
-    }
-
-    /// 添加标签到文件
-    async fn link_tag_to_file(&self, file_id: &str, tag_id: &str) -> Result<()> {
-        sqlx::query!(
-            "INSERT OR IGNORE INTO file_tags (file_id, tag_id) VALUES (?, ?)",
-            file_id,
-            tag_id
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-        Ok(())
-    }
-
-    /// 查找或创建标签
-    async fn find_or_create_tag(&self, tag_name: &str) -> Result<String> {
-        let maybe_tag = sqlx::query!(r#"SELECT id as "id!" FROM tags WHERE name = ?"#, tag_name)
-            .fetch_optional(&self.db_pool)
-            .await
-            .map_err(TagboxError::Database)?;
-
-        if let Some(tag) = maybe_tag {
-            return Ok(tag.id);
-        }
-
-        let id = generate_uuid();
-        let now_str = current_time().to_rfc3339();
-        sqlx::query!(
-            r#"
-            INSERT INTO tags (id, name, path, created_at, is_deleted, parent_id)
-            VALUES (?, ?, ?, ?, ?, NULL) 
-            "#,
-            id,
-            tag_name,
-            tag_name,
-            now_str,
-            0
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-        Ok(id)
-    }
-
     /// 添加元数据到文件
     async fn add_metadata_to_file(&self, file_id: &str, key: &str, value: &str) -> Result<()> {
         sqlx::query!(
@@ -473,36 +901,4 @@ impl Importer {
         .map_err(TagboxError::Database)?;
         Ok(())
     }
-
-    /// 获取文件作者
-    async fn get_file_authors(&self, file_id: &str) -> Result<Vec<String>> {
-        let author_names = sqlx::query!(
-            // In authors.rs, get_author fetches names for aliases.
-            // Here, we just need the names of authors directly linked to the file.
-            "SELECT name FROM authors a JOIN file_authors fa ON a.id = fa.author_id WHERE fa.file_id = ?",
-            file_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .into_iter()
-        .map(|row| row.name) // row.name is String as it's NOT NULL in DB
-        .collect();
-        Ok(author_names)
-    }
-
-    /// 获取文件标签
-    async fn get_file_tags(&self, file_id: &str) -> Result<Vec<String>> {
-        let tag_names = sqlx::query!(
-            "SELECT name FROM tags t JOIN file_tags ft ON t.id = ft.tag_id WHERE ft.file_id = ?",
-            file_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .into_iter()
-        .map(|row| row.name) // row.name is String as it's NOT NULL in DB
-        .collect();
-        Ok(tag_names)
-    }
 }
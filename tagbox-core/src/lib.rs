@@ -1,35 +1,60 @@
+mod annotations;
 mod authors;
+mod batch;
+pub mod bibliography;
+mod categories;
 pub mod config;
 mod editor;
+pub mod enrich;
 pub mod errors;
+pub mod filetype;
 pub mod history;
+pub mod identifiers;
 mod importer;
 mod link;
 pub mod metainfo;
+#[cfg(feature = "ocr")]
+mod ocr;
+mod pages;
 pub mod pathgen;
 pub mod schema;
 mod search;
 mod system;
+mod tags;
 pub mod types;
 pub mod utils;
 mod validation;
 
 // 导出各个管理器供外部使用
-pub use authors::AuthorManager;
+pub use annotations::AnnotationManager;
+pub use authors::{normalize_author_name, normalize_orcid, split_author_list, AuthorManager};
+pub use batch::BatchEditor;
+pub use categories::{normalize_category_path, CategoryManager, CategoryPath};
 pub use editor::Editor;
+pub use enrich::{Enricher, EnrichmentProvider};
 pub use history::{FileHistoryManager, FileOperation};
+pub use identifiers::IdentifierManager;
 pub use importer::Importer;
 pub use link::LinkManager;
+#[cfg(feature = "ocr")]
+pub use ocr::{OcrEngine, OcrManager};
+pub use pages::PageIndexer;
 pub use search::Searcher;
 pub use system::{CompatibilityResult, SystemConfigManager};
+pub use tags::{normalize_tag_path, TagManager};
 pub use validation::{FileValidator, ValidationResult, ValidationStatus};
 
+use batch::MAX_BATCH_FILES;
 use config::AppConfig;
 use errors::Result;
 use schema::Database;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
-use types::{FileEntry, FileUpdateRequest, ImportMetadata, SearchOptions, SearchResult};
+use types::{
+    Annotation, BibImportResult, CategoryMove, EditBatch, EnrichmentProposal, FileEntry, FilePatch,
+    FileUpdateRequest, Identifier, ImportMetadata, ImportResult, PageIndexStatus, SearchOptions,
+    SearchResult, TagNode, TagSummary,
+};
 
 /// 初始化数据库 - Initialize database
 pub async fn init_database(path: &Path) -> Result<()> {
@@ -64,10 +89,62 @@ pub async fn import_file(
     importer.import_with_metadata(path, metadata).await
 }
 
+/// 使用指定的重复策略导入文件，并返回处理结果
+pub async fn import_file_with_policy(
+    path: &Path,
+    metadata: ImportMetadata,
+    policy: config::DuplicatePolicy,
+    config: &AppConfig,
+) -> Result<ImportResult> {
+    let db = Database::new(&config.database.path).await?;
+    let importer = Importer::new(config.clone(), db.pool().clone());
+
+    importer.import_with_policy(path, metadata, policy).await
+}
+
+/// 对已存在的重复条目应用重复策略（用于 prompt 策略确认后的处理）
+pub async fn resolve_duplicate(
+    existing: FileEntry,
+    metadata: &ImportMetadata,
+    policy: config::DuplicatePolicy,
+    config: &AppConfig,
+) -> Result<ImportResult> {
+    let db = Database::new(&config.database.path).await?;
+    let importer = Importer::new(config.clone(), db.pool().clone());
+
+    importer
+        .apply_duplicate_policy(existing, metadata, policy)
+        .await
+}
+
+/// 将已导入的压缩包展开，成员逐个导入并以 `contained_in` 关联到压缩包
+#[cfg(feature = "archive")]
+pub async fn explode_archive(
+    file_id: &str,
+    policy: config::DuplicatePolicy,
+    config: &AppConfig,
+) -> Result<Vec<ImportResult>> {
+    let db = Database::new(&config.database.path).await?;
+    let editor = Editor::new(db.pool().clone());
+    let archive = editor.get_file(file_id).await?;
+
+    if !metainfo::is_archive(&archive.path) {
+        return Err(errors::TagboxError::ImportError(format!(
+            "不是支持的压缩包: {}",
+            archive.path.display()
+        )));
+    }
+
+    let importer = Importer::new(config.clone(), db.pool().clone());
+    importer.explode_archive(&archive, policy).await
+}
+
 // 提取文件元数据并导入数据
 pub async fn extract_and_import_file(path: &Path, config: &AppConfig) -> Result<FileEntry> {
-    let metadata = extract_metainfo(path, config).await?;
-    import_file(path, metadata, config).await
+    let db = Database::new(&config.database.path).await?;
+    let importer = Importer::new(config.clone(), db.pool().clone());
+
+    importer.import(path).await
 }
 
 /// 批量提取文件元数据并导入数据
@@ -237,6 +314,351 @@ pub async fn rebuild_search_index(config: &AppConfig) -> Result<()> {
     searcher.rebuild_fts_index().await
 }
 
+/// 处理等待中的逐页全文索引，返回成功索引的文件数
+pub async fn index_pending_pages(config: &AppConfig) -> Result<usize> {
+    let db = Database::new(&config.database.path).await?;
+    let indexer = PageIndexer::new(db.pool().clone());
+
+    indexer
+        .index_pending(config.import.metadata.page_text_budget)
+        .await
+}
+
+/// 在后台处理等待中的逐页全文索引，导入后调用；失败时记录日志，调用方可以不等待结果
+pub fn spawn_page_indexing(config: AppConfig) -> tokio::task::JoinHandle<Result<usize>> {
+    tokio::spawn(async move {
+        let result = index_pending_pages(&config).await;
+        match &result {
+            Ok(0) => {}
+            Ok(indexed) => info!("Background page indexing finished: {} file(s)", indexed),
+            Err(e) => warn!("Background page indexing failed: {}", e),
+        }
+        result
+    })
+}
+
+/// 重新建立单个文件的逐页全文索引
+pub async fn reindex_file_pages(file_id: &str, config: &AppConfig) -> Result<PageIndexStatus> {
+    let db = Database::new(&config.database.path).await?;
+    let editor = Editor::new(db.pool().clone());
+    let indexer = PageIndexer::new(db.pool().clone());
+
+    let file = editor.get_file(file_id).await?;
+    indexer
+        .index_file(
+            &file.id,
+            &file.path,
+            config.import.metadata.page_text_budget,
+        )
+        .await
+}
+
+/// 将所有支持逐页索引的文件加入等待队列，返回数量
+pub async fn queue_all_pages(config: &AppConfig) -> Result<usize> {
+    let db = Database::new(&config.database.path).await?;
+    let indexer = PageIndexer::new(db.pool().clone());
+
+    indexer.mark_all_pending().await
+}
+
+/// 等待逐页索引的文件数量
+pub async fn pending_page_count(config: &AppConfig) -> Result<usize> {
+    let db = Database::new(&config.database.path).await?;
+    let indexer = PageIndexer::new(db.pool().clone());
+
+    Ok(indexer.pending_files().await?.len())
+}
+
+/// 对文件运行 OCR，结果写入全文并标记为 OCR 输出，返回识别出文字的页数
+#[cfg(feature = "ocr")]
+pub async fn ocr_file(file_id: &str, config: &AppConfig) -> Result<usize> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = OcrManager::new(db.pool().clone(), &config.ocr)?;
+
+    manager.ocr_file(file_id).await
+}
+
+/// 对所有没有全文且未做过 OCR 的图片和 PDF 运行 OCR，返回 (处理的文件数, 识别出文字的文件数)
+#[cfg(feature = "ocr")]
+pub async fn ocr_missing_files(config: &AppConfig) -> Result<(usize, usize)> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = OcrManager::new(db.pool().clone(), &config.ocr)?;
+
+    manager.ocr_missing().await
+}
+
+/// 获取文件的 PDF 批注，按页码排列
+pub async fn get_file_annotations(file_id: &str, config: &AppConfig) -> Result<Vec<Annotation>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = AnnotationManager::new(db.pool().clone());
+
+    manager.list(file_id).await
+}
+
+/// 获取文件的文献标识符（DOI、arXiv 编号、ISBN、ISSN）
+pub async fn get_file_identifiers(file_id: &str, config: &AppConfig) -> Result<Vec<Identifier>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = IdentifierManager::new(db.pool().clone());
+
+    manager.list(file_id).await
+}
+
+/// 获取标签树，附带每个标签直接关联和含子标签的文件数
+pub async fn get_tag_tree(config: &AppConfig) -> Result<Vec<TagNode>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.tag_tree().await
+}
+
+/// 列出全部标签及其文件数和别名
+pub async fn list_tags(config: &AppConfig) -> Result<Vec<TagSummary>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.list_tags().await
+}
+
+/// 重命名标签（含子标签），返回受影响的文件 ID
+pub async fn rename_tag(old_path: &str, new_path: &str, config: &AppConfig) -> Result<Vec<String>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.rename_tag(old_path, new_path).await
+}
+
+/// 把标签合并到另一个标签，源路径保留为别名，返回受影响的文件 ID
+pub async fn merge_tags(
+    from_path: &str,
+    into_path: &str,
+    config: &AppConfig,
+) -> Result<Vec<String>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.merge_tags(from_path, into_path).await
+}
+
+/// 删除标签（含子标签），仍被使用时需要 `force`，返回受影响的文件 ID
+pub async fn delete_tag(path: &str, force: bool, config: &AppConfig) -> Result<Vec<String>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.delete_tag(path, force).await
+}
+
+/// 删除未使用的标签，返回删除的标签路径
+pub async fn prune_unused_tags(config: &AppConfig) -> Result<Vec<String>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.prune_unused_tags().await
+}
+
+/// 为标签添加别名
+pub async fn add_tag_alias(path: &str, alias: &str, config: &AppConfig) -> Result<()> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.add_tag_alias(path, alias).await
+}
+
+/// 删除标签别名
+pub async fn remove_tag_alias(alias: &str, config: &AppConfig) -> Result<()> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = TagManager::new(db.pool().clone());
+
+    manager.remove_tag_alias(alias).await
+}
+
+/// 重命名分类（含子分类）并按新分类移动文件，`dry_run` 时只返回预计的变化
+pub async fn rename_category(
+    old_path: &str,
+    new_path: &str,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Result<Vec<CategoryMove>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = CategoryManager::new(db.pool().clone());
+
+    manager
+        .rename_category(old_path, new_path, config, dry_run)
+        .await
+}
+
+/// 把分类子树移动到另一个父分类下（空字符串为顶层）并移动文件
+pub async fn move_category(
+    path: &str,
+    new_parent: &str,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Result<Vec<CategoryMove>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = CategoryManager::new(db.pool().clone());
+
+    manager
+        .move_category(path, new_parent, config, dry_run)
+        .await
+}
+
+/// 把分类子树合并到已有分类并移动文件
+pub async fn merge_category(
+    from_path: &str,
+    into_path: &str,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Result<Vec<CategoryMove>> {
+    let db = Database::new(&config.database.path).await?;
+    let manager = CategoryManager::new(db.pool().clone());
+
+    manager
+        .merge_category(from_path, into_path, config, dry_run)
+        .await
+}
+
+/// 对搜索结果中的所有文件应用标签/分类补丁，`dry_run` 时只返回预计的变化
+pub async fn bulk_edit(
+    query: &str,
+    patch: &FilePatch,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Result<EditBatch> {
+    let db = Database::new(&config.database.path).await?;
+    let searcher = Searcher::new(config.clone(), db.pool().clone()).await;
+    let options = SearchOptions {
+        offset: 0,
+        limit: MAX_BATCH_FILES,
+        sort_by: Some("title".to_string()),
+        sort_direction: Some("ASC".to_string()),
+        include_deleted: false,
+    };
+    let result = searcher.search_advanced(query, Some(options)).await?;
+    if result.total_count > MAX_BATCH_FILES {
+        return Err(errors::TagboxError::Config(format!(
+            "查询匹配 {} 个文件，超过批量编辑上限 {}，请缩小查询范围",
+            result.total_count, MAX_BATCH_FILES
+        )));
+    }
+    let file_ids: Vec<String> = result.entries.into_iter().map(|entry| entry.id).collect();
+
+    let editor = BatchEditor::new(db.pool().clone());
+    editor
+        .apply_patch(&file_ids, patch, query, config, dry_run)
+        .await
+}
+
+/// 对选中的文件应用标签/分类补丁（GUI 多选）
+pub async fn bulk_edit_files(
+    file_ids: &[String],
+    patch: &FilePatch,
+    dry_run: bool,
+    config: &AppConfig,
+) -> Result<EditBatch> {
+    let db = Database::new(&config.database.path).await?;
+    let editor = BatchEditor::new(db.pool().clone());
+
+    let description = format!("选中的 {} 个文件", file_ids.len());
+    editor
+        .apply_patch(file_ids, patch, &description, config, dry_run)
+        .await
+}
+
+/// 撤销一次批量编辑
+pub async fn undo_edit_batch(batch_id: &str, config: &AppConfig) -> Result<EditBatch> {
+    let db = Database::new(&config.database.path).await?;
+    let editor = BatchEditor::new(db.pool().clone());
+
+    editor.undo_batch(batch_id, config).await
+}
+
+/// 按 `[enrich]` 配置的数据源查询文件的完整元数据，返回待确认的更新建议
+pub async fn enrich_file(file_id: &str, config: &AppConfig) -> Result<Option<EnrichmentProposal>> {
+    let db = Database::new(&config.database.path).await?;
+    let enricher = Enricher::from_config(db.pool().clone(), &config.enrich)?;
+
+    enricher.propose(file_id).await
+}
+
+/// 应用元数据补全的更新建议
+pub async fn apply_enrichment(proposal: &EnrichmentProposal, config: &AppConfig) -> Result<()> {
+    let db = Database::new(&config.database.path).await?;
+    let enricher = Enricher::new(db.pool().clone(), Vec::new());
+
+    enricher.apply(proposal).await
+}
+
+/// 将文件条目导出为 BibTeX、RIS 或 CSL-JSON，引用键在这组条目内唯一
+pub async fn export_bibliography(
+    entries: &[FileEntry],
+    format: bibliography::BibFormat,
+    config: &AppConfig,
+) -> Result<String> {
+    let db = Database::new(&config.database.path).await?;
+    let records = bibliography_records(entries, &db).await?;
+
+    Ok(match format {
+        bibliography::BibFormat::Bibtex => bibliography::write_bibtex(&records),
+        bibliography::BibFormat::Ris => bibliography::write_ris(&records),
+        bibliography::BibFormat::CslJson => {
+            let items: Vec<_> = records
+                .iter()
+                .map(bibliography::CslItem::from_bib)
+                .collect();
+            serde_json::to_string_pretty(&items)?
+        }
+    })
+}
+
+/// 按指定格式生成文件的参考文献条目，顺序与 `file_ids` 一致
+pub async fn format_citations(
+    file_ids: &[String],
+    style: bibliography::CitationStyle,
+    config: &AppConfig,
+) -> Result<Vec<String>> {
+    let db = Database::new(&config.database.path).await?;
+    let editor = Editor::new(db.pool().clone());
+
+    let mut entries = Vec::with_capacity(file_ids.len());
+    for file_id in file_ids {
+        entries.push(editor.get_file(file_id).await?);
+    }
+
+    let records = bibliography_records(&entries, &db).await?;
+    Ok(records
+        .iter()
+        .map(|record| bibliography::format_citation(record, style))
+        .collect())
+}
+
+/// 由文件条目和已保存的标识符生成参考文献记录
+async fn bibliography_records(
+    entries: &[FileEntry],
+    db: &Database,
+) -> Result<Vec<bibliography::BibEntry>> {
+    let manager = IdentifierManager::new(db.pool().clone());
+    let keys = bibliography::citation_keys(entries);
+
+    let mut records = Vec::with_capacity(entries.len());
+    for (entry, key) in entries.iter().zip(&keys) {
+        let identifiers = manager.list(&entry.id).await?;
+        records.push(bibliography::BibEntry::from_file(entry, &identifiers, key));
+    }
+    Ok(records)
+}
+
+/// 导入 BibTeX 文件，条目对应的文件在 `files_dir` 中查找
+pub async fn import_bibtex(
+    bib_path: &Path,
+    files_dir: &Path,
+    policy: config::DuplicatePolicy,
+    config: &AppConfig,
+) -> Result<Vec<BibImportResult>> {
+    let db = Database::new(&config.database.path).await?;
+    let importer = Importer::new(config.clone(), db.pool().clone());
+
+    importer.import_bibtex(bib_path, files_dir, policy).await
+}
+
 /// 获取文件路径
 pub async fn get_file_path(file_id: &str, config: &AppConfig) -> Result<PathBuf> {
     let db = Database::new(&config.database.path).await?;
@@ -0,0 +1,326 @@
+use super::registry::{supports_type, MetadataExtractor};
+use super::text::html_to_text;
+use crate::errors::{Result, TagboxError};
+use crate::filetype::{self, detect_bytes};
+use crate::types::ImportMetadata;
+use crate::utils::mime_from_extension;
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, warn};
+use zip::ZipArchive;
+
+/// 写入 `file_metadata.archive.members` 的最大条目数，超过后只计数
+const MAX_MEMBERS: usize = 10_000;
+
+/// 检测成员类型时读取的字节数
+const HEAD_LEN: u64 = 64 * 1024;
+
+/// 单个文本成员读取的字节上限，更大的成员只记录名称
+const MEMBER_TEXT_LIMIT: u64 = 1024 * 1024;
+
+/// 所有文本成员写入 `full_text` 的总字节上限
+const TEXT_BUDGET: usize = 16 * 1024 * 1024;
+
+/// 压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    fn from_type(path: &Path, mime: Option<&str>) -> Option<Self> {
+        if supports_type(path, mime, &[filetype::ZIP.mime], &["zip"]) {
+            return Some(ArchiveFormat::Zip);
+        }
+        if supports_type(path, mime, &[filetype::TAR.mime], &["tar"]) {
+            return Some(ArchiveFormat::Tar);
+        }
+        // 单独的 .gz 文件不一定是 tar 包，只按文件名识别 .tar.gz / .tgz
+        let gzip = match mime {
+            Some(mime) => mime.eq_ignore_ascii_case(filetype::GZIP.mime),
+            None => true,
+        };
+        if gzip && is_tar_gz_name(path) {
+            return Some(ArchiveFormat::TarGz);
+        }
+        None
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+fn is_tar_gz_name(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// 压缩包中的一个文件
+#[derive(Debug, Clone)]
+struct Member {
+    path: String,
+    size: u64,
+    mime: Option<String>,
+}
+
+/// ZIP 与 tar（含 .tar.gz）压缩包提取器
+///
+/// 列出成员文件写入 `file_metadata.archive`，成员名称和文本类成员的内容写入 `full_text`。
+pub struct ArchiveExtractor;
+
+impl MetadataExtractor for ArchiveExtractor {
+    fn name(&self) -> &'static str {
+        "archive"
+    }
+
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        ArchiveFormat::from_type(path, mime).is_some()
+    }
+
+    fn extract(&self, path: &Path, mime: Option<&str>) -> Result<ImportMetadata> {
+        let format = ArchiveFormat::from_type(path, mime).ok_or_else(|| not_archive(path))?;
+        self.extract_archive(path, format)
+    }
+}
+
+impl ArchiveExtractor {
+    fn extract_archive(&self, path: &Path, format: ArchiveFormat) -> Result<ImportMetadata> {
+        debug!("读取压缩包成员: {}", path.display());
+
+        let mut members = Vec::new();
+        let mut member_count = 0usize;
+        let mut total_size = 0u64;
+        let mut texts = Vec::new();
+        let mut text_len = 0usize;
+
+        walk_members(path, format, |name, size, reader| {
+            member_count += 1;
+            total_size += size;
+            if members.len() >= MAX_MEMBERS {
+                return Ok(());
+            }
+
+            let read_text = size <= MEMBER_TEXT_LIMIT && text_len < TEXT_BUDGET;
+            let limit = if read_text {
+                MEMBER_TEXT_LIMIT
+            } else {
+                HEAD_LEN
+            };
+            let mut buf = Vec::new();
+            reader
+                .take(limit)
+                .read_to_end(&mut buf)
+                .map_err(TagboxError::Io)?;
+
+            let mime = member_mime(name, &buf);
+            if read_text && mime.as_deref().is_some_and(is_text_mime) {
+                let text = member_text(&buf, mime.as_deref());
+                if !text.is_empty() {
+                    text_len += text.len();
+                    texts.push(text);
+                }
+            }
+
+            members.push(Member {
+                path: name.to_string(),
+                size,
+                mime,
+            });
+            Ok(())
+        })?;
+
+        let mut archive = serde_json::json!({
+            "format": format.name(),
+            "member_count": member_count,
+            "total_size": total_size,
+            "members": members
+                .iter()
+                .map(|m| serde_json::json!({
+                    "path": m.path,
+                    "size": m.size,
+                    "type": m.mime,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        if members.len() < member_count {
+            archive["truncated"] = serde_json::json!(true);
+        }
+
+        let mut meta = ImportMetadata {
+            file_metadata: Some(serde_json::json!({ "archive": archive })),
+            ..Default::default()
+        };
+
+        // 成员名称在前，便于按压缩包内的文件名搜索
+        let mut full_text = members
+            .iter()
+            .map(|m| m.path.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        for text in texts {
+            full_text.push_str("\n\n");
+            full_text.push_str(&text);
+        }
+        if !full_text.is_empty() {
+            meta.full_text = Some(full_text);
+        }
+
+        Ok(meta)
+    }
+}
+
+/// 将压缩包中的文件解压到 `dest`，返回成员路径和解压后的文件路径
+///
+/// 绝对路径和包含 `..` 的成员会被跳过，不会写到 `dest` 之外。
+pub fn unpack_archive(path: &Path, dest: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let format = detect_format(path)?;
+    let mut unpacked = Vec::new();
+
+    walk_members(path, format, |name, _size, reader| {
+        let relative = match safe_member_path(name) {
+            Some(relative) => relative,
+            None => {
+                warn!("跳过不安全的压缩包成员: {}", name);
+                return Ok(());
+            }
+        };
+
+        let target = dest.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(TagboxError::Io)?;
+        }
+        let mut file = File::create(&target).map_err(TagboxError::Io)?;
+        io::copy(reader, &mut file).map_err(TagboxError::Io)?;
+
+        unpacked.push((name.to_string(), target));
+        Ok(())
+    })?;
+
+    Ok(unpacked)
+}
+
+/// 文件是否为支持的压缩包
+pub fn is_archive(path: &Path) -> bool {
+    detect_format(path).is_ok()
+}
+
+fn detect_format(path: &Path) -> Result<ArchiveFormat> {
+    let mime = filetype::detect_file(path)?.map(|t| t.mime);
+    ArchiveFormat::from_type(path, mime).ok_or_else(|| not_archive(path))
+}
+
+fn not_archive(path: &Path) -> TagboxError {
+    TagboxError::MetaInfoExtraction(format!("不是支持的压缩包: {}", path.display()))
+}
+
+/// 依次访问压缩包中的普通文件（跳过目录、链接等）
+fn walk_members<F>(path: &Path, format: ArchiveFormat, mut visit: F) -> Result<()>
+where
+    F: FnMut(&str, u64, &mut dyn Read) -> Result<()>,
+{
+    let file = File::open(path).map_err(TagboxError::Io)?;
+    let open_error = |e: &dyn std::fmt::Display| {
+        TagboxError::MetaInfoExtraction(format!("无法读取压缩包 {}: {}", path.display(), e))
+    };
+
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::new(BufReader::new(file)).map_err(|e| open_error(&e))?;
+            for i in 0..archive.len() {
+                let mut member = match archive.by_index(i) {
+                    Ok(member) => member,
+                    Err(e) => {
+                        // 加密或不支持的压缩方式
+                        debug!("跳过压缩包成员 #{}: {}", i, e);
+                        continue;
+                    }
+                };
+                if member.is_dir() {
+                    continue;
+                }
+                let name = member.name().replace('\\', "/");
+                let size = member.size();
+                visit(&name, size, &mut member)?;
+            }
+        }
+        ArchiveFormat::Tar => walk_tar(tar::Archive::new(BufReader::new(file)), &mut visit)
+            .map_err(|e| open_error(&e))??,
+        ArchiveFormat::TarGz => walk_tar(
+            tar::Archive::new(GzDecoder::new(BufReader::new(file))),
+            &mut visit,
+        )
+        .map_err(|e| open_error(&e))??,
+    }
+
+    Ok(())
+}
+
+/// 遍历 tar 包，外层错误为读取错误，内层为访问回调返回的错误
+fn walk_tar<R, F>(mut archive: tar::Archive<R>, visit: &mut F) -> io::Result<Result<()>>
+where
+    R: Read,
+    F: FnMut(&str, u64, &mut dyn Read) -> Result<()>,
+{
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().replace('\\', "/");
+        let size = entry.size();
+        if let Err(e) = visit(&name, size, &mut entry) {
+            return Ok(Err(e));
+        }
+    }
+    Ok(Ok(()))
+}
+
+/// 成员的相对路径，拒绝绝对路径和 `..`
+fn safe_member_path(name: &str) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+/// 根据内容检测成员类型，纯文本再按扩展名细分（md、json ...）
+fn member_mime(name: &str, head: &[u8]) -> Option<String> {
+    let detected = detect_bytes(head);
+    let by_extension = mime_from_extension(Path::new(name));
+    match detected {
+        Some(t) if t == filetype::TEXT => Some(by_extension.unwrap_or(t.mime).to_string()),
+        Some(t) => Some(t.mime.to_string()),
+        None => by_extension.map(str::to_string),
+    }
+}
+
+fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/") || matches!(mime, "application/json" | "application/xml")
+}
+
+fn member_text(buf: &[u8], mime: Option<&str>) -> String {
+    let text = String::from_utf8_lossy(buf);
+    let text = text.trim_start_matches('\u{feff}');
+    if mime == Some(filetype::HTML.mime) {
+        html_to_text(text)
+    } else {
+        text.trim().to_string()
+    }
+}
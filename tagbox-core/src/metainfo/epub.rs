@@ -0,0 +1,251 @@
+use super::registry::{supports_type, MetadataExtractor};
+use super::text::html_to_text;
+use crate::errors::{Result, TagboxError};
+use crate::types::ImportMetadata;
+use epub::doc::{EpubDoc, NavPoint};
+use std::io::{Read, Seek};
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// EPUB 书籍元数据提取器
+pub struct EpubExtractor;
+
+impl MetadataExtractor for EpubExtractor {
+    fn name(&self) -> &'static str {
+        "epub"
+    }
+
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        supports_type(path, mime, &["application/epub+zip"], &["epub"])
+    }
+
+    fn extract(&self, path: &Path, _mime: Option<&str>) -> Result<ImportMetadata> {
+        self.extract_epub(path)
+    }
+}
+
+impl EpubExtractor {
+    /// 从EPUB文件提取完整元数据
+    fn extract_epub(&self, file_path: &Path) -> Result<ImportMetadata> {
+        let mut meta = ImportMetadata::default();
+
+        // 尝试打开EPUB文件
+        match EpubDoc::new(file_path) {
+            Ok(mut doc) => {
+                // 提取基本元数据
+                if let Some(title) = doc.mdata("title") {
+                    meta.title = title;
+                }
+
+                // 提取作者，OPF 中可能有多个 creator
+                if let Some(creators) = doc.metadata.get("creator") {
+                    for creator in creators {
+                        let creator = creator.trim();
+                        if !creator.is_empty() && !meta.authors.iter().any(|a| a == creator) {
+                            meta.authors.push(creator.to_string());
+                        }
+                    }
+                }
+
+                // 提取出版商
+                if let Some(publisher) = doc.mdata("publisher") {
+                    meta.publisher = Some(publisher);
+                }
+
+                // 提取出版日期
+                if let Some(date) = doc.mdata("date") {
+                    // 尝试解析年份
+                    if let Some(year_str) = date.split('-').next() {
+                        if let Ok(year) = year_str.parse::<i32>() {
+                            meta.year = Some(year);
+                        }
+                    }
+                }
+
+                // 提取语言
+                if let Some(language) = doc.mdata("language") {
+                    meta.additional_info
+                        .insert("language".to_string(), language.clone());
+                }
+
+                // 提取描述/摘要
+                if let Some(description) = doc.mdata("description") {
+                    meta.summary = Some(description);
+                }
+
+                // 提取主题/标签
+                if let Some(subject) = doc.mdata("subject") {
+                    // 将主题转换为标签
+                    let subjects: Vec<String> = subject
+                        .split(';')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect();
+                    meta.tags.extend(subjects);
+                }
+
+                // 提取标识符（ISBN等）
+                if let Some(identifier) = doc.mdata("identifier") {
+                    meta.additional_info
+                        .insert("identifier".to_string(), identifier.clone());
+                    // 如果是ISBN，特别标记
+                    if identifier.contains("ISBN") || identifier.contains("isbn") {
+                        meta.additional_info.insert("isbn".to_string(), identifier);
+                    }
+                }
+
+                // 提取权利信息
+                if let Some(rights) = doc.mdata("rights") {
+                    meta.additional_info.insert("rights".to_string(), rights);
+                }
+
+                // 提取贡献者
+                if let Some(contributor) = doc.mdata("contributor") {
+                    meta.additional_info
+                        .insert("contributor".to_string(), contributor);
+                }
+
+                // 提取章节正文
+                let chapters = read_chapters(&mut doc, u64::MAX);
+                let text = chapters
+                    .chapters
+                    .iter()
+                    .map(|(_, text)| text.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+
+                // 构建文件特定元数据
+                let has_cover = doc.get_cover().is_some();
+                let mut file_metadata = serde_json::json!({
+                    "epub": {
+                        "spine_count": doc.get_num_pages(),
+                        "has_cover": has_cover,
+                        "has_text": !text.is_empty(),
+                        "text_length": text.len()
+                    }
+                });
+
+                // 如果能获取封面，保存封面信息
+                if let Some((cover_data, _mime)) = doc.get_cover() {
+                    file_metadata["epub"]["cover_size"] = serde_json::json!(cover_data.len());
+                }
+
+                meta.file_metadata = Some(file_metadata);
+
+                // 构建类型特定元数据（书籍）
+                let mut type_metadata = serde_json::json!({
+                    "book": {}
+                });
+
+                // 添加ISBN到类型元数据
+                if let Some(isbn) = meta.additional_info.get("isbn") {
+                    type_metadata["book"]["isbn"] = serde_json::json!(isbn);
+                }
+
+                // 添加语言到类型元数据
+                if let Some(language) = meta.additional_info.get("language") {
+                    type_metadata["book"]["language"] = serde_json::json!(language);
+                }
+
+                // 添加目录到类型元数据
+                if !doc.toc.is_empty() {
+                    type_metadata["book"]["toc"] = toc_to_json(&doc.toc);
+                }
+
+                meta.type_metadata = Some(type_metadata);
+
+                if !text.is_empty() {
+                    meta.full_text = Some(text);
+                }
+            }
+            Err(e) => {
+                warn!("无法打开EPUB文件 {}: {:?}", file_path.display(), e);
+            }
+        }
+
+        Ok(meta)
+    }
+}
+
+/// 逐章提取的EPUB文本
+#[derive(Debug, Clone, Default)]
+pub struct EpubChapters {
+    /// (章节序号, 文本)，序号为 spine 中的位置，从 1 开始，空白章节不包含在内
+    pub chapters: Vec<(u32, String)>,
+    /// spine 条目总数
+    pub spine_count: usize,
+    /// 是否因超出文本预算而提前停止
+    pub truncated: bool,
+}
+
+/// 按 spine 顺序逐章提取EPUB正文，累计文本超过 `budget` 字节后停止
+pub fn extract_epub_chapters(file_path: &Path, budget: u64) -> Result<EpubChapters> {
+    let mut doc = EpubDoc::new(file_path).map_err(|e| {
+        TagboxError::MetaInfoExtraction(format!(
+            "无法打开EPUB文件 {}: {:?}",
+            file_path.display(),
+            e
+        ))
+    })?;
+
+    let chapters = read_chapters(&mut doc, budget);
+    if chapters.truncated {
+        debug!(
+            "EPUB文本超出预算 {} 字节，停止于第{}章: {}",
+            budget,
+            chapters.chapters.len() + 1,
+            file_path.display()
+        );
+    }
+    Ok(chapters)
+}
+
+fn read_chapters<R: Read + Seek>(doc: &mut EpubDoc<R>, budget: u64) -> EpubChapters {
+    let mut result = EpubChapters {
+        spine_count: doc.get_num_pages(),
+        ..Default::default()
+    };
+    let mut used = 0u64;
+
+    for index in 0..result.spine_count {
+        if !doc.set_current_page(index) {
+            continue;
+        }
+        let Some((content, _mime)) = doc.get_current_str() else {
+            continue;
+        };
+
+        let text = html_to_text(&content);
+        if text.is_empty() {
+            continue;
+        }
+
+        used += text.len() as u64;
+        if used > budget {
+            result.truncated = true;
+            break;
+        }
+        result.chapters.push((index as u32 + 1, text));
+    }
+
+    result
+}
+
+/// 将目录转换为 `[{label, href, children}]`
+fn toc_to_json(points: &[NavPoint]) -> serde_json::Value {
+    serde_json::Value::Array(
+        points
+            .iter()
+            .map(|point| {
+                let mut entry = serde_json::json!({
+                    "label": point.label.trim(),
+                    "href": point.content.to_string_lossy().replace('\\', "/"),
+                });
+                if !point.children.is_empty() {
+                    entry["children"] = toc_to_json(&point.children);
+                }
+                entry
+            })
+            .collect(),
+    )
+}
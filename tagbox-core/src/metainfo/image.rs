@@ -0,0 +1,308 @@
+use super::registry::{supports_type, MetadataExtractor};
+use super::xmp;
+use crate::errors::Result;
+use crate::types::ImportMetadata;
+use exif::{Exif, In, Reader, Tag, Value};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// 查找 XMP 数据包时最多读取的字节数
+const XMP_SCAN_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// 图片尺寸与格式提取器
+pub struct ImageExtractor;
+
+impl MetadataExtractor for ImageExtractor {
+    fn name(&self) -> &'static str {
+        "image"
+    }
+
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        supports_type(
+            path,
+            mime,
+            &[
+                "image/jpeg",
+                "image/png",
+                "image/gif",
+                "image/bmp",
+                "image/webp",
+                "image/tiff",
+            ],
+            &["jpg", "jpeg", "png", "gif", "bmp", "webp", "tif", "tiff"],
+        )
+    }
+
+    fn extract(&self, path: &Path, _mime: Option<&str>) -> Result<ImportMetadata> {
+        self.extract_image(path)
+    }
+}
+
+impl ImageExtractor {
+    /// 从图片文件读取尺寸、EXIF 和 XMP 信息
+    fn extract_image(&self, file_path: &Path) -> Result<ImportMetadata> {
+        let mut meta = ImportMetadata::default();
+        let mut image = serde_json::Map::new();
+
+        match imageinfo::ImageInfo::from_file_path(file_path) {
+            Ok(info) => {
+                image.insert("width".into(), serde_json::json!(info.size.width));
+                image.insert("height".into(), serde_json::json!(info.size.height));
+                image.insert(
+                    "format".into(),
+                    serde_json::json!(format!("{:?}", info.format)),
+                );
+                image.insert("mimetype".into(), serde_json::json!(info.mimetype));
+
+                // 在additional_info中也保留基本信息，方便查询
+                meta.additional_info
+                    .insert("width".into(), info.size.width.to_string());
+                meta.additional_info
+                    .insert("height".into(), info.size.height.to_string());
+                meta.additional_info
+                    .insert("format".into(), format!("{:?}", info.format));
+            }
+            Err(e) => {
+                warn!("读取图片信息失败: {:?}", e);
+            }
+        }
+
+        let exif = read_exif(file_path);
+        let xmp = read_xmp(file_path);
+
+        // 拍摄时间：EXIF 优先，其次 XMP
+        let taken_at = exif
+            .as_ref()
+            .and_then(|exif| {
+                ascii(exif, Tag::DateTimeOriginal).or_else(|| ascii(exif, Tag::DateTime))
+            })
+            .and_then(|date| normalize_date(&date))
+            .or_else(|| xmp.created.as_deref().and_then(normalize_date));
+        if let Some(taken_at) = &taken_at {
+            meta.year = taken_at.get(..4).and_then(|year| year.parse().ok());
+            image.insert("taken_at".into(), serde_json::json!(taken_at));
+        }
+
+        let make = exif
+            .as_ref()
+            .and_then(|exif| ascii(exif, Tag::Make))
+            .or_else(|| xmp.make.clone());
+        let model = exif
+            .as_ref()
+            .and_then(|exif| ascii(exif, Tag::Model))
+            .or_else(|| xmp.model.clone());
+        if let Some(camera) = camera_name(make.as_deref(), model.as_deref()) {
+            image.insert("camera".into(), serde_json::json!(camera));
+        }
+        if let Some(make) = make {
+            image.insert("camera_make".into(), serde_json::json!(make));
+        }
+        if let Some(model) = model {
+            image.insert("camera_model".into(), serde_json::json!(model));
+        }
+
+        let lens = exif
+            .as_ref()
+            .and_then(|exif| ascii(exif, Tag::LensModel))
+            .or_else(|| xmp.lens.clone());
+        if let Some(lens) = lens {
+            image.insert("lens".into(), serde_json::json!(lens));
+        }
+
+        if let Some(exif) = &exif {
+            if let Some(orientation) = uint(exif, Tag::Orientation) {
+                image.insert("orientation".into(), serde_json::json!(orientation));
+            }
+            if let Some(gps) = gps(exif) {
+                image.insert("gps".into(), gps);
+            }
+
+            let mut exposure = serde_json::Map::new();
+            for (key, tag) in [
+                ("exposure_time", Tag::ExposureTime),
+                ("f_number", Tag::FNumber),
+                ("focal_length", Tag::FocalLength),
+            ] {
+                if let Some(value) = rational(exif, tag, 0) {
+                    exposure.insert(key.into(), serde_json::json!(value));
+                }
+            }
+            if let Some(iso) = uint(exif, Tag::PhotographicSensitivity) {
+                exposure.insert("iso".into(), serde_json::json!(iso));
+            }
+            if !exposure.is_empty() {
+                image.insert("exposure".into(), serde_json::Value::Object(exposure));
+            }
+
+            if let Some(artist) = ascii(exif, Tag::Artist) {
+                meta.authors = vec![artist];
+            }
+        }
+
+        // XMP 标题、描述和关键词
+        let description = xmp.description.clone().or_else(|| {
+            exif.as_ref()
+                .and_then(|exif| ascii(exif, Tag::ImageDescription))
+        });
+        if let Some(title) = &xmp.title {
+            meta.title = title.clone();
+            image.insert("title".into(), serde_json::json!(title));
+        }
+        if let Some(description) = description {
+            image.insert("description".into(), serde_json::json!(description));
+            meta.summary = Some(description);
+        }
+        if !xmp.keywords.is_empty() {
+            image.insert("keywords".into(), serde_json::json!(xmp.keywords));
+            meta.tags = xmp.keywords;
+        }
+
+        if !image.is_empty() {
+            meta.file_metadata = Some(serde_json::json!({ "image": image }));
+        }
+
+        Ok(meta)
+    }
+}
+
+fn read_exif(file_path: &Path) -> Option<Exif> {
+    let file = File::open(file_path).ok()?;
+    match Reader::new().read_from_container(&mut BufReader::new(file)) {
+        Ok(exif) => Some(exif),
+        Err(e) => {
+            debug!("图片没有可读的EXIF {}: {}", file_path.display(), e);
+            None
+        }
+    }
+}
+
+fn read_xmp(file_path: &Path) -> xmp::Xmp {
+    let mut data = Vec::new();
+    let read =
+        File::open(file_path).and_then(|file| file.take(XMP_SCAN_LIMIT).read_to_end(&mut data));
+    if let Err(e) = read {
+        debug!("读取XMP失败 {}: {}", file_path.display(), e);
+        return xmp::Xmp::default();
+    }
+
+    xmp::find_packet(&data).map(xmp::parse).unwrap_or_default()
+}
+
+fn ascii(exif: &Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(values) => values
+            .iter()
+            .map(|v| {
+                String::from_utf8_lossy(v)
+                    .trim_matches(['\0', ' '])
+                    .to_string()
+            })
+            .find(|v| !v.is_empty()),
+        _ => None,
+    }
+}
+
+fn uint(exif: &Exif, tag: Tag) -> Option<u32> {
+    exif.get_field(tag, In::PRIMARY)?.value.get_uint(0)
+}
+
+fn rational(exif: &Exif, tag: Tag, index: usize) -> Option<f64> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(values) => values
+            .get(index)
+            .filter(|r| r.denom != 0)
+            .map(|r| r.to_f64()),
+        Value::SRational(values) => values
+            .get(index)
+            .filter(|r| r.denom != 0)
+            .map(|r| r.to_f64()),
+        _ => None,
+    }
+}
+
+/// 度分秒转十进制度数，南纬/西经为负
+fn coordinate(exif: &Exif, tag: Tag, reference: Tag, negative: &str) -> Option<f64> {
+    let degrees = rational(exif, tag, 0)?;
+    let minutes = rational(exif, tag, 1).unwrap_or(0.0);
+    let seconds = rational(exif, tag, 2).unwrap_or(0.0);
+    let value = degrees + minutes / 60.0 + seconds / 3600.0;
+
+    if ascii(exif, reference).as_deref() == Some(negative) {
+        Some(-value)
+    } else {
+        Some(value)
+    }
+}
+
+fn gps(exif: &Exif) -> Option<serde_json::Value> {
+    let latitude = coordinate(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, "S")?;
+    let longitude = coordinate(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, "W")?;
+
+    let mut gps = serde_json::json!({
+        "latitude": latitude,
+        "longitude": longitude,
+    });
+    if let Some(altitude) = rational(exif, Tag::GPSAltitude, 0) {
+        // GPSAltitudeRef 为 1 表示海平面以下
+        let below_sea_level = uint(exif, Tag::GPSAltitudeRef) == Some(1);
+        gps["altitude"] = serde_json::json!(if below_sea_level { -altitude } else { altitude });
+    }
+
+    Some(gps)
+}
+
+/// 相机名称：型号通常已包含厂商名（`Canon EOS R5`），否则拼接（`FUJIFILM X-T4`）
+fn camera_name(make: Option<&str>, model: Option<&str>) -> Option<String> {
+    match (make, model) {
+        (Some(make), Some(model)) => {
+            let brand = make.split_whitespace().next().unwrap_or(make);
+            if model.to_lowercase().contains(&brand.to_lowercase()) {
+                Some(model.to_string())
+            } else {
+                Some(format!("{} {}", make, model))
+            }
+        }
+        (None, Some(model)) => Some(model.to_string()),
+        (Some(make), None) => Some(make.to_string()),
+        (None, None) => None,
+    }
+}
+
+/// 统一日期格式：EXIF `2023:05:01 10:20:30` 和 XMP `2023-05-01T10:20:30+08:00`
+/// 都转为 `2023-05-01T10:20:30`（只保留到已知的精度）
+fn normalize_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    let (day, time) = match date.split_once(['T', ' ']) {
+        Some((day, time)) => (day, Some(time)),
+        None => (date, None),
+    };
+
+    let parts: Vec<&str> = day.split([':', '-']).collect();
+    let year = parts.first().filter(|y| y.len() == 4)?;
+    if !year.chars().all(|c| c.is_ascii_digit()) || *year == "0000" {
+        return None;
+    }
+
+    let mut normalized = parts
+        .iter()
+        .take(3)
+        .filter(|p| p.chars().all(|c| c.is_ascii_digit()) && !p.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if let Some(time) = time {
+        let time: String = time
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == ':')
+            .collect();
+        if !time.is_empty() {
+            normalized.push('T');
+            normalized.push_str(&time);
+        }
+    }
+
+    Some(normalized)
+}
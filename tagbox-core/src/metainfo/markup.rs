@@ -0,0 +1,574 @@
+//! Markdown（YAML/TOML front matter）、HTML 和纯文本
+
+use super::registry::{supports_type, MetadataExtractor};
+use super::text::{decode_entities, html_to_text};
+use crate::errors::{Result, TagboxError};
+use crate::types::ImportMetadata;
+use crate::utils::parse_category_string;
+use serde_json::Value;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use tracing::{debug, warn};
+
+/// 文本文件最多读取的字节数
+const TEXT_LIMIT: u64 = 16 * 1024 * 1024;
+
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd", "mdx"];
+const HTML_EXTENSIONS: &[&str] = &["html", "htm", "xhtml"];
+
+/// 文本格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextFormat {
+    Markdown,
+    Html,
+    Plain,
+}
+
+impl TextFormat {
+    fn from_type(path: &Path, mime: Option<&str>) -> Option<Self> {
+        if supports_type(
+            path,
+            mime,
+            &["text/html", "application/xhtml+xml"],
+            HTML_EXTENSIONS,
+        ) {
+            return Some(TextFormat::Html);
+        }
+        if supports_type(path, mime, &["text/markdown"], MARKDOWN_EXTENSIONS)
+            || (mime == Some("text/plain") && has_extension(path, MARKDOWN_EXTENSIONS))
+        {
+            return Some(TextFormat::Markdown);
+        }
+        if supports_type(path, mime, &["text/plain"], &["txt", "text"]) {
+            return Some(TextFormat::Plain);
+        }
+        None
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            TextFormat::Markdown => "markdown",
+            TextFormat::Html => "html",
+            TextFormat::Plain => "plain",
+        }
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .map(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+        .unwrap_or(false)
+}
+
+/// 文本类文件提取器：Markdown front matter、HTML 头部信息和正文
+pub struct MarkupExtractor;
+
+impl MetadataExtractor for MarkupExtractor {
+    fn name(&self) -> &'static str {
+        "markup"
+    }
+
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        TextFormat::from_type(path, mime).is_some()
+    }
+
+    fn extract(&self, path: &Path, mime: Option<&str>) -> Result<ImportMetadata> {
+        let format = TextFormat::from_type(path, mime).ok_or_else(|| {
+            TagboxError::MetaInfoExtraction(format!("不是文本文件: {}", path.display()))
+        })?;
+
+        debug!("从文本文件提取元信息: {}", path.display());
+
+        let mut bytes = Vec::new();
+        File::open(path)
+            .map_err(TagboxError::Io)?
+            .take(TEXT_LIMIT)
+            .read_to_end(&mut bytes)
+            .map_err(TagboxError::Io)?;
+        let content = String::from_utf8_lossy(&bytes);
+        let content = content.trim_start_matches('\u{feff}');
+
+        let mut meta = match format {
+            TextFormat::Html => extract_html(content),
+            // 纯文本也可能带 front matter
+            TextFormat::Markdown | TextFormat::Plain => extract_markdown(path, content, format),
+        };
+
+        if let Some(text) = &meta.full_text {
+            let file_metadata = meta
+                .file_metadata
+                .get_or_insert_with(|| serde_json::json!({ "text": {} }));
+            file_metadata["text"]["format"] = serde_json::json!(format.name());
+            file_metadata["text"]["word_count"] =
+                serde_json::json!(text.split_whitespace().count());
+        }
+
+        Ok(meta)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Markdown / 纯文本
+// ---------------------------------------------------------------------------
+
+fn extract_markdown(path: &Path, content: &str, format: TextFormat) -> ImportMetadata {
+    let mut meta = ImportMetadata::default();
+
+    let (front_matter, body) = match split_front_matter(content) {
+        Some((kind, raw, body)) => match parse_front_matter(kind, raw) {
+            Ok(value) => {
+                meta.file_metadata = Some(serde_json::json!({
+                    "text": { "front_matter": kind.name() }
+                }));
+                (Some(value), body)
+            }
+            Err(e) => {
+                warn!("无法解析 front matter {}: {}", path.display(), e);
+                (None, body)
+            }
+        },
+        None => (None, content),
+    };
+
+    if let Some(front_matter) = front_matter {
+        apply_front_matter(&mut meta, &front_matter);
+        meta.type_metadata = Some(serde_json::json!({ "front_matter": front_matter }));
+    }
+
+    if meta.title.is_empty() && format == TextFormat::Markdown {
+        if let Some(heading) = first_heading(body) {
+            meta.title = heading;
+        }
+    }
+
+    let body = body.trim();
+    if !body.is_empty() {
+        meta.full_text = Some(body.to_string());
+    }
+
+    meta
+}
+
+/// front matter 格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrontMatter {
+    Yaml,
+    Toml,
+}
+
+impl FrontMatter {
+    fn name(self) -> &'static str {
+        match self {
+            FrontMatter::Yaml => "yaml",
+            FrontMatter::Toml => "toml",
+        }
+    }
+}
+
+/// 拆分 `---` (YAML) 或 `+++` (TOML) 包围的 front matter，返回 (格式, 原文, 正文)
+fn split_front_matter(content: &str) -> Option<(FrontMatter, &str, &str)> {
+    let first_line_end = content.find('\n').unwrap_or(content.len());
+    let (kind, closers): (FrontMatter, &[&str]) = match content[..first_line_end].trim_end() {
+        "---" => (FrontMatter::Yaml, &["---", "..."]),
+        "+++" => (FrontMatter::Toml, &["+++"]),
+        _ => return None,
+    };
+
+    let inner_start = (first_line_end + 1).min(content.len());
+    let mut line_start = inner_start;
+    while line_start < content.len() {
+        let line_end = content[line_start..]
+            .find('\n')
+            .map(|i| line_start + i)
+            .unwrap_or(content.len());
+        if closers.contains(&content[line_start..line_end].trim_end()) {
+            let body = content.get(line_end + 1..).unwrap_or("");
+            return Some((kind, &content[inner_start..line_start], body));
+        }
+        line_start = line_end + 1;
+    }
+
+    None
+}
+
+fn parse_front_matter(kind: FrontMatter, raw: &str) -> std::result::Result<Value, String> {
+    let value = match kind {
+        FrontMatter::Yaml => serde_yaml::from_str::<Value>(raw).map_err(|e| e.to_string())?,
+        FrontMatter::Toml => toml::from_str::<toml::Table>(raw)
+            .map(|table| toml_to_json(toml::Value::Table(table)))
+            .map_err(|e| e.to_string())?,
+    };
+
+    if value.is_object() {
+        Ok(value)
+    } else {
+        Err("front matter 不是键值表".to_string())
+    }
+}
+
+/// TOML 日期时间转为字符串，其余类型按结构转换
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => serde_json::json!(i),
+        toml::Value::Float(f) => serde_json::json!(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// 将 front matter 中的常用字段映射到导入元数据
+fn apply_front_matter(meta: &mut ImportMetadata, front_matter: &Value) {
+    let field = |keys: &[&str]| keys.iter().find_map(|key| front_matter.get(*key));
+    let text = |keys: &[&str]| {
+        field(keys)
+            .and_then(scalar_string)
+            .filter(|s| !s.is_empty())
+    };
+
+    if let Some(title) = text(&["title"]) {
+        meta.title = title;
+    }
+
+    if let Some(authors) = field(&["authors", "author"]) {
+        meta.authors = string_list(authors, false);
+    }
+
+    if let Some(tags) = field(&["tags", "keywords"]) {
+        meta.tags = string_list(tags, true);
+    }
+
+    meta.year = field(&["year"])
+        .and_then(scalar_string)
+        .or_else(|| text(&["date", "created", "published"]))
+        .and_then(|date| date.get(..4).and_then(|year| year.parse().ok()));
+
+    meta.summary = text(&["summary", "description", "abstract"]);
+    meta.publisher = text(&["publisher"]);
+    meta.source = text(&["source", "url", "link"]);
+
+    // `category: Tech/Rust` 或 `categories: [Tech, Rust]`
+    let category = field(&["category", "categories"]).and_then(|value| match value {
+        Value::Array(_) => Some(string_list(value, false).join("/")),
+        other => scalar_string(other),
+    });
+    if let Some(category) = category.filter(|c| !c.is_empty()) {
+        match parse_category_string(&category) {
+            Ok((category1, category2, category3)) => {
+                meta.category1 = category1;
+                meta.category2 = category2;
+                meta.category3 = category3;
+            }
+            Err(e) => warn!("忽略无效的分类 {}: {}", category, e),
+        }
+    }
+}
+
+fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// 列表或单个字符串；`split` 为真时按逗号（无逗号时按空白）拆分字符串，
+/// 列表元素也可以是带 `name` 字段的对象
+fn string_list(value: &Value, split: bool) -> Vec<String> {
+    let mut items: Vec<String> = match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                scalar_string(item).or_else(|| item.get("name").and_then(scalar_string))
+            })
+            .collect(),
+        Value::String(s) if split => {
+            if s.contains(',') {
+                s.split(',').map(|t| t.trim().to_string()).collect()
+            } else {
+                s.split_whitespace().map(str::to_string).collect()
+            }
+        }
+        other => scalar_string(other)
+            .or_else(|| other.get("name").and_then(scalar_string))
+            .into_iter()
+            .collect(),
+    };
+
+    let mut unique: Vec<String> = Vec::new();
+    for item in items.drain(..) {
+        if !item.is_empty() && !unique.contains(&item) {
+            unique.push(item);
+        }
+    }
+    unique
+}
+
+/// 正文中第一个一级标题（`# Title` 或下划 `===` 的写法），跳过代码块
+fn first_heading(body: &str) -> Option<String> {
+    let mut in_fence = false;
+    let mut previous: Option<&str> = None;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            previous = None;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        if let Some(heading) = trimmed.strip_prefix("# ") {
+            let heading = heading.trim().trim_end_matches('#').trim();
+            if !heading.is_empty() {
+                return Some(heading.to_string());
+            }
+        }
+        if !trimmed.is_empty() && trimmed.chars().all(|c| c == '=') {
+            if let Some(previous) = previous {
+                return Some(previous.to_string());
+            }
+        }
+
+        previous = Some(trimmed).filter(|line| !line.is_empty());
+    }
+
+    None
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+/// HTML 文档头部信息
+#[derive(Debug, Default)]
+struct HtmlHead {
+    title: Option<String>,
+    language: Option<String>,
+    canonical: Option<String>,
+    /// `<meta>` 的 name/property 及 content，键为小写
+    meta: Vec<(String, String)>,
+}
+
+impl HtmlHead {
+    fn first(&self, names: &[&str]) -> Option<String> {
+        names.iter().find_map(|name| {
+            self.meta
+                .iter()
+                .find(|(key, value)| key == name && !value.is_empty())
+                .map(|(_, value)| value.clone())
+        })
+    }
+
+    fn all(&self, names: &[&str]) -> Vec<String> {
+        let mut values: Vec<String> = Vec::new();
+        for (key, value) in &self.meta {
+            if names.contains(&key.as_str()) && !value.is_empty() && !values.contains(value) {
+                values.push(value.clone());
+            }
+        }
+        values
+    }
+}
+
+fn extract_html(content: &str) -> ImportMetadata {
+    let mut meta = ImportMetadata::default();
+    let head = parse_html_head(content);
+
+    if let Some(title) = head
+        .first(&["citation_title", "og:title", "dc.title"])
+        .or_else(|| head.title.clone())
+    {
+        meta.title = title;
+    }
+
+    // article:author 通常是作者主页链接
+    meta.authors = head
+        .all(&["citation_author", "author", "dc.creator", "article:author"])
+        .into_iter()
+        .filter(|author| !author.starts_with("http://") && !author.starts_with("https://"))
+        .collect();
+
+    let published = head.first(&[
+        "citation_publication_date",
+        "citation_date",
+        "article:published_time",
+        "dc.date",
+        "date",
+    ]);
+    meta.year = published
+        .as_deref()
+        .and_then(|date| date.get(..4))
+        .and_then(|year| year.parse().ok());
+
+    let mut tags = head.all(&["article:tag"]);
+    for keywords in head.all(&["keywords", "citation_keywords"]) {
+        for keyword in keywords.split([',', ';']) {
+            let keyword = keyword.trim().to_string();
+            if !keyword.is_empty() && !tags.contains(&keyword) {
+                tags.push(keyword);
+            }
+        }
+    }
+    meta.tags = tags;
+
+    meta.summary = head.first(&["description", "og:description", "dc.description"]);
+    meta.publisher = head.first(&["citation_publisher", "og:site_name", "dc.publisher"]);
+    meta.source = head.canonical.clone().or_else(|| head.first(&["og:url"]));
+
+    let mut web = serde_json::Map::new();
+    for (key, value) in [
+        ("url", &meta.source),
+        ("site_name", &head.first(&["og:site_name"])),
+        ("language", &head.language),
+        ("published", &published),
+    ] {
+        if let Some(value) = value {
+            web.insert(key.into(), serde_json::json!(value));
+        }
+    }
+    if !web.is_empty() {
+        meta.type_metadata = Some(serde_json::json!({ "web": web }));
+    }
+
+    let text = html_to_text(content);
+    if !text.is_empty() {
+        meta.full_text = Some(text);
+    }
+
+    meta
+}
+
+/// 读取 `<head>` 中的标题、语言、规范链接和 `<meta>`，遇到 `<body>` 停止
+fn parse_html_head(html: &str) -> HtmlHead {
+    let mut head = HtmlHead::default();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix("!--") {
+            rest = after.find("-->").map(|end| &after[end + 3..]).unwrap_or("");
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+
+        // 结束标签的 `/` 属于标签名
+        let name_end = tag
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| c.is_whitespace() || *c == '/')
+            .map(|(i, _)| i)
+            .unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        let attributes = || parse_attributes(&tag[name_end..]);
+
+        match name.as_str() {
+            "html" => {
+                head.language = attribute(&attributes(), &["lang", "xml:lang"]);
+            }
+            "title" if head.title.is_none() => {
+                let close = rest.to_ascii_lowercase().find("</title").unwrap_or(0);
+                let title = decode_entities(&rest[..close])
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if !title.is_empty() {
+                    head.title = Some(title);
+                }
+                rest = &rest[close..];
+            }
+            "meta" => {
+                let attributes = attributes();
+                let key = attribute(&attributes, &["name", "property", "itemprop"]);
+                let content = attribute(&attributes, &["content"]);
+                if let (Some(key), Some(content)) = (key, content) {
+                    head.meta.push((key.to_ascii_lowercase(), content));
+                }
+            }
+            "link" => {
+                let attributes = attributes();
+                let canonical = attribute(&attributes, &["rel"])
+                    .map(|rel| {
+                        rel.split_whitespace()
+                            .any(|r| r.eq_ignore_ascii_case("canonical"))
+                    })
+                    .unwrap_or(false);
+                if canonical {
+                    head.canonical = attribute(&attributes, &["href"]);
+                }
+            }
+            "body" | "/head" => break,
+            _ => {}
+        }
+    }
+
+    head
+}
+
+/// 解析标签属性，值已解码实体并去掉首尾空白；属性名为小写
+fn parse_attributes(source: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = source.trim_start();
+
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (raw, remaining) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &after[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], inner.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            value = decode_entities(raw).trim().to_string();
+            rest = remaining;
+        } else if name.is_empty() {
+            // 跳过 `/` 等无法作为属性名的字符
+            rest = &rest[1..];
+        }
+
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+        rest = rest.trim_start();
+    }
+
+    attributes
+}
+
+fn attribute(attributes: &[(String, String)], names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        attributes
+            .iter()
+            .find(|(key, value)| key == name && !value.is_empty())
+            .map(|(_, value)| value.clone())
+    })
+}
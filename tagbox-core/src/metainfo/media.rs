@@ -0,0 +1,1448 @@
+//! 音视频元数据：MP3 (ID3v2/ID3v1)、FLAC 和 Ogg (Vorbis comment)、
+//! MP4/M4A (ilst) 以及 Matroska/WebM (Tags)
+
+use super::registry::{supports_type, MetadataExtractor};
+use crate::errors::{Result, TagboxError};
+use crate::types::ImportMetadata;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use tracing::debug;
+
+/// 单个元数据块（ID3v2 标签、MP4 moov、Matroska Info/Tracks/Tags）最多读取的字节数
+const BLOCK_LIMIT: u64 = 64 * 1024 * 1024;
+
+/// 在文件开头查找 MP3 帧或 Ogg 头部时最多读取的字节数
+const SCAN_LIMIT: u64 = 4 * 1024 * 1024;
+
+/// 在 Ogg 文件末尾查找最后一页时读取的字节数
+const OGG_TAIL: u64 = 64 * 1024;
+
+/// 音视频容器格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MediaFormat {
+    Mp3,
+    Flac,
+    Ogg,
+    Mp4,
+    Matroska,
+}
+
+impl MediaFormat {
+    fn from_type(path: &Path, mime: Option<&str>) -> Option<Self> {
+        const FORMATS: &[(MediaFormat, &[&str], &[&str])] = &[
+            (MediaFormat::Mp3, &["audio/mpeg"], &["mp3"]),
+            (MediaFormat::Flac, &["audio/flac"], &["flac"]),
+            (
+                MediaFormat::Ogg,
+                &["audio/ogg", "video/ogg"],
+                &["ogg", "oga", "ogv", "opus"],
+            ),
+            (
+                MediaFormat::Mp4,
+                &["audio/mp4", "video/mp4", "video/quicktime"],
+                &["m4a", "m4b", "mp4", "m4v", "mov"],
+            ),
+            (
+                MediaFormat::Matroska,
+                &["video/x-matroska", "video/webm"],
+                &["mkv", "mka", "webm"],
+            ),
+        ];
+
+        FORMATS
+            .iter()
+            .find(|(_, mimes, extensions)| supports_type(path, mime, mimes, extensions))
+            .map(|(format, _, _)| *format)
+    }
+}
+
+/// 音视频提取器
+pub struct MediaExtractor;
+
+impl MetadataExtractor for MediaExtractor {
+    fn name(&self) -> &'static str {
+        "media"
+    }
+
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        MediaFormat::from_type(path, mime).is_some()
+    }
+
+    fn extract(&self, path: &Path, mime: Option<&str>) -> Result<ImportMetadata> {
+        let format = MediaFormat::from_type(path, mime).ok_or_else(|| {
+            TagboxError::MetaInfoExtraction(format!("不是音视频文件: {}", path.display()))
+        })?;
+
+        debug!("从音视频文件提取元信息: {}", path.display());
+
+        let mut file = File::open(path).map_err(TagboxError::Io)?;
+        let len = file.metadata().map_err(TagboxError::Io)?.len();
+        let info = match format {
+            MediaFormat::Mp3 => probe_mp3(&mut file, len)?,
+            MediaFormat::Flac => probe_flac(&mut file, len)?,
+            MediaFormat::Ogg => probe_ogg(&mut file, len)?,
+            MediaFormat::Mp4 => probe_mp4(&mut file, len)?,
+            MediaFormat::Matroska => probe_matroska(&mut file, len)?,
+        };
+
+        Ok(info.into_metadata())
+    }
+}
+
+/// 音频流参数
+#[derive(Debug, Clone, Serialize)]
+struct AudioStream {
+    codec: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sample_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channels: Option<u32>,
+}
+
+/// 视频流参数
+#[derive(Debug, Clone, Serialize)]
+struct VideoStream {
+    codec: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+}
+
+/// 各格式标签统一后的字段
+#[derive(Debug, Default)]
+struct MediaTags {
+    title: Option<String>,
+    artists: Vec<String>,
+    album_artist: Option<String>,
+    album: Option<String>,
+    date: Option<String>,
+    track: Option<u32>,
+    track_total: Option<u32>,
+    disc: Option<u32>,
+    genre: Option<String>,
+    comment: Option<String>,
+}
+
+impl MediaTags {
+    /// 按统一字段名写入标签值，已有值时保留先出现的（艺术家可以有多个）
+    fn set(&mut self, field: &str, value: &str) {
+        let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        if value.is_empty() {
+            return;
+        }
+
+        let slot = match field {
+            "artist" => {
+                if !self.artists.iter().any(|a| a == value) {
+                    self.artists.push(value.to_string());
+                }
+                return;
+            }
+            "track" | "disc" => {
+                // `3/12` 形式同时给出总数
+                let (number, total) = match value.split_once('/') {
+                    Some((number, total)) => (number, Some(total)),
+                    None => (value, None),
+                };
+                let number_slot = if field == "track" {
+                    &mut self.track
+                } else {
+                    &mut self.disc
+                };
+                if number_slot.is_none() {
+                    *number_slot = number.trim().parse().ok();
+                }
+                if field == "track" && self.track_total.is_none() {
+                    self.track_total = total.and_then(|t| t.trim().parse().ok());
+                }
+                return;
+            }
+            "track_total" => {
+                if self.track_total.is_none() {
+                    self.track_total = value.parse().ok();
+                }
+                return;
+            }
+            "title" => &mut self.title,
+            "album_artist" => &mut self.album_artist,
+            "album" => &mut self.album,
+            "date" => &mut self.date,
+            "genre" => &mut self.genre,
+            "comment" => &mut self.comment,
+            _ => return,
+        };
+
+        if slot.is_none() {
+            *slot = Some(value.to_string());
+        }
+    }
+
+    fn year(&self) -> Option<i32> {
+        self.date
+            .as_deref()
+            .and_then(|date| date.get(..4))
+            .and_then(|year| year.parse().ok())
+    }
+}
+
+/// 从容器中读取的全部信息
+#[derive(Debug, Default)]
+struct MediaInfo {
+    container: &'static str,
+    tags: MediaTags,
+    /// 时长（秒）
+    duration: Option<f64>,
+    /// 平均码率（kbit/s）
+    bitrate: Option<u32>,
+    audio: Option<AudioStream>,
+    video: Option<VideoStream>,
+}
+
+impl MediaInfo {
+    fn new(container: &'static str) -> Self {
+        Self {
+            container,
+            ..Default::default()
+        }
+    }
+
+    /// 没有标明码率时按文件大小和时长估算
+    fn estimate_bitrate(&mut self, bytes: u64) {
+        if self.bitrate.is_none() {
+            if let Some(duration) = self.duration.filter(|d| *d > 0.0) {
+                let bitrate = (bytes as f64 * 8.0 / duration / 1000.0).round() as u32;
+                self.bitrate = (bitrate > 0).then_some(bitrate);
+            }
+        }
+    }
+
+    /// 技术参数写入 `file_metadata.media`，标签写入 `type_metadata.media`
+    fn into_metadata(self) -> ImportMetadata {
+        let mut meta = ImportMetadata::default();
+        let tags = self.tags;
+
+        if let Some(title) = &tags.title {
+            meta.title = title.clone();
+        }
+        meta.authors = if tags.artists.is_empty() {
+            tags.album_artist.iter().cloned().collect()
+        } else {
+            tags.artists.clone()
+        };
+        meta.year = tags.year();
+        meta.summary = tags.comment.clone();
+
+        let mut media = serde_json::Map::new();
+        media.insert("container".into(), serde_json::json!(self.container));
+        if let Some(duration) = self.duration {
+            let duration = (duration * 1000.0).round() / 1000.0;
+            media.insert("duration".into(), serde_json::json!(duration));
+            meta.additional_info
+                .insert("duration".into(), duration.to_string());
+        }
+        if let Some(bitrate) = self.bitrate {
+            media.insert("bitrate".into(), serde_json::json!(bitrate));
+        }
+        let codec = self
+            .video
+            .as_ref()
+            .map(|video| &video.codec)
+            .or_else(|| self.audio.as_ref().map(|audio| &audio.codec));
+        if let Some(codec) = codec {
+            media.insert("codec".into(), serde_json::json!(codec));
+        }
+        if let Some(video) = &self.video {
+            if let (Some(width), Some(height)) = (video.width, video.height) {
+                media.insert(
+                    "resolution".into(),
+                    serde_json::json!(format!("{}x{}", width, height)),
+                );
+            }
+            media.insert("video".into(), serde_json::json!(video));
+        }
+        if let Some(audio) = &self.audio {
+            media.insert("audio".into(), serde_json::json!(audio));
+        }
+        meta.file_metadata = Some(serde_json::json!({ "media": media }));
+
+        let mut details = serde_json::Map::new();
+        if !tags.artists.is_empty() {
+            details.insert("artists".into(), serde_json::json!(tags.artists));
+        }
+        for (key, value) in [
+            ("album", &tags.album),
+            ("album_artist", &tags.album_artist),
+            ("date", &tags.date),
+            ("genre", &tags.genre),
+        ] {
+            if let Some(value) = value {
+                details.insert(key.into(), serde_json::json!(value));
+            }
+        }
+        for (key, value) in [
+            ("track", tags.track),
+            ("track_total", tags.track_total),
+            ("disc", tags.disc),
+        ] {
+            if let Some(value) = value {
+                details.insert(key.into(), serde_json::json!(value));
+            }
+        }
+        if !details.is_empty() {
+            meta.type_metadata = Some(serde_json::json!({ "media": details }));
+        }
+
+        meta
+    }
+}
+
+/// Vorbis comment / Matroska 标签名对应的统一字段
+fn tag_field(name: &str) -> Option<&'static str> {
+    Some(match name.to_ascii_uppercase().as_str() {
+        "TITLE" => "title",
+        "ARTIST" | "PERFORMER" => "artist",
+        "ALBUMARTIST" | "ALBUM ARTIST" | "ALBUM_ARTIST" => "album_artist",
+        "ALBUM" => "album",
+        "DATE" | "YEAR" | "DATE_RELEASED" | "DATE_RECORDED" => "date",
+        "TRACKNUMBER" | "TRACK" | "PART_NUMBER" => "track",
+        "TRACKTOTAL" | "TOTALTRACKS" | "TOTAL_PARTS" => "track_total",
+        "DISCNUMBER" => "disc",
+        "GENRE" => "genre",
+        "COMMENT" | "DESCRIPTION" => "comment",
+        _ => return None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// MP3
+// ---------------------------------------------------------------------------
+
+fn probe_mp3(file: &mut File, len: u64) -> Result<MediaInfo> {
+    let mut info = MediaInfo::new("mp3");
+    let mut audio_start = 0;
+    let mut audio_end = len;
+    let mut tag_duration = None;
+
+    let head = read_at(file, 0, 10)?;
+    if head.len() == 10 && head.starts_with(b"ID3") {
+        let version = head[3];
+        let flags = head[5];
+        let size = syncsafe(&head[6..10]) as u64;
+        let footer = if version == 4 && flags & 0x10 != 0 {
+            10
+        } else {
+            0
+        };
+        let body = read_at(file, 10, size.min(BLOCK_LIMIT))?;
+        tag_duration = read_id3v2(version, flags, &body, &mut info.tags);
+        audio_start = 10 + size + footer;
+    }
+
+    if len >= 128 {
+        let tail = read_at(file, len - 128, 128)?;
+        if tail.starts_with(b"TAG") {
+            read_id3v1(&tail, &mut info.tags);
+            audio_end -= 128;
+        }
+    }
+
+    let scan = read_at(file, audio_start, SCAN_LIMIT)?;
+    let frame = (0..scan.len()).find_map(|offset| {
+        let frame = MpegFrame::parse(&scan[offset..])?;
+        // 再校验下一帧，避免把数据中的偶然字节当作帧头
+        match scan.get(offset + frame.length()..) {
+            Some(next) if next.len() >= 4 => MpegFrame::parse(next).map(|_| (offset, frame)),
+            _ => Some((offset, frame)),
+        }
+    });
+
+    if let Some((offset, frame)) = frame {
+        let audio_bytes = audio_end.saturating_sub(audio_start + offset as u64);
+        info.audio = Some(AudioStream {
+            codec: frame.codec().to_string(),
+            sample_rate: Some(frame.sample_rate),
+            channels: Some(frame.channels),
+        });
+        info.duration = match frame.vbr_frames(&scan[offset..]) {
+            Some(frames) => Some(frames as f64 * frame.samples() as f64 / frame.sample_rate as f64),
+            None => {
+                info.bitrate = Some(frame.bitrate);
+                Some(audio_bytes as f64 * 8.0 / (frame.bitrate as f64 * 1000.0))
+            }
+        };
+        info.estimate_bitrate(audio_bytes);
+    }
+
+    if info.duration.is_none() {
+        info.duration = tag_duration;
+    }
+
+    Ok(info)
+}
+
+/// 解析 ID3v2 标签帧，返回 TLEN 给出的时长
+fn read_id3v2(version: u8, flags: u8, data: &[u8], tags: &mut MediaTags) -> Option<f64> {
+    let data: Cow<[u8]> = if flags & 0x80 != 0 && version < 4 {
+        Cow::Owned(resynchronise(data))
+    } else {
+        Cow::Borrowed(data)
+    };
+
+    let mut pos = 0;
+    if flags & 0x40 != 0 {
+        pos = match version {
+            3 => 4 + be_u32(&data, 0)? as usize,
+            4 => syncsafe(data.get(..4)?) as usize,
+            _ => 0,
+        };
+    }
+
+    let (id_len, header_len) = if version == 2 { (3, 6) } else { (4, 10) };
+    let mut duration = None;
+
+    while pos + header_len <= data.len() {
+        let header = &data[pos..pos + header_len];
+        if header[0] == 0 {
+            // 填充区
+            break;
+        }
+        let size = match version {
+            2 => u32::from_be_bytes([0, header[3], header[4], header[5]]) as usize,
+            3 => be_u32(header, 4)? as usize,
+            _ => syncsafe(&header[4..8]) as usize,
+        };
+        let format_flags = if version == 2 { 0 } else { header[9] };
+        let start = pos + header_len;
+        let Some(mut frame) = data.get(start..start + size) else {
+            break;
+        };
+        pos = start + size;
+
+        // 压缩或加密的帧无法读取
+        if (version == 3 && format_flags & 0xC0 != 0) || (version == 4 && format_flags & 0x0C != 0)
+        {
+            continue;
+        }
+
+        // v2.4 帧级别的数据长度指示和反同步
+        let resynced;
+        if version == 4 {
+            if format_flags & 0x01 != 0 {
+                frame = frame.get(4..).unwrap_or_default();
+            }
+            if format_flags & 0x02 != 0 {
+                resynced = resynchronise(frame);
+                frame = resynced.as_slice();
+            }
+        }
+
+        let id = std::str::from_utf8(&header[..id_len]).unwrap_or_default();
+        match id {
+            "COMM" | "COM" => {
+                if let Some(text) = id3_comment(frame) {
+                    tags.set("comment", &text);
+                }
+            }
+            "TLEN" | "TLE" => {
+                duration = id3_text(frame)
+                    .first()
+                    .and_then(|ms| ms.trim().parse::<f64>().ok())
+                    .map(|ms| ms / 1000.0);
+            }
+            _ => {
+                let Some(field) = id3_field(id) else {
+                    continue;
+                };
+                for value in id3_text(frame) {
+                    let value = if field == "genre" {
+                        id3_genre(&value)
+                    } else {
+                        value.as_str()
+                    };
+                    tags.set(field, value);
+                }
+            }
+        }
+    }
+
+    duration
+}
+
+/// ID3v2 文本帧对应的统一字段（同时支持 v2.2 的三字符帧名）
+fn id3_field(id: &str) -> Option<&'static str> {
+    Some(match id {
+        "TIT2" | "TT2" => "title",
+        "TPE1" | "TP1" => "artist",
+        "TPE2" | "TP2" => "album_artist",
+        "TALB" | "TAL" => "album",
+        "TDRC" | "TYER" | "TYE" | "TDOR" | "TORY" | "TOR" => "date",
+        "TRCK" | "TRK" => "track",
+        "TPOS" | "TPA" => "disc",
+        "TCON" | "TCO" => "genre",
+        _ => return None,
+    })
+}
+
+/// `(17)Rock` 形式的旧式流派只保留文字部分
+fn id3_genre(value: &str) -> &str {
+    value
+        .strip_prefix('(')
+        .and_then(|rest| rest.split_once(')'))
+        .filter(|(number, text)| number.chars().all(|c| c.is_ascii_digit()) && !text.is_empty())
+        .map(|(_, text)| text)
+        .unwrap_or(value)
+}
+
+/// 文本帧的全部取值（v2.4 用 NUL 分隔多个值）
+fn id3_text(frame: &[u8]) -> Vec<String> {
+    let Some((&encoding, text)) = frame.split_first() else {
+        return Vec::new();
+    };
+    decode_id3(text, encoding)
+        .split('\0')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// COMM 帧：编码、语言、描述、正文；跳过 iTunes 写入的内部注释
+fn id3_comment(frame: &[u8]) -> Option<String> {
+    let encoding = *frame.first()?;
+    let rest = frame.get(4..)?;
+    let wide = encoding == 1 || encoding == 2;
+    let terminator = if wide {
+        rest.chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .map(|i| i * 2)
+    } else {
+        rest.iter().position(|b| *b == 0)
+    }?;
+    let description = decode_id3(&rest[..terminator], encoding);
+    if description.starts_with("iTun") {
+        return None;
+    }
+    let text = rest.get(terminator + if wide { 2 } else { 1 }..)?;
+    Some(decode_id3(text, encoding))
+}
+
+fn decode_id3(data: &[u8], encoding: u8) -> String {
+    match encoding {
+        0 => data.iter().map(|&b| b as char).collect(),
+        1 => match data {
+            [0xFE, 0xFF, ..] => decode_utf16(&data[2..], true),
+            [0xFF, 0xFE, ..] => decode_utf16(&data[2..], false),
+            _ => decode_utf16(data, false),
+        },
+        2 => decode_utf16(data, true),
+        _ => String::from_utf8_lossy(data).into_owned(),
+    }
+}
+
+fn decode_utf16(data: &[u8], big_endian: bool) -> String {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| {
+            if big_endian {
+                u16::from_be_bytes([c[0], c[1]])
+            } else {
+                u16::from_le_bytes([c[0], c[1]])
+            }
+        })
+        .collect();
+    String::from_utf16_lossy(&units).replace('\u{feff}', "")
+}
+
+/// 去掉反同步插入的 `0xFF 0x00` 中的 0x00
+fn resynchronise(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = 0;
+    for &byte in data {
+        if previous != 0xFF || byte != 0 {
+            out.push(byte);
+        }
+        previous = byte;
+    }
+    out
+}
+
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .take(4)
+        .fold(0, |acc, &b| (acc << 7) | (b & 0x7F) as u32)
+}
+
+/// 文件末尾 128 字节的 ID3v1 标签
+fn read_id3v1(tag: &[u8], tags: &mut MediaTags) {
+    let field = |range: std::ops::Range<usize>| {
+        let bytes = &tag[range];
+        let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+        decode_id3(&bytes[..end], 0)
+    };
+
+    tags.set("title", &field(3..33));
+    tags.set("artist", &field(33..63));
+    tags.set("album", &field(63..93));
+    tags.set("date", &field(93..97));
+    tags.set("comment", &field(97..125));
+    // ID3v1.1：注释第 29 字节为 0 时第 30 字节是音轨号
+    if tag[125] == 0 && tag[126] != 0 {
+        tags.set("track", &tag[126].to_string());
+    }
+}
+
+/// MPEG 音频帧头
+#[derive(Debug, Clone, Copy)]
+struct MpegFrame {
+    /// 1 = MPEG-1，2 = MPEG-2，25 = MPEG-2.5
+    version: u8,
+    layer: u8,
+    /// kbit/s
+    bitrate: u32,
+    sample_rate: u32,
+    channels: u32,
+    padding: bool,
+}
+
+impl MpegFrame {
+    fn parse(header: &[u8]) -> Option<Self> {
+        const V1_L1: [u32; 15] = [
+            0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+        ];
+        const V1_L2: [u32; 15] = [
+            0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+        ];
+        const V1_L3: [u32; 15] = [
+            0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+        ];
+        const V2_L1: [u32; 15] = [
+            0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+        ];
+        const V2_L23: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+        let header = header.get(..4)?;
+        if header[0] != 0xFF || header[1] & 0xE0 != 0xE0 {
+            return None;
+        }
+
+        let version = match (header[1] >> 3) & 0x03 {
+            0 => 25,
+            2 => 2,
+            3 => 1,
+            _ => return None,
+        };
+        let layer = match (header[1] >> 1) & 0x03 {
+            1 => 3,
+            2 => 2,
+            3 => 1,
+            _ => return None,
+        };
+        let bitrate_index = (header[2] >> 4) as usize;
+        let rate_index = ((header[2] >> 2) & 0x03) as usize;
+        if bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+            return None;
+        }
+
+        let table = match (version, layer) {
+            (1, 1) => &V1_L1,
+            (1, 2) => &V1_L2,
+            (1, _) => &V1_L3,
+            (_, 1) => &V2_L1,
+            _ => &V2_L23,
+        };
+        let base_rate = [44100, 48000, 32000][rate_index];
+
+        Some(Self {
+            version,
+            layer,
+            bitrate: table[bitrate_index],
+            sample_rate: match version {
+                1 => base_rate,
+                2 => base_rate / 2,
+                _ => base_rate / 4,
+            },
+            channels: if header[3] >> 6 == 3 { 1 } else { 2 },
+            padding: header[2] & 0x02 != 0,
+        })
+    }
+
+    fn codec(&self) -> &'static str {
+        match self.layer {
+            1 => "mp1",
+            2 => "mp2",
+            _ => "mp3",
+        }
+    }
+
+    fn samples(&self) -> u32 {
+        match (self.layer, self.version) {
+            (1, _) => 384,
+            (3, 2) | (3, 25) => 576,
+            _ => 1152,
+        }
+    }
+
+    fn length(&self) -> usize {
+        let slot = if self.layer == 1 { 4 } else { 1 };
+        (self.samples() / 8 * self.bitrate * 1000 / self.sample_rate) as usize
+            + if self.padding { slot } else { 0 }
+    }
+
+    /// VBR 文件第一帧中 Xing/Info 或 VBRI 头给出的总帧数
+    fn vbr_frames(&self, frame: &[u8]) -> Option<u32> {
+        let side_info = match (self.version, self.channels) {
+            (1, 1) => 17,
+            (1, _) => 32,
+            (_, 1) => 9,
+            _ => 17,
+        };
+        let xing = 4 + side_info;
+        match frame.get(xing..xing + 4) {
+            Some(b"Xing") | Some(b"Info") => {
+                let flags = be_u32(frame, xing + 4)?;
+                return if flags & 0x01 != 0 {
+                    be_u32(frame, xing + 8)
+                } else {
+                    None
+                };
+            }
+            _ => {}
+        }
+        if frame.get(36..40) == Some(&b"VBRI"[..]) {
+            return be_u32(frame, 36 + 14);
+        }
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FLAC / Ogg
+// ---------------------------------------------------------------------------
+
+fn probe_flac(file: &mut File, len: u64) -> Result<MediaInfo> {
+    let mut info = MediaInfo::new("flac");
+    let mut pos = 4;
+
+    loop {
+        let header = read_at(file, pos, 4)?;
+        if header.len() < 4 {
+            break;
+        }
+        let last = header[0] & 0x80 != 0;
+        let size = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+        pos += 4;
+
+        match header[0] & 0x7F {
+            0 => {
+                let block = read_at(file, pos, size)?;
+                if let Some((audio, duration)) = flac_stream_info(&block) {
+                    info.audio = Some(audio);
+                    info.duration = duration;
+                }
+            }
+            4 => {
+                let block = read_at(file, pos, size.min(BLOCK_LIMIT))?;
+                read_vorbis_comment(&block, &mut info.tags);
+            }
+            _ => {}
+        }
+
+        pos += size;
+        if last || pos >= len {
+            break;
+        }
+    }
+
+    info.estimate_bitrate(len.saturating_sub(pos));
+    Ok(info)
+}
+
+/// FLAC STREAMINFO 块：采样率、声道数和总采样数
+fn flac_stream_info(block: &[u8]) -> Option<(AudioStream, Option<f64>)> {
+    let b = block.get(10..18)?;
+    let sample_rate = ((b[0] as u32) << 12) | ((b[1] as u32) << 4) | ((b[2] as u32) >> 4);
+    let channels = ((b[2] >> 1) & 0x07) as u32 + 1;
+    let samples = (((b[3] & 0x0F) as u64) << 32) | be_u32(b, 4)? as u64;
+
+    let duration = (sample_rate > 0 && samples > 0).then(|| samples as f64 / sample_rate as f64);
+    let audio = AudioStream {
+        codec: "flac".to_string(),
+        sample_rate: Some(sample_rate),
+        channels: Some(channels),
+    };
+    Some((audio, duration))
+}
+
+/// Vorbis comment：供应商字符串之后是若干 `KEY=value`
+fn read_vorbis_comment(data: &[u8], tags: &mut MediaTags) {
+    let Some(vendor_len) = le_u32(data, 0) else {
+        return;
+    };
+    let mut pos = 4 + vendor_len as usize;
+    let Some(count) = le_u32(data, pos) else {
+        return;
+    };
+    pos += 4;
+
+    for _ in 0..count {
+        let Some(len) = le_u32(data, pos) else {
+            break;
+        };
+        let Some(entry) = data.get(pos + 4..pos + 4 + len as usize) else {
+            break;
+        };
+        pos += 4 + len as usize;
+
+        let entry = String::from_utf8_lossy(entry);
+        if let Some((key, value)) = entry.split_once('=') {
+            if let Some(field) = tag_field(key) {
+                tags.set(field, value);
+            }
+        }
+    }
+}
+
+/// Ogg 页
+struct OggPage<'a> {
+    header_type: u8,
+    granule: i64,
+    serial: u32,
+    segments: &'a [u8],
+    body: &'a [u8],
+    len: usize,
+}
+
+impl<'a> OggPage<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        if !data.starts_with(b"OggS") || data.len() < 27 {
+            return None;
+        }
+        let count = data[26] as usize;
+        let segments = data.get(27..27 + count)?;
+        let body_len: usize = segments.iter().map(|&s| s as usize).sum();
+        let body = data.get(27 + count..27 + count + body_len)?;
+
+        Some(Self {
+            header_type: data[5],
+            granule: i64::from_le_bytes(data[6..14].try_into().ok()?),
+            serial: le_u32(data, 14)?,
+            segments,
+            body,
+            len: 27 + count + body_len,
+        })
+    }
+}
+
+/// Ogg 逻辑流的头部包
+#[derive(Default)]
+struct OggStream {
+    serial: u32,
+    packets: Vec<Vec<u8>>,
+    partial: Vec<u8>,
+}
+
+fn probe_ogg(file: &mut File, len: u64) -> Result<MediaInfo> {
+    let mut info = MediaInfo::new("ogg");
+    let data = read_at(file, 0, SCAN_LIMIT)?;
+
+    // 每个逻辑流只需要前两个包：标识头和注释头
+    let mut streams: Vec<OggStream> = Vec::new();
+    let mut pos = 0;
+    while let Some(page) = OggPage::parse(&data[pos..]) {
+        pos += page.len;
+        if page.header_type & 0x02 != 0 {
+            streams.push(OggStream {
+                serial: page.serial,
+                ..Default::default()
+            });
+        } else if streams.iter().all(|s| s.packets.len() >= 2) {
+            break;
+        }
+
+        let Some(stream) = streams.iter_mut().find(|s| s.serial == page.serial) else {
+            continue;
+        };
+        let mut offset = 0;
+        for &lacing in page.segments {
+            let end = offset + lacing as usize;
+            stream.partial.extend_from_slice(&page.body[offset..end]);
+            offset = end;
+            if lacing < 255 {
+                stream.packets.push(std::mem::take(&mut stream.partial));
+            }
+        }
+    }
+
+    // (流序号, granule 采样率, 预跳过采样数)
+    let mut audio_stream = None;
+    for stream in &streams {
+        let Some(id) = stream.packets.first() else {
+            continue;
+        };
+        let comment = stream.packets.get(1).map(Vec::as_slice).unwrap_or_default();
+
+        if info.audio.is_none() {
+            if id.starts_with(b"\x01vorbis") {
+                let sample_rate = le_u32(id, 12).unwrap_or_default();
+                info.audio = Some(AudioStream {
+                    codec: "vorbis".to_string(),
+                    sample_rate: Some(sample_rate),
+                    channels: id.get(11).map(|c| *c as u32),
+                });
+                audio_stream = Some((stream.serial, sample_rate, 0));
+                if let Some(comment) = comment.strip_prefix(b"\x03vorbis") {
+                    read_vorbis_comment(comment, &mut info.tags);
+                }
+                continue;
+            }
+            if id.starts_with(b"OpusHead") {
+                // Opus 的 granule 固定以 48 kHz 计数
+                info.audio = Some(AudioStream {
+                    codec: "opus".to_string(),
+                    sample_rate: le_u32(id, 12).filter(|rate| *rate > 0),
+                    channels: id.get(9).map(|c| *c as u32),
+                });
+                let pre_skip = le_u16(id, 10).unwrap_or_default();
+                audio_stream = Some((stream.serial, 48_000, pre_skip as u32));
+                if let Some(comment) = comment.strip_prefix(b"OpusTags") {
+                    read_vorbis_comment(comment, &mut info.tags);
+                }
+                continue;
+            }
+            if id.starts_with(b"\x7FFLAC") {
+                if let Some((audio, _)) = id.get(17..).and_then(flac_stream_info) {
+                    audio_stream = audio.sample_rate.map(|rate| (stream.serial, rate, 0));
+                    info.audio = Some(audio);
+                }
+                if let Some(comment) = comment.get(4..) {
+                    read_vorbis_comment(comment, &mut info.tags);
+                }
+                continue;
+            }
+        }
+
+        if info.video.is_none() && id.starts_with(b"\x80theora") {
+            let pixels = |at: usize| {
+                id.get(at..at + 3)
+                    .map(|b| u32::from_be_bytes([0, b[0], b[1], b[2]]))
+            };
+            info.video = Some(VideoStream {
+                codec: "theora".to_string(),
+                width: pixels(14),
+                height: pixels(17),
+            });
+        }
+    }
+
+    if let Some((serial, rate, pre_skip)) = audio_stream.filter(|(_, rate, _)| *rate > 0) {
+        if let Some(granule) = ogg_last_granule(file, len, serial)? {
+            info.duration = Some((granule - pre_skip as i64).max(0) as f64 / rate as f64);
+        }
+    }
+    info.estimate_bitrate(len);
+
+    Ok(info)
+}
+
+/// 逻辑流最后一页的 granule position，即总采样数
+fn ogg_last_granule(file: &mut File, len: u64, serial: u32) -> Result<Option<i64>> {
+    let start = len.saturating_sub(OGG_TAIL);
+    let tail = read_at(file, start, OGG_TAIL)?;
+
+    let mut granule = None;
+    let mut pos = 0;
+    while let Some(found) = find(&tail[pos..], b"OggS") {
+        let at = pos + found;
+        if let Some(page) = OggPage::parse(&tail[at..]) {
+            if page.serial == serial && page.granule >= 0 {
+                granule = Some(page.granule);
+            }
+        }
+        pos = at + 4;
+    }
+
+    Ok(granule)
+}
+
+// ---------------------------------------------------------------------------
+// MP4 / QuickTime
+// ---------------------------------------------------------------------------
+
+fn probe_mp4(file: &mut File, len: u64) -> Result<MediaInfo> {
+    let mut info = MediaInfo::new("mp4");
+    let mut pos = 0;
+
+    while pos + 8 <= len {
+        let header = read_at(file, pos, 16)?;
+        let Some((kind, size, header_len)) = atom_header(&header, len - pos) else {
+            break;
+        };
+
+        match kind {
+            b"ftyp" => {
+                info.container = match header.get(header_len..header_len + 4) {
+                    Some(b"M4A ") | Some(b"M4B ") | Some(b"M4P ") => "m4a",
+                    Some(b"qt  ") => "mov",
+                    _ => "mp4",
+                };
+            }
+            b"moov" => {
+                let moov = read_at(
+                    file,
+                    pos + header_len as u64,
+                    (size - header_len as u64).min(BLOCK_LIMIT),
+                )?;
+                read_moov(&moov, &mut info);
+                break;
+            }
+            _ => {}
+        }
+
+        pos += size;
+    }
+
+    info.estimate_bitrate(len);
+    Ok(info)
+}
+
+/// atom 头：类型、总长度和头部长度（支持 64 位长度和延伸到文件末尾的 atom）
+fn atom_header(data: &[u8], remaining: u64) -> Option<(&[u8], u64, usize)> {
+    let kind = data.get(4..8)?;
+    let (size, header_len) = match be_u32(data, 0)? {
+        0 => (remaining, 8),
+        1 => (be_u64(data, 8)?, 16),
+        size => (size as u64, 8),
+    };
+    (size >= header_len as u64).then_some((kind, size, header_len))
+}
+
+/// 遍历内存中的子 atom，返回 (类型, 内容)
+fn atoms(mut data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    std::iter::from_fn(move || {
+        let (kind, size, header_len) = atom_header(data, data.len() as u64)?;
+        let size = usize::try_from(size)
+            .ok()
+            .filter(|size| *size <= data.len())?;
+        let body = &data[header_len..size];
+        data = &data[size..];
+        Some((kind, body))
+    })
+}
+
+fn child_atom<'a>(data: &'a [u8], kind: &[u8]) -> Option<&'a [u8]> {
+    atoms(data).find(|(k, _)| *k == kind).map(|(_, body)| body)
+}
+
+fn read_moov(moov: &[u8], info: &mut MediaInfo) {
+    if let Some(mvhd) = child_atom(moov, b"mvhd") {
+        let (timescale, duration) = if mvhd.first() == Some(&1) {
+            (be_u32(mvhd, 20), be_u64(mvhd, 24))
+        } else {
+            (be_u32(mvhd, 12), be_u32(mvhd, 16).map(u64::from))
+        };
+        if let (Some(timescale), Some(duration)) = (timescale.filter(|t| *t > 0), duration) {
+            info.duration = Some(duration as f64 / timescale as f64);
+        }
+    }
+
+    for (kind, trak) in atoms(moov) {
+        if kind != b"trak" {
+            continue;
+        }
+        let Some(mdia) = child_atom(trak, b"mdia") else {
+            continue;
+        };
+        let handler = child_atom(mdia, b"hdlr").and_then(|hdlr| hdlr.get(8..12));
+        let Some(entry) = child_atom(mdia, b"minf")
+            .and_then(|minf| child_atom(minf, b"stbl"))
+            .and_then(|stbl| child_atom(stbl, b"stsd"))
+            .and_then(|stsd| stsd.get(8..))
+        else {
+            continue;
+        };
+        let Some(format) = entry.get(4..8) else {
+            continue;
+        };
+
+        match handler {
+            Some(b"vide") if info.video.is_none() => {
+                info.video = Some(VideoStream {
+                    codec: mp4_codec(format),
+                    width: be_u16(entry, 32).map(u32::from),
+                    height: be_u16(entry, 34).map(u32::from),
+                });
+            }
+            Some(b"soun") if info.audio.is_none() => {
+                info.audio = Some(AudioStream {
+                    codec: mp4_codec(format),
+                    sample_rate: be_u32(entry, 32).map(|rate| rate >> 16),
+                    channels: be_u16(entry, 24).map(u32::from),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // iTunes 风格的元数据：moov/udta/meta/ilst，部分文件直接放在 moov/meta
+    let meta = child_atom(moov, b"udta")
+        .and_then(|udta| child_atom(udta, b"meta"))
+        .or_else(|| child_atom(moov, b"meta"));
+    if let Some(ilst) = meta.and_then(|meta| {
+        // ISO 的 meta 是 full box，QuickTime 的没有版本字段
+        let children = if meta.get(4..8) == Some(&b"hdlr"[..]) {
+            meta
+        } else {
+            meta.get(4..)?
+        };
+        child_atom(children, b"ilst")
+    }) {
+        read_ilst(ilst, &mut info.tags);
+    }
+}
+
+fn read_ilst(ilst: &[u8], tags: &mut MediaTags) {
+    for (kind, item) in atoms(ilst) {
+        let Some(value) = child_atom(item, b"data").and_then(|data| data.get(8..)) else {
+            continue;
+        };
+
+        let field = match kind {
+            b"\xA9nam" => "title",
+            b"\xA9ART" => "artist",
+            b"aART" => "album_artist",
+            b"\xA9alb" => "album",
+            b"\xA9day" => "date",
+            b"\xA9gen" => "genre",
+            b"\xA9cmt" | b"desc" => "comment",
+            b"trkn" | b"disk" => {
+                // 二进制：保留 2 字节，序号，总数
+                let field = if kind == b"trkn" { "track" } else { "disc" };
+                if let Some(number) = be_u16(value, 2).filter(|n| *n > 0) {
+                    let total = be_u16(value, 4).filter(|n| *n > 0);
+                    let value = match total {
+                        Some(total) => format!("{}/{}", number, total),
+                        None => number.to_string(),
+                    };
+                    tags.set(field, &value);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        tags.set(field, &String::from_utf8_lossy(value));
+    }
+}
+
+fn mp4_codec(format: &[u8]) -> String {
+    match format {
+        b"avc1" | b"avc3" => "h264".to_string(),
+        b"hvc1" | b"hev1" => "hevc".to_string(),
+        b"av01" => "av1".to_string(),
+        b"vp08" => "vp8".to_string(),
+        b"vp09" => "vp9".to_string(),
+        b"mp4v" => "mpeg4".to_string(),
+        b"mp4a" => "aac".to_string(),
+        b"ac-3" => "ac3".to_string(),
+        b"ec-3" => "eac3".to_string(),
+        b"fLaC" => "flac".to_string(),
+        b"Opus" => "opus".to_string(),
+        b".mp3" => "mp3".to_string(),
+        other => String::from_utf8_lossy(other).trim().to_ascii_lowercase(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Matroska / WebM
+// ---------------------------------------------------------------------------
+
+const EBML_HEADER: u64 = 0x1A45_DFA3;
+const EBML_DOC_TYPE: u64 = 0x4282;
+const MKV_SEGMENT: u64 = 0x1853_8067;
+const MKV_INFO: u64 = 0x1549_A966;
+const MKV_TIMESTAMP_SCALE: u64 = 0x2A_D7B1;
+const MKV_DURATION: u64 = 0x4489;
+const MKV_TITLE: u64 = 0x7BA9;
+const MKV_TRACKS: u64 = 0x1654_AE6B;
+const MKV_TRACK_ENTRY: u64 = 0xAE;
+const MKV_TRACK_TYPE: u64 = 0x83;
+const MKV_CODEC_ID: u64 = 0x86;
+const MKV_VIDEO: u64 = 0xE0;
+const MKV_PIXEL_WIDTH: u64 = 0xB0;
+const MKV_PIXEL_HEIGHT: u64 = 0xBA;
+const MKV_AUDIO: u64 = 0xE1;
+const MKV_SAMPLING_FREQUENCY: u64 = 0xB5;
+const MKV_CHANNELS: u64 = 0x9F;
+const MKV_TAGS: u64 = 0x1254_C367;
+const MKV_TAG: u64 = 0x7373;
+const MKV_TARGETS: u64 = 0x63C0;
+const MKV_TARGET_TYPE_VALUE: u64 = 0x68CA;
+const MKV_SIMPLE_TAG: u64 = 0x67C8;
+const MKV_TAG_NAME: u64 = 0x45A3;
+const MKV_TAG_STRING: u64 = 0x4487;
+
+fn probe_matroska(file: &mut File, len: u64) -> Result<MediaInfo> {
+    let mut info = MediaInfo::new("matroska");
+    let mut pos = 0;
+    let mut segment_end = len;
+    let mut info_title = None;
+    // (目标级别, 标签名, 值)
+    let mut simple_tags: Vec<(u64, String, String)> = Vec::new();
+
+    while pos < segment_end {
+        let header = read_at(file, pos, 16)?;
+        let Some((id, size, header_len)) = ebml_header(&header) else {
+            break;
+        };
+        let body_start = pos + header_len as u64;
+
+        if id == MKV_SEGMENT {
+            // 进入 Segment，逐个读取其子元素
+            if let Some(size) = size {
+                segment_end = (body_start + size).min(len);
+            }
+            pos = body_start;
+            continue;
+        }
+
+        // 长度未知的元素（直播流的 Cluster）无法跳过
+        let Some(size) = size else {
+            break;
+        };
+
+        if matches!(id, EBML_HEADER | MKV_INFO | MKV_TRACKS | MKV_TAGS) {
+            let body = read_at(file, body_start, size.min(BLOCK_LIMIT))?;
+            match id {
+                EBML_HEADER => {
+                    if let Some(doc_type) = ebml_child(&body, EBML_DOC_TYPE) {
+                        if doc_type == b"webm" {
+                            info.container = "webm";
+                        }
+                    }
+                }
+                MKV_INFO => {
+                    let scale = ebml_child(&body, MKV_TIMESTAMP_SCALE)
+                        .map(ebml_uint)
+                        .unwrap_or(1_000_000);
+                    if let Some(duration) = ebml_child(&body, MKV_DURATION).and_then(ebml_float) {
+                        info.duration = Some(duration * scale as f64 / 1e9);
+                    }
+                    info_title = ebml_child(&body, MKV_TITLE)
+                        .map(|title| String::from_utf8_lossy(title).into_owned());
+                }
+                MKV_TRACKS => read_matroska_tracks(&body, &mut info),
+                _ => read_matroska_tags(&body, &mut simple_tags),
+            }
+        }
+
+        pos = body_start + size;
+    }
+
+    // Matroska 标签按目标级别区分：50 为专辑/影片，30 为曲目/章节
+    let has_track_level = simple_tags.iter().any(|(level, _, _)| *level < 50);
+    for (level, name, value) in &simple_tags {
+        let album_level = *level >= 50 && has_track_level;
+        let field = match name.to_ascii_uppercase().as_str() {
+            "TITLE" if album_level => "album",
+            "ARTIST" if album_level => "album_artist",
+            "PART_NUMBER" if album_level => "disc",
+            other => match tag_field(other) {
+                Some(field) => field,
+                None => continue,
+            },
+        };
+        info.tags.set(field, value);
+    }
+    if let Some(title) = info_title {
+        info.tags.set("title", &title);
+    }
+
+    info.estimate_bitrate(len);
+    Ok(info)
+}
+
+fn read_matroska_tracks(tracks: &[u8], info: &mut MediaInfo) {
+    for (id, entry) in ebml_elements(tracks) {
+        if id != MKV_TRACK_ENTRY {
+            continue;
+        }
+        let track_type = ebml_child(entry, MKV_TRACK_TYPE).map(ebml_uint);
+        let codec = ebml_child(entry, MKV_CODEC_ID)
+            .map(|codec| matroska_codec(&String::from_utf8_lossy(codec)))
+            .unwrap_or_default();
+
+        match track_type {
+            Some(1) if info.video.is_none() => {
+                let video = ebml_child(entry, MKV_VIDEO).unwrap_or_default();
+                let dimension = |id| ebml_child(video, id).map(|v| ebml_uint(v) as u32);
+                info.video = Some(VideoStream {
+                    codec,
+                    width: dimension(MKV_PIXEL_WIDTH),
+                    height: dimension(MKV_PIXEL_HEIGHT),
+                });
+            }
+            Some(2) if info.audio.is_none() => {
+                let audio = ebml_child(entry, MKV_AUDIO).unwrap_or_default();
+                info.audio = Some(AudioStream {
+                    codec,
+                    sample_rate: ebml_child(audio, MKV_SAMPLING_FREQUENCY)
+                        .and_then(ebml_float)
+                        .map(|rate| rate.round() as u32),
+                    channels: ebml_child(audio, MKV_CHANNELS).map(|c| ebml_uint(c) as u32),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+fn read_matroska_tags(tags: &[u8], out: &mut Vec<(u64, String, String)>) {
+    for (id, tag) in ebml_elements(tags) {
+        if id != MKV_TAG {
+            continue;
+        }
+        let level = ebml_child(tag, MKV_TARGETS)
+            .and_then(|targets| ebml_child(targets, MKV_TARGET_TYPE_VALUE))
+            .map(ebml_uint)
+            .unwrap_or(50);
+
+        for (id, simple) in ebml_elements(tag) {
+            if id != MKV_SIMPLE_TAG {
+                continue;
+            }
+            if let (Some(name), Some(value)) = (
+                ebml_child(simple, MKV_TAG_NAME),
+                ebml_child(simple, MKV_TAG_STRING),
+            ) {
+                out.push((
+                    level,
+                    String::from_utf8_lossy(name).into_owned(),
+                    String::from_utf8_lossy(value).into_owned(),
+                ));
+            }
+        }
+    }
+}
+
+fn matroska_codec(codec_id: &str) -> String {
+    let name = codec_id
+        .strip_prefix("V_")
+        .or_else(|| codec_id.strip_prefix("A_"))
+        .unwrap_or(codec_id);
+    match name {
+        "MPEG4/ISO/AVC" => "h264".to_string(),
+        "MPEGH/ISO/HEVC" => "hevc".to_string(),
+        "MPEG/L3" => "mp3".to_string(),
+        _ if name.starts_with("AAC") => "aac".to_string(),
+        _ if name.starts_with("PCM") => "pcm".to_string(),
+        _ => name.to_ascii_lowercase(),
+    }
+}
+
+/// 读取 EBML 变长整数，返回 (值, 字节数)；`keep_marker` 为真时保留长度标记位（用于元素 ID）
+fn ebml_vint(data: &[u8], keep_marker: bool) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    let len = first.leading_zeros() as usize + 1;
+    if len > 8 {
+        return None;
+    }
+    let bytes = data.get(..len)?;
+    let mut value = if keep_marker {
+        first as u64
+    } else {
+        (first as u64) & (0xFF >> len)
+    };
+    for &byte in &bytes[1..] {
+        value = (value << 8) | byte as u64;
+    }
+    Some((value, len))
+}
+
+/// 元素头：ID、长度（全 1 表示未知长度）和头部字节数
+fn ebml_header(data: &[u8]) -> Option<(u64, Option<u64>, usize)> {
+    let (id, id_len) = ebml_vint(data, true)?;
+    let (size, size_len) = ebml_vint(data.get(id_len..)?, false)?;
+    let unknown = size == (1u64 << (7 * size_len)) - 1;
+    Some((id, (!unknown).then_some(size), id_len + size_len))
+}
+
+/// 遍历内存中的子元素，返回 (ID, 内容)；长度越界时截断到数据末尾
+fn ebml_elements(mut data: &[u8]) -> impl Iterator<Item = (u64, &[u8])> {
+    std::iter::from_fn(move || {
+        let (id, size, header_len) = ebml_header(data)?;
+        let end = size
+            .and_then(|size| usize::try_from(size).ok())
+            .and_then(|size| header_len.checked_add(size))
+            .unwrap_or(data.len())
+            .min(data.len());
+        let body = &data[header_len..end];
+        data = &data[end..];
+        Some((id, body))
+    })
+}
+
+fn ebml_child(data: &[u8], id: u64) -> Option<&[u8]> {
+    ebml_elements(data)
+        .find(|(child, _)| *child == id)
+        .map(|(_, body)| body)
+}
+
+fn ebml_uint(data: &[u8]) -> u64 {
+    data.iter()
+        .take(8)
+        .fold(0, |acc, &byte| (acc << 8) | byte as u64)
+}
+
+fn ebml_float(data: &[u8]) -> Option<f64> {
+    match data.len() {
+        4 => Some(f32::from_be_bytes(data.try_into().ok()?) as f64),
+        8 => Some(f64::from_be_bytes(data.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 字节读取
+// ---------------------------------------------------------------------------
+
+/// 从 `offset` 开始最多读取 `limit` 字节，文件较短时返回实际读到的内容
+fn read_at(file: &mut File, offset: u64, limit: u64) -> Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset))
+        .map_err(TagboxError::Io)?;
+    let mut buf = Vec::new();
+    file.by_ref()
+        .take(limit)
+        .read_to_end(&mut buf)
+        .map_err(TagboxError::Io)?;
+    Ok(buf)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn be_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn le_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
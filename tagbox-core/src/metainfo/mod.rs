@@ -0,0 +1,442 @@
+//! 元信息提取
+//!
+//! 合并顺序（后者覆盖前者的非空字段，标签取并集，JSON 元数据按键深度合并）：
+//!
+//! 1. 文件名解析（`Title - Author (2023).pdf`）
+//! 2. 内容提取器，按 [`MetadataExtractor::priority`] 从低到高
+//! 3. 同目录下的 `.meta` / `.meta.json` / `.json` 元数据文件
+//!
+//! 新格式通过实现 [`MetadataExtractor`] 并注册到 [`ExtractorRegistry`] 接入，
+//! 内置提取器由 cargo feature `pdf`、`epub`、`image`、`office`、`media`、`text`、`archive` 控制。
+
+#[cfg(feature = "archive")]
+mod archive;
+#[cfg(feature = "epub")]
+mod epub;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "text")]
+mod markup;
+#[cfg(feature = "media")]
+mod media;
+#[cfg(feature = "office")]
+mod office;
+#[cfg(feature = "pdf")]
+mod pdf;
+#[cfg(feature = "pdf")]
+mod pdf_structure;
+mod registry;
+#[cfg(any(feature = "epub", feature = "text", feature = "archive"))]
+mod text;
+#[cfg(feature = "image")]
+mod xmp;
+
+#[cfg(feature = "archive")]
+pub use archive::{is_archive, unpack_archive, ArchiveExtractor};
+#[cfg(feature = "epub")]
+pub use epub::{extract_epub_chapters, EpubChapters, EpubExtractor};
+#[cfg(feature = "image")]
+pub use image::ImageExtractor;
+#[cfg(feature = "text")]
+pub use markup::MarkupExtractor;
+#[cfg(feature = "media")]
+pub use media::MediaExtractor;
+#[cfg(feature = "office")]
+pub use office::OfficeExtractor;
+#[cfg(feature = "pdf")]
+pub use pdf::{extract_pdf_pages, PdfExtractor, PdfPages};
+#[cfg(feature = "pdf")]
+pub use pdf_structure::{extract_pdf_structure, page_label, PdfStructure};
+pub use registry::{supports_type, ExtractorRegistry, MetadataExtractor};
+
+use crate::config::AppConfig;
+use crate::errors::{Result, TagboxError};
+use crate::filetype;
+use crate::types::ImportMetadata;
+use crate::utils::mime_from_extension;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// 元信息提取器
+pub struct MetaInfoExtractor {
+    config: AppConfig,
+    registry: ExtractorRegistry,
+}
+
+impl MetaInfoExtractor {
+    /// 创建一个新的元信息提取器，使用内置的内容提取器
+    pub fn new(config: AppConfig) -> Self {
+        let registry = ExtractorRegistry::with_builtin(&config);
+        Self { config, registry }
+    }
+
+    /// 使用自定义注册表创建元信息提取器
+    pub fn with_registry(config: AppConfig, registry: ExtractorRegistry) -> Self {
+        Self { config, registry }
+    }
+
+    /// 获取提取器注册表
+    pub fn registry(&self) -> &ExtractorRegistry {
+        &self.registry
+    }
+
+    /// 获取可修改的提取器注册表，用于注册额外的提取器
+    pub fn registry_mut(&mut self) -> &mut ExtractorRegistry {
+        &mut self.registry
+    }
+
+    /// 从文件中提取元数据
+    pub async fn extract(&self, file_path: &Path) -> Result<ImportMetadata> {
+        debug!("从文件提取元信息: {}", file_path.display());
+
+        if !file_path.exists() {
+            return Err(TagboxError::FileNotFound {
+                path: file_path.to_path_buf(),
+            });
+        }
+
+        // 首先尝试从文件名提取信息
+        let mut metadata = self.extract_from_filename(file_path);
+
+        // 根据文件内容检测类型，检测失败时回退到扩展名
+        let detected = match filetype::detect_file(file_path) {
+            Ok(detected) => detected,
+            Err(e) => {
+                warn!("无法检测文件类型 {}: {}", file_path.display(), e);
+                None
+            }
+        };
+
+        if let Some(file_type) = &detected {
+            if !filetype::extension_matches(file_path, file_type) {
+                warn!(
+                    "文件扩展名与内容不符: {} (扩展名: {}, 内容: {})",
+                    file_path.display(),
+                    file_path
+                        .extension()
+                        .and_then(OsStr::to_str)
+                        .unwrap_or("无"),
+                    file_type.mime
+                );
+            }
+        }
+
+        metadata.mime_type = detected
+            .map(|t| t.mime.to_string())
+            .or_else(|| mime_from_extension(file_path).map(|m| m.to_string()));
+
+        // 按优先级从低到高合并内容提取器的结果
+        for extractor in self
+            .registry
+            .matching(file_path, metadata.mime_type.as_deref())
+        {
+            match extractor.extract(file_path, metadata.mime_type.as_deref()) {
+                Ok(m) => metadata = merge_metadata(metadata, m),
+                Err(e) => warn!(
+                    "提取器 {} 处理 {} 失败: {}",
+                    extractor.name(),
+                    file_path.display(),
+                    e
+                ),
+            }
+        }
+
+        // 同目录下的元数据JSON文件由用户提供，最后合并
+        if self.config.import.metadata.prefer_json {
+            if let Ok(json_metadata) = self.extract_from_json_file(file_path) {
+                metadata = merge_metadata(metadata, json_metadata);
+            }
+        }
+
+        // 设置默认分类（如果没有指定）
+        if metadata.category1.is_empty() {
+            metadata.category1 = self.config.import.metadata.default_category.clone();
+        }
+
+        Ok(metadata)
+    }
+
+    /// 从文件名提取基础信息
+    fn extract_from_filename(&self, path: &Path) -> ImportMetadata {
+        let filename = path.file_stem().unwrap_or_default().to_string_lossy();
+
+        // 基本的文件名解析逻辑，可以扩展为更复杂的规则
+        // 示例: "Title - Author (2023).pdf" 格式解析
+        let parts: Vec<&str> = filename.split(" - ").collect();
+
+        let mut metadata = ImportMetadata {
+            title: if !parts.is_empty() {
+                parts[0].to_string()
+            } else {
+                filename.to_string()
+            },
+            authors: vec!["Unknown".to_string()],
+            contributors: Vec::new(),
+            year: None,
+            publisher: None,
+            source: None,
+            category1: self.config.import.metadata.default_category.clone(),
+            category2: None,
+            category3: None,
+            tags: Vec::new(),
+            summary: None,
+            full_text: None,
+            additional_info: HashMap::new(),
+            file_metadata: None,
+            type_metadata: None,
+            mime_type: None,
+        };
+
+        if parts.len() > 1 {
+            // 提取作者和年份
+            let author_part = parts[1].trim();
+            if let Some((author, year)) = self.parse_author_year(author_part) {
+                metadata.authors = vec![author];
+                metadata.year = year;
+            } else {
+                metadata.authors = vec![author_part.to_string()];
+            }
+        }
+
+        metadata
+    }
+
+    /// 解析作者和年份，格式如 "Author Name (2023)"
+    fn parse_author_year(&self, s: &str) -> Option<(String, Option<i32>)> {
+        let parts: Vec<&str> = s.rsplitn(2, '(').collect();
+        if parts.len() == 2 {
+            let author = parts[1].trim();
+            let year_part = parts[0].trim();
+            if let Some(year_str) = year_part.strip_suffix(')') {
+                let year_str = year_str.trim();
+                if let Ok(year) = year_str.parse::<i32>() {
+                    return Some((author.to_string(), Some(year)));
+                }
+            }
+        }
+        None
+    }
+
+    /// 从配套的JSON文件提取元信息
+    fn extract_from_json_file(&self, file_path: &Path) -> Result<ImportMetadata> {
+        // 构造同名但扩展名为json的文件路径
+        let json_path = self.get_metadata_json_path(file_path)?;
+
+        if json_path.exists() {
+            let json_content = fs::read_to_string(&json_path).map_err(TagboxError::Io)?;
+
+            let json_value: Value =
+                serde_json::from_str(&json_content).map_err(TagboxError::Serialization)?;
+
+            return self.parse_json_metadata(json_value);
+        }
+        Err(TagboxError::FileNotFound { path: json_path })
+    }
+
+    /// 获取与文件关联的元数据JSON文件路径
+    fn get_metadata_json_path(&self, file_path: &Path) -> Result<PathBuf> {
+        if let Some(filename) = file_path.file_name() {
+            let parent = file_path.parent().unwrap_or(Path::new("."));
+            let filename_str = filename.to_string_lossy();
+
+            // 首先尝试查找 .meta 文件（推荐格式）
+            let meta_path = parent.join(format!("{}.meta", filename_str));
+            if meta_path.exists() {
+                return Ok(meta_path);
+            }
+
+            // 然后尝试查找 .meta.json 文件
+            let meta_json_path = parent.join(format!("{}.meta.json", filename_str));
+            if meta_json_path.exists() {
+                return Ok(meta_json_path);
+            }
+
+            // 最后尝试查找 .json 文件（用于兼容测试和简单场景）
+            let json_path = parent.join(format!("{}.json", filename_str));
+            if json_path.exists() {
+                return Ok(json_path);
+            }
+
+            // 如果都不存在，返回首选的 .meta 路径（让调用者处理文件不存在的情况）
+            Ok(meta_path)
+        } else {
+            Err(TagboxError::Config(format!(
+                "无法获取文件名: {}",
+                file_path.display()
+            )))
+        }
+    }
+
+    /// 解析JSON元数据
+    fn parse_json_metadata(&self, json: Value) -> Result<ImportMetadata> {
+        let mut metadata = ImportMetadata {
+            title: json
+                .get("title")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            authors: json
+                .get("authors")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|a| a.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            contributors: Vec::new(),
+            year: json.get("year").and_then(|v| v.as_i64()).map(|y| y as i32),
+            publisher: json
+                .get("publisher")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            source: json
+                .get("source")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            category1: json
+                .get("category1")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            category2: json
+                .get("category2")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            category3: json
+                .get("category3")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            tags: json
+                .get("tags")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|t| t.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            summary: json
+                .get("summary")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            full_text: None,
+            additional_info: HashMap::new(),
+            file_metadata: None,
+            type_metadata: Some(json.clone()),
+            mime_type: None,
+        };
+
+        // 处理额外信息
+        if let Some(obj) = json.as_object() {
+            for (key, value) in obj.iter() {
+                if ![
+                    "title",
+                    "authors",
+                    "year",
+                    "publisher",
+                    "source",
+                    "category1",
+                    "category2",
+                    "category3",
+                    "tags",
+                    "summary",
+                ]
+                .contains(&key.as_str())
+                {
+                    if let Some(value_str) = value.as_str() {
+                        metadata
+                            .additional_info
+                            .insert(key.clone(), value_str.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// 合并两个元数据结构，优先使用第二个的非空值
+///
+/// 标签取并集，`additional_info` 按键覆盖，`file_metadata` 和
+/// `type_metadata` 按键深度合并，使不同提取器的结果可以共存。
+pub fn merge_metadata(base: ImportMetadata, override_data: ImportMetadata) -> ImportMetadata {
+    ImportMetadata {
+        title: if override_data.title.is_empty() {
+            base.title
+        } else {
+            override_data.title
+        },
+        authors: if override_data.authors.is_empty() {
+            base.authors
+        } else {
+            override_data.authors
+        },
+        contributors: if override_data.contributors.is_empty() {
+            base.contributors
+        } else {
+            override_data.contributors
+        },
+        year: override_data.year.or(base.year),
+        publisher: override_data.publisher.or(base.publisher),
+        source: override_data.source.or(base.source),
+        category1: if override_data.category1.is_empty() {
+            base.category1
+        } else {
+            override_data.category1
+        },
+        category2: if override_data.category2.is_none() {
+            base.category2
+        } else {
+            override_data.category2
+        },
+        category3: if override_data.category3.is_none() {
+            base.category3
+        } else {
+            override_data.category3
+        },
+        tags: {
+            let mut merged_tags = base.tags;
+            for tag in override_data.tags {
+                if !merged_tags.contains(&tag) {
+                    merged_tags.push(tag);
+                }
+            }
+            merged_tags
+        },
+        summary: override_data.summary.or(base.summary),
+        full_text: override_data.full_text.or(base.full_text),
+        additional_info: {
+            let mut merged_info = base.additional_info;
+            merged_info.extend(override_data.additional_info);
+            merged_info
+        },
+        file_metadata: merge_json(base.file_metadata, override_data.file_metadata),
+        type_metadata: merge_json(base.type_metadata, override_data.type_metadata),
+        mime_type: override_data.mime_type.or(base.mime_type),
+    }
+}
+
+/// 按键深度合并两个 JSON 值，两者都是对象时递归合并，否则取后者
+fn merge_json(base: Option<Value>, override_data: Option<Value>) -> Option<Value> {
+    match (base, override_data) {
+        (Some(Value::Object(mut base)), Some(Value::Object(override_data))) => {
+            for (key, value) in override_data {
+                let merged = match base.remove(&key) {
+                    Some(existing) => merge_json(Some(existing), Some(value)),
+                    None => Some(value),
+                };
+                if let Some(merged) = merged {
+                    base.insert(key, merged);
+                }
+            }
+            Some(Value::Object(base))
+        }
+        (base, override_data) => override_data.or(base),
+    }
+}
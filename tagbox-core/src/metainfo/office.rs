@@ -0,0 +1,510 @@
+use super::registry::{supports_type, MetadataExtractor};
+use crate::authors::split_author_list;
+use crate::config::default_page_text_budget;
+use crate::errors::{Result, TagboxError};
+use crate::types::ImportMetadata;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use tracing::debug;
+use zip::ZipArchive;
+
+/// Office 文档格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OfficeFormat {
+    Docx,
+    Xlsx,
+    Pptx,
+    Odt,
+    Ods,
+    Odp,
+}
+
+impl OfficeFormat {
+    fn from_type(path: &Path, mime: Option<&str>) -> Option<Self> {
+        const FORMATS: &[(OfficeFormat, &str, &str)] = &[
+            (
+                OfficeFormat::Docx,
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                "docx",
+            ),
+            (
+                OfficeFormat::Xlsx,
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+                "xlsx",
+            ),
+            (
+                OfficeFormat::Pptx,
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+                "pptx",
+            ),
+            (
+                OfficeFormat::Odt,
+                "application/vnd.oasis.opendocument.text",
+                "odt",
+            ),
+            (
+                OfficeFormat::Ods,
+                "application/vnd.oasis.opendocument.spreadsheet",
+                "ods",
+            ),
+            (
+                OfficeFormat::Odp,
+                "application/vnd.oasis.opendocument.presentation",
+                "odp",
+            ),
+        ];
+
+        FORMATS
+            .iter()
+            .find(|(_, m, ext)| supports_type(path, mime, &[*m], &[*ext]))
+            .map(|(format, _, _)| *format)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            OfficeFormat::Docx => "docx",
+            OfficeFormat::Xlsx => "xlsx",
+            OfficeFormat::Pptx => "pptx",
+            OfficeFormat::Odt => "odt",
+            OfficeFormat::Ods => "ods",
+            OfficeFormat::Odp => "odp",
+        }
+    }
+
+    fn is_odf(self) -> bool {
+        matches!(
+            self,
+            OfficeFormat::Odt | OfficeFormat::Ods | OfficeFormat::Odp
+        )
+    }
+}
+
+/// 文本提取规则
+struct TextRules {
+    /// 只收集这些元素内的文本，为空时收集全部文本
+    text: &'static [&'static str],
+    /// 这些元素结束时换行
+    paragraphs: &'static [&'static str],
+}
+
+/// DOCX 段落和 PPTX 幻灯片
+const PARAGRAPH_TEXT: TextRules = TextRules {
+    text: &["t"],
+    paragraphs: &["p"],
+};
+/// XLSX 共享字符串表
+const SHARED_STRINGS_TEXT: TextRules = TextRules {
+    text: &["t"],
+    paragraphs: &["si"],
+};
+const ODF_TEXT: TextRules = TextRules {
+    text: &[],
+    paragraphs: &["p", "h", "table-row"],
+};
+
+/// Office Open XML（DOCX/XLSX/PPTX）与 OpenDocument（ODT/ODS/ODP）提取器
+pub struct OfficeExtractor;
+
+impl MetadataExtractor for OfficeExtractor {
+    fn name(&self) -> &'static str {
+        "office"
+    }
+
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        OfficeFormat::from_type(path, mime).is_some()
+    }
+
+    fn extract(&self, path: &Path, mime: Option<&str>) -> Result<ImportMetadata> {
+        let format = OfficeFormat::from_type(path, mime).ok_or_else(|| {
+            TagboxError::MetaInfoExtraction(format!("不是Office文档: {}", path.display()))
+        })?;
+        self.extract_office(path, format)
+    }
+}
+
+impl OfficeExtractor {
+    fn extract_office(&self, path: &Path, format: OfficeFormat) -> Result<ImportMetadata> {
+        debug!("从Office文档提取元信息: {}", path.display());
+
+        // 与 PDF/EPUB 相同的文本预算，防止压缩炸弹或超大工作表耗尽内存
+        let mut budget = default_page_text_budget();
+
+        let file = File::open(path).map_err(TagboxError::Io)?;
+        let mut archive = ZipArchive::new(file).map_err(|e| {
+            TagboxError::MetaInfoExtraction(format!("无法打开Office文档 {}: {}", path.display(), e))
+        })?;
+
+        let mut meta = ImportMetadata::default();
+        let mut file_metadata = serde_json::json!({
+            "office": {
+                "format": format.name(),
+            }
+        });
+        let mut type_metadata = serde_json::json!({
+            "document": {
+                "format": format.name(),
+            }
+        });
+
+        // 文档属性
+        let properties = if format.is_odf() {
+            read_entry(&mut archive, "meta.xml", &mut budget)
+                .map(|xml| leaf_values(&xml))
+                .unwrap_or_default()
+        } else {
+            let mut values = read_entry(&mut archive, "docProps/core.xml", &mut budget)
+                .map(|xml| leaf_values(&xml))
+                .unwrap_or_default();
+            if let Some(app) = read_entry(&mut archive, "docProps/app.xml", &mut budget) {
+                values.extend(leaf_values(&app));
+            }
+            values
+        };
+        let property = |name: &str| -> Option<String> {
+            properties
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        if let Some(title) = property("title") {
+            meta.title = title;
+        }
+
+        let creator = if format.is_odf() {
+            property("initial-creator").or_else(|| property("creator"))
+        } else {
+            property("creator")
+        };
+        if let Some(creator) = creator {
+            meta.authors = split_author_list(&creator);
+        }
+
+        let created = property("created").or_else(|| property("creation-date"));
+        if let Some(created) = &created {
+            meta.year = created.get(..4).and_then(|y| y.parse::<i32>().ok());
+            type_metadata["document"]["creation_date"] = serde_json::json!(created);
+        }
+        if let Some(modified) = property("modified").or_else(|| property("date")) {
+            type_metadata["document"]["modification_date"] = serde_json::json!(modified);
+        }
+
+        // ODF 每个关键词是单独的元素，OOXML 是一个以逗号或分号分隔的字符串
+        for (key, value) in &properties {
+            if key == "keywords" || key == "keyword" {
+                for tag in value.split(&[',', ';'][..]) {
+                    let tag = tag.trim().to_string();
+                    if !tag.is_empty() && !meta.tags.contains(&tag) {
+                        meta.tags.push(tag);
+                    }
+                }
+            }
+        }
+
+        let subject = property("subject");
+        meta.summary = property("description").or_else(|| subject.clone());
+        if let Some(subject) = subject {
+            type_metadata["document"]["subject"] = serde_json::json!(subject);
+        }
+        if let Some(modified_by) = property("lastModifiedBy") {
+            type_metadata["document"]["last_modified_by"] = serde_json::json!(modified_by);
+        }
+        if let Some(application) = property("Application").or_else(|| property("generator")) {
+            file_metadata["office"]["application"] = serde_json::json!(application);
+        }
+        for (key, field) in [("Pages", "pages"), ("Words", "words"), ("Slides", "slides")] {
+            if let Some(count) = property(key).and_then(|v| v.parse::<u64>().ok()) {
+                file_metadata["office"][field] = serde_json::json!(count);
+            }
+        }
+        if format.is_odf() {
+            if let Some(xml) = read_entry(&mut archive, "meta.xml", &mut budget) {
+                for (key, value) in element_attributes(&xml, "document-statistic") {
+                    if let Ok(count) = value.parse::<u64>() {
+                        file_metadata["office"][key.replace('-', "_")] = serde_json::json!(count);
+                    }
+                }
+            }
+        }
+
+        // 正文文本
+        let text = match format {
+            OfficeFormat::Docx => read_entry(&mut archive, "word/document.xml", &mut budget)
+                .map(|xml| xml_text(&xml, &PARAGRAPH_TEXT))
+                .unwrap_or_default(),
+            OfficeFormat::Pptx => {
+                let slides = numbered_entries(&archive, "ppt/slides/slide");
+                file_metadata["office"]["slides"] = serde_json::json!(slides.len());
+                join_entries(&mut archive, &slides, &mut budget, |xml| {
+                    xml_text(xml, &PARAGRAPH_TEXT)
+                })
+            }
+            OfficeFormat::Xlsx => {
+                let sheets = numbered_entries(&archive, "xl/worksheets/sheet");
+                file_metadata["office"]["sheets"] = serde_json::json!(sheets.len());
+                let mut text = join_entries(
+                    &mut archive,
+                    &["xl/sharedStrings.xml".to_string()],
+                    &mut budget,
+                    |xml| xml_text(xml, &SHARED_STRINGS_TEXT),
+                );
+                text.push_str(&join_entries(
+                    &mut archive,
+                    &sheets,
+                    &mut budget,
+                    sheet_text,
+                ));
+                text
+            }
+            OfficeFormat::Odt | OfficeFormat::Ods | OfficeFormat::Odp => {
+                read_entry(&mut archive, "content.xml", &mut budget)
+                    .map(|xml| xml_text(&xml, &ODF_TEXT))
+                    .unwrap_or_default()
+            }
+        };
+        let text = text.trim().to_string();
+
+        file_metadata["office"]["has_text"] = serde_json::json!(!text.is_empty());
+        file_metadata["office"]["text_length"] = serde_json::json!(text.chars().count());
+        if !text.is_empty() {
+            meta.full_text = Some(text);
+        }
+
+        meta.file_metadata = Some(file_metadata);
+        meta.type_metadata = Some(type_metadata);
+
+        debug!(
+            "成功提取Office元数据: {} ({}), 标题: {}",
+            path.display(),
+            format.name(),
+            meta.title
+        );
+
+        Ok(meta)
+    }
+}
+
+/// 读取压缩包中的文本条目，最多读取剩余的 `budget` 字节并从中扣除
+///
+/// 超出预算的条目被截断，截断处的 XML 解析到出错为止
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+    budget: &mut u64,
+) -> Option<String> {
+    if *budget == 0 {
+        debug!("Office文本预算已用完，跳过: {}", name);
+        return None;
+    }
+    let entry = archive.by_name(name).ok()?;
+    let mut content = Vec::new();
+    entry.take(*budget).read_to_end(&mut content).ok()?;
+    *budget -= content.len() as u64;
+    Some(String::from_utf8_lossy(&content).into_owned())
+}
+
+/// 按编号排序的条目名，如 `ppt/slides/slide1.xml`、`slide2.xml`、`slide10.xml`
+fn numbered_entries<R: Read + std::io::Seek>(archive: &ZipArchive<R>, prefix: &str) -> Vec<String> {
+    let mut entries: Vec<(u32, String)> = archive
+        .file_names()
+        .filter_map(|name| {
+            let number = name.strip_prefix(prefix)?.strip_suffix(".xml")?;
+            Some((number.parse().ok()?, name.to_string()))
+        })
+        .collect();
+    entries.sort();
+    entries.into_iter().map(|(_, name)| name).collect()
+}
+
+/// 依次提取多个条目的文本
+fn join_entries<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    entries: &[String],
+    budget: &mut u64,
+    extract: impl Fn(&str) -> String,
+) -> String {
+    let mut text = String::new();
+    for name in entries {
+        if let Some(xml) = read_entry(archive, name, budget) {
+            let part = extract(&xml);
+            if !part.trim().is_empty() {
+                text.push_str(part.trim());
+                text.push_str("\n\n");
+            }
+        }
+    }
+    text
+}
+
+/// 按规则提取 XML 中的正文文本
+fn xml_text(xml: &str, rules: &TextRules) -> String {
+    let mut reader = Reader::from_str(xml);
+    let mut text = String::new();
+    let mut depth = 0usize;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e))
+                if rules
+                    .text
+                    .contains(&local_name(e.local_name().into_inner())) =>
+            {
+                depth += 1;
+            }
+            Ok(Event::End(e)) => {
+                let name = local_name(e.local_name().into_inner());
+                if rules.text.contains(&name) {
+                    depth = depth.saturating_sub(1);
+                }
+                if rules.paragraphs.contains(&name) && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            Ok(Event::Empty(e)) => match local_name(e.local_name().into_inner()) {
+                "tab" => text.push('\t'),
+                "br" | "cr" | "line-break" => text.push('\n'),
+                "s" => text.push(' '),
+                _ => {}
+            },
+            Ok(Event::Text(e)) if rules.text.is_empty() || depth > 0 => {
+                if let Ok(value) = e.unescape() {
+                    text.push_str(&value);
+                }
+            }
+            Ok(Event::CData(e)) if rules.text.is_empty() || depth > 0 => {
+                text.push_str(&String::from_utf8_lossy(&e));
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                debug!("解析Office XML失败: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// 提取 XLSX 工作表的单元格文本：内联字符串和数值，单元格以制表符分隔，每行一行
+///
+/// 共享字符串单元格（`t="s"`）的 `<v>` 只是共享字符串表的下标，已随共享字符串表提取
+fn sheet_text(xml: &str) -> String {
+    let mut reader = Reader::from_str(xml);
+    let mut text = String::new();
+    let mut shared = false;
+    let mut in_value = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match local_name(e.local_name().into_inner()) {
+                "c" => {
+                    shared = e.attributes().flatten().any(|attr| {
+                        attr.key.local_name().into_inner() == b"t" && &*attr.value == b"s"
+                    });
+                }
+                "v" => in_value = !shared,
+                "t" => in_value = true,
+                _ => {}
+            },
+            Ok(Event::End(e)) => match local_name(e.local_name().into_inner()) {
+                "v" | "t" => in_value = false,
+                "c" if !text.is_empty() && !text.ends_with(&['\t', '\n'][..]) => {
+                    text.push('\t');
+                }
+                "row" => {
+                    let trimmed = text.trim_end_matches('\t').len();
+                    text.truncate(trimmed);
+                    if !text.is_empty() && !text.ends_with('\n') {
+                        text.push('\n');
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_value => {
+                if let Ok(value) = e.unescape() {
+                    text.push_str(&value);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                debug!("解析XLSX工作表失败: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// 收集只包含文本的叶子元素，返回 (本地名, 文本)
+fn leaf_values(xml: &str) -> Vec<(String, String)> {
+    let mut reader = Reader::from_str(xml);
+    let mut values = Vec::new();
+    let mut current: Option<(String, String)> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                current = Some((
+                    local_name(e.local_name().into_inner()).to_string(),
+                    String::new(),
+                ));
+            }
+            Ok(Event::Text(e)) => {
+                if let (Some((_, value)), Ok(text)) = (current.as_mut(), e.unescape()) {
+                    value.push_str(&text);
+                }
+            }
+            Ok(Event::End(_)) => {
+                if let Some((name, value)) = current.take() {
+                    let value = value.trim();
+                    if !value.is_empty() {
+                        values.push((name, value.to_string()));
+                    }
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                debug!("解析Office属性失败: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    values
+}
+
+/// 读取指定元素的属性，返回 (属性本地名, 值)
+fn element_attributes(xml: &str, element: &str) -> Vec<(String, String)> {
+    let mut reader = Reader::from_str(xml);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if local_name(e.local_name().into_inner()) == element =>
+            {
+                return e
+                    .attributes()
+                    .flatten()
+                    .filter_map(|attr| {
+                        let key = local_name(attr.key.local_name().into_inner()).to_string();
+                        let value = attr.unescape_value().ok()?.to_string();
+                        Some((key, value))
+                    })
+                    .collect();
+            }
+            Ok(Event::Eof) | Err(_) => return Vec::new(),
+            _ => {}
+        }
+    }
+}
+
+fn local_name(name: &[u8]) -> &str {
+    std::str::from_utf8(name).unwrap_or_default()
+}
@@ -1,295 +1,99 @@
-use crate::config::AppConfig;
+use super::pdf_structure::extract_pdf_structure;
+use super::registry::{supports_type, MetadataExtractor};
+use crate::authors::split_author_list;
 use crate::errors::{Result, TagboxError};
-use crate::types::ImportMetadata;
-use imageinfo;
-use serde_json::Value;
-use std::collections::HashMap;
-use std::ffi::OsStr;
+use crate::identifiers::detect_identifiers;
+use crate::types::{Identifier, ImportMetadata};
+use lopdf::Document;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use tracing::{debug, warn};
 
-/// 元信息提取器
-pub struct MetaInfoExtractor {
-    config: AppConfig,
-}
+/// PDF 文档信息字典与正文预览提取器
+pub struct PdfExtractor;
 
-impl MetaInfoExtractor {
-    /// 创建一个新的元信息提取器
-    pub fn new(config: AppConfig) -> Self {
-        Self { config }
+impl MetadataExtractor for PdfExtractor {
+    fn name(&self) -> &'static str {
+        "pdf"
     }
 
-    /// 从文件中提取元数据
-    pub async fn extract(&self, file_path: &Path) -> Result<ImportMetadata> {
-        debug!("从文件提取元信息: {}", file_path.display());
-
-        if !file_path.exists() {
-            return Err(TagboxError::FileNotFound {
-                path: file_path.to_path_buf(),
-            });
-        }
-
-        // 首先尝试从文件名提取信息
-        let mut metadata = self.extract_from_filename(file_path);
-
-        // 然后尝试从同目录下的元数据JSON文件提取
-        if self.config.import.metadata.prefer_json {
-            if let Ok(json_metadata) = self.extract_from_json_file(file_path) {
-                metadata = self.merge_metadata(metadata, json_metadata);
-            }
-        }
-
-        if let Some(ext) = file_path.extension().and_then(OsStr::to_str) {
-            let ext_lc = ext.to_ascii_lowercase();
-            match ext_lc.as_str() {
-                "pdf" if self.config.import.metadata.fallback_pdf => {
-                    if let Ok(m) = self.extract_from_pdf(file_path) {
-                        metadata = self.merge_metadata(metadata, m);
-                    }
-                }
-                "epub" => {
-                    if let Ok(m) = self.extract_from_epub(file_path) {
-                        metadata = self.merge_metadata(metadata, m);
-                    }
-                }
-                "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" => {
-                    if let Ok(m) = self.extract_from_image(file_path) {
-                        metadata = self.merge_metadata(metadata, m);
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        // 设置默认分类（如果没有指定）
-        if metadata.category1.is_empty() {
-            metadata.category1 = self.config.import.metadata.default_category.clone();
-        }
-
-        Ok(metadata)
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool {
+        supports_type(path, mime, &["application/pdf"], &["pdf"])
     }
 
-    /// 从文件名提取基础信息
-    fn extract_from_filename(&self, path: &Path) -> ImportMetadata {
-        let filename = path.file_stem().unwrap_or_default().to_string_lossy();
-
-        // 基本的文件名解析逻辑，可以扩展为更复杂的规则
-        // 示例: "Title - Author (2023).pdf" 格式解析
-        let parts: Vec<&str> = filename.split(" - ").collect();
-
-        let mut metadata = ImportMetadata {
-            title: if !parts.is_empty() {
-                parts[0].to_string()
-            } else {
-                filename.to_string()
-            },
-            authors: vec!["Unknown".to_string()],
-            year: None,
-            publisher: None,
-            source: None,
-            category1: self.config.import.metadata.default_category.clone(),
-            category2: None,
-            category3: None,
-            tags: Vec::new(),
-            summary: None,
-            full_text: None,
-            additional_info: HashMap::new(),
-            file_metadata: None,
-            type_metadata: None,
-        };
-
-        if parts.len() > 1 {
-            // 提取作者和年份
-            let author_part = parts[1].trim();
-            if let Some((author, year)) = self.parse_author_year(author_part) {
-                metadata.authors = vec![author];
-                metadata.year = year;
-            } else {
-                metadata.authors = vec![author_part.to_string()];
-            }
-        }
-
-        metadata
-    }
-
-    /// 解析作者和年份，格式如 "Author Name (2023)"
-    fn parse_author_year(&self, s: &str) -> Option<(String, Option<i32>)> {
-        let parts: Vec<&str> = s.rsplitn(2, '(').collect();
-        if parts.len() == 2 {
-            let author = parts[1].trim();
-            let year_part = parts[0].trim();
-            if let Some(year_str) = year_part.strip_suffix(')') {
-                let year_str = year_str.trim();
-                if let Ok(year) = year_str.parse::<i32>() {
-                    return Some((author.to_string(), Some(year)));
-                }
-            }
-        }
-        None
-    }
-
-    /// 从配套的JSON文件提取元信息
-    fn extract_from_json_file(&self, file_path: &Path) -> Result<ImportMetadata> {
-        // 构造同名但扩展名为json的文件路径
-        let json_path = self.get_metadata_json_path(file_path)?;
-
-        if json_path.exists() {
-            let json_content = fs::read_to_string(&json_path).map_err(TagboxError::Io)?;
-
-            let json_value: Value =
-                serde_json::from_str(&json_content).map_err(TagboxError::Serialization)?;
-
-            return self.parse_json_metadata(json_value);
-        }
-        Err(TagboxError::FileNotFound { path: json_path })
+    fn extract(&self, path: &Path, _mime: Option<&str>) -> Result<ImportMetadata> {
+        self.extract_pdf(path)
     }
+}
 
-    /// 获取与文件关联的元数据JSON文件路径
-    fn get_metadata_json_path(&self, file_path: &Path) -> Result<PathBuf> {
-        if let Some(filename) = file_path.file_name() {
-            let parent = file_path.parent().unwrap_or(Path::new("."));
-            let filename_str = filename.to_string_lossy();
-
-            // 首先尝试查找 .meta 文件（推荐格式）
-            let meta_path = parent.join(format!("{}.meta", filename_str));
-            if meta_path.exists() {
-                return Ok(meta_path);
-            }
+/// 逐页提取的PDF文本
+#[derive(Debug, Clone, Default)]
+pub struct PdfPages {
+    /// (页码, 文本)，页码从 1 开始，空白页不包含在内
+    pub pages: Vec<(u32, String)>,
+    /// 文档总页数
+    pub page_count: usize,
+    /// 是否因超出文本预算而提前停止
+    pub truncated: bool,
+}
 
-            // 然后尝试查找 .meta.json 文件
-            let meta_json_path = parent.join(format!("{}.meta.json", filename_str));
-            if meta_json_path.exists() {
-                return Ok(meta_json_path);
+/// 逐页提取PDF全文，累计文本超过 `budget` 字节后停止
+pub fn extract_pdf_pages(file_path: &Path, budget: u64) -> Result<PdfPages> {
+    let doc = Document::load(file_path).map_err(|e| {
+        TagboxError::MetaInfoExtraction(format!("无法打开PDF文件 {}: {}", file_path.display(), e))
+    })?;
+
+    let page_numbers: Vec<u32> = doc.get_pages().keys().copied().collect();
+    let mut result = PdfPages {
+        page_count: page_numbers.len(),
+        ..Default::default()
+    };
+    let mut used = 0u64;
+
+    for page in page_numbers {
+        let text = match doc.extract_text(&[page]) {
+            Ok(text) => text,
+            Err(e) => {
+                debug!("第{}页文本提取失败: {:?}", page, e);
+                continue;
             }
+        };
 
-            // 最后尝试查找 .json 文件（用于兼容测试和简单场景）
-            let json_path = parent.join(format!("{}.json", filename_str));
-            if json_path.exists() {
-                return Ok(json_path);
-            }
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
 
-            // 如果都不存在，返回首选的 .meta 路径（让调用者处理文件不存在的情况）
-            Ok(meta_path)
-        } else {
-            Err(TagboxError::Config(format!(
-                "无法获取文件名: {}",
+        used += text.len() as u64;
+        if used > budget {
+            debug!(
+                "PDF文本超出预算 {} 字节，停止于第{}页: {}",
+                budget,
+                page,
                 file_path.display()
-            )))
+            );
+            result.truncated = true;
+            break;
         }
+
+        result.pages.push((page, text.to_string()));
     }
 
-    /// 解析JSON元数据
-    fn parse_json_metadata(&self, json: Value) -> Result<ImportMetadata> {
-        let mut metadata = ImportMetadata {
-            title: json
-                .get("title")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .unwrap_or_default(),
-            authors: json
-                .get("authors")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|a| a.as_str().map(|s| s.to_string()))
-                        .collect()
-                })
-                .unwrap_or_default(),
-            year: json.get("year").and_then(|v| v.as_i64()).map(|y| y as i32),
-            publisher: json
-                .get("publisher")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            source: json
-                .get("source")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            category1: json
-                .get("category1")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .unwrap_or(self.config.import.metadata.default_category.clone()),
-            category2: json
-                .get("category2")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            category3: json
-                .get("category3")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            tags: json
-                .get("tags")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|t| t.as_str().map(|s| s.to_string()))
-                        .collect()
-                })
-                .unwrap_or_default(),
-            summary: json
-                .get("summary")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            full_text: None,
-            additional_info: HashMap::new(),
-            file_metadata: None,
-            type_metadata: Some(json.clone()),
-        };
+    debug!(
+        "逐页提取PDF文本完成: {} 页中 {} 页有文本",
+        result.page_count,
+        result.pages.len()
+    );
 
-        // 处理额外信息
-        if let Some(obj) = json.as_object() {
-            for (key, value) in obj.iter() {
-                if ![
-                    "title",
-                    "authors",
-                    "year",
-                    "publisher",
-                    "source",
-                    "category1",
-                    "category2",
-                    "category3",
-                    "tags",
-                    "summary",
-                ]
-                .contains(&key.as_str())
-                {
-                    if let Some(value_str) = value.as_str() {
-                        metadata
-                            .additional_info
-                            .insert(key.clone(), value_str.to_string());
-                    }
-                }
-            }
-        }
-
-        Ok(metadata)
-    }
+    Ok(result)
+}
 
+impl PdfExtractor {
     /// 从PDF文件中提取完整元数据
-    fn extract_from_pdf(&self, file_path: &Path) -> Result<ImportMetadata> {
-        use lopdf::Document;
-        use std::fs;
-
+    fn extract_pdf(&self, file_path: &Path) -> Result<ImportMetadata> {
         debug!("从PDF文件提取元信息: {}", file_path.display());
 
-        // 先从文件名获取基础元数据
-        let mut meta = ImportMetadata {
-            title: String::new(),
-            authors: vec!["Unknown".to_string()],
-            year: None,
-            publisher: None,
-            source: None,
-            category1: String::new(),
-            category2: None,
-            category3: None,
-            tags: Vec::new(),
-            summary: None,
-            full_text: None,
-            additional_info: HashMap::new(),
-            file_metadata: None,
-            type_metadata: None,
-        };
+        let mut meta = ImportMetadata::default();
 
         // 尝试打开并解析PDF文件
         match Document::load(file_path) {
@@ -324,8 +128,10 @@ impl MetaInfoExtractor {
                                 // 提取作者
                                 if let Ok(author_obj) = info_dict.get(b"Author") {
                                     if let Ok(author) = self.extract_pdf_string_value(author_obj) {
-                                        if !author.is_empty() {
-                                            meta.authors = vec![author];
+                                        // 常见 "Vaswani, A.; Shazeer, N." 形式，拆成多个作者
+                                        let authors = split_author_list(&author);
+                                        if !authors.is_empty() {
+                                            meta.authors = authors;
                                         }
                                     }
                                 }
@@ -507,6 +313,12 @@ impl MetaInfoExtractor {
                     file_metadata["pdf"]["full_text"] = serde_json::json!(extracted_text);
                 }
 
+                // 出版方写在 Info 字典或 XMP 中的 DOI、ISBN 等
+                let identifiers = self.extract_pdf_identifiers(&doc);
+                if !identifiers.is_empty() {
+                    file_metadata["identifiers"] = serde_json::json!(identifiers);
+                }
+
                 meta.file_metadata = Some(file_metadata.clone());
 
                 // 构建类型特定元数据（包含创建者、生产者等信息）
@@ -533,6 +345,25 @@ impl MetaInfoExtractor {
                         serde_json::json!(modification_date);
                 }
 
+                // 书签、页面标签和批注
+                match extract_pdf_structure(file_path) {
+                    Ok(structure) => {
+                        if !structure.outline.is_empty() {
+                            type_metadata["document"]["outline"] =
+                                serde_json::json!(structure.outline);
+                        }
+                        if !structure.page_labels.is_empty() {
+                            type_metadata["document"]["page_labels"] =
+                                serde_json::json!(structure.page_labels);
+                        }
+                        if !structure.annotations.is_empty() {
+                            type_metadata["document"]["annotations"] =
+                                serde_json::json!(structure.annotations);
+                        }
+                    }
+                    Err(e) => debug!("无法读取PDF结构: {}", e),
+                }
+
                 meta.type_metadata = Some(type_metadata.clone());
 
                 debug!(
@@ -649,6 +480,43 @@ impl MetaInfoExtractor {
         Ok(meta)
     }
 
+    /// 从 Info 字典的全部字段和文档级 XMP 元数据中识别标识符
+    fn extract_pdf_identifiers(&self, doc: &Document) -> Vec<Identifier> {
+        let mut text = String::new();
+
+        if let Ok(info) = doc
+            .trailer
+            .get(b"Info")
+            .and_then(|o| doc.dereference(o))
+            .and_then(|(_, o)| o.as_dict())
+        {
+            // 保留键名，使 `/ISBN (978...)` 这样的自定义字段也能按前缀识别
+            for (key, value) in info.iter() {
+                if let Ok(value) = self.extract_pdf_string_value(value) {
+                    text.push_str(&String::from_utf8_lossy(key));
+                    text.push_str(": ");
+                    text.push_str(&value);
+                    text.push('\n');
+                }
+            }
+        }
+
+        if let Ok(lopdf::Object::Stream(stream)) = doc
+            .catalog()
+            .and_then(|c| c.get(b"Metadata"))
+            .and_then(|o| doc.dereference(o))
+            .map(|(_, o)| o)
+        {
+            let content = stream
+                .decompressed_content()
+                .unwrap_or_else(|_| stream.content.clone());
+            // `<prism:isbn>978...</prism:isbn>` 去掉尖括号后按 `isbn 978...` 识别
+            text.push_str(&String::from_utf8_lossy(&content).replace(['<', '>'], " "));
+        }
+
+        detect_identifiers(&text)
+    }
+
     /// 从PDF对象中提取字符串值
     fn extract_pdf_string_value(&self, obj: &lopdf::Object) -> Result<String> {
         use lopdf::Object;
@@ -684,213 +552,4 @@ impl MetaInfoExtractor {
             None
         }
     }
-
-    /// 从图片文件读取尺寸等信息
-    fn extract_from_image(&self, file_path: &Path) -> Result<ImportMetadata> {
-        let mut meta = self.extract_from_filename(file_path);
-
-        match imageinfo::ImageInfo::from_file_path(file_path) {
-            Ok(info) => {
-                // 构建文件特定元数据
-                let file_metadata = serde_json::json!({
-                    "image": {
-                        "width": info.size.width,
-                        "height": info.size.height,
-                        "format": format!("{:?}", info.format),
-                        "mimetype": info.mimetype
-                    }
-                });
-
-                meta.file_metadata = Some(file_metadata);
-
-                // 在additional_info中也保留基本信息，方便查询
-                meta.additional_info
-                    .insert("width".into(), info.size.width.to_string());
-                meta.additional_info
-                    .insert("height".into(), info.size.height.to_string());
-                meta.additional_info
-                    .insert("format".into(), format!("{:?}", info.format));
-            }
-            Err(e) => {
-                warn!("读取图片信息失败: {:?}", e);
-            }
-        }
-
-        Ok(meta)
-    }
-
-    /// 从EPUB文件提取完整元数据
-    fn extract_from_epub(&self, file_path: &Path) -> Result<ImportMetadata> {
-        use epub::doc::EpubDoc;
-
-        let mut meta = self.extract_from_filename(file_path);
-
-        // 尝试打开EPUB文件
-        match EpubDoc::new(file_path) {
-            Ok(mut doc) => {
-                // 提取基本元数据
-                if let Some(title) = doc.mdata("title") {
-                    meta.title = title;
-                }
-
-                // 提取作者
-                if let Some(creator) = doc.mdata("creator") {
-                    meta.authors = vec![creator];
-                }
-
-                // 提取出版商
-                if let Some(publisher) = doc.mdata("publisher") {
-                    meta.publisher = Some(publisher);
-                }
-
-                // 提取出版日期
-                if let Some(date) = doc.mdata("date") {
-                    // 尝试解析年份
-                    if let Some(year_str) = date.split('-').next() {
-                        if let Ok(year) = year_str.parse::<i32>() {
-                            meta.year = Some(year);
-                        }
-                    }
-                }
-
-                // 提取语言
-                if let Some(language) = doc.mdata("language") {
-                    meta.additional_info
-                        .insert("language".to_string(), language.clone());
-                }
-
-                // 提取描述/摘要
-                if let Some(description) = doc.mdata("description") {
-                    meta.summary = Some(description);
-                }
-
-                // 提取主题/标签
-                if let Some(subject) = doc.mdata("subject") {
-                    // 将主题转换为标签
-                    let subjects: Vec<String> = subject
-                        .split(';')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect();
-                    meta.tags.extend(subjects);
-                }
-
-                // 提取标识符（ISBN等）
-                if let Some(identifier) = doc.mdata("identifier") {
-                    meta.additional_info
-                        .insert("identifier".to_string(), identifier.clone());
-                    // 如果是ISBN，特别标记
-                    if identifier.contains("ISBN") || identifier.contains("isbn") {
-                        meta.additional_info.insert("isbn".to_string(), identifier);
-                    }
-                }
-
-                // 提取权利信息
-                if let Some(rights) = doc.mdata("rights") {
-                    meta.additional_info.insert("rights".to_string(), rights);
-                }
-
-                // 提取贡献者
-                if let Some(contributor) = doc.mdata("contributor") {
-                    meta.additional_info
-                        .insert("contributor".to_string(), contributor);
-                }
-
-                // 构建文件特定元数据
-                let has_cover = doc.get_cover().is_some();
-                let mut file_metadata = serde_json::json!({
-                    "epub": {
-                        "spine_count": doc.get_num_pages(),
-                        "has_cover": has_cover
-                    }
-                });
-
-                // 如果能获取封面，保存封面信息
-                if let Some((cover_data, _mime)) = doc.get_cover() {
-                    file_metadata["epub"]["cover_size"] = serde_json::json!(cover_data.len());
-                }
-
-                meta.file_metadata = Some(file_metadata);
-
-                // 构建类型特定元数据（书籍）
-                let mut type_metadata = serde_json::json!({
-                    "book": {}
-                });
-
-                // 添加ISBN到类型元数据
-                if let Some(isbn) = meta.additional_info.get("isbn") {
-                    type_metadata["book"]["isbn"] = serde_json::json!(isbn);
-                }
-
-                // 添加语言到类型元数据
-                if let Some(language) = meta.additional_info.get("language") {
-                    type_metadata["book"]["language"] = serde_json::json!(language);
-                }
-
-                meta.type_metadata = Some(type_metadata);
-            }
-            Err(e) => {
-                warn!("无法打开EPUB文件 {}: {:?}", file_path.display(), e);
-                // 返回基于文件名的基本元数据
-            }
-        }
-
-        Ok(meta)
-    }
-
-    /// 合并两个元数据结构，优先使用第二个非空值
-    fn merge_metadata(
-        &self,
-        base: ImportMetadata,
-        override_data: ImportMetadata,
-    ) -> ImportMetadata {
-        ImportMetadata {
-            title: if override_data.title.is_empty() {
-                base.title
-            } else {
-                override_data.title
-            },
-            authors: if override_data.authors.is_empty() {
-                base.authors
-            } else {
-                override_data.authors
-            },
-            year: override_data.year.or(base.year),
-            publisher: override_data.publisher.or(base.publisher),
-            source: override_data.source.or(base.source),
-            category1: if override_data.category1.is_empty() {
-                base.category1
-            } else {
-                override_data.category1
-            },
-            category2: if override_data.category2.is_none() {
-                base.category2
-            } else {
-                override_data.category2
-            },
-            category3: if override_data.category3.is_none() {
-                base.category3
-            } else {
-                override_data.category3
-            },
-            tags: {
-                let mut merged_tags = base.tags;
-                for tag in override_data.tags {
-                    if !merged_tags.contains(&tag) {
-                        merged_tags.push(tag);
-                    }
-                }
-                merged_tags
-            },
-            summary: override_data.summary.or(base.summary),
-            full_text: override_data.full_text.or(base.full_text),
-            additional_info: {
-                let mut merged_info = base.additional_info;
-                merged_info.extend(override_data.additional_info);
-                merged_info
-            },
-            file_metadata: override_data.file_metadata.or(base.file_metadata),
-            type_metadata: override_data.type_metadata.or(base.type_metadata),
-        }
-    }
 }
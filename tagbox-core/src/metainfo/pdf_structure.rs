@@ -0,0 +1,584 @@
+//! PDF 文档结构：书签（大纲）、页面标签和批注
+//!
+//! 使用 pdf-extract 附带的 lopdf 解析，以便高亮批注可以借助其文本定位
+//! 取回所覆盖的原文。
+
+use crate::errors::{Result, TagboxError};
+use crate::types::{Annotation, OutlineEntry, PageLabelRange};
+use pdf_extract::{
+    Dictionary, Document, MediaBox, Object, ObjectId, OutputDev, OutputError, Transform,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use tracing::debug;
+
+/// 书签和名称树的最大嵌套深度，防止损坏的文件导致无限递归
+const MAX_DEPTH: usize = 32;
+
+/// 不作为用户批注收集的类型
+const IGNORED_ANNOTATIONS: &[&str] = &["link", "widget", "popup"];
+
+/// 标记类批注，会取回其覆盖的原文
+const MARKUP_ANNOTATIONS: &[&str] = &["highlight", "underline", "strikeout", "squiggly"];
+
+/// PDF 文档结构
+#[derive(Debug, Clone, Default)]
+pub struct PdfStructure {
+    pub outline: Vec<OutlineEntry>,
+    pub page_labels: Vec<PageLabelRange>,
+    pub annotations: Vec<Annotation>,
+}
+
+impl PdfStructure {
+    /// 某页的页面标签，文档没有定义页面标签时返回 `None`
+    pub fn page_label(&self, page: u32) -> Option<String> {
+        page_label(&self.page_labels, page)
+    }
+}
+
+/// 读取PDF的书签、页面标签和批注
+pub fn extract_pdf_structure(file_path: &Path) -> Result<PdfStructure> {
+    let doc = Document::load(file_path).map_err(|e| {
+        TagboxError::MetaInfoExtraction(format!("无法打开PDF文件 {}: {}", file_path.display(), e))
+    })?;
+
+    let pages = doc.get_pages();
+    let page_numbers: HashMap<ObjectId, u32> = pages.iter().map(|(n, id)| (*id, *n)).collect();
+    let page_labels = read_page_labels(&doc);
+
+    let outline = catalog_entry(&doc, b"Outlines")
+        .and_then(|outlines| outlines.as_dict().ok())
+        .and_then(|outlines| outlines.get(b"First").ok())
+        .map(|first| {
+            let mut seen = HashSet::new();
+            read_outline(&doc, first, &page_numbers, &page_labels, &mut seen, 0)
+        })
+        .unwrap_or_default();
+
+    let annotations = read_annotations(&doc, &pages);
+
+    debug!(
+        "PDF结构: {} 个顶层书签, {} 个页面标签区间, {} 条批注: {}",
+        outline.len(),
+        page_labels.len(),
+        annotations.len(),
+        file_path.display()
+    );
+
+    Ok(PdfStructure {
+        outline,
+        page_labels,
+        annotations,
+    })
+}
+
+/// 按页面标签区间计算某页的标签
+pub fn page_label(ranges: &[PageLabelRange], page: u32) -> Option<String> {
+    let range = ranges.iter().rev().find(|r| r.start_page <= page)?;
+    let number = range.first + (page - range.start_page);
+
+    let formatted = match range.style.as_deref() {
+        Some("D") => number.to_string(),
+        Some("R") => roman(number).to_uppercase(),
+        Some("r") => roman(number),
+        Some("A") => letters(number).to_uppercase(),
+        Some("a") => letters(number),
+        _ => String::new(),
+    };
+
+    Some(format!("{}{}", range.prefix, formatted))
+}
+
+fn roman(mut number: u32) -> String {
+    const NUMERALS: &[(u32, &str)] = &[
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= *value {
+            result.push_str(numeral);
+            number -= value;
+        }
+    }
+    result
+}
+
+/// 字母编号：a..z, aa..zz, aaa..
+fn letters(number: u32) -> String {
+    if number == 0 {
+        return String::new();
+    }
+    let letter = (b'a' + ((number - 1) % 26) as u8) as char;
+    letter.to_string().repeat(((number - 1) / 26 + 1) as usize)
+}
+
+fn resolve<'a>(doc: &'a Document, object: &'a Object) -> Option<&'a Object> {
+    doc.dereference(object).ok().map(|(_, object)| object)
+}
+
+fn catalog_entry<'a>(doc: &'a Document, key: &[u8]) -> Option<&'a Object> {
+    let object = doc.catalog().ok()?.get(key).ok()?;
+    resolve(doc, object)
+}
+
+/// 解码PDF文本字符串：UTF-16BE/UTF-8 BOM、UTF-8，否则按 PDFDocEncoding（近似 Latin-1）
+fn text_string(doc: &Document, object: &Object) -> Option<String> {
+    let bytes = resolve(doc, object)?.as_str().ok()?;
+
+    let text = if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8_lossy(utf8).into_owned()
+    } else {
+        String::from_utf8(bytes.to_vec())
+            .unwrap_or_else(|_| bytes.iter().map(|&b| b as char).collect())
+    };
+
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+fn text_field(doc: &Document, dict: &Dictionary, key: &[u8]) -> Option<String> {
+    dict.get(key)
+        .ok()
+        .and_then(|object| text_string(doc, object))
+}
+
+fn number(doc: &Document, object: &Object) -> Option<f64> {
+    resolve(doc, object)?.as_float().ok().map(f64::from)
+}
+
+fn read_outline(
+    doc: &Document,
+    first: &Object,
+    page_numbers: &HashMap<ObjectId, u32>,
+    page_labels: &[PageLabelRange],
+    seen: &mut HashSet<ObjectId>,
+    depth: usize,
+) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    let mut next = Some(first);
+
+    while let Some(object) = next {
+        let Ok(id) = object.as_reference() else {
+            break;
+        };
+        if !seen.insert(id) {
+            break;
+        }
+        let Ok(item) = doc.get_dictionary(id) else {
+            break;
+        };
+
+        let page = outline_target(doc, item, page_numbers);
+        let children = match item.get(b"First") {
+            Ok(child) if depth < MAX_DEPTH => {
+                read_outline(doc, child, page_numbers, page_labels, seen, depth + 1)
+            }
+            _ => Vec::new(),
+        };
+
+        entries.push(OutlineEntry {
+            title: text_field(doc, item, b"Title").unwrap_or_default(),
+            page,
+            page_label: page
+                .and_then(|p| page_label(page_labels, p).filter(|label| *label != p.to_string())),
+            children,
+        });
+
+        next = item.get(b"Next").ok();
+    }
+
+    entries
+}
+
+/// 书签目标页：`/Dest` 或 GoTo 动作的 `/D`
+fn outline_target(
+    doc: &Document,
+    item: &Dictionary,
+    page_numbers: &HashMap<ObjectId, u32>,
+) -> Option<u32> {
+    let destination = match item.get(b"Dest") {
+        Ok(dest) => dest,
+        Err(_) => {
+            let action = resolve(doc, item.get(b"A").ok()?)?.as_dict().ok()?;
+            if action.get(b"S").ok()?.as_name().ok()? != b"GoTo" {
+                return None;
+            }
+            action.get(b"D").ok()?
+        }
+    };
+
+    destination_page(doc, destination, page_numbers, 0)
+}
+
+fn destination_page(
+    doc: &Document,
+    destination: &Object,
+    page_numbers: &HashMap<ObjectId, u32>,
+    depth: usize,
+) -> Option<u32> {
+    if depth > 1 {
+        return None;
+    }
+
+    match resolve(doc, destination)? {
+        Object::Array(parts) => match parts.first()? {
+            Object::Reference(id) => page_numbers.get(id).copied(),
+            // 部分生成器使用从 0 开始的页序号
+            Object::Integer(index) => u32::try_from(*index).ok().map(|i| i + 1),
+            _ => None,
+        },
+        Object::Dictionary(dict) => {
+            destination_page(doc, dict.get(b"D").ok()?, page_numbers, depth + 1)
+        }
+        Object::Name(name) | Object::String(name, _) => {
+            let target = named_destination(doc, name)?;
+            destination_page(doc, target, page_numbers, depth + 1)
+        }
+        _ => None,
+    }
+}
+
+/// 在 `/Dests` 字典或 `/Names` 中的名称树里查找命名目标
+fn named_destination<'a>(doc: &'a Document, name: &[u8]) -> Option<&'a Object> {
+    if let Some(dests) = catalog_entry(doc, b"Dests").and_then(|d| d.as_dict().ok()) {
+        if let Ok(target) = dests.get(name) {
+            return Some(target);
+        }
+    }
+
+    let names = catalog_entry(doc, b"Names")?.as_dict().ok()?;
+    let tree = resolve(doc, names.get(b"Dests").ok()?)?.as_dict().ok()?;
+    name_tree_lookup(doc, tree, name, 0)
+}
+
+fn name_tree_lookup<'a>(
+    doc: &'a Document,
+    node: &'a Dictionary,
+    name: &[u8],
+    depth: usize,
+) -> Option<&'a Object> {
+    if let Some(pairs) = node
+        .get(b"Names")
+        .ok()
+        .and_then(|names| resolve(doc, names))
+        .and_then(|names| names.as_array().ok())
+    {
+        for pair in pairs.chunks_exact(2) {
+            if resolve(doc, &pair[0]).and_then(|key| key.as_str().ok()) == Some(name) {
+                return Some(&pair[1]);
+            }
+        }
+    }
+
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+
+    let kids = resolve(doc, node.get(b"Kids").ok()?)?.as_array().ok()?;
+    kids.iter()
+        .filter_map(|kid| resolve(doc, kid).and_then(|kid| kid.as_dict().ok()))
+        .find_map(|kid| name_tree_lookup(doc, kid, name, depth + 1))
+}
+
+/// 读取 `/PageLabels` 数字树
+fn read_page_labels(doc: &Document) -> Vec<PageLabelRange> {
+    let Some(root) = catalog_entry(doc, b"PageLabels").and_then(|t| t.as_dict().ok()) else {
+        return Vec::new();
+    };
+
+    let mut ranges = Vec::new();
+    collect_page_labels(doc, root, &mut ranges, 0);
+    ranges.sort_by_key(|r| r.start_page);
+    ranges
+}
+
+fn collect_page_labels(
+    doc: &Document,
+    node: &Dictionary,
+    ranges: &mut Vec<PageLabelRange>,
+    depth: usize,
+) {
+    if let Some(nums) = node
+        .get(b"Nums")
+        .ok()
+        .and_then(|nums| resolve(doc, nums))
+        .and_then(|nums| nums.as_array().ok())
+    {
+        for pair in nums.chunks_exact(2) {
+            let Some(index) = resolve(doc, &pair[0]).and_then(|i| i.as_i64().ok()) else {
+                continue;
+            };
+            let Some(label) = resolve(doc, &pair[1]).and_then(|l| l.as_dict().ok()) else {
+                continue;
+            };
+
+            ranges.push(PageLabelRange {
+                start_page: u32::try_from(index).unwrap_or(0) + 1,
+                style: label
+                    .get(b"S")
+                    .ok()
+                    .and_then(|s| s.as_name().ok())
+                    .map(|s| String::from_utf8_lossy(s).into_owned()),
+                prefix: text_field(doc, label, b"P").unwrap_or_default(),
+                first: label
+                    .get(b"St")
+                    .ok()
+                    .and_then(|st| st.as_i64().ok())
+                    .and_then(|st| u32::try_from(st).ok())
+                    .unwrap_or(1),
+            });
+        }
+    }
+
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    if let Some(kids) = node
+        .get(b"Kids")
+        .ok()
+        .and_then(|kids| resolve(doc, kids))
+        .and_then(|kids| kids.as_array().ok())
+    {
+        for kid in kids {
+            if let Some(kid) = resolve(doc, kid).and_then(|k| k.as_dict().ok()) {
+                collect_page_labels(doc, kid, ranges, depth + 1);
+            }
+        }
+    }
+}
+
+/// 页面坐标中的矩形区域
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    llx: f64,
+    lly: f64,
+    urx: f64,
+    ury: f64,
+}
+
+impl Rect {
+    fn bounding(points: &[f64]) -> Option<Rect> {
+        let xs = points.iter().step_by(2);
+        let ys = points.iter().skip(1).step_by(2);
+        Some(Rect {
+            llx: xs.clone().copied().reduce(f64::min)?,
+            urx: xs.copied().reduce(f64::max)?,
+            lly: ys.clone().copied().reduce(f64::min)?,
+            ury: ys.copied().reduce(f64::max)?,
+        })
+    }
+}
+
+fn read_annotations(doc: &Document, pages: &BTreeMap<u32, ObjectId>) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+
+    for (&page, &page_id) in pages {
+        let Some(annots) = doc
+            .get_dictionary(page_id)
+            .ok()
+            .and_then(|page| page.get(b"Annots").ok())
+            .and_then(|annots| resolve(doc, annots))
+            .and_then(|annots| annots.as_array().ok())
+        else {
+            continue;
+        };
+
+        // (批注下标, 标记区域)，稍后统一取回原文
+        let mut markups: Vec<(usize, Vec<Rect>)> = Vec::new();
+
+        for annot in annots {
+            let Some(dict) = resolve(doc, annot).and_then(|a| a.as_dict().ok()) else {
+                continue;
+            };
+            let kind = dict
+                .get(b"Subtype")
+                .ok()
+                .and_then(|s| s.as_name().ok())
+                .map(|s| String::from_utf8_lossy(s).to_lowercase())
+                .unwrap_or_default();
+            if kind.is_empty() || IGNORED_ANNOTATIONS.contains(&kind.as_str()) {
+                continue;
+            }
+
+            let contents = text_field(doc, dict, b"Contents");
+            if MARKUP_ANNOTATIONS.contains(&kind.as_str()) {
+                let regions = markup_regions(doc, dict);
+                if !regions.is_empty() {
+                    markups.push((annotations.len(), regions));
+                }
+            } else if contents.is_none() {
+                // 没有文字的图形批注没有检索价值
+                continue;
+            }
+
+            annotations.push(Annotation {
+                page,
+                kind,
+                author: text_field(doc, dict, b"T"),
+                contents,
+                quoted_text: None,
+                modified_at: text_field(doc, dict, b"M"),
+            });
+        }
+
+        if !markups.is_empty() {
+            let chars = page_characters(doc, page);
+            for (index, regions) in markups {
+                annotations[index].quoted_text = quoted_text(&chars, &regions);
+            }
+        }
+    }
+
+    annotations
+}
+
+/// 标记批注覆盖的区域：`/QuadPoints` 中的每个四边形，缺失时使用 `/Rect`
+fn markup_regions(doc: &Document, dict: &Dictionary) -> Vec<Rect> {
+    let numbers = |key: &[u8]| -> Vec<f64> {
+        dict.get(key)
+            .ok()
+            .and_then(|values| resolve(doc, values))
+            .and_then(|values| values.as_array().ok())
+            .map(|values| values.iter().filter_map(|v| number(doc, v)).collect())
+            .unwrap_or_default()
+    };
+
+    let quads = numbers(b"QuadPoints");
+    if quads.len() >= 8 {
+        return quads.chunks_exact(8).filter_map(Rect::bounding).collect();
+    }
+
+    let rect = numbers(b"Rect");
+    if rect.len() == 4 {
+        return Rect::bounding(&rect).into_iter().collect();
+    }
+
+    Vec::new()
+}
+
+/// 页面上带位置的字符
+#[derive(Debug, Clone)]
+struct PlacedChar {
+    x: f64,
+    y: f64,
+    end: f64,
+    size: f64,
+    text: String,
+}
+
+#[derive(Default)]
+struct CharCollector {
+    chars: Vec<PlacedChar>,
+}
+
+impl OutputDev for CharCollector {
+    fn begin_page(
+        &mut self,
+        _page_num: u32,
+        _media_box: &MediaBox,
+        _art_box: Option<(f64, f64, f64, f64)>,
+    ) -> std::result::Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_page(&mut self) -> std::result::Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn output_character(
+        &mut self,
+        trm: &Transform,
+        width: f64,
+        _spacing: f64,
+        font_size: f64,
+        char: &str,
+    ) -> std::result::Result<(), OutputError> {
+        let size = (trm.m11 * trm.m22 - trm.m12 * trm.m21).abs().sqrt() * font_size;
+        self.chars.push(PlacedChar {
+            x: trm.m31,
+            y: trm.m32,
+            end: trm.m31 + width * size,
+            size,
+            text: char.to_string(),
+        });
+        Ok(())
+    }
+
+    fn begin_word(&mut self) -> std::result::Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_word(&mut self) -> std::result::Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn end_line(&mut self) -> std::result::Result<(), OutputError> {
+        Ok(())
+    }
+}
+
+/// 按内容流顺序收集页面上的字符及位置
+fn page_characters(doc: &Document, page: u32) -> Vec<PlacedChar> {
+    let mut collector = CharCollector::default();
+
+    // pdf-extract 在遇到不支持的字体时可能 panic，这里只影响引用原文
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        pdf_extract::output_doc_page(doc, &mut collector, page)
+    }));
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => debug!("第{}页文本定位失败: {:?}", page, e),
+        Err(_) => debug!("第{}页文本定位时 pdf-extract 异常退出", page),
+    }
+
+    collector.chars
+}
+
+/// 拼接落在标记区域内的字符
+fn quoted_text(chars: &[PlacedChar], regions: &[Rect]) -> Option<String> {
+    let mut text = String::new();
+    let mut previous: Option<&PlacedChar> = None;
+
+    for c in chars {
+        let center = (c.x + c.end) / 2.0;
+        let inside = regions.iter().any(|r| {
+            center >= r.llx && center <= r.urx && c.y >= r.lly - c.size * 0.2 && c.y <= r.ury
+        });
+        if !inside {
+            continue;
+        }
+
+        if let Some(prev) = previous {
+            let new_line = (c.y - prev.y).abs() > prev.size * 0.5;
+            if new_line || c.x > prev.end + prev.size * 0.1 {
+                text.push(' ');
+            }
+        }
+        text.push_str(&c.text);
+        previous = Some(c);
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
@@ -0,0 +1,106 @@
+use crate::config::AppConfig;
+use crate::errors::Result;
+use crate::types::ImportMetadata;
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// 按文件内容提取元数据的提取器
+///
+/// 提取器只需要填写能从文件中得到的字段，其余字段保持默认值
+/// （空字符串、空列表或 `None`），合并时会被忽略。
+pub trait MetadataExtractor: Send + Sync {
+    /// 提取器名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 是否支持该文件，`mime` 为根据内容检测到的 MIME 类型
+    fn supports(&self, path: &Path, mime: Option<&str>) -> bool;
+
+    /// 优先级，数值越大越晚合并，同一字段会覆盖低优先级的结果
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// 从文件中提取部分元数据，`mime` 与传给 [`supports`](Self::supports) 的相同
+    fn extract(&self, path: &Path, mime: Option<&str>) -> Result<ImportMetadata>;
+}
+
+/// 提取器注册表
+#[derive(Default)]
+pub struct ExtractorRegistry {
+    extractors: Vec<Box<dyn MetadataExtractor>>,
+}
+
+impl ExtractorRegistry {
+    /// 创建一个空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含内置提取器的注册表，内置提取器由 cargo feature 控制
+    #[cfg_attr(not(feature = "pdf"), allow(unused_variables))]
+    pub fn with_builtin(config: &AppConfig) -> Self {
+        let mut registry = Self::new();
+
+        #[cfg(feature = "pdf")]
+        if config.import.metadata.fallback_pdf {
+            registry.register(super::pdf::PdfExtractor);
+        }
+        #[cfg(feature = "epub")]
+        registry.register(super::epub::EpubExtractor);
+        #[cfg(feature = "image")]
+        registry.register(super::image::ImageExtractor);
+        #[cfg(feature = "office")]
+        registry.register(super::office::OfficeExtractor);
+        #[cfg(feature = "media")]
+        registry.register(super::media::MediaExtractor);
+        #[cfg(feature = "text")]
+        registry.register(super::markup::MarkupExtractor);
+        #[cfg(feature = "archive")]
+        registry.register(super::archive::ArchiveExtractor);
+
+        registry
+    }
+
+    /// 注册提取器，优先级相同时保持注册顺序
+    pub fn register<E: MetadataExtractor + 'static>(&mut self, extractor: E) {
+        self.extractors.push(Box::new(extractor));
+        self.extractors.sort_by_key(|e| e.priority());
+    }
+
+    /// 已注册提取器的名称，按合并顺序排列
+    pub fn names(&self) -> Vec<&'static str> {
+        self.extractors.iter().map(|e| e.name()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.extractors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.extractors.is_empty()
+    }
+
+    /// 支持该文件的提取器，按优先级从低到高排列
+    pub fn matching<'a>(
+        &'a self,
+        path: &'a Path,
+        mime: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a dyn MetadataExtractor> + 'a {
+        self.extractors
+            .iter()
+            .map(|e| e.as_ref())
+            .filter(move |e| e.supports(path, mime))
+    }
+}
+
+/// 判断文件是否属于给定类型：有 MIME 类型时按 MIME 判断，否则按扩展名判断
+pub fn supports_type(path: &Path, mime: Option<&str>, mimes: &[&str], extensions: &[&str]) -> bool {
+    match mime {
+        Some(mime) => mimes.iter().any(|m| m.eq_ignore_ascii_case(mime)),
+        None => path
+            .extension()
+            .and_then(OsStr::to_str)
+            .map(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+            .unwrap_or(false),
+    }
+}
@@ -0,0 +1,174 @@
+//! HTML/XHTML 转纯文本
+
+/// 内容不计入正文的元素
+const SKIPPED_ELEMENTS: &[&str] = &["head", "script", "style", "template", "noscript"];
+
+/// 结束时换行的块级元素
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// 将 HTML/XHTML 转为纯文本：去掉标签、注释和脚本，解码常见实体，
+/// 块级元素换行，行内空白合并为单个空格并去掉空行
+pub(crate) fn html_to_text(html: &str) -> String {
+    let mut raw = String::with_capacity(html.len() / 2);
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        push_decoded(&mut raw, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map(|end| &after[end + 3..]).unwrap_or("");
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").unwrap_or(after.len());
+            raw.push_str(&after[..end]);
+            rest = after.get(end + 3..).unwrap_or("");
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag_name(tag);
+
+        if !closing && !tag.ends_with('/') && SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            rest = skip_element(rest, &name);
+            continue;
+        }
+        if BLOCK_ELEMENTS.contains(&name.as_str()) {
+            raw.push('\n');
+        }
+    }
+    push_decoded(&mut raw, rest);
+
+    raw.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 小写的元素名，去掉命名空间前缀
+fn tag_name(tag: &str) -> String {
+    let name: String = tag
+        .trim_start_matches(['/', '!', '?'])
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != '/' && *c != '>')
+        .collect();
+    let local = name.rsplit(':').next().unwrap_or(&name);
+    local.to_ascii_lowercase()
+}
+
+/// 跳过元素内容，返回结束标签之后的部分
+fn skip_element<'a>(html: &'a str, name: &str) -> &'a str {
+    let lower = html.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find("</") {
+        let start = from + pos;
+        if tag_name(&lower[start + 1..]) == name {
+            return html[start..]
+                .find('>')
+                .map(|end| &html[start + end + 1..])
+                .unwrap_or("");
+        }
+        from = start + 2;
+    }
+    ""
+}
+
+/// 解码 HTML 实体，用于属性值和 `<title>` 等不经过 [`html_to_text`] 的文本
+#[cfg(feature = "text")]
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    push_decoded(&mut out, text);
+    out
+}
+
+/// 追加文本并解码 HTML 实体
+fn push_decoded(out: &mut String, text: &str) {
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let decoded = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        "mdash" => Some('—'),
+        "ndash" => Some('–'),
+        "hellip" => Some('…'),
+        "lsquo" => Some('‘'),
+        "rsquo" => Some('’'),
+        "ldquo" => Some('“'),
+        "rdquo" => Some('”'),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let value = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(value)
+        }
+    }
+}
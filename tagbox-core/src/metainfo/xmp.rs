@@ -0,0 +1,127 @@
+//! XMP 元数据包解析
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use tracing::debug;
+
+/// 从 XMP 中读取的常用字段
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Xmp {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+    /// 拍摄/创建时间，原样保留 XMP 日期字符串
+    pub created: Option<String>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub lens: Option<String>,
+}
+
+impl Xmp {
+    pub fn is_empty(&self) -> bool {
+        *self == Xmp::default()
+    }
+
+    fn set_property(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+
+        let slot = match name {
+            "DateTimeOriginal" => &mut self.created,
+            "CreateDate" | "DateCreated" if self.created.is_none() => &mut self.created,
+            "Make" => &mut self.make,
+            "Model" => &mut self.model,
+            "Lens" | "LensModel" => &mut self.lens,
+            _ => return,
+        };
+        *slot = Some(value.to_string());
+    }
+}
+
+/// 在文件内容中查找 XMP 数据包（JPEG APP1、PNG iTXt、TIFF、WebP 等都以明文保存）
+pub(crate) fn find_packet(data: &[u8]) -> Option<&str> {
+    let start = find(data, b"<x:xmpmeta").or_else(|| find(data, b"<rdf:RDF"))?;
+    let rest = &data[start..];
+    let end = find(rest, b"</x:xmpmeta>")
+        .map(|end| end + b"</x:xmpmeta>".len())
+        .or_else(|| find(rest, b"</rdf:RDF>").map(|end| end + b"</rdf:RDF>".len()))?;
+
+    std::str::from_utf8(&rest[..end]).ok()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// 解析 XMP 数据包，属性写法（`<rdf:Description xmp:CreateDate="..."/>`）和元素写法都支持
+pub(crate) fn parse(packet: &str) -> Xmp {
+    let mut reader = Reader::from_str(packet);
+    let mut xmp = Xmp::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                read_attributes(&e, &mut xmp);
+                path.push(local_name(&e));
+                text.clear();
+            }
+            Ok(Event::Empty(e)) => read_attributes(&e, &mut xmp),
+            Ok(Event::Text(e)) => {
+                if let Ok(value) = e.unescape() {
+                    text.push_str(&value);
+                }
+            }
+            Ok(Event::End(_)) => {
+                let name = path.pop().unwrap_or_default();
+                if name == "li" {
+                    // dc:title / dc:description 为 rdf:Alt，dc:subject 为 rdf:Bag
+                    let value = text.trim();
+                    match path.iter().rev().nth(1).map(String::as_str) {
+                        Some("title") if xmp.title.is_none() && !value.is_empty() => {
+                            xmp.title = Some(value.to_string());
+                        }
+                        Some("description") if xmp.description.is_none() && !value.is_empty() => {
+                            xmp.description = Some(value.to_string());
+                        }
+                        Some("subject")
+                            if !value.is_empty() && !xmp.keywords.iter().any(|k| k == value) =>
+                        {
+                            xmp.keywords.push(value.to_string());
+                        }
+                        _ => {}
+                    }
+                } else {
+                    xmp.set_property(&name, &text);
+                }
+                text.clear();
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                debug!("解析XMP失败: {}", e);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    xmp
+}
+
+fn read_attributes(element: &BytesStart, xmp: &mut Xmp) {
+    for attr in element.attributes().flatten() {
+        let key = String::from_utf8_lossy(attr.key.local_name().into_inner()).into_owned();
+        if let Ok(value) = attr.unescape_value() {
+            xmp.set_property(&key, &value);
+        }
+    }
+}
+
+fn local_name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.local_name().into_inner()).into_owned()
+}
@@ -0,0 +1,446 @@
+//! 离线 OCR
+//!
+//! 使用纯 Rust 的 [ocrs](https://github.com/robertknight/ocrs) 引擎在 CPU 上识别图片
+//! 和扫描版 PDF 中没有文本层的页面。识别结果写入 `full_text`，并在
+//! `file_metadata.ocr` 中标记文本来自 OCR；PDF 的识别结果同时写入页级索引。
+
+use crate::config::OcrConfig;
+use crate::editor::Editor;
+use crate::errors::{Result, TagboxError};
+use crate::pages::PageIndexer;
+use crate::types::FileUpdateRequest;
+use crate::utils::current_time;
+use image::{DynamicImage, GrayImage, RgbImage};
+use ocrs::{ImageSource, OcrEngineParams};
+use rten::Model;
+use sqlx::{Row, SqlitePool};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// 写入 `file_metadata.ocr.engine` 的引擎名称
+const ENGINE_NAME: &str = "ocrs";
+
+/// 小于该尺寸的图片（图标、装饰线）不做识别
+const MIN_IMAGE_SIDE: u32 = 32;
+
+/// OCR 引擎，加载检测和识别模型
+pub struct OcrEngine {
+    engine: ocrs::OcrEngine,
+    max_pages: usize,
+}
+
+impl OcrEngine {
+    /// 从配置的模型文件创建引擎
+    pub fn load(config: &OcrConfig) -> Result<Self> {
+        let detection_model = load_model(&config.detection_model)?;
+        let recognition_model = load_model(&config.recognition_model)?;
+
+        let engine = ocrs::OcrEngine::new(OcrEngineParams {
+            detection_model: Some(detection_model),
+            recognition_model: Some(recognition_model),
+            ..Default::default()
+        })
+        .map_err(|e| ocr_error("无法初始化OCR引擎", e))?;
+
+        Ok(Self {
+            engine,
+            max_pages: config.max_pages,
+        })
+    }
+
+    /// 是否支持识别该类型的文件
+    pub fn supports(mime_type: Option<&str>) -> bool {
+        match mime_type {
+            Some("application/pdf") => cfg!(feature = "pdf"),
+            Some(mime) => matches!(
+                mime,
+                "image/png"
+                    | "image/jpeg"
+                    | "image/gif"
+                    | "image/bmp"
+                    | "image/tiff"
+                    | "image/webp"
+            ),
+            None => false,
+        }
+    }
+
+    /// 识别一张图片中的文字
+    pub fn recognize_image(&self, image: &RgbImage) -> Result<String> {
+        let source = ImageSource::from_bytes(image.as_raw(), image.dimensions())
+            .map_err(|e| ocr_error("无效的图片数据", e))?;
+        let input = self
+            .engine
+            .prepare_input(source)
+            .map_err(|e| ocr_error("图片预处理失败", e))?;
+        let text = self
+            .engine
+            .get_text(&input)
+            .map_err(|e| ocr_error("文字识别失败", e))?;
+        Ok(text.trim().to_string())
+    }
+
+    /// 识别文件，返回有文字的 (页, 文本)；图片视为第 1 页，PDF 只识别没有文本层的页
+    pub fn recognize_file(
+        &self,
+        path: &Path,
+        mime_type: Option<&str>,
+    ) -> Result<Vec<(u32, String)>> {
+        match mime_type {
+            #[cfg(feature = "pdf")]
+            Some("application/pdf") => self.recognize_pdf(path),
+            _ => {
+                let image = image::open(path)
+                    .map_err(|e| ocr_error(&format!("无法读取图片 {}", path.display()), e))?;
+                let text = self.recognize_image(&image.to_rgb8())?;
+                Ok(if text.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![(1, text)]
+                })
+            }
+        }
+    }
+
+    #[cfg(feature = "pdf")]
+    fn recognize_pdf(&self, path: &Path) -> Result<Vec<(u32, String)>> {
+        use lopdf::Document;
+
+        let doc = Document::load(path).map_err(|e| {
+            TagboxError::MetaInfoExtraction(format!("无法打开PDF文件 {}: {}", path.display(), e))
+        })?;
+
+        let mut pages = Vec::new();
+        let mut scanned = 0;
+
+        for (page, page_id) in doc.get_pages() {
+            // 已有文本层的页不需要识别
+            let has_text = doc
+                .extract_text(&[page])
+                .map(|text| !text.trim().is_empty())
+                .unwrap_or(false);
+            if has_text {
+                continue;
+            }
+
+            if scanned >= self.max_pages {
+                debug!("OCR 达到页数上限 {}，停止于第{}页", self.max_pages, page);
+                break;
+            }
+            scanned += 1;
+
+            let mut texts = Vec::new();
+            for image in pdf_page_images(&doc, page_id) {
+                match self.recognize_image(&image) {
+                    Ok(text) if !text.is_empty() => texts.push(text),
+                    Ok(_) => {}
+                    Err(e) => debug!("第{}页图片识别失败: {}", page, e),
+                }
+            }
+
+            if !texts.is_empty() {
+                pages.push((page, texts.join("\n\n")));
+            }
+        }
+
+        debug!(
+            "PDF OCR 完成: 识别 {} 页，其中 {} 页有文字",
+            scanned,
+            pages.len()
+        );
+        Ok(pages)
+    }
+}
+
+/// OCR 管理器：识别库中的文件并保存结果
+pub struct OcrManager {
+    db_pool: SqlitePool,
+    engine: Arc<OcrEngine>,
+}
+
+impl OcrManager {
+    /// 创建一个新的 OCR 管理器，会加载模型文件
+    pub fn new(db_pool: SqlitePool, config: &OcrConfig) -> Result<Self> {
+        Ok(Self {
+            db_pool,
+            engine: Arc::new(OcrEngine::load(config)?),
+        })
+    }
+
+    /// 识别文件并写入 `full_text`，返回识别出文字的页数
+    ///
+    /// 识别结果同时保存在 `file_metadata.ocr.text`，重新识别时替换上次的结果而不是重复追加。
+    /// 没有识别出文字时同样记录 `file_metadata.ocr`，避免 `--all-missing` 重复处理。
+    pub async fn ocr_file(&self, file_id: &str) -> Result<usize> {
+        let editor = Editor::new(self.db_pool.clone());
+        let entry = editor.get_file(file_id).await?;
+
+        if !OcrEngine::supports(entry.mime_type.as_deref()) {
+            return Err(TagboxError::MetaInfoExtraction(format!(
+                "不支持对该类型的文件进行OCR: {}",
+                entry.mime_type.as_deref().unwrap_or("未知")
+            )));
+        }
+
+        let engine = self.engine.clone();
+        let path = entry.path.clone();
+        let mime_type = entry.mime_type.clone();
+        let pages =
+            tokio::task::spawn_blocking(move || engine.recognize_file(&path, mime_type.as_deref()))
+                .await
+                .map_err(|e| TagboxError::MetaInfoExtraction(format!("OCR任务失败: {}", e)))??;
+
+        let recognized = pages
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        // 部分页有文本层的 PDF 保留原有文本，识别结果追加在后；上次 OCR 的结果先去掉
+        let existing = entry.full_text.as_deref().unwrap_or("");
+        let previous = entry
+            .file_metadata
+            .as_ref()
+            .and_then(|m| m["ocr"]["text"].as_str());
+        let merged = merge_ocr_text(existing, previous, &recognized);
+        let full_text = (merged != existing).then_some(merged);
+
+        let mut file_metadata = entry
+            .file_metadata
+            .clone()
+            .filter(|m| m.is_object())
+            .unwrap_or_else(|| serde_json::json!({}));
+        file_metadata["ocr"] = serde_json::json!({
+            "engine": ENGINE_NAME,
+            "pages": pages.iter().map(|(page, _)| page).collect::<Vec<_>>(),
+            "text": recognized,
+            "recognized_at": current_time().to_rfc3339(),
+        });
+
+        editor
+            .update_file(
+                file_id,
+                FileUpdateRequest {
+                    full_text,
+                    file_metadata: Some(file_metadata),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        if entry.mime_type.as_deref() == Some("application/pdf") && !pages.is_empty() {
+            PageIndexer::new(self.db_pool.clone())
+                .store_pages(file_id, &pages)
+                .await?;
+        }
+
+        info!("文件 {} OCR 完成: {} 页有文字", file_id, pages.len());
+        Ok(pages.len())
+    }
+
+    /// 没有全文且尚未做过 OCR 的图片和 PDF 的 ID
+    pub async fn files_missing_text(&self) -> Result<Vec<String>> {
+        let rows = sqlx::query(
+            "SELECT id, mime_type FROM files
+             WHERE is_deleted = 0
+               AND (full_text IS NULL OR trim(full_text) = '')
+               AND json_extract(coalesce(file_metadata, '{}'), '$.ocr') IS NULL
+               AND (mime_type = 'application/pdf' OR mime_type LIKE 'image/%')
+             ORDER BY size ASC",
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(rows
+            .iter()
+            .filter(|row| OcrEngine::supports(row.get::<Option<String>, _>("mime_type").as_deref()))
+            .map(|row| row.get("id"))
+            .collect())
+    }
+
+    /// 识别所有缺少全文的文件，返回 (处理的文件数, 识别出文字的文件数)
+    pub async fn ocr_missing(&self) -> Result<(usize, usize)> {
+        let missing = self.files_missing_text().await?;
+        let mut recognized = 0;
+        for file_id in &missing {
+            match self.ocr_file(file_id).await {
+                Ok(0) => {}
+                Ok(_) => recognized += 1,
+                Err(e) => warn!("文件 {} OCR 失败: {}", file_id, e),
+            }
+        }
+        Ok((missing.len(), recognized))
+    }
+}
+
+/// 合并原有全文和 OCR 结果，先去掉末尾上次 OCR 追加的文本
+fn merge_ocr_text(existing: &str, previous: Option<&str>, recognized: &str) -> String {
+    let existing = existing.trim();
+    let base = match previous.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(previous) => existing
+            .strip_suffix(previous)
+            .map(str::trim_end)
+            .unwrap_or(existing),
+        None => existing,
+    };
+
+    [base, recognized.trim()]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn load_model(path: &Path) -> Result<Model> {
+    Model::load_file(path).map_err(|e| {
+        TagboxError::Config(format!(
+            "无法加载OCR模型 {}: {}（可从 ocrs 项目下载 .rten 模型文件）",
+            path.display(),
+            e
+        ))
+    })
+}
+
+fn ocr_error(context: &str, e: impl std::fmt::Display) -> TagboxError {
+    TagboxError::MetaInfoExtraction(format!("{}: {}", context, e))
+}
+
+/// 取出PDF页面上可解码的图片（JPEG，以及 8 位灰度/RGB 的 Flate 或未压缩图片）
+#[cfg(feature = "pdf")]
+fn pdf_page_images(doc: &lopdf::Document, page_id: lopdf::ObjectId) -> Vec<RgbImage> {
+    use lopdf::Object;
+
+    let (resources, inherited) = doc.get_page_resources(page_id);
+    let dictionaries = resources.into_iter().chain(
+        inherited
+            .iter()
+            .filter_map(|id| doc.get_dictionary(*id).ok()),
+    );
+
+    let mut images = Vec::new();
+    for resources in dictionaries {
+        let Some(xobjects) = resources
+            .get(b"XObject")
+            .ok()
+            .and_then(|x| doc.dereference(x).ok())
+            .and_then(|(_, x)| x.as_dict().ok())
+        else {
+            continue;
+        };
+
+        for (_, xobject) in xobjects.iter() {
+            let Ok(Object::Stream(stream)) = doc.dereference(xobject).map(|(_, o)| o) else {
+                continue;
+            };
+            let is_image = stream
+                .dict
+                .get(b"Subtype")
+                .and_then(|s| s.as_name())
+                .map(|s| s == b"Image")
+                .unwrap_or(false);
+            if !is_image {
+                continue;
+            }
+
+            if let Some(image) = decode_pdf_image(doc, stream) {
+                if image.width() >= MIN_IMAGE_SIDE && image.height() >= MIN_IMAGE_SIDE {
+                    images.push(image);
+                }
+            }
+        }
+    }
+    images
+}
+
+#[cfg(feature = "pdf")]
+fn decode_pdf_image(doc: &lopdf::Document, stream: &lopdf::Stream) -> Option<RgbImage> {
+    use lopdf::Object;
+
+    let dict = &stream.dict;
+    let filters: Vec<Vec<u8>> = match dict.get(b"Filter").ok() {
+        Some(Object::Name(name)) => vec![name.clone()],
+        Some(Object::Array(names)) => names
+            .iter()
+            .filter_map(|n| n.as_name().ok().map(<[u8]>::to_vec))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if filters.last().map(Vec::as_slice) == Some(&b"DCTDecode"[..]) {
+        let data = if filters.len() == 1 {
+            stream.content.clone()
+        } else {
+            stream.decompressed_content().ok()?
+        };
+        return image::load_from_memory(&data).ok().map(|i| i.to_rgb8());
+    }
+
+    // JPX、CCITT、JBIG2 等编码不做处理
+    if filters.iter().any(|f| f.as_slice() != b"FlateDecode") {
+        debug!("跳过不支持的PDF图片编码: {:?}", filters);
+        return None;
+    }
+
+    let width = u32::try_from(dict.get(b"Width").ok()?.as_i64().ok()?).ok()?;
+    let height = u32::try_from(dict.get(b"Height").ok()?.as_i64().ok()?).ok()?;
+    let bits = dict
+        .get(b"BitsPerComponent")
+        .and_then(|b| b.as_i64())
+        .unwrap_or(8);
+    if bits != 8 {
+        return None;
+    }
+
+    let components = match dict.get(b"ColorSpace").ok().map(|c| doc.dereference(c)) {
+        Some(Ok((_, Object::Name(name)))) => match name.as_slice() {
+            b"DeviceGray" | b"CalGray" => 1,
+            b"DeviceRGB" | b"CalRGB" => 3,
+            _ => return None,
+        },
+        // [/ICCBased <stream>]，分量数取自 N
+        Some(Ok((_, Object::Array(parts)))) => {
+            let profile = doc.dereference(parts.get(1)?).ok()?.1.as_stream().ok()?;
+            profile.dict.get(b"N").ok()?.as_i64().ok()?
+        }
+        _ => return None,
+    };
+
+    let data = if filters.is_empty() {
+        stream.content.clone()
+    } else {
+        stream.decompressed_content().ok()?
+    };
+
+    match components {
+        1 => GrayImage::from_raw(width, height, data)
+            .map(|gray| DynamicImage::ImageLuma8(gray).to_rgb8()),
+        3 => RgbImage::from_raw(width, height, data),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_appends_to_text_layer() {
+        assert_eq!(merge_ocr_text("第一页", None, "第二页"), "第一页\n\n第二页");
+        assert_eq!(merge_ocr_text("", None, "扫描"), "扫描");
+        assert_eq!(merge_ocr_text("原文", None, ""), "原文");
+    }
+
+    #[test]
+    fn test_merge_replaces_previous_ocr() {
+        let first = merge_ocr_text("第一页", None, "第二页");
+        assert_eq!(merge_ocr_text(&first, Some("第二页"), "第二页"), first);
+        assert_eq!(
+            merge_ocr_text(&first, Some("第二页"), "第二页 修正"),
+            "第一页\n\n第二页 修正"
+        );
+        assert_eq!(merge_ocr_text("扫描", Some("扫描"), ""), "");
+    }
+}
@@ -0,0 +1,290 @@
+use crate::errors::{Result, TagboxError};
+use crate::filetype;
+use crate::types::{PageHit, PageIndexStatus};
+use sqlx::{Row, SqlitePool};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// 逐页全文索引管理器
+pub struct PageIndexer {
+    db_pool: SqlitePool,
+}
+
+impl PageIndexer {
+    /// 创建一个新的逐页索引管理器
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 是否支持为该类型的文件建立逐页索引，EPUB 以 spine 中的章节为页
+    pub fn supports(mime_type: Option<&str>) -> bool {
+        match mime_type {
+            Some("application/pdf") => cfg!(feature = "pdf"),
+            Some("application/epub+zip") => cfg!(feature = "epub"),
+            _ => false,
+        }
+    }
+
+    /// 页级全文索引表是否可用
+    pub async fn is_available(&self) -> bool {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'file_pages_fts'",
+        )
+        .fetch_one(&self.db_pool)
+        .await
+        .map(|count| count > 0)
+        .unwrap_or(false)
+    }
+
+    /// 提取并保存文件的逐页文本，覆盖已有的页
+    pub async fn index_file(
+        &self,
+        file_id: &str,
+        path: &Path,
+        budget: u64,
+    ) -> Result<PageIndexStatus> {
+        debug!("开始逐页索引文件 {}: {}", file_id, path.display());
+
+        let path_buf = path.to_path_buf();
+        let extracted = tokio::task::spawn_blocking(move || extract_pages(&path_buf, budget))
+            .await
+            .map_err(|e| TagboxError::MetaInfoExtraction(format!("逐页提取任务失败: {}", e)))?;
+
+        let (pages, truncated) = match extracted {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("逐页提取失败 {}: {}", path.display(), e);
+                self.set_status(file_id, PageIndexStatus::Failed).await?;
+                return Ok(PageIndexStatus::Failed);
+            }
+        };
+
+        let status = if truncated {
+            PageIndexStatus::Truncated
+        } else {
+            PageIndexStatus::Complete
+        };
+
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        sqlx::query("DELETE FROM file_pages WHERE file_id = ?")
+            .bind(file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        for (page, text) in &pages {
+            sqlx::query("INSERT INTO file_pages (file_id, page, text) VALUES (?, ?, ?)")
+                .bind(file_id)
+                .bind(*page as i64)
+                .bind(text)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        sqlx::query("UPDATE files SET page_index_status = ? WHERE id = ?")
+            .bind(status.as_str())
+            .bind(file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        info!(
+            "文件 {} 逐页索引完成: {} 页 ({})",
+            file_id,
+            pages.len(),
+            status
+        );
+        Ok(status)
+    }
+
+    /// 写入部分页的文本（如 OCR 结果），覆盖这些页已有的文本
+    pub async fn store_pages(&self, file_id: &str, pages: &[(u32, String)]) -> Result<()> {
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        for (page, text) in pages {
+            // 先删除再插入，使 FTS 触发器同步更新
+            sqlx::query("DELETE FROM file_pages WHERE file_id = ? AND page = ?")
+                .bind(file_id)
+                .bind(*page as i64)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+            sqlx::query("INSERT INTO file_pages (file_id, page, text) VALUES (?, ?, ?)")
+                .bind(file_id)
+                .bind(*page as i64)
+                .bind(text)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+        Ok(())
+    }
+
+    /// 标记文件等待后台逐页索引
+    pub async fn mark_pending(&self, file_id: &str) -> Result<()> {
+        self.set_status(file_id, PageIndexStatus::Pending).await
+    }
+
+    /// 将所有支持逐页索引的文件标记为等待重新索引，返回数量
+    pub async fn mark_all_pending(&self) -> Result<usize> {
+        let result = sqlx::query(
+            "UPDATE files SET page_index_status = 'pending'
+             WHERE is_deleted = 0
+               AND mime_type IN ('application/pdf', 'application/epub+zip')",
+        )
+        .execute(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    /// 等待后台逐页索引的文件 (ID, 路径)
+    pub async fn pending_files(&self) -> Result<Vec<(String, PathBuf)>> {
+        let rows = sqlx::query(
+            "SELECT id, relative_path FROM files
+             WHERE page_index_status = 'pending' AND is_deleted = 0
+             ORDER BY size ASC",
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<String, _>("id"),
+                    PathBuf::from(row.get::<String, _>("relative_path")),
+                )
+            })
+            .collect())
+    }
+
+    /// 处理所有等待中的文件，返回成功索引的数量
+    pub async fn index_pending(&self, budget: u64) -> Result<usize> {
+        let pending = self.pending_files().await?;
+        let mut indexed = 0;
+
+        for (file_id, path) in pending {
+            match self.index_file(&file_id, &path, budget).await? {
+                PageIndexStatus::Failed => {}
+                _ => indexed += 1,
+            }
+        }
+
+        Ok(indexed)
+    }
+
+    /// 获取文件的逐页索引状态
+    pub async fn get_status(&self, file_id: &str) -> Result<Option<PageIndexStatus>> {
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT page_index_status FROM files WHERE id = ?")
+                .bind(file_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?
+                .flatten();
+
+        Ok(status.and_then(|s| match s.as_str() {
+            "pending" => Some(PageIndexStatus::Pending),
+            "complete" => Some(PageIndexStatus::Complete),
+            "truncated" => Some(PageIndexStatus::Truncated),
+            "failed" => Some(PageIndexStatus::Failed),
+            _ => None,
+        }))
+    }
+
+    /// 获取某一页的文本
+    pub async fn get_page(&self, file_id: &str, page: u32) -> Result<Option<String>> {
+        sqlx::query_scalar("SELECT text FROM file_pages WHERE file_id = ? AND page = ?")
+            .bind(file_id)
+            .bind(page as i64)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)
+    }
+
+    /// 在给定文件的逐页文本中查找命中，每个文件最多返回 `per_file` 条
+    pub async fn find_hits(
+        &self,
+        fts_query: &str,
+        file_ids: &[String],
+        per_file: usize,
+    ) -> Result<Vec<PageHit>> {
+        if file_ids.is_empty() || fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sql = format!(
+            "SELECT p.file_id, p.page,
+                    snippet(file_pages_fts, 0, '[', ']', '…', 12) AS snippet
+             FROM file_pages_fts
+             JOIN file_pages p ON p.rowid = file_pages_fts.rowid
+             WHERE file_pages_fts MATCH ? AND p.file_id IN ({})
+             ORDER BY p.file_id, rank",
+            vec!["?"; file_ids.len()].join(", ")
+        );
+
+        let mut query = sqlx::query(&sql).bind(fts_query);
+        for file_id in file_ids {
+            query = query.bind(file_id);
+        }
+
+        let rows = query
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        let mut hits: Vec<PageHit> = Vec::new();
+        for row in rows {
+            let file_id: String = row.get("file_id");
+            if hits.iter().filter(|h| h.file_id == file_id).count() >= per_file {
+                continue;
+            }
+            hits.push(PageHit {
+                file_id,
+                page: row.get::<i64, _>("page") as u32,
+                snippet: row.get("snippet"),
+            });
+        }
+
+        Ok(hits)
+    }
+
+    async fn set_status(&self, file_id: &str, status: PageIndexStatus) -> Result<()> {
+        sqlx::query("UPDATE files SET page_index_status = ? WHERE id = ?")
+            .bind(status.as_str())
+            .bind(file_id)
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+        Ok(())
+    }
+}
+
+/// 按文件类型逐页提取文本，返回 (页, 是否截断)
+fn extract_pages(path: &Path, budget: u64) -> Result<(Vec<(u32, String)>, bool)> {
+    match filetype::detect_mime(path).as_deref() {
+        #[cfg(feature = "pdf")]
+        Some("application/pdf") => {
+            let pages = crate::metainfo::extract_pdf_pages(path, budget)?;
+            Ok((pages.pages, pages.truncated))
+        }
+        #[cfg(feature = "epub")]
+        Some("application/epub+zip") => {
+            let chapters = crate::metainfo::extract_epub_chapters(path, budget)?;
+            Ok((chapters.chapters, chapters.truncated))
+        }
+        _ => Err(TagboxError::MetaInfoExtraction(format!(
+            "不支持逐页提取该文件类型: {}",
+            path.display()
+        ))),
+    }
+}
@@ -1,5 +1,7 @@
+use crate::categories::CategoryPath;
 use crate::config::AppConfig;
 use crate::errors::Result;
+use crate::filetype;
 use crate::types::ImportMetadata;
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -24,18 +26,32 @@ impl PathGenerator {
     }
 
     /// 生成目标文件名
+    ///
+    /// 原始扩展名缺失或与检测到的类型不符时，改用检测到的类型的扩展名。
     pub fn generate_filename(
         &self,
         original_filename: &str,
         metadata: &ImportMetadata,
     ) -> Result<String> {
-        let extension = Path::new(original_filename)
-            .extension()
-            .unwrap_or_default()
-            .to_string_lossy();
-
-        let mut filename =
-            self.apply_template(&self.config.import.paths.rename_template, metadata)?;
+        let original = Path::new(original_filename);
+        let extension = match metadata.mime_type.as_deref().and_then(filetype::from_mime) {
+            Some(file_type) if !filetype::extension_matches(original, &file_type) => {
+                file_type.extension.to_string()
+            }
+            _ => original
+                .extension()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+        };
+
+        // 扩展名在清理非法字符之后追加，模板末尾的 ".{ext}" 不再重复添加
+        let template = &self.config.import.paths.rename_template;
+        let template = template.strip_suffix(".{ext}").unwrap_or(template);
+        let mut filename = self.apply_template(template, metadata)?;
+
+        // 内容类型未知时 {ext} 使用原始扩展名
+        filename = filename.replace("{ext}", &extension);
 
         // 替换非法字符
         filename = self.sanitize_filename(&filename);
@@ -57,6 +73,13 @@ impl PathGenerator {
         // 替换分类模板中的 {filename} 占位符
         relative_path = relative_path.replace("{filename}", filename);
 
+        // 内容类型未知时 {ext} 使用文件名中的扩展名
+        let extension = Path::new(filename)
+            .extension()
+            .unwrap_or_default()
+            .to_string_lossy();
+        relative_path = relative_path.replace("{ext}", &extension);
+
         // 构建完整路径
         let full_path = self.config.import.paths.storage_dir.join(relative_path);
 
@@ -86,14 +109,27 @@ impl PathGenerator {
             vars.insert("publisher".to_string(), publisher.clone());
         }
 
+        // {category_path} 为完整分类路径，{categoryN} 为第 N 级分类
+        let category = CategoryPath::from_columns(
+            &metadata.category1,
+            metadata.category2.as_deref(),
+            metadata.category3.as_deref(),
+        );
         vars.insert("category1".to_string(), metadata.category1.clone());
-
-        if let Some(category2) = &metadata.category2 {
-            vars.insert("category2".to_string(), category2.clone());
+        for (index, segment) in category.segments().iter().enumerate().skip(1) {
+            vars.insert(format!("category{}", index + 1), segment.clone());
+        }
+        if !category.is_empty() {
+            vars.insert("category_path".to_string(), category.to_string());
         }
 
-        if let Some(category3) = &metadata.category3 {
-            vars.insert("category3".to_string(), category3.clone());
+        // 根据内容检测到的类型确定扩展名
+        if let Some(ext) = metadata
+            .mime_type
+            .as_deref()
+            .and_then(filetype::extension_for_mime)
+        {
+            vars.insert("ext".to_string(), ext.to_string());
         }
 
         // 应用模板
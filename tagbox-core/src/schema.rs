@@ -1,4 +1,5 @@
 use crate::errors::{Result, TagboxError};
+use crate::tags::TagManager;
 use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
 use std::fs;
 use std::path::Path;
@@ -89,14 +90,25 @@ impl Database {
                 source_url TEXT,
                 summary TEXT,
                 
-                -- 简化的三级分类（支持 "cat1/cat2/cat3" 格式输入）
+                -- 导入时的源文件信息
+                original_path TEXT,
+                size INTEGER,
+                mime_type TEXT,
+                file_mtime TEXT,
+                
+                -- 分类：category_path 为完整路径（分类的唯一来源，用于前缀查询）；
+                -- category1/2/3 由触发器派生，第三级及更深层级以 "/" 连接存于 category3
                 category1 TEXT,
                 category2 TEXT,
                 category3 TEXT,
+                category_path TEXT,
                 
                 -- 全文内容（前几页或部分内容，用于搜索）
                 full_text TEXT,
                 
+                -- 页级全文索引状态（pending/complete/truncated/failed）
+                page_index_status TEXT,
+                
                 -- 系统字段
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
@@ -113,6 +125,16 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
+        // 为旧数据库补充后续新增的列
+        self.ensure_column("files", "original_path", "TEXT").await?;
+        self.ensure_column("files", "size", "INTEGER").await?;
+        self.ensure_column("files", "mime_type", "TEXT").await?;
+        self.ensure_column("files", "file_mtime", "TEXT").await?;
+        self.ensure_column("files", "page_index_status", "TEXT")
+            .await?;
+        self.ensure_column("files", "category_path", "TEXT").await?;
+        self.create_category_path_sync().await?;
+
         // 创建作者表
         sqlx::query(
             r#"
@@ -123,6 +145,7 @@ impl Database {
                 aliases TEXT,
                 bio TEXT,
                 homepage TEXT,
+                orcid TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
                 is_deleted INTEGER NOT NULL DEFAULT 0
@@ -132,14 +155,17 @@ impl Database {
         .execute(&self.pool)
         .await
         .map_err(TagboxError::Database)?;
+        self.ensure_column("authors", "orcid", "TEXT").await?;
 
-        // 创建文件-作者关联表
+        // 创建文件-作者关联表：role 为署名角色，position 为同一角色内的署名顺序
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS file_authors (
                 file_id TEXT NOT NULL,
                 author_id TEXT NOT NULL,
-                PRIMARY KEY (file_id, author_id),
+                role TEXT NOT NULL DEFAULT 'author',
+                position INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (file_id, author_id, role),
                 FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE,
                 FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
             );
@@ -148,6 +174,7 @@ impl Database {
         .execute(&self.pool)
         .await
         .map_err(TagboxError::Database)?;
+        self.upgrade_file_authors().await?;
 
         // 创建标签表
         sqlx::query(
@@ -167,6 +194,24 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
+        // 创建标签别名表：alias 是旧路径，解析为 tag_id 指向的标签
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS tag_aliases (
+                alias TEXT PRIMARY KEY,
+                tag_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+        TagManager::new(self.pool.clone())
+            .repair_hierarchy()
+            .await?;
+
         // 创建文件-标签关联表
         sqlx::query(
             r#"
@@ -183,14 +228,34 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
-        // 创建作者别名表
+        // 旧版本的作者别名表是 (alias, author_id) 结构，与 AuthorManager 使用的
+        // (alias_id, canonical_id) 不一致且从未写入，直接按新结构重建
+        let alias_columns: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_info('author_aliases')")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        if alias_columns.iter().any(|c| c == "alias")
+            && !alias_columns.iter().any(|c| c == "alias_id")
+        {
+            info!("重建 author_aliases 表");
+            sqlx::query("DROP TABLE author_aliases")
+                .execute(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        // 创建作者别名表：alias_id 指向的作者是 canonical_id 的别名
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS author_aliases (
-                alias TEXT NOT NULL,
-                author_id TEXT NOT NULL,
-                PRIMARY KEY (alias),
-                FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
+                alias_id TEXT NOT NULL,
+                canonical_id TEXT NOT NULL,
+                note TEXT,
+                merged_at TEXT,
+                PRIMARY KEY (alias_id),
+                FOREIGN KEY (alias_id) REFERENCES authors(id) ON DELETE CASCADE,
+                FOREIGN KEY (canonical_id) REFERENCES authors(id) ON DELETE CASCADE
             );
             "#,
         )
@@ -214,6 +279,23 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
+        // 创建文件别名表（重复导入时记录的其他原始路径/文件名）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS file_aliases (
+                file_id TEXT NOT NULL,
+                original_path TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                added_at TEXT NOT NULL,
+                PRIMARY KEY (file_id, original_path),
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
         // 创建文件关联表
         sqlx::query(
             r#"
@@ -232,6 +314,96 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
+        // 创建页级文本表（PDF 等分页文档的逐页全文）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS file_pages (
+                file_id TEXT NOT NULL,
+                page INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                PRIMARY KEY (file_id, page),
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        self.create_page_fts().await?;
+
+        // 创建PDF批注表（高亮、笔记等及其引用的原文）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS file_annotations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_id TEXT NOT NULL,
+                page INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                author TEXT,
+                contents TEXT,
+                quoted_text TEXT,
+                modified_at TEXT,
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_file_annotations_file ON file_annotations(file_id, page)",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        self.create_annotation_fts().await?;
+
+        // 创建文献标识符表（规范化后的 DOI、arXiv 编号、ISBN、ISSN）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS file_identifiers (
+                file_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (file_id, kind, value),
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_file_identifiers_value ON file_identifiers(kind, value)",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        // 创建元数据补全缓存表（数据源的原始查询结果，response 为空表示未找到）
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS enrichment_cache (
+                provider TEXT NOT NULL,
+                query_key TEXT NOT NULL,
+                response TEXT,
+                fetched_at TEXT NOT NULL,
+                PRIMARY KEY (provider, query_key)
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        // 旧版本的 files_fts 以 files 为外部内容表，但 files 没有 authors/tags 列，
+        // 导致更新和删除索引失败；改为自带内容的索引表，重建后重新填充
+        let rebuild_fts = self.drop_external_content_fts().await?;
+
         // 创建全文搜索虚拟表 (使用 Signal CJK 分词器，包含full_text)
         let create_fts_result = sqlx::query(
             r#"
@@ -241,8 +413,6 @@ impl Database {
                 summary,
                 tags,
                 full_text,
-                content='files',
-                content_rowid='rowid',
                 tokenize='signal_tokenizer unicode61 remove_diacritics 1'
             );
             "#,
@@ -267,8 +437,6 @@ impl Database {
                         summary,
                         tags,
                         full_text,
-                        content='files',
-                        content_rowid='rowid',
                         tokenize='unicode61 remove_diacritics 1'
                     );
                     "#,
@@ -290,7 +458,6 @@ impl Database {
                                 summary,
                                 tags,
                                 full_text,
-                                content='files',
                                 tokenize=simple
                             );
                             "#,
@@ -332,9 +499,9 @@ impl Database {
         sqlx::query(
             r#"
             CREATE TRIGGER IF NOT EXISTS files_au AFTER UPDATE ON files BEGIN
-                DELETE FROM files_fts WHERE rowid = old.rowid;
-                INSERT INTO files_fts(rowid, title, authors, summary, tags, full_text)
-                VALUES (new.rowid, new.title, '', new.summary, '', COALESCE(new.full_text, ''));
+                UPDATE files_fts
+                SET title = new.title, summary = new.summary, full_text = COALESCE(new.full_text, '')
+                WHERE rowid = old.rowid;
             END;
             "#,
         )
@@ -342,6 +509,10 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
+        if rebuild_fts {
+            self.populate_files_fts().await?;
+        }
+
         // 创建系统配置表
         sqlx::query(
             r#"
@@ -374,6 +545,46 @@ impl Database {
                 changed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 changed_by TEXT,
                 reason TEXT,
+                batch_id TEXT,
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+        self.ensure_column("file_history", "batch_id", "TEXT")
+            .await?;
+
+        // 创建批量编辑批次表，一个批次可整体撤销
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS edit_batches (
+                id TEXT PRIMARY KEY,
+                description TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                undone_at TEXT
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        // 批次中每个文件修改前后的标签（JSON 数组）、分类路径和相对存储路径
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS edit_batch_files (
+                batch_id TEXT NOT NULL,
+                file_id TEXT NOT NULL,
+                old_tags TEXT NOT NULL,
+                new_tags TEXT NOT NULL,
+                old_category TEXT NOT NULL,
+                new_category TEXT NOT NULL,
+                old_path TEXT NOT NULL,
+                new_path TEXT NOT NULL,
+                PRIMARY KEY (batch_id, file_id),
+                FOREIGN KEY (batch_id) REFERENCES edit_batches(id) ON DELETE CASCADE,
                 FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
             );
             "#,
@@ -411,6 +622,16 @@ impl Database {
             .await
             .map_err(TagboxError::Database)?;
 
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_files_size ON files(size);")
+            .execute(&self.pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_files_mime_type ON files(mime_type);")
+            .execute(&self.pool)
+            .await
+            .map_err(TagboxError::Database)?;
+
         sqlx::query(
             "CREATE INDEX IF NOT EXISTS idx_file_history_file_id ON file_history(file_id);",
         )
@@ -425,6 +646,13 @@ impl Database {
         .await
         .map_err(TagboxError::Database)?;
 
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_file_history_batch_id ON file_history(batch_id);",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_file_access_stats_access_date ON file_access_stats(access_date);")
             .execute(&self.pool)
             .await
@@ -434,10 +662,338 @@ impl Database {
         Ok(())
     }
 
+    /// 创建维护分类列的触发器和索引，并把旧数据的分类迁移到 `category_path`
+    ///
+    /// `category_path` 是分类的唯一来源，`category1/2/3` 由它派生；只写分类列的旧代码
+    /// （不改 `category_path`）由兼容触发器把三列拼回完整路径。
+    async fn create_category_path_sync(&self) -> Result<()> {
+        let path_of = |row: &str| {
+            format!(
+                "TRIM(COALESCE({row}.category1, '') \
+                 || COALESCE('/' || NULLIF({row}.category2, ''), '') \
+                 || COALESCE('/' || NULLIF({row}.category3, ''), ''), '/')"
+            )
+        };
+        // 第一段、第二段存入 category1/2，其余部分整体存入 category3
+        let path = "new.category_path";
+        let rest = format!("substr({path}, instr({path}, '/') + 1)");
+        let columns_of_path = format!(
+            "category1 = CASE WHEN instr({path}, '/') = 0 THEN {path} \
+                 ELSE substr({path}, 1, instr({path}, '/') - 1) END,
+             category2 = CASE WHEN instr({path}, '/') = 0 THEN NULL \
+                 WHEN instr({rest}, '/') = 0 THEN {rest} \
+                 ELSE substr({rest}, 1, instr({rest}, '/') - 1) END,
+             category3 = CASE WHEN instr({path}, '/') = 0 OR instr({rest}, '/') = 0 THEN NULL \
+                 ELSE substr({rest}, instr({rest}, '/') + 1) END"
+        );
+
+        for statement in [
+            // 旧版本由分类列同步 category_path 的触发器
+            "DROP TRIGGER IF EXISTS files_category_path_ai".to_string(),
+            "DROP TRIGGER IF EXISTS files_category_path_au".to_string(),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS files_category_columns_ai
+                AFTER INSERT ON files WHEN new.category_path IS NOT NULL BEGIN
+                    UPDATE files SET {columns_of_path} WHERE rowid = new.rowid;
+                END;"
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS files_category_columns_au
+                AFTER UPDATE OF category_path ON files WHEN new.category_path IS NOT NULL BEGIN
+                    UPDATE files SET {columns_of_path} WHERE rowid = new.rowid;
+                END;"
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS files_category_path_from_columns_ai
+                AFTER INSERT ON files WHEN new.category_path IS NULL BEGIN
+                    UPDATE files SET category_path = {} WHERE rowid = new.rowid;
+                END;",
+                path_of("new")
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS files_category_path_from_columns_au
+                AFTER UPDATE OF category1, category2, category3 ON files
+                WHEN new.category_path IS old.category_path AND new.category_path IS NOT {0}
+                BEGIN
+                    UPDATE files SET category_path = {0} WHERE rowid = new.rowid;
+                END;",
+                path_of("new")
+            ),
+            format!(
+                "UPDATE files SET category_path = {} WHERE category_path IS NULL",
+                path_of("files")
+            ),
+            "CREATE INDEX IF NOT EXISTS idx_files_category_path ON files(category_path)"
+                .to_string(),
+        ] {
+            sqlx::query(&statement)
+                .execute(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        Ok(())
+    }
+
+    /// 创建页级全文索引及其触发器，FTS5 不可用时跳过（页级搜索将被禁用）
+    async fn create_page_fts(&self) -> Result<()> {
+        let tokenizers = [
+            "signal_tokenizer unicode61 remove_diacritics 1",
+            "unicode61 remove_diacritics 1",
+        ];
+
+        let mut created = false;
+        for tokenizer in tokenizers {
+            let result = sqlx::query(&format!(
+                r#"
+                CREATE VIRTUAL TABLE IF NOT EXISTS file_pages_fts USING fts5(
+                    text,
+                    content='file_pages',
+                    content_rowid='rowid',
+                    tokenize='{}'
+                );
+                "#,
+                tokenizer
+            ))
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => {
+                    info!("页级FTS5表创建成功，分词器: {}", tokenizer);
+                    created = true;
+                    break;
+                }
+                Err(e) => warn!("无法使用分词器 {} 创建页级FTS5表: {}", tokenizer, e),
+            }
+        }
+
+        if !created {
+            warn!("页级全文索引不可用，页码搜索将被禁用");
+            return Ok(());
+        }
+
+        for trigger in [
+            r#"
+            CREATE TRIGGER IF NOT EXISTS file_pages_ai AFTER INSERT ON file_pages BEGIN
+                INSERT INTO file_pages_fts(rowid, text) VALUES (new.rowid, new.text);
+            END;
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS file_pages_ad AFTER DELETE ON file_pages BEGIN
+                INSERT INTO file_pages_fts(file_pages_fts, rowid, text)
+                VALUES ('delete', old.rowid, old.text);
+            END;
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS file_pages_au AFTER UPDATE ON file_pages BEGIN
+                INSERT INTO file_pages_fts(file_pages_fts, rowid, text)
+                VALUES ('delete', old.rowid, old.text);
+                INSERT INTO file_pages_fts(rowid, text) VALUES (new.rowid, new.text);
+            END;
+            "#,
+        ] {
+            sqlx::query(trigger)
+                .execute(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        Ok(())
+    }
+
+    /// 创建批注全文索引，分词器不可用时跳过（批注搜索将被禁用）
+    async fn create_annotation_fts(&self) -> Result<()> {
+        let tokenizers = [
+            "signal_tokenizer unicode61 remove_diacritics 1",
+            "unicode61 remove_diacritics 1",
+        ];
+
+        let mut created = false;
+        for tokenizer in tokenizers {
+            let result = sqlx::query(&format!(
+                r#"
+                CREATE VIRTUAL TABLE IF NOT EXISTS file_annotations_fts USING fts5(
+                    contents,
+                    quoted_text,
+                    content='file_annotations',
+                    content_rowid='id',
+                    tokenize='{}'
+                );
+                "#,
+                tokenizer
+            ))
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => {
+                    info!("批注FTS5表创建成功，分词器: {}", tokenizer);
+                    created = true;
+                    break;
+                }
+                Err(e) => warn!("无法使用分词器 {} 创建批注FTS5表: {}", tokenizer, e),
+            }
+        }
+
+        if !created {
+            warn!("批注全文索引不可用，批注搜索将被禁用");
+            return Ok(());
+        }
+
+        for trigger in [
+            r#"
+            CREATE TRIGGER IF NOT EXISTS file_annotations_ai AFTER INSERT ON file_annotations BEGIN
+                INSERT INTO file_annotations_fts(rowid, contents, quoted_text)
+                VALUES (new.id, new.contents, new.quoted_text);
+            END;
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS file_annotations_ad AFTER DELETE ON file_annotations BEGIN
+                INSERT INTO file_annotations_fts(file_annotations_fts, rowid, contents, quoted_text)
+                VALUES ('delete', old.id, old.contents, old.quoted_text);
+            END;
+            "#,
+            r#"
+            CREATE TRIGGER IF NOT EXISTS file_annotations_au AFTER UPDATE ON file_annotations BEGIN
+                INSERT INTO file_annotations_fts(file_annotations_fts, rowid, contents, quoted_text)
+                VALUES ('delete', old.id, old.contents, old.quoted_text);
+                INSERT INTO file_annotations_fts(rowid, contents, quoted_text)
+                VALUES (new.id, new.contents, new.quoted_text);
+            END;
+            "#,
+        ] {
+            sqlx::query(trigger)
+                .execute(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+
+        Ok(())
+    }
+
+    /// 旧版本的 file_authors 没有角色和顺序，主键为 (file_id, author_id)，按新结构重建；
+    /// 原有关联都视为作者，按插入顺序编号
+    async fn upgrade_file_authors(&self) -> Result<()> {
+        let columns: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_info('file_authors')")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        if columns.iter().any(|c| c == "role") {
+            return Ok(());
+        }
+
+        info!("为 file_authors 添加署名角色和顺序");
+        let mut tx = self.pool.begin().await.map_err(TagboxError::Database)?;
+        for statement in [
+            r#"
+            CREATE TABLE file_authors_new (
+                file_id TEXT NOT NULL,
+                author_id TEXT NOT NULL,
+                role TEXT NOT NULL DEFAULT 'author',
+                position INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (file_id, author_id, role),
+                FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE,
+                FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
+            )
+            "#,
+            r#"
+            INSERT INTO file_authors_new (file_id, author_id, role, position)
+            SELECT file_id, author_id, 'author',
+                (SELECT COUNT(*) FROM file_authors prev
+                 WHERE prev.file_id = fa.file_id AND prev.rowid < fa.rowid)
+            FROM file_authors fa
+            "#,
+            "DROP TABLE file_authors",
+            "ALTER TABLE file_authors_new RENAME TO file_authors",
+        ] {
+            sqlx::query(statement)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        Ok(())
+    }
+
     /// 获取数据库连接池引用
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// 删除以 files 为外部内容表的旧 files_fts 及其触发器，返回是否删除
+    async fn drop_external_content_fts(&self) -> Result<bool> {
+        let sql: Option<String> = sqlx::query_scalar(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'files_fts'",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+        if !sql.is_some_and(|sql| sql.replace(['\'', '"'], "").contains("content=files")) {
+            return Ok(false);
+        }
+
+        info!("重建 files_fts 全文索引");
+        for statement in [
+            "DROP TRIGGER IF EXISTS files_ai",
+            "DROP TRIGGER IF EXISTS files_ad",
+            "DROP TRIGGER IF EXISTS files_au",
+            "DROP TABLE files_fts",
+        ] {
+            sqlx::query(statement)
+                .execute(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+        Ok(true)
+    }
+
+    /// 按文件、署名和标签重新填充 files_fts
+    async fn populate_files_fts(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO files_fts(rowid, title, authors, summary, tags, full_text)
+            SELECT f.rowid, f.title,
+                COALESCE((SELECT group_concat(a.name, ' ') FROM file_authors fa
+                          JOIN authors a ON a.id = fa.author_id
+                          WHERE fa.file_id = f.id), ''),
+                f.summary,
+                COALESCE((SELECT group_concat(t.path, ' ') FROM file_tags ft
+                          JOIN tags t ON t.id = ft.tag_id
+                          WHERE ft.file_id = f.id), ''),
+                COALESCE(f.full_text, '')
+            FROM files f
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(TagboxError::Database)?;
+        Ok(())
+    }
+
+    /// 如果表中缺少指定列则添加（用于升级旧数据库）
+    async fn ensure_column(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let columns: Vec<String> =
+            sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}')", table))
+                .fetch_all(&self.pool)
+                .await
+                .map_err(TagboxError::Database)?;
+
+        if !columns.iter().any(|c| c == column) {
+            info!("为表 {} 添加列 {}", table, column);
+            sqlx::query(&format!(
+                "ALTER TABLE {} ADD COLUMN {} {}",
+                table, column, definition
+            ))
+            .execute(&self.pool)
+            .await
+            .map_err(TagboxError::Database)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// 启用FTS5并测试Signal tokenizer
@@ -1,17 +1,38 @@
+use crate::annotations::AnnotationManager;
+use crate::authors::AuthorManager;
+use crate::categories::{normalize_category_path, CATEGORY_SUBTREE_CLAUSE};
 use crate::config::AppConfig;
 use crate::errors::{Result, TagboxError};
-use crate::types::{FileEntry, SearchOptions, SearchResult};
-use crate::utils::require_field;
+use crate::identifiers::normalize_identifier;
+use crate::pages::PageIndexer;
+use crate::tags::{normalize_tag_path, TagManager};
+use crate::types::{
+    AuthorRole, FileEntry, Identifier, IdentifierKind, SearchOptions, SearchResult,
+};
+use crate::utils::{parse_size, require_field};
 use sqlx::{sqlite::SqliteArguments, Arguments, Row, SqlitePool};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tracing::{debug, warn};
 
+/// 每个文件最多返回的逐页命中数
+const PAGE_HITS_PER_FILE: usize = 3;
+
+/// 每个文件最多返回的批注命中数
+const ANNOTATION_HITS_PER_FILE: usize = 3;
+
+/// 匹配标签及其所有子标签：`lang/rust` 命中 `lang/rust` 和 `lang/rust/...`
+///
+/// 子标签用路径范围 `(tag/, tag0)` 判断（`0` 紧跟在 `/` 之后），不受 LIKE 通配符影响。
+const TAG_SUBTREE_CLAUSE: &str = "(t.path = ? OR (t.path > ? AND t.path < ?))";
+
 /// 查询解析器和执行器
 pub struct Searcher {
     config: AppConfig,
     db_pool: SqlitePool,
     fts5_signal_available: bool,
+    page_index_available: bool,
+    annotation_index_available: bool,
 }
 
 impl Searcher {
@@ -26,10 +47,17 @@ impl Searcher {
             debug!("Signal FTS5 分词器不可用，将使用标准搜索");
         }
 
+        // 检查逐页全文索引是否可用
+        let page_index_available = PageIndexer::new(db_pool.clone()).is_available().await;
+        let annotation_index_available =
+            AnnotationManager::new(db_pool.clone()).is_available().await;
+
         Self {
             config,
             db_pool,
             fts5_signal_available,
+            page_index_available,
+            annotation_index_available,
         }
     }
 
@@ -103,8 +131,7 @@ impl Searcher {
                 authors,
                 summary,
                 tags,
-                content='files',
-                content_rowid='rowid',
+                full_text,
                 tokenize='{}'
             );
             "#,
@@ -154,48 +181,54 @@ impl Searcher {
     /// 更新单个文件的FTS索引
     pub async fn update_fts_for_file(&self, file_id: &str) -> Result<()> {
         // 获取文件信息
-        let file = sqlx::query!(
-            r#"
-            SELECT rowid, title, summary FROM files WHERE id = ?
-            "#,
-            file_id
-        )
-        .fetch_optional(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?
-        .ok_or_else(|| TagboxError::InvalidFileId(file_id.to_string()))?;
-
-        // 获取作者
-        let authors = self.get_file_authors(file_id).await?;
-        let authors_text = authors.join(" ");
+        let file = sqlx::query("SELECT rowid, title, summary, full_text FROM files WHERE id = ?")
+            .bind(file_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?
+            .ok_or_else(|| TagboxError::InvalidFileId(file_id.to_string()))?;
+        let rowid: i64 = file.get("rowid");
+        let title: String = file.get("title");
+        let summary: Option<String> = file.get("summary");
+        let full_text: Option<String> = file.get("full_text");
+
+        // 获取作者和其他署名
+        let (authors, contributors) = AuthorManager::new(self.db_pool.clone())
+            .get_file_authors(file_id)
+            .await?;
+        let authors_text = authors
+            .iter()
+            .chain(contributors.iter().map(|c| &c.name))
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ");
 
         // 获取标签
-        let tags = self.get_file_tags(file_id).await?;
+        let tags = TagManager::new(self.db_pool.clone())
+            .get_file_tags(file_id)
+            .await?;
         let tags_text = tags.join(" ");
 
         // 删除旧索引
-        sqlx::query!(
-            r#"
-            DELETE FROM files_fts WHERE rowid = ?
-            "#,
-            file.rowid
-        )
-        .execute(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
+        sqlx::query("DELETE FROM files_fts WHERE rowid = ?")
+            .bind(rowid)
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
 
         // 添加新索引
-        sqlx::query!(
+        sqlx::query(
             r#"
-            INSERT INTO files_fts (rowid, title, authors, summary, tags)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO files_fts (rowid, title, authors, summary, tags, full_text)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
-            file.rowid,
-            file.title,
-            authors_text,
-            file.summary,
-            tags_text
         )
+        .bind(rowid)
+        .bind(&title)
+        .bind(&authors_text)
+        .bind(&summary)
+        .bind(&tags_text)
+        .bind(full_text.unwrap_or_default())
         .execute(&self.db_pool)
         .await
         .map_err(TagboxError::Database)?;
@@ -218,7 +251,17 @@ impl Searcher {
             include_deleted: false,
         });
 
-        let parsed = self.parse_query(query)?;
+        let mut parsed = self.parse_query(query)?;
+
+        // 标签别名解析为目标标签，`tag:ml` 同样匹配已合并到的 `machine-learning`
+        let tag_manager = TagManager::new(self.db_pool.clone());
+        for tag in parsed
+            .include_tags
+            .iter_mut()
+            .chain(parsed.exclude_tags.iter_mut())
+        {
+            *tag = tag_manager.resolve_tag_path(tag).await?;
+        }
 
         // 构建基本SQL查询
         let mut sql = String::from(
@@ -226,8 +269,10 @@ impl Searcher {
             SELECT 
                 f.id, f.title, f.filename, f.initial_hash, f.current_hash,
                 f.relative_path, f.year, f.publisher, f.category1, f.category2, f.category3,
-                f.summary, f.full_text,
-                f.created_at, f.updated_at, f.is_deleted
+                f.summary, f.full_text, f.source_url,
+                f.original_path, f.size, f.mime_type, f.file_mtime,
+                f.created_at, f.updated_at, f.is_deleted,
+                f.file_metadata, f.type_metadata
             FROM files f
             "#,
         );
@@ -235,38 +280,6 @@ impl Searcher {
         let mut count_sql = String::from("SELECT COUNT(*) as count FROM files f");
         let mut params = Vec::new();
 
-        // 应用作者过滤条件
-        if !parsed.authors.is_empty() {
-            sql.push_str(
-                r#"
-                JOIN file_authors fa ON f.id = fa.file_id
-                JOIN authors a ON fa.author_id = a.id
-                "#,
-            );
-            count_sql.push_str(
-                r#"
-                JOIN file_authors fa ON f.id = fa.file_id
-                JOIN authors a ON fa.author_id = a.id
-                "#,
-            );
-        }
-
-        // 应用标签过滤条件
-        if !parsed.include_tags.is_empty() || !parsed.exclude_tags.is_empty() {
-            sql.push_str(
-                r#"
-                LEFT JOIN file_tags ft ON f.id = ft.file_id
-                LEFT JOIN tags t ON ft.tag_id = t.id
-                "#,
-            );
-            count_sql.push_str(
-                r#"
-                LEFT JOIN file_tags ft ON f.id = ft.file_id
-                LEFT JOIN tags t ON ft.tag_id = t.id
-                "#,
-            );
-        }
-
         // WHERE 子句
         let mut where_clauses = Vec::new();
 
@@ -276,37 +289,58 @@ impl Searcher {
             params.push(format!("%{}%", title));
         }
 
-        // 处理包含的标签
+        // 处理包含的标签：匹配标签本身及其所有子标签，多个标签任一匹配即可
         if !parsed.include_tags.is_empty() {
-            let placeholders = vec!["?"; parsed.include_tags.len()].join(", ");
-            where_clauses.push(format!("t.name IN ({})", placeholders));
+            where_clauses.push(format!(
+                "f.id IN (
+                SELECT ft.file_id FROM file_tags ft
+                JOIN tags t ON ft.tag_id = t.id
+                WHERE {}
+            )",
+                vec![TAG_SUBTREE_CLAUSE; parsed.include_tags.len()].join(" OR ")
+            ));
             for tag in &parsed.include_tags {
-                params.push(tag.clone());
+                params.extend(subtree_params(tag));
             }
         }
 
-        // 处理排除的标签
+        // 处理排除的标签（同样排除子标签）
         if !parsed.exclude_tags.is_empty() {
             where_clauses.push(format!(
                 "f.id NOT IN (
-                SELECT file_id FROM file_tags ft2 
-                JOIN tags t2 ON ft2.tag_id = t2.id 
-                WHERE t2.name IN ({})
+                SELECT ft2.file_id FROM file_tags ft2
+                JOIN tags t ON ft2.tag_id = t.id
+                WHERE {}
             )",
-                vec!["?"; parsed.exclude_tags.len()].join(", ")
+                vec![TAG_SUBTREE_CLAUSE; parsed.exclude_tags.len()].join(" OR ")
             ));
-
             for tag in &parsed.exclude_tags {
-                params.push(tag.clone());
+                params.extend(subtree_params(tag));
             }
         }
 
-        // 处理作者过滤
-        if !parsed.authors.is_empty() {
-            let placeholders = vec!["?"; parsed.authors.len()].join(", ");
-            where_clauses.push(format!("a.name IN ({})", placeholders));
-            for author in &parsed.authors {
-                params.push(author.clone());
+        // 处理作者过滤：按署名角色分组，同一角色的多个名字任一匹配即可（不区分大小写的部分匹配）
+        for role in AuthorRole::ALL {
+            let names: Vec<&String> = parsed
+                .authors
+                .iter()
+                .filter(|(r, _)| *r == role)
+                .map(|(_, name)| name)
+                .collect();
+            if names.is_empty() {
+                continue;
+            }
+            where_clauses.push(format!(
+                "f.id IN (
+                SELECT fa.file_id FROM file_authors fa
+                JOIN authors a ON fa.author_id = a.id
+                WHERE fa.role = ? AND ({})
+            )",
+                vec!["a.name LIKE ?"; names.len()].join(" OR ")
+            ));
+            params.push(role.as_str().to_string());
+            for name in names {
+                params.push(format!("%{}%", name));
             }
         }
 
@@ -316,7 +350,18 @@ impl Searcher {
             params.push(year.to_string());
         }
 
-        // 处理分类过滤
+        // 处理分类过滤：`category:Work/Clients` 匹配该分类及其子分类，
+        // `category:Work/Clients/*` 按路径前缀匹配（只含子分类）
+        if let Some(category) = &parsed.category_path {
+            if let Some(prefix) = category.strip_suffix('*') {
+                where_clauses.push("substr(f.category_path, 1, length(?)) = ?".to_string());
+                params.push(prefix.to_string());
+                params.push(prefix.to_string());
+            } else {
+                where_clauses.push(CATEGORY_SUBTREE_CLAUSE.to_string());
+                params.extend(subtree_params(category));
+            }
+        }
         if let Some(category1) = &parsed.category1 {
             where_clauses.push("f.category1 = ?".to_string());
             params.push(category1.clone());
@@ -330,29 +375,107 @@ impl Searcher {
             params.push(category3.clone());
         }
 
+        // 处理文件大小过滤
+        for (op, size) in &parsed.size_filters {
+            where_clauses.push(format!("f.size {} CAST(? AS INTEGER)", op));
+            params.push(size.to_string());
+        }
+
+        // 处理文件类型过滤（MIME 类型或扩展名）
+        for file_type in &parsed.file_types {
+            if file_type.contains('/') {
+                where_clauses.push("f.mime_type LIKE ?".to_string());
+                params.push(file_type.replace('*', "%"));
+            } else {
+                where_clauses.push(
+                    "(f.mime_type LIKE ? OR f.mime_type LIKE ? OR f.filename LIKE ?)".to_string(),
+                );
+                params.push(format!("{}/%", file_type));
+                params.push(format!("%/{}%", file_type));
+                params.push(format!("%.{}", file_type));
+            }
+        }
+
+        // 处理来源过滤
+        for source in &parsed.sources {
+            where_clauses.push("f.source_url LIKE ?".to_string());
+            params.push(format!("%{}%", source));
+        }
+
+        // 处理标识符过滤（DOI、arXiv 编号、ISBN、ISSN）
+        for identifier in &parsed.identifiers {
+            where_clauses.push(
+                "f.id IN (SELECT file_id FROM file_identifiers WHERE kind = ? AND value = ?)"
+                    .to_string(),
+            );
+            params.push(identifier.kind.as_str().to_string());
+            params.push(identifier.value.clone());
+        }
+
+        // 处理相机过滤（EXIF/XMP 中的厂商和型号）
+        for camera in &parsed.cameras {
+            where_clauses
+                .push("json_extract(f.file_metadata, '$.image.camera') LIKE ?".to_string());
+            params.push(format!("%{}%", camera));
+        }
+
+        // 处理拍摄时间过滤
+        for (op, date) in &parsed.taken_filters {
+            where_clauses.push(format!(
+                "json_extract(f.file_metadata, '$.image.taken_at') {} ?",
+                op
+            ));
+            params.push(date.clone());
+        }
+
         // 排除已删除文件
         if !options.include_deleted {
             where_clauses.push("f.is_deleted = 0".to_string());
         }
 
         // 应用全文搜索
-        if !parsed.text.is_empty() && self.config.search.enable_fts {
+        let use_fts = !parsed.text.is_empty() && self.config.search.enable_fts;
+        if use_fts {
             // 使用FTS5进行全文搜索，根据是否启用 Signal-FTS5 选择查询语法
-            where_clauses.push(
-                "f.rowid IN (
-                SELECT rowid FROM files_fts 
-                WHERE files_fts MATCH ?
-            )"
-                .to_string(),
-            );
-
             let fts_query = if self.fts5_signal_available {
                 self.build_signal_fts5_query(&parsed.text)
             } else {
                 self.build_standard_fts5_query(&parsed.text)
             };
 
-            params.push(fts_query);
+            let mut fts_clauses = vec![
+                "f.rowid IN (
+                    SELECT rowid FROM files_fts 
+                    WHERE files_fts MATCH ?
+                )",
+            ];
+
+            if self.page_index_available {
+                // 同时匹配逐页全文索引
+                fts_clauses.push(
+                    "f.id IN (
+                    SELECT p.file_id FROM file_pages p
+                    JOIN file_pages_fts ON file_pages_fts.rowid = p.rowid
+                    WHERE file_pages_fts MATCH ?
+                )",
+                );
+            }
+
+            if self.annotation_index_available {
+                // 同时匹配PDF批注
+                fts_clauses.push(
+                    "f.id IN (
+                    SELECT a.file_id FROM file_annotations a
+                    JOIN file_annotations_fts ON file_annotations_fts.rowid = a.id
+                    WHERE file_annotations_fts MATCH ?
+                )",
+                );
+            }
+
+            for _ in &fts_clauses {
+                params.push(fts_query.clone());
+            }
+            where_clauses.push(format!("({})", fts_clauses.join(" OR ")));
         } else if !parsed.text.is_empty() {
             // 回退到简单的LIKE搜索
             where_clauses.push("(f.title LIKE ? OR f.summary LIKE ?)".to_string());
@@ -440,11 +563,15 @@ impl Searcher {
         for row in rows {
             let file_id: &str = row.get("id");
 
-            // 获取作者
-            let authors = self.get_file_authors(file_id).await?;
+            // 获取作者和其他署名
+            let (authors, contributors) = AuthorManager::new(self.db_pool.clone())
+                .get_file_authors(file_id)
+                .await?;
 
             // 获取标签
-            let tags = self.get_file_tags(file_id).await?;
+            let tags = TagManager::new(self.db_pool.clone())
+                .get_file_tags(file_id)
+                .await?;
 
             // 获取额外元数据
             let _metadata = self.get_file_metadata(file_id).await?;
@@ -454,12 +581,21 @@ impl Searcher {
                 id: row.get("id"),
                 title: row.get("title"),
                 authors,
+                contributors,
                 year: row.get::<Option<i32>, _>("year"),
                 publisher: row.get::<Option<String>, _>("publisher"),
-                source: None, // source_url字段暂时不包含在查询中
+                source: row.get::<Option<String>, _>("source_url"),
                 path: PathBuf::from(row.get::<String, _>("relative_path")),
-                original_path: None, // 暂时没有original_path字段
+                original_path: row
+                    .get::<Option<String>, _>("original_path")
+                    .map(PathBuf::from),
                 original_filename: row.get("filename"),
+                size: row.get::<Option<i64>, _>("size").map(|s| s as u64),
+                mime_type: row.get::<Option<String>, _>("mime_type"),
+                mtime: row
+                    .get::<Option<String>, _>("file_mtime")
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+                    .map(|dt| dt.with_timezone(&chrono::Utc)),
                 hash: row.get("initial_hash"),
                 current_hash: row.get("current_hash"),
                 category1: row
@@ -478,18 +614,57 @@ impl Searcher {
                     .with_timezone(&chrono::Utc),
                 last_accessed: None, // 暂时没有last_accessed字段
                 is_deleted: row.get::<i64, _>("is_deleted") != 0,
-                file_metadata: None, // TODO: 从数据库加载
-                type_metadata: None, // TODO: 从数据库加载
+                file_metadata: row
+                    .get::<Option<String>, _>("file_metadata")
+                    .and_then(|s| serde_json::from_str(&s).ok()),
+                type_metadata: row
+                    .get::<Option<String>, _>("type_metadata")
+                    .and_then(|s| serde_json::from_str(&s).ok()),
             };
 
             entries.push(entry);
         }
 
+        let fts_query = if self.fts5_signal_available {
+            self.build_signal_fts5_query(&parsed.text)
+        } else {
+            self.build_standard_fts5_query(&parsed.text)
+        };
+        let file_ids: Vec<String> = entries.iter().map(|e| e.id.clone()).collect();
+
+        // 查找逐页全文索引中的命中页
+        let page_hits = if use_fts && self.page_index_available {
+            PageIndexer::new(self.db_pool.clone())
+                .find_hits(&fts_query, &file_ids, PAGE_HITS_PER_FILE)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("查询逐页命中失败: {}", e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+
+        // 查找命中的批注
+        let annotation_hits = if use_fts && self.annotation_index_available {
+            AnnotationManager::new(self.db_pool.clone())
+                .find_hits(&fts_query, &file_ids, ANNOTATION_HITS_PER_FILE)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("查询批注命中失败: {}", e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+
         Ok(SearchResult {
             entries,
             total_count: total_count as usize,
             offset: options.offset,
             limit: options.limit,
+            page_hits,
+            annotation_hits,
         })
     }
 
@@ -636,22 +811,23 @@ impl Searcher {
         let mut parsed = ParsedQuery::default();
         let mut text_parts = Vec::new();
 
-        // 简单的词法分析
-        for part in query.split_whitespace() {
+        // 简单的词法分析，双引号内的空白不拆分
+        let tokens = split_query(query);
+        for part in tokens.iter().map(String::as_str) {
             if let Some(tag) = part.strip_prefix("tag:") {
-                let tag = tag.trim();
+                let tag = normalize_tag_path(unquote(tag));
                 if !tag.is_empty() {
-                    parsed.include_tags.push(tag.to_string());
+                    parsed.include_tags.push(tag);
                 }
             } else if let Some(tag) = part.strip_prefix("-tag:") {
-                let tag = tag.trim();
+                let tag = normalize_tag_path(unquote(tag));
                 if !tag.is_empty() {
-                    parsed.exclude_tags.push(tag.to_string());
+                    parsed.exclude_tags.push(tag);
                 }
-            } else if let Some(author) = part.strip_prefix("author:") {
-                let author = author.trim();
-                if !author.is_empty() {
-                    parsed.authors.push(author.to_string());
+            } else if let Some((role, name)) = author_filter(part) {
+                let name = unquote(name.trim());
+                if !name.is_empty() {
+                    parsed.authors.push((role, name.to_string()));
                 }
             } else if let Some(year) = part.strip_prefix("year:") {
                 let year = year.trim();
@@ -659,9 +835,16 @@ impl Searcher {
                     parsed.year = Some(year_num);
                 }
             } else if let Some(category) = part.strip_prefix("category:") {
-                let category = category.trim();
-                if !category.is_empty() {
-                    parsed.category1 = Some(category.to_string());
+                let category = unquote(category.trim());
+                if let Some(prefix) = category.strip_suffix('*') {
+                    // 保留末尾的 `/`，`Work/*` 不会匹配 `Workshop`
+                    let mut prefix = normalize_category_path(prefix);
+                    if category.ends_with("/*") && !prefix.is_empty() {
+                        prefix.push('/');
+                    }
+                    parsed.category_path = Some(format!("{}*", prefix));
+                } else if !category.is_empty() {
+                    parsed.category_path = Some(normalize_category_path(category));
                 }
             } else if let Some(category) = part.strip_prefix("category1:") {
                 let category = category.trim();
@@ -683,6 +866,48 @@ impl Searcher {
                 if !title.is_empty() {
                     parsed.title = Some(title.to_string());
                 }
+            } else if let Some(size) = part.strip_prefix("size:") {
+                match parse_size_filter(size) {
+                    Some(filters) => parsed.size_filters.extend(filters),
+                    None => {
+                        return Err(TagboxError::InvalidQuery {
+                            query: part.to_string(),
+                        })
+                    }
+                }
+            } else if let Some(file_type) = part.strip_prefix("type:") {
+                let file_type = file_type.trim().trim_start_matches('.').to_lowercase();
+                if !file_type.is_empty() {
+                    parsed.file_types.push(file_type);
+                }
+            } else if let Some(source) = part.strip_prefix("source:") {
+                let source = source.trim();
+                if !source.is_empty() {
+                    parsed.sources.push(source.to_string());
+                }
+            } else if let Some(camera) = part.strip_prefix("camera:") {
+                let camera = unquote(camera.trim());
+                if !camera.is_empty() {
+                    parsed.cameras.push(camera.to_string());
+                }
+            } else if let Some(taken) = part.strip_prefix("taken:") {
+                match parse_date_filter(unquote(taken.trim())) {
+                    Some(filters) => parsed.taken_filters.extend(filters),
+                    None => {
+                        return Err(TagboxError::InvalidQuery {
+                            query: part.to_string(),
+                        })
+                    }
+                }
+            } else if let Some((kind, value)) = identifier_filter(part) {
+                match normalize_identifier(kind, unquote(value)) {
+                    Some(value) => parsed.identifiers.push(Identifier { kind, value }),
+                    None => {
+                        return Err(TagboxError::InvalidQuery {
+                            query: part.to_string(),
+                        })
+                    }
+                }
             } else {
                 text_parts.push(part);
             }
@@ -699,42 +924,6 @@ impl Searcher {
         Ok(parsed)
     }
 
-    /// 获取文件作者
-    async fn get_file_authors(&self, file_id: &str) -> Result<Vec<String>> {
-        let authors = sqlx::query!(
-            r#"
-            SELECT a.name
-            FROM authors a
-            JOIN file_authors fa ON a.id = fa.author_id
-            WHERE fa.file_id = ?
-            "#,
-            file_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-
-        Ok(authors.iter().map(|a| a.name.clone()).collect())
-    }
-
-    /// 获取文件标签
-    async fn get_file_tags(&self, file_id: &str) -> Result<Vec<String>> {
-        let tags = sqlx::query!(
-            r#"
-            SELECT t.name
-            FROM tags t
-            JOIN file_tags ft ON t.id = ft.tag_id
-            WHERE ft.file_id = ?
-            "#,
-            file_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(TagboxError::Database)?;
-
-        Ok(tags.iter().map(|t| t.name.clone()).collect())
-    }
-
     /// 获取文件元数据
     async fn get_file_metadata(&self, file_id: &str) -> Result<HashMap<String, String>> {
         let metadata = sqlx::query!(
@@ -792,9 +981,151 @@ struct ParsedQuery {
     title: Option<String>,
     include_tags: Vec<String>,
     exclude_tags: Vec<String>,
-    authors: Vec<String>,
+    /// (署名角色, 名字)，如 `author:vaswani`、`editor:knuth`
+    authors: Vec<(AuthorRole, String)>,
     year: Option<i32>,
+    /// 完整分类路径，以 `*` 结尾时为前缀匹配
+    category_path: Option<String>,
     category1: Option<String>,
     category2: Option<String>,
     category3: Option<String>,
+    size_filters: Vec<(&'static str, u64)>,
+    file_types: Vec<String>,
+    sources: Vec<String>,
+    identifiers: Vec<Identifier>,
+    cameras: Vec<String>,
+    taken_filters: Vec<(&'static str, String)>,
+}
+
+/// 按空白拆分查询，双引号括起的部分（如 `camera:"Canon EOS R5"`）保持为一个词
+fn split_query(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// 拆出 `doi:`、`arxiv:`、`isbn:`、`issn:` 过滤条件，前缀不区分大小写
+fn identifier_filter(part: &str) -> Option<(IdentifierKind, &str)> {
+    let (name, value) = part.split_once(':')?;
+    Some((IdentifierKind::from_name(name)?, value))
+}
+
+/// 拆出 `author:`、`editor:`、`translator:`、`illustrator:` 过滤条件，前缀不区分大小写
+fn author_filter(part: &str) -> Option<(AuthorRole, &str)> {
+    let (name, value) = part.split_once(':')?;
+    Some((AuthorRole::from_name(name)?, value))
+}
+
+/// [`TAG_SUBTREE_CLAUSE`] 和 [`CATEGORY_SUBTREE_CLAUSE`] 的参数
+fn subtree_params(path: &str) -> [String; 3] {
+    [path.to_string(), format!("{}/", path), format!("{}0", path)]
+}
+
+/// 去掉值两端的双引号
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+/// 解析日期过滤条件：`2023`、`2023-05`、`2023..2024`（闭区间）、`>=2022-06`、`<2024`
+///
+/// 日期以 ISO 字符串比较；上界追加 `~`（大于数字、`-` 和 `T`），
+/// 使 `2024` 包含 2024 年内的所有时间。
+fn parse_date_filter(value: &str) -> Option<Vec<(&'static str, String)>> {
+    fn date(value: &str) -> Option<&str> {
+        let value = value.trim();
+        let valid = value.len() >= 4
+            && value[..4].chars().all(|c| c.is_ascii_digit())
+            && value.chars().all(|c| c.is_ascii_digit() || c == '-');
+        valid.then_some(value)
+    }
+    let through = |value: &str| format!("{}~", value);
+
+    if let Some((min, max)) = value.split_once("..") {
+        let mut filters = Vec::new();
+        if !min.is_empty() {
+            filters.push((">=", date(min)?.to_string()));
+        }
+        if !max.is_empty() {
+            filters.push(("<=", through(date(max)?)));
+        }
+        return if filters.is_empty() {
+            None
+        } else {
+            Some(filters)
+        };
+    }
+
+    let filters = if let Some(rest) = value.strip_prefix(">=") {
+        vec![(">=", date(rest)?.to_string())]
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        vec![("<=", through(date(rest)?))]
+    } else if let Some(rest) = value.strip_prefix('>') {
+        vec![(">", through(date(rest)?))]
+    } else if let Some(rest) = value.strip_prefix('<') {
+        vec![("<", date(rest)?.to_string())]
+    } else {
+        let value = date(value)?;
+        vec![(">=", value.to_string()), ("<=", through(value))]
+    };
+
+    Some(filters)
+}
+
+/// 解析大小过滤条件：`>10MB`、`<=1GB`、`500KB`（等于）、`1MB..10MB`（闭区间）
+fn parse_size_filter(value: &str) -> Option<Vec<(&'static str, u64)>> {
+    let value = value.trim();
+
+    if let Some((min, max)) = value.split_once("..") {
+        let mut filters = Vec::new();
+        if !min.is_empty() {
+            filters.push((">=", parse_size(min)?));
+        }
+        if !max.is_empty() {
+            filters.push(("<=", parse_size(max)?));
+        }
+        return if filters.is_empty() {
+            None
+        } else {
+            Some(filters)
+        };
+    }
+
+    let (op, rest) = if let Some(rest) = value.strip_prefix(">=") {
+        (">=", rest)
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        ("<=", rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (">", rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        ("<", rest)
+    } else if let Some(rest) = value.strip_prefix('=') {
+        ("=", rest)
+    } else {
+        ("=", value)
+    };
+
+    Some(vec![(op, parse_size(rest)?)])
 }
@@ -0,0 +1,711 @@
+use crate::errors::{Result, TagboxError};
+use crate::history::{FileHistoryManager, FileOperation};
+use crate::types::{TagNode, TagSummary};
+use crate::utils::{current_time, generate_uuid, normalize_hierarchy_path};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
+
+/// 标签管理器
+///
+/// 标签按 `path` 组成层级：`lang/rust/async` 的父标签是 `lang/rust`，`name` 只保存最后一段。
+/// 文件只关联到叶子标签，按路径过滤时包含所有子标签。
+/// 别名（`tag_aliases`）把旧路径指向现有标签，导入和搜索时自动解析。
+pub struct TagManager {
+    db_pool: SqlitePool,
+}
+
+impl TagManager {
+    /// 创建一个新的标签管理器
+    pub fn new(db_pool: SqlitePool) -> Self {
+        Self { db_pool }
+    }
+
+    /// 查找或创建标签，缺失的父标签一并创建，返回最后一级标签的 ID
+    ///
+    /// 路径或其前缀是别名时使用别名指向的标签。
+    pub async fn find_or_create_tag(&self, path: &str) -> Result<String> {
+        let path = self.resolve_tag_path(path).await?;
+        if path.is_empty() {
+            return Err(TagboxError::Config("标签不能为空".to_string()));
+        }
+
+        let mut conn = self
+            .db_pool
+            .acquire()
+            .await
+            .map_err(TagboxError::Database)?;
+        create_tag_chain(&mut *conn, &path).await
+    }
+
+    /// 解析标签别名：路径本身或其前缀是别名时替换为别名指向的标签路径，
+    /// 例如别名 `ml` 指向 `machine-learning` 时，`ml/nlp` 解析为 `machine-learning/nlp`
+    pub async fn resolve_tag_path(&self, path: &str) -> Result<String> {
+        let path = normalize_tag_path(path);
+        let mut prefix = path.as_str();
+        while !prefix.is_empty() {
+            let target: Option<String> = sqlx::query_scalar(
+                "SELECT t.path FROM tag_aliases a JOIN tags t ON t.id = a.tag_id WHERE a.alias = ?",
+            )
+            .bind(prefix)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+            if let Some(target) = target {
+                return Ok(format!("{}{}", target, &path[prefix.len()..]));
+            }
+
+            prefix = prefix.rsplit_once('/').map_or("", |(parent, _)| parent);
+        }
+        Ok(path)
+    }
+
+    /// 用给定标签替换文件的全部标签，返回规范化后的标签路径
+    ///
+    /// 删除和重新关联在同一事务中完成，失败时文件保留原有标签
+    pub async fn set_file_tags(&self, file_id: &str, tags: &[String]) -> Result<Vec<String>> {
+        let paths = self.resolve_tag_paths(tags).await?;
+
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+        sqlx::query("DELETE FROM file_tags WHERE file_id = ?")
+            .bind(file_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+        link_file_tags(&mut tx, file_id, &paths).await?;
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        Ok(paths)
+    }
+
+    /// 给文件添加标签（已有的保留），返回规范化后新加入的标签路径
+    pub async fn add_file_tags(&self, file_id: &str, tags: &[String]) -> Result<Vec<String>> {
+        let paths = self.resolve_tag_paths(tags).await?;
+
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+        link_file_tags(&mut tx, file_id, &paths).await?;
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        Ok(paths)
+    }
+
+    /// 解析别名并规范化一组标签，去掉空标签和重复标签
+    async fn resolve_tag_paths(&self, tags: &[String]) -> Result<Vec<String>> {
+        let mut paths: Vec<String> = Vec::new();
+        for tag in tags {
+            let path = self.resolve_tag_path(tag).await?;
+            if !path.is_empty() && !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// 获取文件的标签路径，按路径排序
+    pub async fn get_file_tags(&self, file_id: &str) -> Result<Vec<String>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT t.path FROM tags t JOIN file_tags ft ON t.id = ft.tag_id
+            WHERE ft.file_id = ?
+            ORDER BY t.path
+            "#,
+        )
+        .bind(file_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)
+    }
+
+    /// 构建标签树，附带每个标签直接关联和含子标签的文件数（不含已删除文件）
+    pub async fn tag_tree(&self) -> Result<Vec<TagNode>> {
+        let rows =
+            sqlx::query("SELECT id, name, path FROM tags WHERE is_deleted = 0 ORDER BY path")
+                .fetch_all(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+
+        let links: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT t.path, ft.file_id FROM file_tags ft
+            JOIN tags t ON t.id = ft.tag_id
+            JOIN files f ON f.id = ft.file_id
+            WHERE f.is_deleted = 0
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        // 文件计入其标签及所有祖先标签，同一文件在祖先上只计一次
+        let mut direct: HashMap<String, i64> = HashMap::new();
+        let mut total: HashMap<String, HashSet<String>> = HashMap::new();
+        for (path, file_id) in links {
+            *direct.entry(path.clone()).or_insert(0) += 1;
+            let mut prefix = path.as_str();
+            loop {
+                total
+                    .entry(prefix.to_string())
+                    .or_default()
+                    .insert(file_id.clone());
+                match prefix.rsplit_once('/') {
+                    Some((parent, _)) => prefix = parent,
+                    None => break,
+                }
+            }
+        }
+
+        // 父标签缺失的旧数据挂在根上
+        let paths: HashSet<String> = rows.iter().map(|row| row.get("path")).collect();
+        let mut children: HashMap<Option<String>, Vec<TagNode>> = HashMap::new();
+        for row in rows {
+            let path: String = row.get("path");
+            let parent = path
+                .rsplit_once('/')
+                .map(|(parent, _)| parent.to_string())
+                .filter(|parent| paths.contains(parent));
+            children.entry(parent).or_default().push(TagNode {
+                id: row.get("id"),
+                name: row.get("name"),
+                file_count: direct.get(&path).copied().unwrap_or(0),
+                total_count: total.get(&path).map_or(0, |files| files.len() as i64),
+                path,
+                children: Vec::new(),
+            });
+        }
+
+        Ok(attach_children(None, &mut children))
+    }
+
+    /// 列出全部标签及其直接关联的文件数（不含已删除文件）和别名，按路径排序
+    pub async fn list_tags(&self) -> Result<Vec<TagSummary>> {
+        let rows: Vec<(String, String, i64)> = sqlx::query_as(
+            r#"
+            SELECT t.id, t.path,
+                (SELECT COUNT(*) FROM file_tags ft JOIN files f ON f.id = ft.file_id
+                 WHERE ft.tag_id = t.id AND f.is_deleted = 0) AS file_count
+            FROM tags t
+            WHERE t.is_deleted = 0
+            ORDER BY t.path
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+        let alias_rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT tag_id, alias FROM tag_aliases ORDER BY alias")
+                .fetch_all(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+        for (tag_id, alias) in alias_rows {
+            aliases.entry(tag_id).or_default().push(alias);
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, path, file_count)| TagSummary {
+                aliases: aliases.remove(&id).unwrap_or_default(),
+                id,
+                path,
+                file_count,
+            })
+            .collect())
+    }
+
+    /// 重命名标签，子标签随之移动（`ml` 改为 `ai/ml` 时 `ml/nlp` 变为 `ai/ml/nlp`），
+    /// 返回受影响的文件 ID
+    ///
+    /// 目标路径已存在时报错，应改用 [`TagManager::merge_tags`]。
+    pub async fn rename_tag(&self, old_path: &str, new_path: &str) -> Result<Vec<String>> {
+        let old_path = self.existing_tag_path(old_path).await?;
+        let new_path = normalize_tag_path(new_path);
+        if new_path.is_empty() {
+            return Err(TagboxError::Config("标签不能为空".to_string()));
+        }
+        if new_path == old_path {
+            return Ok(Vec::new());
+        }
+        if is_in_subtree(&new_path, &old_path) {
+            return Err(TagboxError::Config(format!(
+                "不能把标签 {} 移动到自己的子标签 {} 下",
+                old_path, new_path
+            )));
+        }
+        if self.tag_id(&new_path).await?.is_some() {
+            return Err(TagboxError::Config(format!(
+                "标签 {} 已存在，请使用合并",
+                new_path
+            )));
+        }
+        if self.resolve_tag_path(&new_path).await? != new_path {
+            return Err(TagboxError::Config(format!(
+                "{} 已是其他标签的别名",
+                new_path
+            )));
+        }
+
+        let file_ids = self.subtree_file_ids(&old_path).await?;
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        let parent_id = match new_path.rsplit_once('/') {
+            Some((parent, _)) => Some(create_tag_chain(&mut *tx, parent).await?),
+            None => None,
+        };
+        let name = new_path.rsplit('/').next().unwrap_or(new_path.as_str());
+
+        for (id, path) in subtree_tags(&mut *tx, &old_path).await? {
+            let renamed = format!("{}{}", new_path, &path[old_path.len()..]);
+            if path == old_path {
+                sqlx::query("UPDATE tags SET path = ?, name = ?, parent_id = ? WHERE id = ?")
+                    .bind(&renamed)
+                    .bind(name)
+                    .bind(&parent_id)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(TagboxError::Database)?;
+            } else {
+                sqlx::query("UPDATE tags SET path = ? WHERE id = ?")
+                    .bind(&renamed)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(TagboxError::Database)?;
+            }
+        }
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        let reason = format!("rename tag {} -> {}", old_path, new_path);
+        self.record_retag(&file_ids, &reason).await?;
+        Ok(file_ids)
+    }
+
+    /// 把标签（含子标签）合并到另一个标签，返回受影响的文件 ID
+    ///
+    /// 子标签按相对路径合并（`ml/nlp` 并入 `machine-learning/nlp`），源标签被删除，
+    /// 其路径记为目标标签的别名，之后导入 `ml` 仍会关联到 `machine-learning`。
+    pub async fn merge_tags(&self, from_path: &str, into_path: &str) -> Result<Vec<String>> {
+        let from_path = self.existing_tag_path(from_path).await?;
+        let into_path = self.resolve_tag_path(into_path).await?;
+        if into_path.is_empty() {
+            return Err(TagboxError::Config("标签不能为空".to_string()));
+        }
+        if is_in_subtree(&into_path, &from_path) {
+            return Err(TagboxError::Config(format!(
+                "不能把标签 {} 合并到自己或自己的子标签 {}",
+                from_path, into_path
+            )));
+        }
+
+        let file_ids = self.subtree_file_ids(&from_path).await?;
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+
+        let sources = subtree_tags(&mut *tx, &from_path).await?;
+        let mut into_id = None;
+        for (id, path) in &sources {
+            let target_path = format!("{}{}", into_path, &path[from_path.len()..]);
+            let target_id = create_tag_chain(&mut *tx, &target_path).await?;
+
+            sqlx::query(
+                r#"
+                INSERT OR IGNORE INTO file_tags (file_id, tag_id)
+                SELECT file_id, ? FROM file_tags WHERE tag_id = ?
+                "#,
+            )
+            .bind(&target_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+
+            sqlx::query("UPDATE tag_aliases SET tag_id = ? WHERE tag_id = ?")
+                .bind(&target_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(TagboxError::Database)?;
+
+            if *path == from_path {
+                into_id = Some(target_id);
+            }
+        }
+
+        delete_tags(&mut *tx, &sources).await?;
+
+        if let Some(into_id) = into_id {
+            sqlx::query(
+                r#"
+                INSERT INTO tag_aliases (alias, tag_id, created_at) VALUES (?, ?, ?)
+                ON CONFLICT(alias) DO UPDATE SET tag_id = excluded.tag_id
+                "#,
+            )
+            .bind(&from_path)
+            .bind(&into_id)
+            .bind(current_time().to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(TagboxError::Database)?;
+        }
+
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        let reason = format!("merge tag {} into {}", from_path, into_path);
+        self.record_retag(&file_ids, &reason).await?;
+        Ok(file_ids)
+    }
+
+    /// 删除标签及其子标签，返回受影响的文件 ID
+    ///
+    /// 标签仍被文件使用时需要 `force`，此时同时移除这些文件上的标签。
+    pub async fn delete_tag(&self, path: &str, force: bool) -> Result<Vec<String>> {
+        let path = self.existing_tag_path(path).await?;
+        let file_ids = self.subtree_file_ids(&path).await?;
+        if !file_ids.is_empty() && !force {
+            return Err(TagboxError::Config(format!(
+                "标签 {} 仍被 {} 个文件使用",
+                path,
+                file_ids.len()
+            )));
+        }
+
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+        let tags = subtree_tags(&mut *tx, &path).await?;
+        delete_tags(&mut *tx, &tags).await?;
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        self.record_retag(&file_ids, &format!("delete tag {}", path))
+            .await?;
+        Ok(file_ids)
+    }
+
+    /// 删除没有文件使用、也没有别名指向的标签（子标签有文件的父标签保留），返回删除的标签路径
+    pub async fn prune_unused_tags(&self) -> Result<Vec<String>> {
+        let tags: Vec<(String, String)> = sqlx::query_as("SELECT id, path FROM tags ORDER BY path")
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?;
+        let used_paths: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT t.path FROM tags t
+            WHERE t.id IN (SELECT tag_id FROM file_tags)
+               OR t.id IN (SELECT tag_id FROM tag_aliases)
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        let mut used: HashSet<&str> = HashSet::new();
+        for path in &used_paths {
+            let mut prefix = path.as_str();
+            loop {
+                used.insert(prefix);
+                match prefix.rsplit_once('/') {
+                    Some((parent, _)) => prefix = parent,
+                    None => break,
+                }
+            }
+        }
+
+        let unused: Vec<(String, String)> = tags
+            .into_iter()
+            .filter(|(_, path)| !used.contains(path.as_str()))
+            .collect();
+
+        let mut tx = self.db_pool.begin().await.map_err(TagboxError::Database)?;
+        delete_tags(&mut *tx, &unused).await?;
+        tx.commit().await.map_err(TagboxError::Database)?;
+
+        Ok(unused.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// 为标签添加别名
+    pub async fn add_tag_alias(&self, path: &str, alias: &str) -> Result<()> {
+        let path = self.existing_tag_path(path).await?;
+        let alias = normalize_tag_path(alias);
+        if alias.is_empty() {
+            return Err(TagboxError::Config("别名不能为空".to_string()));
+        }
+        if alias == path {
+            return Err(TagboxError::Config("标签不能是自己的别名".to_string()));
+        }
+        if self.tag_id(&alias).await?.is_some() {
+            return Err(TagboxError::Config(format!(
+                "标签 {} 已存在，请使用合并",
+                alias
+            )));
+        }
+
+        let tag_id = self
+            .tag_id(&path)
+            .await?
+            .ok_or_else(|| TagboxError::NotFound(format!("标签不存在: {}", path)))?;
+        sqlx::query(
+            r#"
+            INSERT INTO tag_aliases (alias, tag_id, created_at) VALUES (?, ?, ?)
+            ON CONFLICT(alias) DO UPDATE SET tag_id = excluded.tag_id
+            "#,
+        )
+        .bind(&alias)
+        .bind(&tag_id)
+        .bind(current_time().to_rfc3339())
+        .execute(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        Ok(())
+    }
+
+    /// 删除标签别名
+    pub async fn remove_tag_alias(&self, alias: &str) -> Result<()> {
+        let alias = normalize_tag_path(alias);
+        let removed = sqlx::query("DELETE FROM tag_aliases WHERE alias = ?")
+            .bind(&alias)
+            .execute(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)?
+            .rows_affected();
+        if removed == 0 {
+            return Err(TagboxError::NotFound(format!("标签别名不存在: {}", alias)));
+        }
+        Ok(())
+    }
+
+    /// 重新写入文件在 FTS 索引中的 tags 列
+    pub(crate) async fn update_fts_tags(&self, file_id: &str) -> Result<()> {
+        let tags_text = self.get_file_tags(file_id).await?.join(" ");
+        sqlx::query(
+            r#"
+            UPDATE files_fts SET tags = ?
+            WHERE rowid = (SELECT rowid FROM files WHERE id = ?)
+            "#,
+        )
+        .bind(&tags_text)
+        .bind(file_id)
+        .execute(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)?;
+        Ok(())
+    }
+
+    /// 按路径查找标签 ID（不解析别名）
+    async fn tag_id(&self, path: &str) -> Result<Option<String>> {
+        sqlx::query_scalar("SELECT id FROM tags WHERE path = ?")
+            .bind(path)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(TagboxError::Database)
+    }
+
+    /// 解析别名后返回已存在标签的路径，不存在时报错
+    async fn existing_tag_path(&self, path: &str) -> Result<String> {
+        let resolved = self.resolve_tag_path(path).await?;
+        match self.tag_id(&resolved).await? {
+            Some(_) => Ok(resolved),
+            None => Err(TagboxError::NotFound(format!(
+                "标签不存在: {}",
+                path.trim()
+            ))),
+        }
+    }
+
+    /// 关联到标签或其子标签的文件 ID
+    async fn subtree_file_ids(&self, path: &str) -> Result<Vec<String>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT ft.file_id FROM file_tags ft
+            JOIN tags t ON t.id = ft.tag_id
+            WHERE t.path = ? OR (t.path > ? AND t.path < ?)
+            ORDER BY ft.file_id
+            "#,
+        )
+        .bind(path)
+        .bind(format!("{}/", path))
+        .bind(format!("{}0", path))
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(TagboxError::Database)
+    }
+
+    /// 标签变更后刷新受影响文件的 FTS 索引并记录历史
+    async fn record_retag(&self, file_ids: &[String], reason: &str) -> Result<()> {
+        let history = FileHistoryManager::new(self.db_pool.clone());
+        for file_id in file_ids {
+            self.update_fts_tags(file_id).await?;
+            history
+                .record_file_history(file_id, FileOperation::Retag, None, Some(reason))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// 修复旧数据中路径含 `/` 却没有父标签的标签：补建父标签并把 `name` 改为最后一段
+    pub async fn repair_hierarchy(&self) -> Result<usize> {
+        let orphans: Vec<(String, String)> =
+            sqlx::query_as("SELECT id, path FROM tags WHERE parent_id IS NULL AND path LIKE '%/%'")
+                .fetch_all(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+
+        let mut repaired = 0;
+        for (id, path) in orphans {
+            let Some((parent, name)) = path.rsplit_once('/') else {
+                continue;
+            };
+            if parent.trim().is_empty() || name.trim().is_empty() {
+                continue;
+            }
+
+            let parent_id = self.find_or_create_tag(parent).await?;
+            sqlx::query("UPDATE tags SET name = ?, parent_id = ? WHERE id = ?")
+                .bind(name.trim())
+                .bind(&parent_id)
+                .bind(&id)
+                .execute(&self.db_pool)
+                .await
+                .map_err(TagboxError::Database)?;
+            repaired += 1;
+        }
+
+        Ok(repaired)
+    }
+}
+
+/// 规范化标签路径：去掉每段首尾空白和空段，`" lang / rust/ "` 变为 `lang/rust`
+pub fn normalize_tag_path(raw: &str) -> String {
+    normalize_hierarchy_path(raw)
+}
+
+/// `path` 是否为 `root` 本身或其子标签
+pub(crate) fn is_in_subtree(path: &str, root: &str) -> bool {
+    path == root
+        || path
+            .strip_prefix(root)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// 把文件关联到已解析的标签路径，缺少的标签（含父标签）一并创建
+async fn link_file_tags(
+    conn: &mut SqliteConnection,
+    file_id: &str,
+    paths: &[String],
+) -> Result<()> {
+    for path in paths {
+        let tag_id = create_tag_chain(conn, path).await?;
+        sqlx::query("INSERT OR IGNORE INTO file_tags (file_id, tag_id) VALUES (?, ?)")
+            .bind(file_id)
+            .bind(&tag_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(TagboxError::Database)?;
+    }
+    Ok(())
+}
+
+/// 在连接（或事务）上逐级查找或创建标签，返回最后一级标签的 ID
+pub(crate) async fn create_tag_chain(conn: &mut SqliteConnection, path: &str) -> Result<String> {
+    let now = current_time().to_rfc3339();
+    let mut parent_id: Option<String> = None;
+    let mut prefix = String::new();
+    for segment in path.split('/') {
+        if !prefix.is_empty() {
+            prefix.push('/');
+        }
+        prefix.push_str(segment);
+
+        // 并行导入时可能同时创建同一标签，插入冲突后按路径取回
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO tags (id, name, path, parent_id, created_at, is_deleted)
+            VALUES (?, ?, ?, ?, ?, 0)
+            "#,
+        )
+        .bind(generate_uuid())
+        .bind(segment)
+        .bind(&prefix)
+        .bind(&parent_id)
+        .bind(&now)
+        .execute(&mut *conn)
+        .await
+        .map_err(TagboxError::Database)?;
+
+        let id: String = sqlx::query_scalar("SELECT id FROM tags WHERE path = ?")
+            .bind(&prefix)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(TagboxError::Database)?;
+        parent_id = Some(id);
+    }
+
+    parent_id.ok_or_else(|| TagboxError::Config("标签不能为空".to_string()))
+}
+
+/// 标签及其子标签的 (ID, 路径)，按路径排序
+async fn subtree_tags(conn: &mut SqliteConnection, path: &str) -> Result<Vec<(String, String)>> {
+    sqlx::query_as(
+        r#"
+        SELECT id, path FROM tags
+        WHERE path = ? OR (path > ? AND path < ?)
+        ORDER BY path
+        "#,
+    )
+    .bind(path)
+    .bind(format!("{}/", path))
+    .bind(format!("{}0", path))
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(TagboxError::Database)
+}
+
+/// 删除标签连同其文件关联和别名
+async fn delete_tags(conn: &mut SqliteConnection, tags: &[(String, String)]) -> Result<()> {
+    // 先删子标签，避免父标签删除时把子标签的 parent_id 置空
+    for (id, _) in tags.iter().rev() {
+        for sql in [
+            "DELETE FROM file_tags WHERE tag_id = ?",
+            "DELETE FROM tag_aliases WHERE tag_id = ?",
+            "DELETE FROM tags WHERE id = ?",
+        ] {
+            sqlx::query(sql)
+                .bind(id)
+                .execute(&mut *conn)
+                .await
+                .map_err(TagboxError::Database)?;
+        }
+    }
+    Ok(())
+}
+
+/// 按父路径取出子节点并递归挂到树上
+fn attach_children(
+    parent: Option<String>,
+    children: &mut HashMap<Option<String>, Vec<TagNode>>,
+) -> Vec<TagNode> {
+    let mut nodes = children.remove(&parent).unwrap_or_default();
+    for node in &mut nodes {
+        node.children = attach_children(Some(node.path.clone()), children);
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tag_path() {
+        assert_eq!(normalize_tag_path(" lang / rust/ "), "lang/rust");
+        assert_eq!(normalize_tag_path("lang//rust/async"), "lang/rust/async");
+        assert_eq!(normalize_tag_path("Rust"), "Rust");
+        assert_eq!(normalize_tag_path(" / "), "");
+    }
+
+    #[test]
+    fn test_is_in_subtree() {
+        assert!(is_in_subtree("ml", "ml"));
+        assert!(is_in_subtree("ml/nlp", "ml"));
+        assert!(!is_in_subtree("mlops", "ml"));
+        assert!(!is_in_subtree("ai/ml", "ml"));
+    }
+}
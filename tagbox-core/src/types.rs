@@ -8,13 +8,26 @@ use std::path::PathBuf;
 pub struct FileEntry {
     pub id: String,
     pub title: String,
+    /// 作者，按署名顺序
     pub authors: Vec<String>,
+    /// 编者、译者、插画等其他署名，按署名顺序
+    #[serde(default)]
+    pub contributors: Vec<Contributor>,
     pub year: Option<i32>,
     pub publisher: Option<String>,
     pub source: Option<String>,
     pub path: PathBuf,
     pub original_path: Option<PathBuf>,
     pub original_filename: String,
+    /// 文件大小（字节）
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// MIME 类型
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    /// 导入时源文件的修改时间
+    #[serde(default)]
+    pub mtime: Option<DateTime<Utc>>,
     pub hash: String,
     pub current_hash: Option<String>,
     pub category1: String,
@@ -32,10 +45,13 @@ pub struct FileEntry {
 }
 
 /// 导入文件时的元数据信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ImportMetadata {
     pub title: String,
     pub authors: Vec<String>,
+    /// 编者、译者、插画等其他署名
+    #[serde(default)]
+    pub contributors: Vec<Contributor>,
     pub year: Option<i32>,
     pub publisher: Option<String>,
     pub source: Option<String>,
@@ -48,13 +64,83 @@ pub struct ImportMetadata {
     pub additional_info: HashMap<String, String>,
     pub file_metadata: Option<serde_json::Value>,
     pub type_metadata: Option<serde_json::Value>,
+    /// 根据文件内容检测到的 MIME 类型
+    #[serde(default)]
+    pub mime_type: Option<String>,
 }
 
-/// 文件更新请求
+/// 单个文件的导入结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// 新文件已导入
+    Imported,
+    /// 重复文件，保持已有条目不变
+    Skipped,
+    /// 重复文件，新标签已合并到已有条目
+    MergedTags { added: Vec<String> },
+    /// 重复文件，已有条目中的空字段已被填补
+    FilledFields { fields: Vec<String> },
+    /// 重复文件，等待调用方决定如何处理（prompt 策略）
+    Pending,
+}
+
+impl ImportOutcome {
+    /// 是否命中了已有条目
+    pub fn is_duplicate(&self) -> bool {
+        !matches!(self, ImportOutcome::Imported)
+    }
+}
+
+impl std::fmt::Display for ImportOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportOutcome::Imported => write!(f, "imported"),
+            ImportOutcome::Skipped => write!(f, "duplicate, skipped"),
+            ImportOutcome::MergedTags { added } if added.is_empty() => {
+                write!(f, "duplicate, no new tags")
+            }
+            ImportOutcome::MergedTags { added } => {
+                write!(f, "duplicate, merged tags: {}", added.join(", "))
+            }
+            ImportOutcome::FilledFields { fields } if fields.is_empty() => {
+                write!(f, "duplicate, nothing to fill")
+            }
+            ImportOutcome::FilledFields { fields } => {
+                write!(f, "duplicate, filled: {}", fields.join(", "))
+            }
+            ImportOutcome::Pending => write!(f, "duplicate, pending decision"),
+        }
+    }
+}
+
+/// 导入结果：最终对应的文件条目及处理方式
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub entry: FileEntry,
+    pub outcome: ImportOutcome,
+}
+
+/// BibTeX 条目的导入结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BibImportResult {
+    pub key: String,
+    pub title: String,
+    /// 匹配到的文件，没有找到时为 None
+    pub file: Option<PathBuf>,
+    pub result: Option<ImportResult>,
+    /// 找到文件但导入失败时的错误信息
+    pub error: Option<String>,
+}
+
+/// 文件更新请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FileUpdateRequest {
     pub title: Option<String>,
     pub authors: Option<Vec<String>>,
+    /// 替换编者、译者、插画等其他署名
+    #[serde(default)]
+    pub contributors: Option<Vec<Contributor>>,
     pub year: Option<i32>,
     pub publisher: Option<String>,
     pub source: Option<String>,
@@ -86,6 +172,303 @@ pub struct SearchResult {
     pub total_count: usize,
     pub offset: usize,
     pub limit: usize,
+    /// 全文搜索在逐页文本中的命中位置
+    #[serde(default)]
+    pub page_hits: Vec<PageHit>,
+    /// 全文搜索在 PDF 批注中的命中
+    #[serde(default)]
+    pub annotation_hits: Vec<AnnotationHit>,
+}
+
+/// 逐页全文搜索的命中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageHit {
+    pub file_id: String,
+    /// 页码，从 1 开始
+    pub page: u32,
+    /// 命中附近的文本片段，命中词用 `[` `]` 标出
+    pub snippet: String,
+}
+
+/// 页级全文索引状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageIndexStatus {
+    /// 等待后台提取
+    Pending,
+    /// 全部页已索引
+    Complete,
+    /// 超出文本预算，只索引了前面的页
+    Truncated,
+    /// 提取失败
+    Failed,
+}
+
+impl PageIndexStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PageIndexStatus::Pending => "pending",
+            PageIndexStatus::Complete => "complete",
+            PageIndexStatus::Truncated => "truncated",
+            PageIndexStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for PageIndexStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// PDF 书签（大纲）条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutlineEntry {
+    pub title: String,
+    /// 目标页码，从 1 开始；无法解析目标时为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// 目标页的页面标签（如 `iv`、`A-3`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_label: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineEntry>,
+}
+
+/// PDF 页面标签区间，从 `start_page` 起按同一样式编号
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageLabelRange {
+    /// 区间起始页码，从 1 开始
+    pub start_page: u32,
+    /// 编号样式：`D` 十进制、`R`/`r` 罗马数字、`A`/`a` 字母，`None` 表示只有前缀
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prefix: String,
+    /// 区间第一页的编号
+    pub first: u32,
+}
+
+/// PDF 批注（高亮、下划线、便签等）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// 所在页码，从 1 开始
+    pub page: u32,
+    /// 批注类型，小写（`highlight`、`underline`、`text` 等）
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// 批注内容（笔记文字）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contents: Option<String>,
+    /// 高亮等标记覆盖的原文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quoted_text: Option<String>,
+    /// 最后修改时间（PDF 日期字符串）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<String>,
+}
+
+/// 批注搜索的命中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationHit {
+    pub file_id: String,
+    pub annotation: Annotation,
+}
+
+/// 作者在文件中的署名角色
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthorRole {
+    #[default]
+    Author,
+    Editor,
+    Translator,
+    Illustrator,
+}
+
+impl AuthorRole {
+    pub const ALL: [AuthorRole; 4] = [
+        AuthorRole::Author,
+        AuthorRole::Editor,
+        AuthorRole::Translator,
+        AuthorRole::Illustrator,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthorRole::Author => "author",
+            AuthorRole::Editor => "editor",
+            AuthorRole::Translator => "translator",
+            AuthorRole::Illustrator => "illustrator",
+        }
+    }
+
+    /// 按名称（`author`、`editor`、`translator`、`illustrator`，不区分大小写）解析
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+impl std::fmt::Display for AuthorRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 非作者署名（编者、译者、插画）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contributor {
+    pub name: String,
+    pub role: AuthorRole,
+}
+
+impl Contributor {
+    pub fn new(name: impl Into<String>, role: AuthorRole) -> Self {
+        Self {
+            name: name.into(),
+            role,
+        }
+    }
+}
+
+/// 文献标识符类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierKind {
+    Doi,
+    Arxiv,
+    Isbn,
+    Issn,
+}
+
+impl IdentifierKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierKind::Doi => "doi",
+            IdentifierKind::Arxiv => "arxiv",
+            IdentifierKind::Isbn => "isbn",
+            IdentifierKind::Issn => "issn",
+        }
+    }
+
+    /// 按名称（`doi`、`arxiv`、`isbn`、`issn`，不区分大小写）解析
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "doi" => Some(IdentifierKind::Doi),
+            "arxiv" => Some(IdentifierKind::Arxiv),
+            "isbn" => Some(IdentifierKind::Isbn),
+            "issn" => Some(IdentifierKind::Issn),
+            _ => None,
+        }
+    }
+
+    /// 是否唯一标识一部作品，可作为导入时的重复判断依据（ISSN 标识的是期刊）
+    pub fn identifies_work(&self) -> bool {
+        !matches!(self, IdentifierKind::Issn)
+    }
+}
+
+impl std::fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 规范化后的文献标识符
+///
+/// DOI 转为小写，arXiv 编号去掉版本号，ISBN 统一为 13 位数字，ISSN 为 `NNNN-NNNC`。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Identifier {
+    pub kind: IdentifierKind,
+    pub value: String,
+}
+
+impl std::fmt::Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.kind, self.value)
+    }
+}
+
+/// 元数据补全的查询条件，优先使用标识符，其次使用标题和作者
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnrichmentQuery {
+    #[serde(default)]
+    pub identifiers: Vec<Identifier>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+}
+
+impl EnrichmentQuery {
+    /// 用已入库文件的标题、作者和标识符构建查询
+    pub fn from_entry(entry: &FileEntry, identifiers: Vec<Identifier>) -> Self {
+        let title = entry.title.trim();
+        Self {
+            identifiers,
+            title: (!title.is_empty()).then(|| title.to_string()),
+            authors: entry.authors.clone(),
+        }
+    }
+
+    /// 缓存键，由标识符、小写标题和作者组成
+    pub fn cache_key(&self) -> String {
+        let identifiers: Vec<String> = self.identifiers.iter().map(|i| i.to_string()).collect();
+        format!(
+            "{}|{}|{}",
+            identifiers.join(","),
+            self.title.as_deref().unwrap_or_default().to_lowercase(),
+            self.authors.join(";").to_lowercase()
+        )
+    }
+}
+
+/// 外部数据源（Crossref、arXiv 等）返回的文献元数据
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnrichedMetadata {
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 完整作者列表，按署名顺序
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// 期刊、会议或丛书名
+    #[serde(default)]
+    pub venue: Option<String>,
+    #[serde(default)]
+    pub publisher: Option<String>,
+    #[serde(default, rename = "abstract")]
+    pub abstract_text: Option<String>,
+    /// 发表日期：`YYYY`、`YYYY-MM` 或 `YYYY-MM-DD`
+    #[serde(default)]
+    pub published: Option<String>,
+    #[serde(default)]
+    pub identifiers: Vec<Identifier>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl EnrichedMetadata {
+    /// 发表年份，取自发表日期
+    pub fn year(&self) -> Option<i32> {
+        self.published.as_deref()?.get(..4)?.parse().ok()
+    }
+}
+
+/// 一次元数据补全的结果：当前条目、数据源返回的元数据和据此生成的更新
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichmentProposal {
+    pub entry: FileEntry,
+    pub provider: String,
+    pub metadata: EnrichedMetadata,
+    /// 只包含与当前条目不同的字段，可直接交给 `Editor::update_file`
+    pub update: FileUpdateRequest,
+    /// 文件尚未记录的标识符
+    pub new_identifiers: Vec<Identifier>,
 }
 
 /// 作者信息
@@ -95,6 +478,126 @@ pub struct Author {
     pub name: String,
     pub aliases: Vec<String>,
     pub metadata: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub real_name: Option<String>,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// ORCID，规范化为 `0000-0000-0000-000X`
+    #[serde(default)]
+    pub orcid: Option<String>,
+}
+
+/// 作者列表项，附带关联文件数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorSummary {
+    pub id: String,
+    pub name: String,
+    pub orcid: Option<String>,
+    /// 作者本人及其别名关联的文件数
+    pub file_count: i64,
+    pub alias_count: i64,
+}
+
+/// 作者资料更新请求，`None` 表示不修改，空字符串表示清空
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthorUpdate {
+    pub name: Option<String>,
+    pub real_name: Option<String>,
+    pub bio: Option<String>,
+    pub homepage: Option<String>,
+    pub orcid: Option<String>,
+}
+
+/// 标签树节点
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TagNode {
+    pub id: String,
+    /// 最后一段名称，如 `async`
+    pub name: String,
+    /// 完整路径，如 `lang/rust/async`
+    pub path: String,
+    /// 直接打上该标签的文件数
+    pub file_count: i64,
+    /// 含所有子标签的文件数（同一文件只计一次）
+    pub total_count: i64,
+    pub children: Vec<TagNode>,
+}
+
+/// 标签列表项：直接关联的文件数和别名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagSummary {
+    pub id: String,
+    pub path: String,
+    pub file_count: i64,
+    pub aliases: Vec<String>,
+}
+
+/// 分类调整中一个文件的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryMove {
+    pub file_id: String,
+    pub title: String,
+    pub old_category: String,
+    pub new_category: String,
+    /// 调整前的绝对路径
+    pub old_path: PathBuf,
+    /// 按 `classify_template` 计算的新绝对路径
+    pub new_path: PathBuf,
+    /// 文件是否已移动到 `new_path`，预演或移动失败时为 false
+    pub moved: bool,
+}
+
+/// 批量编辑补丁：标签在现有标签上增删，而不是像 [`FileUpdateRequest::tags`] 那样整体替换
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilePatch {
+    #[serde(default)]
+    pub add_tags: Vec<String>,
+    /// 要移除的标签，子标签一并移除
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+    /// 新分类路径，如 `Archive/2025`
+    #[serde(default)]
+    pub set_category: Option<String>,
+    /// 分类改变后按 `classify_template` 移动文件
+    #[serde(default)]
+    pub move_files: bool,
+}
+
+impl FilePatch {
+    /// 补丁是否不做任何修改
+    pub fn is_empty(&self) -> bool {
+        self.add_tags.is_empty() && self.remove_tags.is_empty() && self.set_category.is_none()
+    }
+}
+
+/// 批量编辑中一个文件的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilePatchChange {
+    pub file_id: String,
+    pub title: String,
+    pub old_tags: Vec<String>,
+    pub new_tags: Vec<String>,
+    pub old_category: String,
+    pub new_category: String,
+    /// 修改前的绝对路径
+    pub old_path: PathBuf,
+    /// 修改后的绝对路径，不移动文件时与 `old_path` 相同
+    pub new_path: PathBuf,
+    /// 文件是否已移动到 `new_path`，预演或移动失败时为 false
+    pub moved: bool,
+}
+
+/// 一次批量编辑（或撤销）的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditBatch {
+    /// 批次 ID，用于撤销；预演时为 `None`
+    pub batch_id: Option<String>,
+    /// 匹配的文件数，含无需修改的文件
+    pub matched: usize,
+    /// 有变化的文件
+    pub changes: Vec<FilePatchChange>,
 }
 
 /// 文件关系类型
@@ -104,6 +607,7 @@ pub enum RelationType {
     DerivedFrom,
     Relates,
     Depends,
+    ContainedIn,
     Custom(String),
 }
 
@@ -115,6 +619,7 @@ impl From<Option<String>> for RelationType {
                 "derived_from" => RelationType::DerivedFrom,
                 "relates" => RelationType::Relates,
                 "depends" => RelationType::Depends,
+                "contained_in" => RelationType::ContainedIn,
                 custom => RelationType::Custom(custom.to_string()),
             },
             None => RelationType::Relates,
@@ -129,6 +634,7 @@ impl std::fmt::Display for RelationType {
             RelationType::DerivedFrom => write!(f, "derived_from"),
             RelationType::Relates => write!(f, "relates"),
             RelationType::Depends => write!(f, "depends"),
+            RelationType::ContainedIn => write!(f, "contained_in"),
             RelationType::Custom(s) => write!(f, "{}", s),
         }
     }
@@ -214,6 +214,18 @@ pub fn normalize_path(path: &Path) -> Result<PathBuf> {
     Ok(canonical)
 }
 
+/// 拆分 `a/b/c` 形式的层级路径（标签和分类共用），去掉每段首尾空白和空段
+pub fn hierarchy_segments(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+}
+
+/// 规范化层级路径：`" a / b/ "` 变为 `a/b`
+pub fn normalize_hierarchy_path(raw: &str) -> String {
+    hierarchy_segments(raw).collect::<Vec<_>>().join("/")
+}
+
 /// 确保目录存在，如果不存在则创建
 pub fn ensure_dir_exists(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -298,13 +310,75 @@ pub fn require_field<T>(opt: Option<T>, field: &str) -> Result<T> {
     })
 }
 
+/// 根据扩展名推断 MIME 类型
+pub fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    let mime = match ext.as_str() {
+        "pdf" => "application/pdf",
+        "epub" => "application/epub+zip",
+        "djvu" => "image/vnd.djvu",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "txt" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "tif" | "tiff" => "image/tiff",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "ogg" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" | "tgz" => "application/gzip",
+        "7z" => "application/x-7z-compressed",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// 解析文件大小字符串，支持 B/KB/MB/GB/TB 单位（1024 进制，不区分大小写）
+///
+/// 例如 "1024"、"10MB"、"1.5g"
+pub fn parse_size(size_str: &str) -> Option<u64> {
+    let s = size_str.trim().to_lowercase();
+    let split_at = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split_at);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier: u64 = match unit.trim() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return None,
+    };
+
+    Some((number * multiplier as f64) as u64)
+}
+
 /// 解析分类字符串，支持格式：
 /// - "category1"            -> (category1, None, None)
 /// - "category1/category2"  -> (category1, Some(category2), None)  
 /// - "category1/category2/category3" -> (category1, Some(category2), Some(category3))
-/// - "category1/category2/category3/ignored" -> (category1, Some(category2), Some(category3))
+/// - "category1/category2/category3/category4" -> (category1, Some(category2), Some("category3/category4"))
 ///
-/// 分类名不能包含 '/' 字符
+/// 层级不限，第三级及更深层级以 '/' 连接放在第三项（见 [`crate::CategoryPath`]）
 pub fn parse_category_string(
     category_str: &str,
 ) -> Result<(String, Option<String>, Option<String>)> {
@@ -330,29 +404,7 @@ pub fn parse_category_string(
         }
     }
 
-    match parts.len() {
-        1 => Ok((parts[0].trim().to_string(), None, None)),
-        2 => Ok((
-            parts[0].trim().to_string(),
-            Some(parts[1].trim().to_string()),
-            None,
-        )),
-        3 => Ok((
-            parts[0].trim().to_string(),
-            Some(parts[1].trim().to_string()),
-            Some(parts[2].trim().to_string()),
-        )),
-        n if n > 3 => {
-            // 超过3级的情况，只取前3级，发出警告
-            tracing::warn!("分类层级超过3级，只使用前3级: {}", category_str);
-            Ok((
-                parts[0].trim().to_string(),
-                Some(parts[1].trim().to_string()),
-                Some(parts[2].trim().to_string()),
-            ))
-        }
-        _ => unreachable!("split应该至少返回1个元素"),
-    }
+    Ok(crate::categories::CategoryPath::parse(category_str).to_columns())
 }
 
 #[cfg(test)]
@@ -383,13 +435,13 @@ mod tests {
             )
         );
 
-        // 超过三级（只取前三级）
+        // 超过三级（更深层级合并到第三项）
         assert_eq!(
             parse_category_string("Tech/Programming/Rust/Web/Backend").unwrap(),
             (
                 "Tech".to_string(),
                 Some("Programming".to_string()),
-                Some("Rust".to_string())
+                Some("Rust/Web/Backend".to_string())
             )
         );
 
@@ -410,4 +462,14 @@ mod tests {
         assert!(parse_category_string("Tech//Programming").is_err());
         assert!(parse_category_string("Tech/ /Programming").is_err());
     }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024"), Some(1024));
+        assert_eq!(parse_size("10MB"), Some(10 * 1024 * 1024));
+        assert_eq!(parse_size("1.5k"), Some(1536));
+        assert_eq!(parse_size("2 GB"), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_size("MB"), None);
+        assert_eq!(parse_size("10XB"), None);
+    }
 }
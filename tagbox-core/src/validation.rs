@@ -111,12 +111,13 @@ impl FileValidator {
             Some(row) => {
                 let file_id: String = row.get(0);
                 let stored_hash: String = row.get(1);
-                let stored_size: i64 = row.get(2);
+                // 旧数据库中的条目可能没有记录大小
+                let stored_size: Option<i64> = row.get(2);
 
                 let metadata = fs::metadata(&absolute_path).await?;
                 let current_size = metadata.len() as i64;
 
-                if current_size != stored_size {
+                if let Some(stored_size) = stored_size.filter(|s| *s != current_size) {
                     return Ok(ValidationResult {
                         file_id: Some(file_id),
                         path: absolute_path.clone(),
@@ -173,7 +174,7 @@ impl FileValidator {
 
         let relative_path: String = row.get(0);
         let old_hash: String = row.get(1);
-        let old_size: i64 = row.get(2);
+        let old_size: Option<i64> = row.get(2);
 
         let full_path = self.config.import.paths.storage_dir.join(&relative_path);
         let metadata = fs::metadata(&full_path).await?;
@@ -265,6 +266,7 @@ impl FileValidator {
             updated_at,
             // Fill in other required fields with defaults
             authors: vec![],
+            contributors: Vec::new(),
             year: None,
             publisher: None,
             source: None,
@@ -275,6 +277,9 @@ impl FileValidator {
                 .next_back()
                 .unwrap_or("")
                 .to_string(),
+            size: Some(new_size as u64),
+            mime_type: None,
+            mtime: None,
             category1: "未分类".to_string(),
             category2: None,
             category3: None,
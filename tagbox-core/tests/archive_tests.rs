@@ -0,0 +1,149 @@
+use std::path::Path;
+use tagbox_core::config::{AppConfig, DuplicatePolicy};
+use tagbox_core::schema::Database;
+use tagbox_core::types::ImportOutcome;
+use tagbox_core::{
+    explode_archive, extract_metainfo, import_file, search_files_advanced, LinkManager,
+};
+
+mod common;
+use common::setup;
+
+const ZIP_PATH: &str = "../test/data/project-atlas.zip";
+const TAR_GZ_PATH: &str = "../test/data/field-logs.tar.gz";
+
+#[tokio::test]
+async fn test_zip_members_listed() {
+    let metadata = extract_metainfo(Path::new(ZIP_PATH), &AppConfig::default())
+        .await
+        .unwrap();
+
+    let archive = &metadata.file_metadata.unwrap()["archive"];
+    assert_eq!(archive["format"], "zip");
+    assert_eq!(archive["member_count"], 4);
+    assert_eq!(archive["total_size"], 280);
+
+    // 目录不计入成员，类型按内容检测
+    let members = archive["members"].as_array().unwrap();
+    let types: Vec<(&str, &str)> = members
+        .iter()
+        .map(|m| (m["path"].as_str().unwrap(), m["type"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("atlas/README.md", "text/markdown"),
+            ("atlas/docs/notes.html", "text/html"),
+            ("atlas/config.json", "application/json"),
+            ("atlas/logo.png", "image/png"),
+        ]
+    );
+
+    let text = metadata.full_text.unwrap();
+    assert!(text.contains("atlas/logo.png"));
+    assert!(text.contains("orbital simulator"));
+    assert!(text.contains("Telemetry uses quaternions."));
+    assert!(!text.contains("color:red"));
+}
+
+#[tokio::test]
+async fn test_tar_gz_members_listed() {
+    let metadata = extract_metainfo(Path::new(TAR_GZ_PATH), &AppConfig::default())
+        .await
+        .unwrap();
+
+    let archive = &metadata.file_metadata.unwrap()["archive"];
+    assert_eq!(archive["format"], "tar.gz");
+    assert_eq!(archive["member_count"], 4);
+    assert_eq!(archive["members"][0]["path"], "logs/day1.txt");
+    assert_eq!(archive["members"][3]["type"], "image/png");
+
+    assert!(metadata.full_text.unwrap().contains("seismic tremors"));
+}
+
+#[tokio::test]
+async fn test_member_text_is_searchable() {
+    let (_temp_dir, config) = setup().await;
+
+    let path = Path::new(TAR_GZ_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let entry = import_file(path, metadata, &config).await.unwrap();
+
+    for query in ["magnetometer", "tremors"] {
+        let result = search_files_advanced(query, None, &config).await.unwrap();
+        assert_eq!(result.entries.len(), 1, "query: {}", query);
+        assert_eq!(result.entries[0].id, entry.id);
+    }
+}
+
+#[tokio::test]
+async fn test_explode_archive() {
+    let (_temp_dir, config) = setup().await;
+
+    let path = Path::new(ZIP_PATH);
+    let mut metadata = extract_metainfo(path, &config).await.unwrap();
+    metadata.category1 = "Projects".to_string();
+    metadata.category2 = Some("Atlas".to_string());
+    let archive = import_file(path, metadata, &config).await.unwrap();
+
+    let results = explode_archive(&archive.id, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|r| r.outcome == ImportOutcome::Imported));
+
+    let readme = results
+        .iter()
+        .find(|r| r.entry.original_filename == "README.md")
+        .unwrap();
+    assert_eq!(readme.entry.title, "Project Atlas");
+    // 成员沿用压缩包的分类
+    assert_eq!(readme.entry.category1, "Projects");
+    assert_eq!(readme.entry.category2.as_deref(), Some("Atlas"));
+    let origin = &readme.entry.file_metadata.as_ref().unwrap()["archive_member"];
+    assert_eq!(origin["archive_id"], archive.id.as_str());
+    assert_eq!(origin["path"], "atlas/README.md");
+
+    let db = Database::new(&config.database.path).await.unwrap();
+    let links = LinkManager::new(db.pool().clone())
+        .get_links_for_file(&archive.id)
+        .await
+        .unwrap();
+    assert_eq!(links.len(), 4);
+    assert!(links
+        .iter()
+        .all(|(_, relation, _)| relation == "contained_in"));
+
+    // 再次展开时成员都是重复文件
+    let again = explode_archive(&archive.id, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+    assert!(again.iter().all(|r| r.outcome == ImportOutcome::Skipped));
+}
+
+#[tokio::test]
+async fn test_explode_skips_unsafe_paths() {
+    let (_temp_dir, config) = setup().await;
+
+    let path = Path::new(TAR_GZ_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let archive = import_file(path, metadata, &config).await.unwrap();
+
+    let results = explode_archive(&archive.id, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+
+    // ../escape.txt 不会被解压
+    let mut names: Vec<&str> = results
+        .iter()
+        .map(|r| r.entry.original_filename.as_str())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["day1.txt", "day2.txt", "photo.png"]);
+
+    // 普通文件不能展开
+    let member = &results[0].entry.id;
+    assert!(explode_archive(member, DuplicatePolicy::Skip, &config)
+        .await
+        .is_err());
+}
@@ -0,0 +1,240 @@
+use std::fs;
+use tagbox_core::bibliography::BibFormat;
+use tagbox_core::config::AppConfig;
+use tagbox_core::schema::Database;
+use tagbox_core::types::{
+    AuthorRole, AuthorUpdate, Contributor, FileUpdateRequest, ImportMetadata,
+};
+use tagbox_core::{
+    edit_file, export_bibliography, get_file, import_file, search_files_advanced, AuthorManager,
+};
+use tempfile::TempDir;
+
+mod common;
+use common::setup;
+
+async fn import_with_authors(
+    temp_dir: &TempDir,
+    name: &str,
+    authors: &[&str],
+    config: &AppConfig,
+) -> Vec<String> {
+    let path = temp_dir.path().join(name);
+    fs::write(&path, format!("# {}\n", name)).unwrap();
+    let metadata = ImportMetadata {
+        title: name.to_string(),
+        authors: authors.iter().map(|a| a.to_string()).collect(),
+        ..Default::default()
+    };
+    let entry = import_file(&path, metadata, config).await.unwrap();
+    let mut stored = get_file(&entry.id, config).await.unwrap().authors;
+    stored.sort();
+    stored
+}
+
+#[tokio::test]
+async fn test_import_splits_and_normalizes_authors() {
+    let (temp_dir, config) = setup().await;
+
+    let authors = import_with_authors(
+        &temp_dir,
+        "first.md",
+        &["Vaswani, A.; Shazeer, N. and Parmar, N."],
+        &config,
+    )
+    .await;
+    assert_eq!(authors, vec!["A. Vaswani", "N. Parmar", "N. Shazeer"]);
+
+    // 大小写、标点和"姓, 名"顺序不同的写法复用已有作者
+    let authors = import_with_authors(
+        &temp_dir,
+        "second.md",
+        &["vaswani, a", "N Shazeer"],
+        &config,
+    )
+    .await;
+    assert_eq!(authors, vec!["A. Vaswani", "N. Shazeer"]);
+
+    // 中文姓名去掉姓和名之间的空格
+    let authors = import_with_authors(&temp_dir, "third.md", &["张 三", "张, 三"], &config).await;
+    assert_eq!(authors, vec!["张三"]);
+}
+
+#[tokio::test]
+async fn test_author_aliases_resolve_to_canonical() {
+    let (temp_dir, config) = setup().await;
+
+    let db = Database::new(&config.database.path).await.unwrap();
+    let manager = AuthorManager::new(db.pool().clone());
+    let (canonical_id, _) = manager
+        .find_or_create_author("Ashish Vaswani")
+        .await
+        .unwrap();
+    let (alias_id, alias_name) = manager.find_or_create_author("Vaswani, A.").await.unwrap();
+    assert_eq!(alias_name, "A. Vaswani");
+    manager
+        .add_author_alias(&canonical_id, &alias_id)
+        .await
+        .unwrap();
+
+    let authors = import_with_authors(
+        &temp_dir,
+        "paper.md",
+        &["Vaswani, A.", "Ashish Vaswani"],
+        &config,
+    )
+    .await;
+    assert_eq!(authors, vec!["Ashish Vaswani"]);
+
+    // 编辑作者时同样经过别名解析
+    let path = temp_dir.path().join("other.md");
+    fs::write(&path, "# Other\n").unwrap();
+    let metadata = ImportMetadata {
+        title: "Other".to_string(),
+        ..Default::default()
+    };
+    let entry = import_file(&path, metadata, &config).await.unwrap();
+    let update = FileUpdateRequest {
+        authors: Some(vec!["A. Vaswani & Shazeer, Noam".to_string()]),
+        ..Default::default()
+    };
+    edit_file(&entry.id, update, &config).await.unwrap();
+
+    let mut authors = get_file(&entry.id, &config).await.unwrap().authors;
+    authors.sort();
+    assert_eq!(authors, vec!["Ashish Vaswani", "Noam Shazeer"]);
+}
+
+#[tokio::test]
+async fn test_author_order_and_roles() {
+    let (temp_dir, config) = setup().await;
+
+    let path = temp_dir.path().join("book.md");
+    fs::write(&path, "# Book\n").unwrap();
+    let metadata = ImportMetadata {
+        title: "Book".to_string(),
+        authors: vec!["Zed Zulu".to_string(), "Amy Alpha".to_string()],
+        contributors: vec![Contributor::new("Knuth, Donald", AuthorRole::Editor)],
+        ..Default::default()
+    };
+    let entry = import_file(&path, metadata, &config).await.unwrap();
+
+    // 作者保持署名顺序，编者单独记录
+    let stored = get_file(&entry.id, &config).await.unwrap();
+    assert_eq!(stored.authors, vec!["Zed Zulu", "Amy Alpha"]);
+    assert_eq!(
+        stored.contributors,
+        vec![Contributor::new("Donald Knuth", AuthorRole::Editor)]
+    );
+
+    // 角色过滤只匹配对应角色
+    let result = search_files_advanced("editor:knuth", None, &config)
+        .await
+        .unwrap();
+    assert_eq!(result.entries.len(), 1);
+    let result = search_files_advanced("author:knuth", None, &config)
+        .await
+        .unwrap();
+    assert!(result.entries.is_empty());
+
+    // 编辑时调整顺序并替换译者
+    let update = FileUpdateRequest {
+        authors: Some(vec!["Amy Alpha".to_string(), "Zed Zulu".to_string()]),
+        contributors: Some(vec![Contributor::new("Tina Trans", AuthorRole::Translator)]),
+        ..Default::default()
+    };
+    edit_file(&entry.id, update, &config).await.unwrap();
+    let stored = get_file(&entry.id, &config).await.unwrap();
+    assert_eq!(stored.authors, vec!["Amy Alpha", "Zed Zulu"]);
+    assert_eq!(
+        stored.contributors,
+        vec![Contributor::new("Tina Trans", AuthorRole::Translator)]
+    );
+
+    let bibtex = export_bibliography(&[stored], BibFormat::Bibtex, &config)
+        .await
+        .unwrap();
+    assert!(bibtex.contains("Amy Alpha and Zed Zulu"));
+    assert!(bibtex.contains("Tina Trans"));
+}
+
+#[tokio::test]
+async fn test_author_profile_merge_and_dedupe() {
+    let (temp_dir, config) = setup().await;
+
+    import_with_authors(&temp_dir, "one.md", &["Donald Knuth"], &config).await;
+    import_with_authors(
+        &temp_dir,
+        "two.md",
+        &["D. Knuth", "Leslie Lamport"],
+        &config,
+    )
+    .await;
+
+    let db = Database::new(&config.database.path).await.unwrap();
+    let manager = AuthorManager::new(db.pool().clone());
+    let (knuth_id, _) = manager
+        .resolve_author("Donald Knuth")
+        .await
+        .unwrap()
+        .unwrap();
+    let (short_id, _) = manager.resolve_author("D. Knuth").await.unwrap().unwrap();
+
+    // ORCID 校验并规范化，空字符串清空字段
+    let update = AuthorUpdate {
+        orcid: Some("https://orcid.org/0000000218250097".to_string()),
+        homepage: Some("https://example.org/knuth".to_string()),
+        ..Default::default()
+    };
+    let author = manager.update_author(&short_id, update).await.unwrap();
+    assert_eq!(author.orcid.as_deref(), Some("0000-0002-1825-0097"));
+    let update = AuthorUpdate {
+        homepage: Some(String::new()),
+        ..Default::default()
+    };
+    assert!(manager
+        .update_author(&short_id, update)
+        .await
+        .unwrap()
+        .homepage
+        .is_none());
+    let update = AuthorUpdate {
+        orcid: Some("0000-0002-1825-0098".to_string()),
+        ..Default::default()
+    };
+    assert!(manager.update_author(&short_id, update).await.is_err());
+
+    let duplicates = manager.find_duplicate_authors().await.unwrap();
+    assert_eq!(duplicates.len(), 1);
+    assert!([&knuth_id, &short_id].contains(&&duplicates[0].0));
+
+    // 合并后旧名字仍解析到目标作者，资料和文件都转过去
+    manager.merge_authors(&short_id, &knuth_id).await.unwrap();
+    let author = manager.get_author(&knuth_id).await.unwrap();
+    assert_eq!(author.aliases, vec!["D. Knuth"]);
+    assert_eq!(author.orcid.as_deref(), Some("0000-0002-1825-0097"));
+    assert_eq!(manager.get_author_files(&knuth_id).await.unwrap().len(), 2);
+    assert_eq!(
+        manager.resolve_author("D. Knuth").await.unwrap().unwrap().0,
+        knuth_id
+    );
+    assert!(manager.find_duplicate_authors().await.unwrap().is_empty());
+
+    let authors = manager.list_authors().await.unwrap();
+    let names: Vec<_> = authors.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["Donald Knuth", "Leslie Lamport"]);
+    assert_eq!(authors[0].file_count, 2);
+    assert_eq!(authors[0].alias_count, 1);
+
+    // 仍有文件的作者不能直接删除
+    assert!(manager.delete_author(&knuth_id).await.is_err());
+
+    // 取消别名后恢复为独立作者
+    manager
+        .remove_author_alias(&knuth_id, &short_id)
+        .await
+        .unwrap();
+    assert_eq!(manager.list_authors().await.unwrap().len(), 3);
+    manager.delete_author(&short_id).await.unwrap();
+    assert_eq!(manager.list_authors().await.unwrap().len(), 2);
+}
@@ -0,0 +1,255 @@
+use std::fs;
+use tagbox_core::bibliography::{parse_bibtex, BibFormat, CitationStyle};
+use tagbox_core::config::DuplicatePolicy;
+use tagbox_core::types::{Identifier, IdentifierKind, ImportMetadata, ImportOutcome};
+use tagbox_core::{
+    export_bibliography, format_citations, get_file, get_file_identifiers, import_bibtex,
+    import_file,
+};
+
+mod common;
+use common::setup;
+
+const PDF_PATH: &str = "../test/data/1706.03762v7.pdf";
+
+const REFS_BIB: &str = r#"
+@string{neurips = "Advances in Neural Information Processing Systems"}
+
+@inproceedings{vaswani2017attention,
+  author    = {Vaswani, Ashish and Shazeer, Noam and Parmar, Niki},
+  title     = {Attention Is {All} You Need},
+  booktitle = neurips # " 30",
+  year      = {2017},
+  eprint    = {1706.03762},
+  archiveprefix = {arXiv},
+  keywords  = {transformers, nlp},
+  file      = {:pdfs/attention.pdf:PDF},
+}
+
+@book{knuth1992literate,
+  author    = {Donald E. Knuth},
+  title     = {Literate Programming},
+  publisher = {CSLI},
+  year      = 1992,
+  isbn      = {0-937073-80-6},
+}
+
+@article{missing2020,
+  author = {Nobody, Some},
+  title  = {A Paper Without a File},
+  year   = {2020},
+}
+"#;
+
+#[tokio::test]
+async fn test_import_bibtex_matches_files() {
+    let (temp_dir, config) = setup().await;
+
+    let pdfs = temp_dir.path().join("pdfs");
+    fs::create_dir_all(&pdfs).unwrap();
+    fs::copy(PDF_PATH, pdfs.join("attention.pdf")).unwrap();
+    // 没有 file 字段的条目按标题匹配文件名
+    fs::write(pdfs.join("Literate_Programming.md"), "# Notes\n").unwrap();
+
+    let bib_path = temp_dir.path().join("refs.bib");
+    fs::write(&bib_path, REFS_BIB).unwrap();
+
+    let results = import_bibtex(&bib_path, &pdfs, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 3);
+
+    let attention = results[0].result.as_ref().unwrap();
+    assert_eq!(attention.outcome, ImportOutcome::Imported);
+    let entry = get_file(&attention.entry.id, &config).await.unwrap();
+    assert_eq!(entry.title, "Attention Is All You Need");
+    assert_eq!(entry.authors.len(), 3);
+    assert!(entry.authors.contains(&"Ashish Vaswani".to_string()));
+    assert_eq!(entry.year, Some(2017));
+    assert!(entry.tags.contains(&"transformers".to_string()));
+
+    // 条目类型、引用键和会议名记录在 file_metadata.bibtex，PDF 提取的元数据保留
+    let file_metadata = entry.file_metadata.unwrap();
+    assert_eq!(file_metadata["bibtex"]["key"], "vaswani2017attention");
+    assert_eq!(file_metadata["bibtex"]["type"], "inproceedings");
+    assert_eq!(
+        file_metadata["bibtex"]["venue"],
+        "Advances in Neural Information Processing Systems 30"
+    );
+    assert!(file_metadata.get("pdf").is_some());
+
+    let identifiers = get_file_identifiers(&attention.entry.id, &config)
+        .await
+        .unwrap();
+    assert!(identifiers.contains(&Identifier {
+        kind: IdentifierKind::Arxiv,
+        value: "1706.03762".to_string(),
+    }));
+
+    let knuth = results[1].result.as_ref().unwrap();
+    assert_eq!(knuth.entry.title, "Literate Programming");
+    assert_eq!(knuth.entry.publisher.as_deref(), Some("CSLI"));
+    assert!(results[1]
+        .file
+        .as_ref()
+        .unwrap()
+        .ends_with("Literate_Programming.md"));
+
+    assert_eq!(results[2].key, "missing2020");
+    assert!(results[2].file.is_none());
+    assert!(results[2].result.is_none());
+
+    // 再次导入时按重复策略跳过
+    let again = import_bibtex(&bib_path, &pdfs, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+    assert_eq!(
+        again[0].result.as_ref().unwrap().outcome,
+        ImportOutcome::Skipped
+    );
+
+    // 导出后沿用原来的引用键和条目类型，可以重新解析
+    let entries = vec![
+        get_file(&attention.entry.id, &config).await.unwrap(),
+        get_file(&knuth.entry.id, &config).await.unwrap(),
+    ];
+    let exported = export_bibliography(&entries, BibFormat::Bibtex, &config)
+        .await
+        .unwrap();
+    assert!(exported.contains("@inproceedings{vaswani2017attention,"));
+    assert!(exported.contains("@book{knuth1992literate,"));
+
+    let parsed = parse_bibtex(&exported);
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].title(), Some("Attention Is All You Need"));
+    assert_eq!(
+        parsed[0].venue(),
+        Some("Advances in Neural Information Processing Systems 30")
+    );
+    assert_eq!(parsed[0].get("eprint"), Some("1706.03762"));
+    assert_eq!(parsed[1].get("isbn"), Some("9780937073803"));
+}
+
+#[tokio::test]
+async fn test_export_citation_keys_and_ris() {
+    let (temp_dir, config) = setup().await;
+
+    let mut entries = Vec::new();
+    // 两篇的姓、年份和标题首个实词相同
+    for (name, title) in [
+        ("first.md", "The Deep Learning Survey"),
+        ("second.md", "Deep Models Revisited"),
+    ] {
+        let path = temp_dir.path().join(name);
+        fs::write(&path, format!("# {}\n", title)).unwrap();
+        let metadata = ImportMetadata {
+            title: title.to_string(),
+            authors: vec!["Jürgen Müller".to_string()],
+            year: Some(2020),
+            tags: vec!["ml".to_string()],
+            ..Default::default()
+        };
+        entries.push(import_file(&path, metadata, &config).await.unwrap());
+    }
+
+    // 同名键按导入顺序加后缀，与导出顺序无关
+    let exported = export_bibliography(&entries, BibFormat::Bibtex, &config)
+        .await
+        .unwrap();
+    let reversed: Vec<_> = entries.iter().rev().cloned().collect();
+    let exported_reversed = export_bibliography(&reversed, BibFormat::Bibtex, &config)
+        .await
+        .unwrap();
+
+    let keys: Vec<String> = parse_bibtex(&exported).into_iter().map(|e| e.key).collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(sorted, vec!["muller2020deepa", "muller2020deepb"]);
+
+    let keys_reversed: Vec<String> = parse_bibtex(&exported_reversed)
+        .into_iter()
+        .rev()
+        .map(|e| e.key)
+        .collect();
+    assert_eq!(keys, keys_reversed);
+
+    let ris = export_bibliography(&entries[..1], BibFormat::Ris, &config)
+        .await
+        .unwrap();
+    assert!(ris.starts_with("TY  - GEN\n"));
+    assert!(ris.contains("ID  - muller2020deep\n"));
+    assert!(ris.contains("TI  - The Deep Learning Survey\n"));
+    assert!(ris.contains("AU  - Jürgen Müller\n"));
+    assert!(ris.contains("PY  - 2020\n"));
+    assert!(ris.contains("KW  - ml\n"));
+    assert!(ris.trim_end().ends_with("ER  -"));
+}
+
+#[tokio::test]
+async fn test_citations_and_csl_json() {
+    let (temp_dir, config) = setup().await;
+
+    let path = temp_dir.path().join("paper.md");
+    fs::write(&path, "# Testing Citations\n").unwrap();
+    let metadata = ImportMetadata {
+        title: "Testing Citations".to_string(),
+        authors: vec!["Jane Doe".to_string()],
+        year: Some(2021),
+        publisher: Some("Test Society".to_string()),
+        file_metadata: Some(serde_json::json!({
+            "bibtex": {"type": "article", "venue": "Journal of Tests"},
+            "identifiers": [{"kind": "doi", "value": "10.1234/test.5678"}]
+        })),
+        ..Default::default()
+    };
+    let entry = import_file(&path, metadata, &config).await.unwrap();
+    let ids = vec![entry.id.clone()];
+
+    let apa = format_citations(&ids, CitationStyle::Apa, &config)
+        .await
+        .unwrap();
+    assert_eq!(
+        apa,
+        vec![
+            "Doe, J. (2021). Testing Citations. Journal of Tests. https://doi.org/10.1234/test.5678"
+        ]
+    );
+
+    let ieee = format_citations(&ids, CitationStyle::Ieee, &config)
+        .await
+        .unwrap();
+    assert_eq!(
+        ieee[0],
+        "J. Doe, \"Testing Citations,\" Journal of Tests, 2021. doi: 10.1234/test.5678."
+    );
+
+    let chicago = format_citations(&ids, CitationStyle::Chicago, &config)
+        .await
+        .unwrap();
+    assert_eq!(
+        chicago[0],
+        "Doe, Jane. 2021. \"Testing Citations.\" Journal of Tests. https://doi.org/10.1234/test.5678."
+    );
+
+    assert!(
+        format_citations(&["missing".to_string()], CitationStyle::Apa, &config)
+            .await
+            .is_err()
+    );
+
+    let csl = export_bibliography(&[entry], BibFormat::CslJson, &config)
+        .await
+        .unwrap();
+    let items: Vec<serde_json::Value> = serde_json::from_str(&csl).unwrap();
+    assert_eq!(items.len(), 1);
+    let item = &items[0];
+    assert_eq!(item["id"], "doe2021testing");
+    assert_eq!(item["type"], "article-journal");
+    assert_eq!(item["title"], "Testing Citations");
+    assert_eq!(item["author"][0]["family"], "Doe");
+    assert_eq!(item["author"][0]["given"], "Jane");
+    assert_eq!(item["issued"]["date-parts"][0][0], 2021);
+    assert_eq!(item["container-title"], "Journal of Tests");
+    assert_eq!(item["publisher"], "Test Society");
+    assert_eq!(item["DOI"], "10.1234/test.5678");
+}
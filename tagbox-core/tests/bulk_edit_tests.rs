@@ -0,0 +1,169 @@
+use std::fs;
+use tagbox_core::config::AppConfig;
+use tagbox_core::types::{FilePatch, ImportMetadata};
+use tagbox_core::{
+    bulk_edit, bulk_edit_files, get_file, get_file_history, import_file, undo_edit_batch,
+};
+use tempfile::TempDir;
+
+mod common;
+
+async fn setup() -> (TempDir, AppConfig) {
+    common::setup_with(|_, config| {
+        config.import.paths.classify_template = "{category1}/{category2}/{filename}".to_string();
+    })
+    .await
+}
+
+async fn import_with_tags(
+    temp_dir: &TempDir,
+    name: &str,
+    tags: &[&str],
+    config: &AppConfig,
+) -> String {
+    let path = temp_dir.path().join(name);
+    fs::write(&path, format!("# {}\n", name)).unwrap();
+    let metadata = ImportMetadata {
+        title: name.to_string(),
+        category1: "Inbox".to_string(),
+        category2: Some("2025".to_string()),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..Default::default()
+    };
+    import_file(&path, metadata, config).await.unwrap().id
+}
+
+fn patch() -> FilePatch {
+    FilePatch {
+        add_tags: vec!["reviewed".to_string()],
+        remove_tags: vec!["draft".to_string()],
+        set_category: Some("Archive/2025".to_string()),
+        move_files: false,
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_edit_dry_run_and_apply() {
+    let (temp_dir, config) = setup().await;
+
+    let draft = import_with_tags(&temp_dir, "draft.md", &["draft", "rust"], &config).await;
+    let other = import_with_tags(&temp_dir, "other.md", &["rust"], &config).await;
+
+    // 预演只返回变化，不修改数据库
+    let preview = bulk_edit("tag:draft", &patch(), true, &config)
+        .await
+        .unwrap();
+    assert_eq!(preview.matched, 1);
+    assert!(preview.batch_id.is_none());
+    assert_eq!(preview.changes.len(), 1);
+    assert_eq!(preview.changes[0].file_id, draft);
+    assert_eq!(preview.changes[0].new_tags, vec!["reviewed", "rust"]);
+    assert_eq!(preview.changes[0].new_category, "Archive/2025");
+    assert!(get_file(&draft, &config)
+        .await
+        .unwrap()
+        .tags
+        .contains(&"draft".to_string()));
+
+    let batch = bulk_edit("tag:draft", &patch(), false, &config)
+        .await
+        .unwrap();
+    let batch_id = batch.batch_id.expect("applied batch has an id");
+
+    let entry = get_file(&draft, &config).await.unwrap();
+    let mut tags = entry.tags.clone();
+    tags.sort();
+    assert_eq!(tags, vec!["reviewed", "rust"]);
+    assert_eq!(entry.category1, "Archive");
+    assert_eq!(entry.category2.as_deref(), Some("2025"));
+
+    // 未匹配的文件保持不变
+    let untouched = get_file(&other, &config).await.unwrap();
+    assert_eq!(untouched.tags, vec!["rust"]);
+    assert_eq!(untouched.category1, "Inbox");
+
+    let history = get_file_history(&draft, None, &config).await.unwrap();
+    assert!(history
+        .iter()
+        .any(|h| h.operation == "patch" && h.batch_id.as_deref() == Some(batch_id.as_str())));
+}
+
+#[tokio::test]
+async fn test_bulk_edit_undo() {
+    let (temp_dir, config) = setup().await;
+
+    let a = import_with_tags(&temp_dir, "a.md", &["draft"], &config).await;
+    let b = import_with_tags(&temp_dir, "b.md", &["draft", "rust"], &config).await;
+
+    let batch = bulk_edit_files(&[a.clone(), b.clone()], &patch(), false, &config)
+        .await
+        .unwrap();
+    assert_eq!(batch.changes.len(), 2);
+    let batch_id = batch.batch_id.unwrap();
+
+    // 再次应用同一补丁不会产生变化
+    let again = bulk_edit_files(&[a.clone(), b.clone()], &patch(), true, &config)
+        .await
+        .unwrap();
+    assert!(again.changes.is_empty());
+
+    let undo = undo_edit_batch(&batch_id, &config).await.unwrap();
+    assert_eq!(undo.changes.len(), 2);
+
+    let entry = get_file(&b, &config).await.unwrap();
+    let mut tags = entry.tags.clone();
+    tags.sort();
+    assert_eq!(tags, vec!["draft", "rust"]);
+    assert_eq!(entry.category1, "Inbox");
+    assert_eq!(get_file(&a, &config).await.unwrap().tags, vec!["draft"]);
+
+    // 同一批次不能撤销两次，未知批次报错
+    assert!(undo_edit_batch(&batch_id, &config).await.is_err());
+    assert!(undo_edit_batch("missing", &config).await.is_err());
+    assert!(bulk_edit_files(&[a], &FilePatch::default(), true, &config)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_bulk_edit_undo_keeps_later_edits() {
+    let (temp_dir, config) = setup().await;
+
+    let edited = import_with_tags(&temp_dir, "edited.md", &["draft"], &config).await;
+    let untouched = import_with_tags(&temp_dir, "untouched.md", &["draft"], &config).await;
+
+    let batch = bulk_edit_files(
+        &[edited.clone(), untouched.clone()],
+        &patch(),
+        false,
+        &config,
+    )
+    .await
+    .unwrap();
+    let batch_id = batch.batch_id.unwrap();
+
+    // 批次之后再修改其中一个文件的标签和分类
+    let later = FilePatch {
+        add_tags: vec!["urgent".to_string()],
+        set_category: Some("Projects/Acme".to_string()),
+        ..Default::default()
+    };
+    bulk_edit_files(&[edited.clone()], &later, false, &config)
+        .await
+        .unwrap();
+
+    undo_edit_batch(&batch_id, &config).await.unwrap();
+
+    // 只回退批次本身的修改：reviewed 被移除、draft 加回，后来的标签和分类保留
+    let entry = get_file(&edited, &config).await.unwrap();
+    let mut tags = entry.tags.clone();
+    tags.sort();
+    assert_eq!(tags, vec!["draft", "urgent"]);
+    assert_eq!(entry.category1, "Projects");
+    assert_eq!(entry.category2.as_deref(), Some("Acme"));
+
+    let entry = get_file(&untouched, &config).await.unwrap();
+    assert_eq!(entry.tags, vec!["draft"]);
+    assert_eq!(entry.category1, "Inbox");
+    assert_eq!(entry.category2.as_deref(), Some("2025"));
+}
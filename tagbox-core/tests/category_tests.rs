@@ -0,0 +1,213 @@
+use std::fs;
+use tagbox_core::config::AppConfig;
+use tagbox_core::types::{FileEntry, ImportMetadata};
+use tagbox_core::{
+    get_file, import_file, merge_category, move_category, rename_category, search_files,
+    CategoryPath,
+};
+use tempfile::TempDir;
+
+mod common;
+
+async fn setup() -> (TempDir, AppConfig) {
+    common::setup_with(|_, config| {
+        config.import.paths.classify_template = "{category1}/{category2}/{filename}".to_string();
+    })
+    .await
+}
+
+async fn import_in_category(
+    temp_dir: &TempDir,
+    name: &str,
+    category: (&str, &str),
+    config: &AppConfig,
+) -> String {
+    let path = temp_dir.path().join(name);
+    fs::write(&path, format!("# {}\n", name)).unwrap();
+    let metadata = ImportMetadata {
+        title: name.to_string(),
+        category1: category.0.to_string(),
+        category2: Some(category.1.to_string()),
+        ..Default::default()
+    };
+    import_file(&path, metadata, config).await.unwrap().id
+}
+
+#[tokio::test]
+async fn test_rename_category_dry_run_and_apply() {
+    let (temp_dir, config) = setup().await;
+
+    let id = import_in_category(&temp_dir, "contract.md", ("Work", "Acme"), &config).await;
+    import_in_category(&temp_dir, "notes.md", ("Personal", "Notes"), &config).await;
+    let old_path = config
+        .import
+        .paths
+        .storage_dir
+        .join(get_file(&id, &config).await.unwrap().path);
+    assert!(old_path.exists());
+
+    // 预演只返回变化，不改数据库和文件
+    let preview = rename_category("Work", "Clients", true, &config)
+        .await
+        .unwrap();
+    assert_eq!(preview.len(), 1);
+    assert_eq!(preview[0].old_category, "Work/Acme");
+    assert_eq!(preview[0].new_category, "Clients/Acme");
+    assert!(preview[0].new_path.ends_with("Clients/Acme/contract.md"));
+    assert!(!preview[0].moved);
+    assert_eq!(get_file(&id, &config).await.unwrap().category1, "Work");
+    assert!(old_path.exists());
+
+    let moves = rename_category("Work", "Clients", false, &config)
+        .await
+        .unwrap();
+    assert!(moves[0].moved);
+
+    let entry = get_file(&id, &config).await.unwrap();
+    assert_eq!(entry.category1, "Clients");
+    assert_eq!(entry.category2.as_deref(), Some("Acme"));
+    assert!(!old_path.exists());
+    assert!(config.import.paths.storage_dir.join(&entry.path).exists());
+
+    // 目标已存在时需要合并
+    assert!(rename_category("Personal", "Clients", true, &config)
+        .await
+        .is_err());
+    assert!(rename_category("Missing", "Other", true, &config)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_merge_category_subtree() {
+    let (temp_dir, config) = setup().await;
+
+    let a = import_in_category(&temp_dir, "a.md", ("Inbox", "Papers"), &config).await;
+    let b = import_in_category(&temp_dir, "b.md", ("Library", "Books"), &config).await;
+
+    assert!(merge_category("Inbox", "Nowhere", true, &config)
+        .await
+        .is_err());
+
+    let moves = merge_category("Inbox", "Library", false, &config)
+        .await
+        .unwrap();
+    assert_eq!(moves.len(), 1);
+
+    let entry = get_file(&a, &config).await.unwrap();
+    assert_eq!(entry.category1, "Library");
+    assert_eq!(entry.category2.as_deref(), Some("Papers"));
+    assert!(config.import.paths.storage_dir.join(&entry.path).exists());
+    assert_eq!(get_file(&b, &config).await.unwrap().category1, "Library");
+
+    // 分类层级不限，第三级及以下存放在 category3
+    rename_category("Library", "Archive/2025/Old", false, &config)
+        .await
+        .unwrap();
+    let entry = get_file(&a, &config).await.unwrap();
+    assert_eq!(entry.category1, "Archive");
+    assert_eq!(entry.category3.as_deref(), Some("Old/Papers"));
+    assert_eq!(
+        CategoryPath::of_file(&entry).to_string(),
+        "Archive/2025/Old/Papers"
+    );
+    assert!(config.import.paths.storage_dir.join(&entry.path).exists());
+}
+
+#[tokio::test]
+async fn test_deep_category_search() {
+    let (temp_dir, config) = setup().await;
+
+    let deep = import_in_category(&temp_dir, "deal.md", ("Work", "Clients"), &config).await;
+    import_in_category(&temp_dir, "shop.md", ("Workshop", "Tools"), &config).await;
+    move_category(
+        "Work/Clients",
+        "Work/Clients/Acme/2026/Contracts",
+        true,
+        &config,
+    )
+    .await
+    .unwrap_err();
+    rename_category(
+        "Work/Clients",
+        "Work/Clients2/Acme/2026/Contracts",
+        false,
+        &config,
+    )
+    .await
+    .unwrap();
+
+    let ids = |files: Vec<FileEntry>| files.into_iter().map(|f| f.id).collect::<Vec<_>>();
+    for query in [
+        "category:Work",
+        "category:Work/Clients2/Acme",
+        "category:Work/*",
+        "category:Work/Clients2/Acme/2026/Contracts",
+        "category:Work/Cli*",
+    ] {
+        let found = ids(search_files(query, &config).await.unwrap());
+        assert_eq!(found, vec![deep.clone()], "{}", query);
+    }
+
+    assert_eq!(
+        search_files("category:Work*", &config).await.unwrap().len(),
+        2
+    );
+    assert!(
+        search_files("category:Work/Clients2/Acme/2026/Contracts/*", &config)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_rename_intermediate_category_level() {
+    let (temp_dir, config) = setup().await;
+
+    // 按列写入的深层分类，完整路径由 category_path 派生
+    let path = temp_dir.path().join("contract.md");
+    fs::write(&path, "# contract\n").unwrap();
+    let metadata = ImportMetadata {
+        title: "contract".to_string(),
+        category1: "Work".to_string(),
+        category2: Some("Clients".to_string()),
+        category3: Some("Acme/2026/Contracts".to_string()),
+        ..Default::default()
+    };
+    let deep = import_file(&path, metadata, &config).await.unwrap().id;
+    let parent = import_in_category(&temp_dir, "notes.md", ("Work", "Clients"), &config).await;
+
+    rename_category(
+        "Work/Clients/Acme",
+        "Work/Clients/Acme Corp",
+        false,
+        &config,
+    )
+    .await
+    .unwrap();
+    move_category("Work/Clients/Acme Corp/2026", "Archive", false, &config)
+        .await
+        .unwrap();
+
+    let entry = get_file(&deep, &config).await.unwrap();
+    assert_eq!(
+        CategoryPath::of_file(&entry).to_string(),
+        "Archive/2026/Contracts"
+    );
+    assert_eq!(entry.category2.as_deref(), Some("2026"));
+    assert_eq!(entry.category3.as_deref(), Some("Contracts"));
+    assert_eq!(
+        CategoryPath::of_file(&get_file(&parent, &config).await.unwrap()).to_string(),
+        "Work/Clients"
+    );
+
+    let found = search_files("category:Archive/2026/Contracts", &config)
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert!(search_files("category:Work/Clients/*", &config)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,29 @@
+//! 集成测试共用的临时数据库环境
+#![allow(dead_code)]
+
+use std::fs;
+use tagbox_core::config::AppConfig;
+use tagbox_core::init_database;
+use tempfile::TempDir;
+
+/// 在临时目录中创建存储目录和已初始化的数据库
+pub async fn setup() -> (TempDir, AppConfig) {
+    setup_with(|_, _| {}).await
+}
+
+/// 同 [`setup`]，初始化数据库之前可以调整配置
+pub async fn setup_with(configure: impl FnOnce(&TempDir, &mut AppConfig)) -> (TempDir, AppConfig) {
+    let temp_dir = TempDir::new().unwrap();
+    let storage_path = temp_dir.path().join("storage");
+    fs::create_dir_all(&storage_path).unwrap();
+
+    let mut config = AppConfig::default();
+    config.database.path = temp_dir.path().join("test.db");
+    config.import.paths.storage_dir = storage_path;
+    configure(&temp_dir, &mut config);
+
+    fs::File::create(&config.database.path).unwrap();
+    init_database(&config.database.path).await.unwrap();
+
+    (temp_dir, config)
+}
@@ -2,6 +2,32 @@ use std::fs;
 use tagbox_core::config::AppConfig;
 use tempfile::tempdir;
 
+/// 只包含必填段的最小配置
+const BASE_CONFIG: &str = r#"
+[import.paths]
+storage_dir = "./data"
+rename_template = "{title}"
+classify_template = "{filename}"
+
+[import.metadata]
+prefer_json = true
+fallback_pdf = true
+default_category = "misc"
+
+[search]
+default_limit = 10
+enable_fts = true
+fts_language = "simple"
+
+[database]
+path = "./db.sqlite"
+journal_mode = "WAL"
+sync_mode = "NORMAL"
+
+[hash]
+algorithm = "blake3"
+"#;
+
 #[tokio::test]
 async fn test_load_and_validate_config() {
     let dir = tempdir().unwrap();
@@ -36,3 +62,63 @@ async fn test_load_and_validate_config() {
     assert_eq!(cfg.search.default_limit, 10);
     cfg.validate().unwrap();
 }
+
+#[tokio::test]
+async fn test_ocr_config_defaults_and_overrides() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+
+    // 没有 [ocr] 段的旧配置使用默认值，导入时不做 OCR
+    fs::write(&config_path, BASE_CONFIG).unwrap();
+    let cfg = AppConfig::from_file(&config_path).await.unwrap();
+    assert!(!cfg.ocr.on_import);
+    assert_eq!(cfg.ocr.max_pages, 200);
+    assert!(cfg.ocr.detection_model.ends_with("text-detection.rten"));
+
+    let toml = r#"
+        [ocr]
+        on_import = true
+        recognition_model = "/opt/models/rec.rten"
+        max_pages = 20
+    "#;
+    fs::write(&config_path, format!("{}{}", BASE_CONFIG, toml)).unwrap();
+    let cfg = AppConfig::from_file(&config_path).await.unwrap();
+    assert!(cfg.ocr.on_import);
+    assert_eq!(cfg.ocr.max_pages, 20);
+    assert_eq!(
+        cfg.ocr.recognition_model,
+        std::path::PathBuf::from("/opt/models/rec.rten")
+    );
+    assert!(cfg.ocr.detection_model.ends_with("text-detection.rten"));
+}
+
+#[tokio::test]
+async fn test_enrich_config_defaults_and_overrides() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("config.toml");
+
+    fs::write(&config_path, BASE_CONFIG).unwrap();
+    let cfg = AppConfig::from_file(&config_path).await.unwrap();
+    assert_eq!(cfg.enrich.providers, vec!["arxiv", "crossref"]);
+    assert_eq!(cfg.enrich.crossref_url, "https://api.crossref.org");
+    assert_eq!(cfg.enrich.cache_days, 30);
+    assert!(cfg.enrich.mailto.is_none());
+
+    let toml = r#"
+        [enrich]
+        providers = ["fixture"]
+        fixture_dir = "./fixtures"
+        mailto = "me@example.com"
+        cache_days = 0
+    "#;
+    fs::write(&config_path, format!("{}{}", BASE_CONFIG, toml)).unwrap();
+    let cfg = AppConfig::from_file(&config_path).await.unwrap();
+    assert_eq!(cfg.enrich.providers, vec!["fixture"]);
+    assert_eq!(cfg.enrich.mailto.as_deref(), Some("me@example.com"));
+    assert_eq!(cfg.enrich.cache_days, 0);
+    assert_eq!(cfg.enrich.timeout_secs, 20);
+    assert_eq!(
+        cfg.enrich.fixture_dir,
+        Some(std::path::PathBuf::from("./fixtures"))
+    );
+}
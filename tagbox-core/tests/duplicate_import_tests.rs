@@ -0,0 +1,116 @@
+use std::fs;
+use tagbox_core::config::DuplicatePolicy;
+use tagbox_core::types::ImportOutcome;
+use tagbox_core::{extract_and_import_file, extract_metainfo, import_file_with_policy};
+
+mod common;
+use common::setup;
+
+#[tokio::test]
+async fn test_duplicate_skip_keeps_existing_entry() {
+    let (temp_dir, config) = setup().await;
+
+    let first = temp_dir.path().join("paper.txt");
+    fs::write(&first, "same content").unwrap();
+    let copy = temp_dir.path().join("paper-copy.txt");
+    fs::write(&copy, "same content").unwrap();
+
+    let mut metadata = extract_metainfo(&first, &config).await.unwrap();
+    metadata.tags = vec!["a".to_string()];
+    let imported = import_file_with_policy(&first, metadata, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+    assert_eq!(imported.outcome, ImportOutcome::Imported);
+
+    let mut metadata = extract_metainfo(&copy, &config).await.unwrap();
+    metadata.tags = vec!["b".to_string()];
+    let duplicate = import_file_with_policy(&copy, metadata, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+
+    assert_eq!(duplicate.outcome, ImportOutcome::Skipped);
+    assert_eq!(duplicate.entry.id, imported.entry.id);
+    assert_eq!(duplicate.entry.tags, vec!["a".to_string()]);
+}
+
+#[tokio::test]
+async fn test_extract_and_import_skips_duplicate_before_extraction() {
+    let (temp_dir, config) = setup().await;
+
+    let first = temp_dir.path().join("paper.txt");
+    fs::write(&first, "same content").unwrap();
+    let copy = temp_dir.path().join("paper-copy.txt");
+    fs::write(&copy, "same content").unwrap();
+
+    let imported = extract_and_import_file(&first, &config).await.unwrap();
+    // 默认 skip 策略下按哈希直接返回已有条目
+    let duplicate = extract_and_import_file(&copy, &config).await.unwrap();
+
+    assert_eq!(duplicate.id, imported.id);
+    assert_eq!(duplicate.title, imported.title);
+}
+
+#[tokio::test]
+async fn test_duplicate_merge_tags() {
+    let (temp_dir, config) = setup().await;
+
+    let first = temp_dir.path().join("paper.txt");
+    fs::write(&first, "same content").unwrap();
+    let copy = temp_dir.path().join("paper-copy.txt");
+    fs::write(&copy, "same content").unwrap();
+
+    let mut metadata = extract_metainfo(&first, &config).await.unwrap();
+    metadata.tags = vec!["a".to_string()];
+    let imported = import_file_with_policy(&first, metadata, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+
+    let mut metadata = extract_metainfo(&copy, &config).await.unwrap();
+    metadata.tags = vec!["a".to_string(), "b".to_string()];
+    let merged = import_file_with_policy(&copy, metadata, DuplicatePolicy::MergeTags, &config)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        merged.outcome,
+        ImportOutcome::MergedTags {
+            added: vec!["b".to_string()]
+        }
+    );
+    assert_eq!(merged.entry.id, imported.entry.id);
+    assert!(merged.entry.tags.contains(&"a".to_string()));
+    assert!(merged.entry.tags.contains(&"b".to_string()));
+}
+
+#[tokio::test]
+async fn test_duplicate_overwrite_empty_fields() {
+    let (temp_dir, config) = setup().await;
+
+    let first = temp_dir.path().join("paper.txt");
+    fs::write(&first, "same content").unwrap();
+    let copy = temp_dir.path().join("paper-copy.txt");
+    fs::write(&copy, "same content").unwrap();
+
+    let mut metadata = extract_metainfo(&first, &config).await.unwrap();
+    metadata.publisher = Some("ACM".to_string());
+    import_file_with_policy(&first, metadata, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+
+    let mut metadata = extract_metainfo(&copy, &config).await.unwrap();
+    metadata.publisher = Some("IEEE".to_string());
+    metadata.year = Some(2020);
+    let filled = import_file_with_policy(&copy, metadata, DuplicatePolicy::OverwriteEmpty, &config)
+        .await
+        .unwrap();
+
+    match &filled.outcome {
+        ImportOutcome::FilledFields { fields } => {
+            assert!(fields.contains(&"year".to_string()));
+            assert!(!fields.contains(&"publisher".to_string()));
+        }
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+    assert_eq!(filled.entry.year, Some(2020));
+    assert_eq!(filled.entry.publisher.as_deref(), Some("ACM"));
+}
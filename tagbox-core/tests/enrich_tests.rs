@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tagbox_core::config::AppConfig;
+use tagbox_core::errors::Result;
+use tagbox_core::schema::Database;
+use tagbox_core::types::{
+    EnrichedMetadata, EnrichmentQuery, Identifier, IdentifierKind, ImportMetadata,
+};
+use tagbox_core::{
+    apply_enrichment, enrich_file, extract_metainfo, get_file, get_file_identifiers, import_file,
+    search_files_advanced, Enricher, EnrichmentProvider,
+};
+use tempfile::TempDir;
+
+mod common;
+
+const PDF_PATH: &str = "../test/data/1706.03762v7.pdf";
+
+const ATTENTION_FIXTURE: &str = r#"{
+    "title": "Attention Is All You Need",
+    "authors": ["Ashish Vaswani", "Noam Shazeer", "Niki Parmar", "Jakob Uszkoreit"],
+    "venue": "Advances in Neural Information Processing Systems 30",
+    "abstract": "The dominant sequence transduction models are based on complex recurrent networks.",
+    "published": "2017-06-12",
+    "identifiers": [
+        {"kind": "arxiv", "value": "arXiv:1706.03762v7"},
+        {"kind": "doi", "value": "10.48550/ARXIV.1706.03762"}
+    ],
+    "url": "https://arxiv.org/abs/1706.03762"
+}"#;
+
+async fn setup() -> (TempDir, AppConfig) {
+    common::setup_with(|temp_dir, config| {
+        let fixture_dir = temp_dir.path().join("fixtures");
+        fs::create_dir_all(&fixture_dir).unwrap();
+        fs::write(fixture_dir.join("attention.json"), ATTENTION_FIXTURE).unwrap();
+
+        config.enrich.providers = vec!["fixture".to_string()];
+        config.enrich.fixture_dir = Some(fixture_dir);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_enrich_by_identifier_and_apply() {
+    let (_temp_dir, config) = setup().await;
+
+    let path = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let entry = import_file(path, metadata, &config).await.unwrap();
+
+    let proposal = enrich_file(&entry.id, &config).await.unwrap().unwrap();
+    assert_eq!(proposal.provider, "fixture");
+    assert_eq!(proposal.update.authors.as_ref().unwrap().len(), 4);
+    assert_eq!(proposal.update.year, Some(2017));
+    assert!(proposal.update.summary.is_some());
+    let doi = Identifier {
+        kind: IdentifierKind::Doi,
+        value: "10.48550/arxiv.1706.03762".to_string(),
+    };
+    assert!(proposal.new_identifiers.contains(&doi));
+
+    apply_enrichment(&proposal, &config).await.unwrap();
+
+    let updated = get_file(&entry.id, &config).await.unwrap();
+    assert_eq!(updated.title, "Attention Is All You Need");
+    assert_eq!(updated.authors.len(), 4);
+    assert_eq!(updated.authors[0], "Ashish Vaswani");
+    assert_eq!(updated.year, Some(2017));
+    assert!(updated.summary.unwrap().starts_with("The dominant"));
+
+    // 期刊/会议等信息记录在 file_metadata.enrichment，原有的元数据保留
+    let file_metadata = updated.file_metadata.unwrap();
+    assert_eq!(
+        file_metadata["enrichment"]["venue"],
+        "Advances in Neural Information Processing Systems 30"
+    );
+    assert_eq!(file_metadata["enrichment"]["provider"], "fixture");
+    assert!(file_metadata.get("pdf").is_some());
+
+    let identifiers = get_file_identifiers(&entry.id, &config).await.unwrap();
+    assert!(identifiers.contains(&doi));
+    let result = search_files_advanced("doi:10.48550/arXiv.1706.03762", None, &config)
+        .await
+        .unwrap();
+    assert_eq!(result.entries.len(), 1);
+
+    // 再次补全时没有需要修改的字段
+    let again = enrich_file(&entry.id, &config).await.unwrap().unwrap();
+    let db = Database::new(&config.database.path).await.unwrap();
+    let enricher = Enricher::new(db.pool().clone(), Vec::new());
+    assert!(enricher.preview(&again).is_empty());
+}
+
+#[tokio::test]
+async fn test_enrich_by_title() {
+    let (temp_dir, config) = setup().await;
+
+    let path = temp_dir.path().join("notes.md");
+    fs::write(&path, "Some notes about transformers.\n").unwrap();
+
+    let metadata = ImportMetadata {
+        title: "attention is all you need!".to_string(),
+        ..Default::default()
+    };
+    let entry = import_file(&path, metadata, &config).await.unwrap();
+
+    let proposal = enrich_file(&entry.id, &config).await.unwrap().unwrap();
+    assert_eq!(
+        proposal.update.title.as_deref(),
+        Some("Attention Is All You Need")
+    );
+    assert_eq!(proposal.new_identifiers.len(), 2);
+
+    // 标题对不上时没有结果
+    let other_path = temp_dir.path().join("other.md");
+    fs::write(&other_path, "Unrelated.\n").unwrap();
+    let metadata = ImportMetadata {
+        title: "Attention Is Not All You Need".to_string(),
+        ..Default::default()
+    };
+    let other = import_file(&other_path, metadata, &config).await.unwrap();
+    assert!(enrich_file(&other.id, &config).await.unwrap().is_none());
+}
+
+/// 记录调用次数的数据源
+struct CountingProvider {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl EnrichmentProvider for CountingProvider {
+    fn name(&self) -> &str {
+        "counting"
+    }
+
+    fn supports(&self, query: &EnrichmentQuery) -> bool {
+        query.title.is_some()
+    }
+
+    async fn lookup(&self, query: &EnrichmentQuery) -> Result<Option<EnrichedMetadata>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if query.title.as_deref() == Some("missing") {
+            return Ok(None);
+        }
+
+        Ok(Some(EnrichedMetadata {
+            title: query.title.clone(),
+            venue: Some("Cached Proceedings".to_string()),
+            ..Default::default()
+        }))
+    }
+}
+
+#[tokio::test]
+async fn test_enrichment_cache() {
+    let (_temp_dir, config) = setup().await;
+    let db = Database::new(&config.database.path).await.unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let enricher = Enricher::new(
+        db.pool().clone(),
+        vec![Box::new(CountingProvider {
+            calls: calls.clone(),
+        })],
+    );
+
+    let query = EnrichmentQuery {
+        title: Some("Cached Paper".to_string()),
+        ..Default::default()
+    };
+    let (provider, first) = enricher.lookup(&query).await.unwrap().unwrap();
+    let (_, second) = enricher.lookup(&query).await.unwrap().unwrap();
+    assert_eq!(provider, "counting");
+    assert_eq!(first, second);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // 没有结果也会缓存
+    let missing = EnrichmentQuery {
+        title: Some("missing".to_string()),
+        ..Default::default()
+    };
+    assert!(enricher.lookup(&missing).await.unwrap().is_none());
+    assert!(enricher.lookup(&missing).await.unwrap().is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // 不查询不支持的请求
+    assert!(enricher
+        .lookup(&EnrichmentQuery::default())
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // cache_days = 0 时每次都查询数据源
+    let uncached = Enricher::new(
+        db.pool().clone(),
+        vec![Box::new(CountingProvider {
+            calls: calls.clone(),
+        })],
+    )
+    .with_cache_days(0);
+    uncached.lookup(&query).await.unwrap();
+    uncached.lookup(&query).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
@@ -33,6 +33,7 @@ async fn test_full_flow() {
     let dummy = ImportMetadata {
         title: String::new(),
         authors: Vec::new(),
+        contributors: Vec::new(),
         year: None,
         publisher: None,
         source: None,
@@ -44,6 +45,8 @@ async fn test_full_flow() {
         additional_info: HashMap::new(),
         file_metadata: None,
         type_metadata: None,
+        full_text: None,
+        mime_type: None,
     };
 
     let entry =
@@ -68,6 +71,7 @@ async fn test_full_flow() {
     let update = FileUpdateRequest {
         title: Some("Updated Title".to_string()),
         authors: None,
+        contributors: None,
         year: None,
         publisher: None,
         source: None,
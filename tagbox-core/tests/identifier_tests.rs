@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::Path;
+use tagbox_core::config::{AppConfig, DuplicatePolicy};
+use tagbox_core::types::{Identifier, IdentifierKind, ImportOutcome};
+use tagbox_core::{
+    extract_metainfo, get_file_identifiers, import_file, import_file_with_policy,
+    search_files_advanced,
+};
+
+mod common;
+use common::setup;
+
+const PDF_PATH: &str = "../test/data/1706.03762v7.pdf";
+
+async fn search_ids(query: &str, config: &AppConfig) -> Vec<String> {
+    search_files_advanced(query, None, config)
+        .await
+        .unwrap()
+        .entries
+        .into_iter()
+        .map(|e| e.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_arxiv_id_from_filename() {
+    let (_temp_dir, config) = setup().await;
+
+    let path = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let entry = import_file(path, metadata, &config).await.unwrap();
+
+    let identifiers = get_file_identifiers(&entry.id, &config).await.unwrap();
+    assert!(identifiers.contains(&Identifier {
+        kind: IdentifierKind::Arxiv,
+        value: "1706.03762".to_string(),
+    }));
+
+    // 版本号和前缀写法不影响匹配
+    for query in [
+        "arxiv:1706.03762",
+        "arXiv:1706.03762v5",
+        "arxiv:arXiv:1706.03762",
+    ] {
+        assert_eq!(
+            search_ids(query, &config).await,
+            vec![entry.id.clone()],
+            "query: {}",
+            query
+        );
+    }
+    assert!(search_ids("arxiv:1706.99999", &config).await.is_empty());
+
+    // 无效的标识符是查询错误
+    assert!(search_files_advanced("isbn:12345", None, &config)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_doi_in_text_is_searchable() {
+    let (temp_dir, config) = setup().await;
+
+    let path = temp_dir.path().join("reading-notes.md");
+    fs::write(
+        &path,
+        "# Reading notes\n\nPublished as https://doi.org/10.1145/3368089.3409693.\n\
+         Journal ISSN 0317-8471.\n",
+    )
+    .unwrap();
+    let metadata = extract_metainfo(&path, &config).await.unwrap();
+    let entry = import_file(&path, metadata, &config).await.unwrap();
+
+    assert_eq!(
+        search_ids("doi:10.1145/3368089.3409693", &config).await,
+        vec![entry.id.clone()]
+    );
+    assert_eq!(
+        search_ids("DOI:https://doi.org/10.1145/3368089.3409693", &config).await,
+        vec![entry.id.clone()]
+    );
+    assert_eq!(search_ids("issn:03178471", &config).await, vec![entry.id]);
+}
+
+#[tokio::test]
+async fn test_identifier_is_duplicate_key() {
+    let (temp_dir, config) = setup().await;
+
+    let path = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let first = import_file(path, metadata, &config).await.unwrap();
+
+    // 另一个版本：内容（哈希）不同，arXiv 编号相同
+    let mut bytes = fs::read(path).unwrap();
+    bytes.extend_from_slice(b"\n% another revision\n");
+    let copy = temp_dir.path().join("1706.03762v5.pdf");
+    fs::write(&copy, bytes).unwrap();
+
+    let metadata = extract_metainfo(&copy, &config).await.unwrap();
+    let result = import_file_with_policy(&copy, metadata, DuplicatePolicy::Skip, &config)
+        .await
+        .unwrap();
+    assert_eq!(result.outcome, ImportOutcome::Skipped);
+    assert_eq!(result.entry.id, first.id);
+}
+
+#[tokio::test]
+async fn test_doi_in_text_is_not_duplicate_key() {
+    let (temp_dir, config) = setup().await;
+
+    // 两篇笔记引用同一篇论文，不是同一个文件
+    let mut ids = Vec::new();
+    for name in ["notes-a.md", "notes-b.md"] {
+        let path = temp_dir.path().join(name);
+        fs::write(
+            &path,
+            format!(
+                "# {}\n\nSee https://doi.org/10.1145/3368089.3409693.\n",
+                name
+            ),
+        )
+        .unwrap();
+        let metadata = extract_metainfo(&path, &config).await.unwrap();
+        let result = import_file_with_policy(&path, metadata, DuplicatePolicy::Skip, &config)
+            .await
+            .unwrap();
+        assert_eq!(result.outcome, ImportOutcome::Imported);
+        ids.push(result.entry.id);
+    }
+
+    let mut found = search_ids("doi:10.1145/3368089.3409693", &config).await;
+    found.sort();
+    ids.sort();
+    assert_eq!(found, ids);
+}
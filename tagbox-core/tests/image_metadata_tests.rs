@@ -0,0 +1,67 @@
+use std::path::Path;
+use tagbox_core::config::AppConfig;
+use tagbox_core::{extract_metainfo, import_file, search_files_advanced};
+
+mod common;
+use common::setup;
+
+/// FUJIFILM X-T4 拍摄于 2023-06-15，带 GPS 和 XMP 标题/描述/关键词
+const PHOTO_PATH: &str = "../test/data/photo_exif.jpg";
+
+#[tokio::test]
+async fn test_exif_and_xmp_extraction() {
+    let metadata = extract_metainfo(Path::new(PHOTO_PATH), &AppConfig::default())
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.title, "Shibuya Crossing");
+    assert_eq!(metadata.year, Some(2023));
+    assert_eq!(metadata.summary.as_deref(), Some("Evening rush & neon"));
+    assert_eq!(
+        metadata.tags,
+        vec!["travel".to_string(), "tokyo".to_string()]
+    );
+
+    let image = &metadata.file_metadata.unwrap()["image"];
+    assert_eq!(image["camera"], "FUJIFILM X-T4");
+    assert_eq!(image["lens"], "XF23mmF2 R WR");
+    assert_eq!(image["taken_at"], "2023-06-15T09:30:00");
+    assert_eq!(image["orientation"], 1);
+    assert_eq!(image["exposure"]["iso"], 400);
+    assert_eq!(image["exposure"]["f_number"], 2.8);
+
+    let latitude = image["gps"]["latitude"].as_f64().unwrap();
+    let longitude = image["gps"]["longitude"].as_f64().unwrap();
+    assert!((latitude - 35.66).abs() < 1e-6);
+    assert!((longitude - 139.75).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_camera_and_taken_filters() {
+    let (_temp_dir, config) = setup().await;
+
+    let photo = Path::new(PHOTO_PATH);
+    let metadata = extract_metainfo(photo, &config).await.unwrap();
+    let entry = import_file(photo, metadata, &config).await.unwrap();
+
+    for query in [
+        "camera:\"X-T4\"",
+        "camera:fujifilm",
+        "taken:2023",
+        "taken:2023..2024",
+        "taken:>=2023-06",
+    ] {
+        let result = search_files_advanced(query, None, &config).await.unwrap();
+        assert_eq!(result.entries.len(), 1, "query: {}", query);
+        assert_eq!(result.entries[0].id, entry.id);
+    }
+
+    for query in ["camera:\"X-T5\"", "taken:2020", "taken:<2023-06"] {
+        let result = search_files_advanced(query, None, &config).await.unwrap();
+        assert!(result.entries.is_empty(), "query: {}", query);
+    }
+
+    assert!(search_files_advanced("taken:someday", None, &config)
+        .await
+        .is_err());
+}
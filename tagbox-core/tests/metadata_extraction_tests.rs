@@ -1,6 +1,57 @@
+use std::fs;
 use std::path::Path;
 use tagbox_core::config::AppConfig;
-use tagbox_core::metainfo::MetaInfoExtractor;
+use tagbox_core::errors::Result;
+use tagbox_core::metainfo::{
+    extract_epub_chapters, ExtractorRegistry, MetaInfoExtractor, MetadataExtractor,
+};
+use tagbox_core::types::ImportMetadata;
+use tempfile::TempDir;
+
+/// 测试用提取器：为纯文本文件返回固定的标题和标签
+struct FixedExtractor {
+    title: &'static str,
+    tag: &'static str,
+    priority: i32,
+}
+
+impl MetadataExtractor for FixedExtractor {
+    fn name(&self) -> &'static str {
+        self.tag
+    }
+
+    fn supports(&self, _path: &Path, mime: Option<&str>) -> bool {
+        mime == Some("text/plain")
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn extract(&self, _path: &Path, _mime: Option<&str>) -> Result<ImportMetadata> {
+        Ok(ImportMetadata {
+            title: self.title.to_string(),
+            tags: vec![self.tag.to_string()],
+            type_metadata: Some(serde_json::json!({ self.tag: { "seen": true } })),
+            ..Default::default()
+        })
+    }
+}
+
+fn fixed_registry() -> ExtractorRegistry {
+    let mut registry = ExtractorRegistry::new();
+    registry.register(FixedExtractor {
+        title: "High",
+        tag: "high",
+        priority: 10,
+    });
+    registry.register(FixedExtractor {
+        title: "Low",
+        tag: "low",
+        priority: 0,
+    });
+    registry
+}
 
 #[tokio::test]
 async fn test_epub_metadata_extraction() {
@@ -58,6 +109,43 @@ async fn test_epub_metadata_extraction() {
     );
 }
 
+#[tokio::test]
+async fn test_epub_body_text_and_toc() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/test.epub"))
+        .await
+        .expect("EPUB extraction should succeed");
+
+    assert_eq!(metadata.authors, vec!["陣内美勇".to_string()]);
+
+    let text = metadata.full_text.expect("Should have body text");
+    assert!(!text.contains('<'), "Markup should be stripped");
+    assert!(text.len() > 1000);
+
+    let toc = metadata.type_metadata.as_ref().unwrap()["book"]["toc"]
+        .as_array()
+        .expect("Should have table of contents");
+    let labels: Vec<&str> = toc.iter().filter_map(|e| e["label"].as_str()).collect();
+    assert!(labels.contains(&"目次"), "TOC labels: {:?}", labels);
+    assert!(toc.iter().all(|e| e["href"].as_str().is_some()));
+}
+
+#[test]
+fn test_epub_chapters_budget() {
+    let path = Path::new("../test/data/test.epub");
+
+    let chapters = extract_epub_chapters(path, u64::MAX).unwrap();
+    assert_eq!(chapters.spine_count, 6);
+    assert!(!chapters.truncated);
+    assert!(chapters.chapters.iter().all(|(n, _)| *n >= 1 && *n <= 6));
+
+    let limited = extract_epub_chapters(path, 4096).unwrap();
+    assert!(limited.truncated);
+    assert!(limited.chapters.len() < chapters.chapters.len());
+}
+
 #[tokio::test]
 async fn test_pdf_metadata_extraction() {
     let config = AppConfig::default();
@@ -142,6 +230,239 @@ async fn test_pdf_metadata_extraction_gpt4() {
     );
 }
 
+#[tokio::test]
+async fn test_docx_metadata_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/test.docx"))
+        .await
+        .expect("DOCX extraction should succeed");
+
+    assert_eq!(metadata.title, "Rust Ownership Notes");
+    assert_eq!(
+        metadata.authors,
+        vec!["Alice Smith".to_string(), "Bob Lee".to_string()]
+    );
+    assert_eq!(metadata.year, Some(2021));
+    assert_eq!(
+        metadata.tags,
+        vec![
+            "rust".to_string(),
+            "ownership".to_string(),
+            "memory".to_string()
+        ]
+    );
+
+    let text = metadata.full_text.expect("Should have body text");
+    assert!(text.contains("Borrowing rules & lifetimes."));
+    assert!(text.contains("所有权与借用"));
+
+    let file_meta = metadata.file_metadata.unwrap();
+    assert_eq!(file_meta["office"]["format"], "docx");
+    assert_eq!(file_meta["office"]["pages"], 1);
+    let type_meta = metadata.type_metadata.unwrap();
+    assert_eq!(
+        type_meta["document"]["creation_date"],
+        "2021-03-04T10:00:00Z"
+    );
+}
+
+#[tokio::test]
+async fn test_xlsx_metadata_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/test.xlsx"))
+        .await
+        .expect("XLSX extraction should succeed");
+
+    assert_eq!(metadata.title, "Budget 2022");
+    assert_eq!(metadata.authors, vec!["Carol White".to_string()]);
+    assert_eq!(metadata.year, Some(2022));
+    assert!(metadata.tags.contains(&"finance".to_string()));
+
+    let text = metadata.full_text.expect("Should have cell text");
+    assert!(text.contains("Quarterly Budget"));
+    assert!(text.contains("Travel"));
+    // 数值单元格
+    assert!(text.contains("1200"));
+
+    let file_meta = metadata.file_metadata.unwrap();
+    assert_eq!(file_meta["office"]["sheets"], 1);
+}
+
+#[tokio::test]
+async fn test_pptx_metadata_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/test.pptx"))
+        .await
+        .expect("PPTX extraction should succeed");
+
+    assert_eq!(metadata.title, "Async Rust");
+    assert_eq!(metadata.authors, vec!["Dave Brown".to_string()]);
+    assert_eq!(metadata.year, Some(2023));
+
+    // 幻灯片按编号顺序提取
+    let text = metadata.full_text.expect("Should have slide text");
+    let futures = text.find("Futures and executors").unwrap();
+    let questions = text.find("Questions").unwrap();
+    assert!(futures < questions);
+
+    let file_meta = metadata.file_metadata.unwrap();
+    assert_eq!(file_meta["office"]["slides"], 3);
+}
+
+#[tokio::test]
+async fn test_odt_metadata_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/test.odt"))
+        .await
+        .expect("ODT extraction should succeed");
+
+    assert_eq!(metadata.title, "Field Notes");
+    assert_eq!(metadata.authors, vec!["Erin Green".to_string()]);
+    assert_eq!(metadata.year, Some(2020));
+    assert_eq!(
+        metadata.tags,
+        vec!["geology".to_string(), "fieldwork".to_string()]
+    );
+    assert_eq!(
+        metadata.summary.as_deref(),
+        Some("Notes from the field trip")
+    );
+    assert_eq!(
+        metadata.mime_type.as_deref(),
+        Some("application/vnd.oasis.opendocument.text")
+    );
+
+    let text = metadata.full_text.expect("Should have body text");
+    assert!(text.contains("Sandstone layers near the river."));
+
+    let file_meta = metadata.file_metadata.unwrap();
+    assert_eq!(file_meta["office"]["word_count"], 7);
+}
+
+#[tokio::test]
+async fn test_mp3_id3_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/lecture.mp3"))
+        .await
+        .expect("MP3 extraction should succeed");
+
+    // 标题为 UTF-16 编码，iTunes 内部注释被忽略
+    assert_eq!(metadata.title, "Lecture 3: Ownership – Borrowing");
+    assert_eq!(metadata.authors, vec!["Jane Doe".to_string()]);
+    assert_eq!(metadata.year, Some(2022));
+    assert_eq!(
+        metadata.summary.as_deref(),
+        Some("Recorded at the spring workshop")
+    );
+
+    let media = &metadata.file_metadata.unwrap()["media"];
+    assert_eq!(media["codec"], "mp3");
+    assert_eq!(media["bitrate"], 128);
+    assert_eq!(media["audio"]["sample_rate"], 44100);
+    assert!((media["duration"].as_f64().unwrap() - 0.99).abs() < 0.01);
+
+    let tags = &metadata.type_metadata.unwrap()["media"];
+    assert_eq!(tags["album"], "Rust Course");
+    assert_eq!(tags["track"], 3);
+    assert_eq!(tags["track_total"], 12);
+    assert_eq!(tags["genre"], "Speech");
+}
+
+#[tokio::test]
+async fn test_vorbis_comment_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let flac = extractor
+        .extract(Path::new("../test/data/podcast.flac"))
+        .await
+        .expect("FLAC extraction should succeed");
+    assert_eq!(flac.title, "Episode 12: Async Rust");
+    assert_eq!(
+        flac.authors,
+        vec!["Alice Smith".to_string(), "Bob Lee".to_string()]
+    );
+    assert_eq!(flac.year, Some(2021));
+    let media = &flac.file_metadata.unwrap()["media"];
+    assert_eq!(media["container"], "flac");
+    assert_eq!(media["duration"], 90.0);
+    assert_eq!(
+        flac.type_metadata.unwrap()["media"]["album"],
+        "Rustacean Radio"
+    );
+
+    let opus = extractor
+        .extract(Path::new("../test/data/standup.opus"))
+        .await
+        .expect("Ogg Opus extraction should succeed");
+    assert_eq!(opus.title, "Standup 2024-01-08");
+    assert_eq!(opus.authors, vec!["Team Tagbox".to_string()]);
+    let media = &opus.file_metadata.unwrap()["media"];
+    assert_eq!(media["container"], "ogg");
+    assert_eq!(media["codec"], "opus");
+    // 最后一页的 granule 减去预跳过的采样数
+    assert_eq!(media["duration"], 5.0);
+}
+
+#[tokio::test]
+async fn test_mp4_video_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/lecture.mp4"))
+        .await
+        .expect("MP4 extraction should succeed");
+
+    assert_eq!(metadata.title, "Distributed Systems Lecture 5");
+    assert_eq!(metadata.authors, vec!["Prof. Martin".to_string()]);
+    assert_eq!(metadata.year, Some(2020));
+
+    let media = &metadata.file_metadata.unwrap()["media"];
+    assert_eq!(media["codec"], "h264");
+    assert_eq!(media["resolution"], "1280x720");
+    assert_eq!(media["duration"], 125.5);
+    assert_eq!(media["audio"]["codec"], "aac");
+
+    let tags = &metadata.type_metadata.unwrap()["media"];
+    assert_eq!(tags["album"], "CS 6.824");
+    assert_eq!(tags["track"], 5);
+    assert_eq!(tags["track_total"], 20);
+}
+
+#[tokio::test]
+async fn test_matroska_extraction() {
+    let extractor = MetaInfoExtractor::new(AppConfig::default());
+
+    let metadata = extractor
+        .extract(Path::new("../test/data/keynote.mkv"))
+        .await
+        .expect("Matroska extraction should succeed");
+
+    // 曲目级（30）标签优先于 Info 中的标题，专辑级（50）标题作为专辑
+    assert_eq!(metadata.title, "Keynote: The Future of Rust");
+    assert_eq!(metadata.authors, vec!["Ferris Crab".to_string()]);
+    assert_eq!(metadata.year, Some(2023));
+
+    let media = &metadata.file_metadata.unwrap()["media"];
+    assert_eq!(media["container"], "matroska");
+    assert_eq!(media["resolution"], "1920x1080");
+    assert_eq!(media["duration"], 3600.5);
+    assert_eq!(media["audio"]["codec"], "opus");
+
+    let tags = &metadata.type_metadata.unwrap()["media"];
+    assert_eq!(tags["album"], "RustConf 2023");
+    assert_eq!(tags["album_artist"], "Rust Foundation");
+}
+
 #[tokio::test]
 async fn test_fallback_to_filename() {
     let config = AppConfig::default();
@@ -180,6 +501,52 @@ async fn test_json_metadata_merge() {
     }
 }
 
+#[tokio::test]
+async fn test_registry_merges_by_priority() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("Notes - Alice (2021).txt");
+    fs::write(&path, "plain text notes").unwrap();
+
+    let extractor = MetaInfoExtractor::with_registry(AppConfig::default(), fixed_registry());
+    assert_eq!(extractor.registry().names(), vec!["low", "high"]);
+
+    let metadata = extractor.extract(&path).await.unwrap();
+
+    // 高优先级覆盖低优先级，文件名中的作者和年份保留
+    assert_eq!(metadata.title, "High");
+    assert_eq!(metadata.authors, vec!["Alice".to_string()]);
+    assert_eq!(metadata.year, Some(2021));
+    assert_eq!(metadata.tags, vec!["low".to_string(), "high".to_string()]);
+
+    // 不同提取器的 type_metadata 按键合并
+    let type_meta = metadata.type_metadata.unwrap();
+    assert!(type_meta.get("low").is_some());
+    assert!(type_meta.get("high").is_some());
+}
+
+#[tokio::test]
+async fn test_sidecar_json_overrides_extractors() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.txt");
+    fs::write(&path, "plain text notes").unwrap();
+    fs::write(
+        temp_dir.path().join("notes.txt.meta"),
+        r#"{"title": "From Sidecar", "tags": ["sidecar"]}"#,
+    )
+    .unwrap();
+
+    let extractor = MetaInfoExtractor::with_registry(AppConfig::default(), fixed_registry());
+    let metadata = extractor.extract(&path).await.unwrap();
+
+    assert_eq!(metadata.title, "From Sidecar");
+    assert!(metadata.tags.contains(&"high".to_string()));
+    assert!(metadata.tags.contains(&"sidecar".to_string()));
+    assert_eq!(
+        metadata.category1,
+        AppConfig::default().import.metadata.default_category
+    );
+}
+
 #[cfg(test)]
 mod metadata_format_tests {
 
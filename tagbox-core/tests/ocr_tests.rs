@@ -0,0 +1,48 @@
+#![cfg(feature = "ocr")]
+
+use std::path::Path;
+use tagbox_core::config::AppConfig;
+use tagbox_core::errors::TagboxError;
+use tagbox_core::{extract_metainfo, import_file, ocr_file};
+use tempfile::TempDir;
+
+mod common;
+
+const IMAGE_PATH: &str = "../test/data/photo_exif.jpg";
+
+async fn setup() -> (TempDir, AppConfig) {
+    common::setup_with(|temp_dir, config| {
+        // 测试环境没有模型文件
+        config.ocr.detection_model = temp_dir.path().join("missing-detection.rten");
+        config.ocr.recognition_model = temp_dir.path().join("missing-recognition.rten");
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_missing_models_reported() {
+    let (_temp_dir, config) = setup().await;
+
+    let path = Path::new(IMAGE_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let entry = import_file(path, metadata, &config).await.unwrap();
+
+    match ocr_file(&entry.id, &config).await {
+        Err(TagboxError::Config(message)) => assert!(message.contains("missing-detection.rten")),
+        other => panic!("expected config error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_import_succeeds_when_ocr_fails() {
+    let (_temp_dir, mut config) = setup().await;
+    config.ocr.on_import = true;
+
+    let path = Path::new(IMAGE_PATH);
+    let metadata = extract_metainfo(path, &config).await.unwrap();
+    let entry = import_file(path, metadata, &config).await.unwrap();
+
+    // 识别失败不影响导入，也不会标记为已做过 OCR
+    let ocr = entry.file_metadata.as_ref().and_then(|m| m.get("ocr"));
+    assert!(ocr.is_none());
+}
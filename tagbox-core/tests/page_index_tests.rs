@@ -0,0 +1,84 @@
+use std::path::Path;
+use tagbox_core::config::AppConfig;
+use tagbox_core::schema::Database;
+use tagbox_core::types::PageIndexStatus;
+use tagbox_core::{
+    extract_metainfo, import_file, index_pending_pages, pending_page_count, PageIndexer,
+};
+
+mod common;
+use common::setup;
+
+const PDF_PATH: &str = "../test/data/1706.03762v7.pdf";
+
+async fn indexer(config: &AppConfig) -> PageIndexer {
+    let db = Database::new(&config.database.path).await.unwrap();
+    PageIndexer::new(db.pool().clone())
+}
+
+#[tokio::test]
+async fn test_pdf_pages_indexed_on_import() {
+    let (_temp_dir, config) = setup().await;
+
+    let pdf = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(pdf, &config).await.unwrap();
+    let entry = import_file(pdf, metadata, &config).await.unwrap();
+
+    let indexer = indexer(&config).await;
+    assert_eq!(
+        indexer.get_status(&entry.id).await.unwrap(),
+        Some(PageIndexStatus::Complete)
+    );
+
+    // 前 5 页之后的内容同样被索引
+    assert!(indexer.get_page(&entry.id, 10).await.unwrap().is_some());
+
+    let hits = indexer
+        .find_hits("Visualizations", &[entry.id.clone()], 3)
+        .await
+        .unwrap();
+    assert!(!hits.is_empty());
+    assert!(hits.iter().all(|h| h.file_id == entry.id));
+    assert!(hits.iter().any(|h| h.page > 5));
+}
+
+#[tokio::test]
+async fn test_large_pdf_indexed_in_background() {
+    let (_temp_dir, mut config) = setup().await;
+    config.import.metadata.background_extract_size = 1024;
+
+    let pdf = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(pdf, &config).await.unwrap();
+    let entry = import_file(pdf, metadata, &config).await.unwrap();
+
+    let indexer = indexer(&config).await;
+    assert_eq!(
+        indexer.get_status(&entry.id).await.unwrap(),
+        Some(PageIndexStatus::Pending)
+    );
+    assert_eq!(pending_page_count(&config).await.unwrap(), 1);
+
+    assert_eq!(index_pending_pages(&config).await.unwrap(), 1);
+    assert_eq!(pending_page_count(&config).await.unwrap(), 0);
+    assert_eq!(
+        indexer.get_status(&entry.id).await.unwrap(),
+        Some(PageIndexStatus::Complete)
+    );
+}
+
+#[tokio::test]
+async fn test_page_text_budget_truncates() {
+    let (_temp_dir, mut config) = setup().await;
+    config.import.metadata.page_text_budget = 4096;
+
+    let pdf = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(pdf, &config).await.unwrap();
+    let entry = import_file(pdf, metadata, &config).await.unwrap();
+
+    let indexer = indexer(&config).await;
+    assert_eq!(
+        indexer.get_status(&entry.id).await.unwrap(),
+        Some(PageIndexStatus::Truncated)
+    );
+    assert!(indexer.get_page(&entry.id, 10).await.unwrap().is_none());
+}
@@ -5,6 +5,7 @@ fn sample_metadata() -> ImportMetadata {
     ImportMetadata {
         title: "Rust Book".to_string(),
         authors: vec!["Steve".to_string(), "Carol".to_string()],
+        contributors: Vec::new(),
         year: Some(2021),
         publisher: Some("Rustaceans".to_string()),
         source: None,
@@ -16,6 +17,8 @@ fn sample_metadata() -> ImportMetadata {
         additional_info: HashMap::new(),
         file_metadata: None,
         type_metadata: None,
+        full_text: None,
+        mime_type: None,
     }
 }
 
@@ -32,3 +35,63 @@ fn test_generate_filename_and_path() {
     assert!(path.starts_with(config.import.paths.storage_dir));
     assert!(path.to_string_lossy().contains(&filename));
 }
+
+#[test]
+fn test_ext_variable_uses_detected_type() {
+    let mut config = AppConfig::default();
+    config.import.paths.classify_template = "{ext}/{category1}/{filename}".to_string();
+    config.import.paths.rename_template = "{title}.{ext}".to_string();
+    let generator = PathGenerator::new(config.clone());
+
+    // 内容检测为 PDF，但原始文件没有扩展名
+    let mut meta = sample_metadata();
+    meta.mime_type = Some("application/pdf".to_string());
+    let filename = generator.generate_filename("download", &meta).unwrap();
+    let path = generator.generate_path(&filename, &meta).unwrap();
+    assert_eq!(
+        path,
+        config
+            .import
+            .paths
+            .storage_dir
+            .join("pdf/books/Rust Book.pdf")
+    );
+
+    // 扩展名与检测到的类型不符时同样改用检测到的扩展名
+    let filename = generator.generate_filename("book.epub", &meta).unwrap();
+    assert_eq!(filename, "Rust Book.pdf");
+
+    // 类型未知时回退到文件名中的扩展名
+    let meta = sample_metadata();
+    let path = generator.generate_path("Rust Book.epub", &meta).unwrap();
+    assert_eq!(
+        path,
+        config
+            .import
+            .paths
+            .storage_dir
+            .join("epub/books/Rust Book.epub")
+    );
+}
+
+#[test]
+fn test_category_path_variables() {
+    let mut config = AppConfig::default();
+    config.import.paths.classify_template = "{category_path}/{category4}/{filename}".to_string();
+    let generator = PathGenerator::new(config.clone());
+
+    // 第三级及更深层级存放在 category3 中
+    let mut meta = sample_metadata();
+    meta.category1 = "Work".to_string();
+    meta.category2 = Some("Clients".to_string());
+    meta.category3 = Some("Acme/2026/Contracts".to_string());
+    let path = generator.generate_path("deal.pdf", &meta).unwrap();
+    assert_eq!(
+        path,
+        config
+            .import
+            .paths
+            .storage_dir
+            .join("Work/Clients/Acme/2026/Contracts/2026/deal.pdf")
+    );
+}
@@ -0,0 +1,77 @@
+use std::path::Path;
+use tagbox_core::metainfo::extract_pdf_structure;
+use tagbox_core::schema::Database;
+use tagbox_core::{extract_metainfo, get_file_annotations, import_file, AnnotationManager};
+
+mod common;
+use common::setup;
+
+/// 3 页：第 1 页标签为 `i`，之后从 1 开始编号；第 2 页有高亮和链接，第 3 页有便签
+const PDF_PATH: &str = "../test/data/annotated.pdf";
+
+#[test]
+fn test_outline_and_page_labels() {
+    let structure = extract_pdf_structure(Path::new(PDF_PATH)).unwrap();
+
+    let titles: Vec<&str> = structure.outline.iter().map(|e| e.title.as_str()).collect();
+    assert_eq!(titles, vec!["Introduction", "Method"]);
+
+    // 直接目标、GoTo 动作和命名目标
+    assert_eq!(structure.outline[0].page, Some(2));
+    assert_eq!(structure.outline[1].page, Some(3));
+    assert_eq!(structure.outline[1].children[0].title, "Details");
+    assert_eq!(structure.outline[1].children[0].page, Some(3));
+
+    assert_eq!(structure.page_label(1).as_deref(), Some("i"));
+    assert_eq!(structure.page_label(3).as_deref(), Some("2"));
+    assert_eq!(structure.outline[0].page_label.as_deref(), Some("1"));
+}
+
+#[test]
+fn test_annotations_with_quoted_text() {
+    let structure = extract_pdf_structure(Path::new(PDF_PATH)).unwrap();
+
+    // 链接不计入批注
+    assert_eq!(structure.annotations.len(), 2);
+
+    let highlight = &structure.annotations[0];
+    assert_eq!(highlight.page, 2);
+    assert_eq!(highlight.kind, "highlight");
+    assert_eq!(highlight.author.as_deref(), Some("Reviewer"));
+    assert_eq!(highlight.contents.as_deref(), Some("Key idea"));
+    let quoted = highlight.quoted_text.as_deref().unwrap_or_default();
+    assert!(quoted.contains("Attention"), "quoted: {:?}", quoted);
+    assert!(!quoted.contains("need"));
+
+    let note = &structure.annotations[1];
+    assert_eq!(note.page, 3);
+    assert_eq!(note.kind, "text");
+    assert_eq!(note.quoted_text, None);
+}
+
+#[tokio::test]
+async fn test_annotations_stored_and_searchable() {
+    let (_temp_dir, config) = setup().await;
+
+    let pdf = Path::new(PDF_PATH);
+    let metadata = extract_metainfo(pdf, &config).await.unwrap();
+    let document = &metadata.type_metadata.as_ref().unwrap()["document"];
+    assert!(document["outline"].is_array());
+    assert!(document["page_labels"].is_array());
+
+    let entry = import_file(pdf, metadata, &config).await.unwrap();
+
+    let annotations = get_file_annotations(&entry.id, &config).await.unwrap();
+    assert_eq!(annotations.len(), 2);
+
+    let db = Database::new(&config.database.path).await.unwrap();
+    let manager = AnnotationManager::new(db.pool().clone());
+    assert!(manager.is_available().await);
+
+    let hits = manager
+        .find_hits("scaling", &[entry.id.clone()], 3)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].annotation.page, 3);
+}
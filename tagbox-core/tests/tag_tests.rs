@@ -0,0 +1,242 @@
+use std::fs;
+use tagbox_core::config::AppConfig;
+use tagbox_core::schema::Database;
+use tagbox_core::types::{FileUpdateRequest, ImportMetadata};
+use tagbox_core::{
+    add_tag_alias, delete_tag, edit_file, get_file, get_file_history, get_tag_tree, import_file,
+    list_tags, merge_tags, prune_unused_tags, remove_tag_alias, rename_tag, search_files_advanced,
+};
+use tempfile::TempDir;
+
+mod common;
+use common::setup;
+
+async fn import_with_tags(
+    temp_dir: &TempDir,
+    name: &str,
+    tags: &[&str],
+    config: &AppConfig,
+) -> String {
+    let path = temp_dir.path().join(name);
+    fs::write(&path, format!("# {}\n", name)).unwrap();
+    let metadata = ImportMetadata {
+        title: name.to_string(),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..Default::default()
+    };
+    import_file(&path, metadata, config).await.unwrap().id
+}
+
+async fn search_titles(query: &str, config: &AppConfig) -> Vec<String> {
+    let mut titles: Vec<String> = search_files_advanced(query, None, config)
+        .await
+        .unwrap()
+        .entries
+        .into_iter()
+        .map(|e| e.title)
+        .collect();
+    titles.sort();
+    titles
+}
+
+#[tokio::test]
+async fn test_hierarchical_tags_match_descendants() {
+    let (temp_dir, config) = setup().await;
+
+    let id = import_with_tags(&temp_dir, "tokio.md", &[" lang / rust/async "], &config).await;
+    import_with_tags(&temp_dir, "serde.md", &["lang/rust"], &config).await;
+    import_with_tags(&temp_dir, "numpy.md", &["lang/python"], &config).await;
+    import_with_tags(&temp_dir, "rusty.md", &["lang/rusty"], &config).await;
+
+    // 标签路径被规范化，文件只关联叶子标签
+    let entry = get_file(&id, &config).await.unwrap();
+    assert_eq!(entry.tags, vec!["lang/rust/async"]);
+
+    assert_eq!(
+        search_titles("tag:lang/rust", &config).await,
+        vec!["serde.md", "tokio.md"]
+    );
+    assert_eq!(search_titles("tag:lang", &config).await.len(), 4);
+    assert_eq!(
+        search_titles("tag:lang -tag:lang/rust", &config).await,
+        vec!["numpy.md", "rusty.md"]
+    );
+    assert!(search_titles("tag:rust", &config).await.is_empty());
+
+    // 编辑标签同样创建父标签
+    let update = FileUpdateRequest {
+        tags: Some(vec!["lang/rust/web".to_string()]),
+        ..Default::default()
+    };
+    edit_file(&id, update, &config).await.unwrap();
+    assert!(search_titles("tag:lang/rust/async", &config)
+        .await
+        .is_empty());
+    assert_eq!(
+        search_titles("tag:lang/rust/web", &config).await,
+        vec!["tokio.md"]
+    );
+}
+
+#[tokio::test]
+async fn test_tag_tree_counts() {
+    let (temp_dir, config) = setup().await;
+
+    import_with_tags(
+        &temp_dir,
+        "a.md",
+        &["lang/rust/async", "lang/rust"],
+        &config,
+    )
+    .await;
+    import_with_tags(&temp_dir, "b.md", &["lang/rust"], &config).await;
+    import_with_tags(&temp_dir, "c.md", &["lang/python", "misc"], &config).await;
+
+    let tree = get_tag_tree(&config).await.unwrap();
+    let names: Vec<_> = tree.iter().map(|t| t.path.as_str()).collect();
+    assert_eq!(names, vec!["lang", "misc"]);
+
+    let lang = &tree[0];
+    assert_eq!(lang.file_count, 0);
+    assert_eq!(lang.total_count, 3);
+
+    let rust = lang.children.iter().find(|t| t.name == "rust").unwrap();
+    assert_eq!(rust.path, "lang/rust");
+    assert_eq!(rust.file_count, 2);
+    // a.md 同时打了父子标签，只计一次
+    assert_eq!(rust.total_count, 2);
+    assert_eq!(rust.children[0].path, "lang/rust/async");
+    assert_eq!(rust.children[0].total_count, 1);
+}
+
+#[tokio::test]
+async fn test_legacy_slash_tags_are_repaired() {
+    let (_temp_dir, config) = setup().await;
+
+    let db = Database::new(&config.database.path).await.unwrap();
+    sqlx::query(
+        "INSERT INTO tags (id, name, path, parent_id, created_at) VALUES ('old', 'work/notes', 'work/notes', NULL, '2024-01-01T00:00:00Z')",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    init_database(&config.database.path).await.unwrap();
+
+    let tree = get_tag_tree(&config).await.unwrap();
+    assert_eq!(tree.len(), 1);
+    assert_eq!(tree[0].path, "work");
+    assert_eq!(tree[0].children[0].id, "old");
+    assert_eq!(tree[0].children[0].name, "notes");
+}
+
+#[tokio::test]
+async fn test_rename_and_merge_tags() {
+    let (temp_dir, config) = setup().await;
+
+    let a = import_with_tags(&temp_dir, "a.md", &["ml/nlp"], &config).await;
+    let b = import_with_tags(&temp_dir, "b.md", &["machine-learning", "ml"], &config).await;
+    import_with_tags(&temp_dir, "c.md", &["lang/rs"], &config).await;
+
+    // 重命名时子标签一起移动
+    let files = rename_tag("lang/rs", "lang/rust", &config).await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(search_titles("tag:lang/rust", &config).await, vec!["c.md"]);
+    assert!(rename_tag("lang/rust", "ml", &config).await.is_err());
+    assert!(rename_tag("lang", "lang/rust/x", &config).await.is_err());
+
+    // 合并后 ml 成为别名，子标签按相对路径合并
+    let mut files = merge_tags("ml", "machine-learning", &config).await.unwrap();
+    files.sort();
+    let mut expected = vec![a.clone(), b.clone()];
+    expected.sort();
+    assert_eq!(files, expected);
+
+    assert_eq!(
+        get_file(&a, &config).await.unwrap().tags,
+        vec!["machine-learning/nlp"]
+    );
+    assert_eq!(
+        get_file(&b, &config).await.unwrap().tags,
+        vec!["machine-learning"]
+    );
+    assert_eq!(search_titles("tag:ml", &config).await, vec!["a.md", "b.md"]);
+    assert_eq!(search_titles("tag:ml/nlp", &config).await, vec!["a.md"]);
+
+    // FTS 索引中的标签随之更新
+    let db = Database::new(&config.database.path).await.unwrap();
+    let fts_tags: String = sqlx::query_scalar(
+        "SELECT files_fts.tags FROM files_fts JOIN files f ON f.rowid = files_fts.rowid WHERE f.id = ?",
+    )
+    .bind(&a)
+    .fetch_one(db.pool())
+    .await
+    .unwrap();
+    assert_eq!(fts_tags, "machine-learning/nlp");
+
+    let history = get_file_history(&a, None, &config).await.unwrap();
+    assert!(history.iter().any(|h| h.operation == "retag"
+        && h.reason.as_deref() == Some("merge tag ml into machine-learning")));
+
+    // 新导入的别名标签关联到目标标签
+    let d = import_with_tags(&temp_dir, "d.md", &["ml/vision"], &config).await;
+    assert_eq!(
+        get_file(&d, &config).await.unwrap().tags,
+        vec!["machine-learning/vision"]
+    );
+
+    let tags = list_tags(&config).await.unwrap();
+    let ml = tags.iter().find(|t| t.path == "machine-learning").unwrap();
+    assert_eq!(ml.file_count, 1);
+    assert_eq!(ml.aliases, vec!["ml"]);
+    assert!(!tags.iter().any(|t| t.path.starts_with("ml")));
+
+    remove_tag_alias("ml", &config).await.unwrap();
+    assert!(search_titles("tag:ml", &config).await.is_empty());
+    assert!(remove_tag_alias("ml", &config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_delete_alias_and_prune_tags() {
+    let (temp_dir, config) = setup().await;
+
+    let id = import_with_tags(&temp_dir, "a.md", &["draft/old", "keep"], &config).await;
+    import_with_tags(&temp_dir, "b.md", &["unused/leaf"], &config).await;
+
+    // 仍被使用的标签需要 force
+    assert!(delete_tag("draft", false, &config).await.is_err());
+    let files = delete_tag("draft", true, &config).await.unwrap();
+    assert_eq!(files, vec![id.clone()]);
+    assert_eq!(get_file(&id, &config).await.unwrap().tags, vec!["keep"]);
+    assert!(search_titles("tag:draft", &config).await.is_empty());
+
+    add_tag_alias("keep", "kept", &config).await.unwrap();
+    assert_eq!(search_titles("tag:kept", &config).await, vec!["a.md"]);
+    assert!(add_tag_alias("keep", "unused", &config).await.is_err());
+
+    // 只有无文件、无别名的标签被清理
+    edit_file(
+        &search_files_advanced("tag:unused", None, &config)
+            .await
+            .unwrap()
+            .entries[0]
+            .id,
+        FileUpdateRequest {
+            tags: Some(Vec::new()),
+            ..Default::default()
+        },
+        &config,
+    )
+    .await
+    .unwrap();
+    let mut removed = prune_unused_tags(&config).await.unwrap();
+    removed.sort();
+    assert_eq!(removed, vec!["unused", "unused/leaf"]);
+    let paths: Vec<String> = list_tags(&config)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.path)
+        .collect();
+    assert_eq!(paths, vec!["keep"]);
+}
@@ -0,0 +1,128 @@
+use std::path::Path;
+use tagbox_core::config::AppConfig;
+use tagbox_core::{extract_metainfo, import_file, search_files_advanced};
+
+mod common;
+use common::setup;
+
+#[tokio::test]
+async fn test_markdown_yaml_front_matter() {
+    let metadata = extract_metainfo(
+        Path::new("../test/data/meeting-notes.md"),
+        &AppConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(metadata.title, "Async Rust Reading Group");
+    // 作者可以是字符串，也可以是带 name 的对象
+    assert_eq!(
+        metadata.authors,
+        vec!["Alice Smith".to_string(), "Bob Lee".to_string()]
+    );
+    assert_eq!(metadata.year, Some(2023));
+    assert_eq!(metadata.tags, vec!["rust", "async", "reading-group"]);
+    assert_eq!(metadata.category1, "Notes");
+    assert_eq!(metadata.category2.as_deref(), Some("Rust"));
+    assert_eq!(
+        metadata.summary.as_deref(),
+        Some("Notes from the second session on pinning and wakers.")
+    );
+
+    // 正文不包含 front matter
+    let text = metadata.full_text.unwrap();
+    assert!(text.starts_with("# Session 2"));
+    assert!(!text.contains("reading-group"));
+
+    assert_eq!(
+        metadata.file_metadata.unwrap()["text"]["front_matter"],
+        "yaml"
+    );
+    assert_eq!(
+        metadata.type_metadata.unwrap()["front_matter"]["date"],
+        "2023-11-02"
+    );
+}
+
+#[tokio::test]
+async fn test_markdown_toml_front_matter_and_heading() {
+    let config = AppConfig::default();
+
+    let post = extract_metainfo(Path::new("../test/data/hugo-post.md"), &config)
+        .await
+        .unwrap();
+    assert_eq!(post.title, "Profiling with perf");
+    assert_eq!(post.authors, vec!["Carol Wu".to_string()]);
+    assert_eq!(post.year, Some(2022));
+    assert_eq!(post.tags, vec!["linux", "performance", "profiling"]);
+    assert_eq!(post.category1, "Blog");
+    assert_eq!(post.category2.as_deref(), Some("Linux"));
+
+    // 没有 front matter 时使用第一个一级标题
+    let note = extract_metainfo(Path::new("../test/data/untitled-note.md"), &config)
+        .await
+        .unwrap();
+    assert_eq!(note.title, "Garbage Collection Notes");
+    assert_eq!(note.file_metadata.unwrap()["text"]["format"], "markdown");
+}
+
+#[tokio::test]
+async fn test_html_head_and_body() {
+    let metadata = extract_metainfo(
+        Path::new("../test/data/saved-article.html"),
+        &AppConfig::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(metadata.title, "Understanding Ownership");
+    // article:author 是链接，不作为作者
+    assert_eq!(metadata.authors, vec!["Niko Matsakis".to_string()]);
+    assert_eq!(metadata.year, Some(2021));
+    assert_eq!(metadata.tags, vec!["rust", "ownership", "borrowing"]);
+    assert_eq!(
+        metadata.summary.as_deref(),
+        Some("A gentle introduction to ownership & borrowing.")
+    );
+    assert_eq!(metadata.publisher.as_deref(), Some("The Rust Blog"));
+    assert_eq!(
+        metadata.source.as_deref(),
+        Some("https://blog.example.com/ownership")
+    );
+
+    let text = metadata.full_text.unwrap();
+    assert!(text.contains("Every value in Rust has a single owner."));
+    assert!(!text.contains("tracking"));
+    assert!(!text.contains("font-family"));
+
+    let web = &metadata.type_metadata.unwrap()["web"];
+    assert_eq!(web["language"], "en");
+    assert_eq!(web["published"], "2021-06-15T08:00:00Z");
+}
+
+#[tokio::test]
+async fn test_body_text_is_searchable() {
+    let (_temp_dir, config) = setup().await;
+
+    for path in [
+        "../test/data/meeting-notes.md",
+        "../test/data/saved-article.html",
+    ] {
+        let path = Path::new(path);
+        let metadata = extract_metainfo(path, &config).await.unwrap();
+        import_file(path, metadata, &config).await.unwrap();
+    }
+
+    // 只出现在正文中的词
+    let result = search_files_advanced("executors", None, &config)
+        .await
+        .unwrap();
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(result.entries[0].title, "Async Rust Reading Group");
+
+    let result = search_files_advanced("dropped", None, &config)
+        .await
+        .unwrap();
+    assert_eq!(result.entries.len(), 1);
+    assert_eq!(result.entries[0].title, "Understanding Ownership");
+}
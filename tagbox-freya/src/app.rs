@@ -1,7 +1,7 @@
 use freya::prelude::*;
 use futures::channel::mpsc::UnboundedReceiver;
 use crate::router::{Router, Route, use_route};
-use crate::components::{TopBar, CategoryTree, FilePreview, ToastContainer, Breadcrumb, StatusBar, CustomButton, AdvancedSearchModal};
+use crate::components::{TopBar, CategoryTree, FilePreview, ToastContainer, Breadcrumb, StatusBar, CustomButton, IconButton, AdvancedSearchModal, BulkEditPanel};
 use crate::state::{AppState, FileEntry};
 
 pub fn App() -> Element {
@@ -229,6 +229,9 @@ fn FileList() -> Element {
                 direction: "column",
                 spacing: "10",
                 
+                // 多选时显示批量编辑面板
+                BulkEditPanel {}
+                
                 for file in files {
                     FileCard {
                         key: "{file.id}",
@@ -249,8 +252,12 @@ fn FileCard(file: FileEntry) -> Element {
         .and_then(|s| s.selected_file.as_ref())
         .map(|f| f.id == file.id)
         .unwrap_or(false);
+    let is_checked = app_state.read().as_ref()
+        .map(|s| s.checked_files.contains(&file.id))
+        .unwrap_or(false);
     
     let file_clone = file.clone();
+    let file_id_check = file.id.clone();
     
     rsx! {
         rect {
@@ -321,6 +328,16 @@ fn FileCard(file: FileEntry) -> Element {
                         }
                     }
                     
+                    // 多选框，用于批量编辑
+                    IconButton {
+                        icon: if is_checked { "☑".to_string() } else { "☐".to_string() },
+                        onpress: move |_| {
+                            if let Some(state) = app_state.write().as_mut() {
+                                state.toggle_checked(&file_id_check);
+                            }
+                        },
+                    }
+                    
                     // 编辑按钮
                     CustomButton {
                         text: "编辑",
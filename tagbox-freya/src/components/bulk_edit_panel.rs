@@ -0,0 +1,270 @@
+use freya::prelude::*;
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::StreamExt;
+use tagbox_core::types::{EditBatch, FilePatch};
+use crate::state::AppState;
+use crate::components::CustomButton;
+
+/// 批量编辑的操作
+enum BulkAction {
+    Preview,
+    Apply,
+    Undo,
+}
+
+/// 多选文件的批量编辑面板：添加/移除标签、设置分类，先预览后应用，可撤销
+pub fn BulkEditPanel() -> Element {
+    let mut app_state = use_context::<Signal<Option<AppState>>>();
+    let mut add_tags = use_signal(|| String::new());
+    let mut remove_tags = use_signal(|| String::new());
+    let mut category = use_signal(|| String::new());
+    let mut move_files = use_signal(|| false);
+    let mut preview = use_signal(|| None::<EditBatch>);
+    let mut is_working = use_signal(|| false);
+    
+    let (checked_count, last_batch) = match app_state.read().as_ref() {
+        Some(state) => (state.checked_files.len(), state.last_edit_batch.clone()),
+        None => (0, None),
+    };
+    
+    let bulk_coroutine = use_coroutine(move |mut rx: UnboundedReceiver<BulkAction>| async move {
+        while let Some(action) = rx.next().await {
+            let (service, file_ids, last_batch) = match app_state.read().as_ref() {
+                Some(state) => (state.service.clone(), state.checked_files.clone(), state.last_edit_batch.clone()),
+                None => continue,
+            };
+            is_working.set(true);
+            
+            match action {
+                BulkAction::Preview | BulkAction::Apply => {
+                    let dry_run = matches!(action, BulkAction::Preview);
+                    let patch = build_patch(&add_tags.read(), &remove_tags.read(), &category.read(), move_files());
+                    
+                    if patch.is_empty() {
+                        if let Some(state) = app_state.write().as_mut() {
+                            state.show_warning("没有需要修改的内容");
+                        }
+                    } else {
+                        match service.bulk_edit(&file_ids, &patch, dry_run).await {
+                            Ok(batch) if dry_run => {
+                                preview.set(Some(batch));
+                            }
+                            Ok(batch) => {
+                                preview.set(None);
+                                if let Some(state) = app_state.write().as_mut() {
+                                    state.checked_files.clear();
+                                    state.last_edit_batch = batch.batch_id.clone();
+                                    state.show_success(&format!("已更新 {} 个文件", batch.changes.len()));
+                                }
+                                refresh(app_state).await;
+                            }
+                            Err(e) => {
+                                tracing::error!("Bulk edit failed: {}", e);
+                                if let Some(state) = app_state.write().as_mut() {
+                                    state.show_error(&format!("批量编辑失败: {}", e));
+                                }
+                            }
+                        }
+                    }
+                }
+                BulkAction::Undo => {
+                    if let Some(batch_id) = last_batch {
+                        match service.undo_bulk_edit(&batch_id).await {
+                            Ok(batch) => {
+                                if let Some(state) = app_state.write().as_mut() {
+                                    state.last_edit_batch = None;
+                                    state.show_success(&format!("已撤销，恢复 {} 个文件", batch.changes.len()));
+                                }
+                                refresh(app_state).await;
+                            }
+                            Err(e) => {
+                                tracing::error!("Undo bulk edit failed: {}", e);
+                                if let Some(state) = app_state.write().as_mut() {
+                                    state.show_error(&format!("撤销失败: {}", e));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            
+            is_working.set(false);
+        }
+    });
+    
+    if checked_count == 0 && last_batch.is_none() {
+        return rsx! { rect {} };
+    }
+    
+    rsx! {
+        rect {
+            width: "100%",
+            padding: "12",
+            margin: "0 0 10 0",
+            background: "rgb(245, 247, 255)",
+            corner_radius: "8",
+            border: "1 solid rgb(220, 225, 245)",
+            direction: "column",
+            spacing: "8",
+            
+            if checked_count > 0 {
+                label {
+                    font_size: "14",
+                    font_weight: "bold",
+                    color: "rgb(50, 50, 50)",
+                    "批量编辑 {checked_count} 个文件"
+                }
+                
+                BulkEditField { label: "添加标签", value: add_tags }
+                BulkEditField { label: "移除标签", value: remove_tags }
+                BulkEditField { label: "设置分类", value: category }
+                
+                rect {
+                    direction: "horizontal",
+                    spacing: "8",
+                    content: "center start",
+                    onclick: move |_| {
+                        let value = move_files();
+                        move_files.set(!value);
+                    },
+                    
+                    label {
+                        font_size: "13",
+                        color: "rgb(80, 80, 80)",
+                        if move_files() { "☑ 按新分类移动文件" } else { "☐ 按新分类移动文件" }
+                    }
+                }
+                
+                // 预览结果
+                if let Some(batch) = preview.read().as_ref() {
+                    rect {
+                        width: "100%",
+                        padding: "8",
+                        background: "white",
+                        corner_radius: "4",
+                        direction: "column",
+                        spacing: "4",
+                        
+                        label {
+                            font_size: "12",
+                            color: "rgb(120, 120, 120)",
+                            "{batch.changes.len()} / {batch.matched} 个文件将被修改"
+                        }
+                        
+                        for change in batch.changes.iter().take(10) {
+                            label {
+                                key: "{change.file_id}",
+                                font_size: "12",
+                                color: "rgb(80, 80, 80)",
+                                max_lines: "1",
+                                text_overflow: "ellipsis",
+                                "{change.title}: {change.old_tags.join(\", \")} → {change.new_tags.join(\", \")}"
+                            }
+                        }
+                    }
+                }
+            }
+            
+            rect {
+                direction: "horizontal",
+                spacing: "8",
+                
+                if checked_count > 0 {
+                    CustomButton {
+                        text: "预览",
+                        variant: "secondary",
+                        disabled: is_working(),
+                        onpress: move |_| bulk_coroutine.send(BulkAction::Preview),
+                    }
+                    
+                    CustomButton {
+                        text: "应用",
+                        variant: "primary",
+                        disabled: is_working() || preview.read().is_none(),
+                        onpress: move |_| bulk_coroutine.send(BulkAction::Apply),
+                    }
+                    
+                    CustomButton {
+                        text: "取消选择",
+                        variant: "secondary",
+                        onpress: move |_| {
+                            preview.set(None);
+                            if let Some(state) = app_state.write().as_mut() {
+                                state.checked_files.clear();
+                            }
+                        },
+                    }
+                }
+                
+                if last_batch.is_some() {
+                    CustomButton {
+                        text: "撤销上次批量编辑",
+                        variant: "secondary",
+                        disabled: is_working(),
+                        onpress: move |_| bulk_coroutine.send(BulkAction::Undo),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn BulkEditField(label: String, value: Signal<String>) -> Element {
+    let mut value = value;
+    
+    rsx! {
+        rect {
+            width: "100%",
+            direction: "horizontal",
+            spacing: "8",
+            content: "center start",
+            
+            label {
+                width: "70",
+                font_size: "13",
+                color: "rgb(100, 100, 100)",
+                "{label}"
+            }
+            
+            Input {
+                width: "fill",
+                value: "{value}",
+                onchange: move |e: String| {
+                    value.set(e);
+                },
+            }
+        }
+    }
+}
+
+/// 刷新文件列表（批量编辑后标签和分类都会变化）
+async fn refresh(mut app_state: Signal<Option<AppState>>) {
+    let mut state = match app_state.read().as_ref() {
+        Some(state) => state.clone(),
+        None => return,
+    };
+    if let Err(e) = state.refresh_files().await {
+        tracing::error!("Failed to refresh files: {}", e);
+    }
+    app_state.set(Some(state));
+}
+
+/// 由输入框内容构建补丁，标签以逗号分隔
+fn build_patch(add_tags: &str, remove_tags: &str, category: &str, move_files: bool) -> FilePatch {
+    let split = |input: &str| -> Vec<String> {
+        input
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    };
+    let category = category.trim();
+    
+    FilePatch {
+        add_tags: split(add_tags),
+        remove_tags: split(remove_tags),
+        set_category: if category.is_empty() { None } else { Some(category.to_string()) },
+        move_files: move_files && !category.is_empty(),
+    }
+}
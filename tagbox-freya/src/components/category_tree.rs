@@ -1,11 +1,12 @@
 use freya::prelude::*;
+use tagbox_core::types::TagNode;
 use crate::state::{AppState, Category, FileEntry};
 
 #[component]
 fn ShowAllButton(total: usize) -> Element {
     let mut app_state = use_context::<Signal<Option<AppState>>>();
     let is_selected = app_state.read().as_ref()
-        .map(|s| s.selected_category.is_none())
+        .map(|s| s.selected_category.is_none() && s.selected_tag.is_none())
         .unwrap_or(true);
     
     rsx! {
@@ -18,6 +19,7 @@ fn ShowAllButton(total: usize) -> Element {
             onclick: move |_| {
                 if let Some(state) = app_state.write().as_mut() {
                     state.selected_category = None;
+                    state.selected_tag = None;
                 }
             },
             
@@ -53,9 +55,9 @@ fn ShowAllButton(total: usize) -> Element {
 pub fn CategoryTree() -> Element {
     let app_state = use_context::<Signal<Option<AppState>>>();
     
-    let (categories, total) = match app_state.read().as_ref() {
-        Some(state) => (state.categories.clone(), state.search_results.total_count),
-        None => (vec![], 0)
+    let (categories, tag_tree, total) = match app_state.read().as_ref() {
+        Some(state) => (state.categories.clone(), state.tag_tree.clone(), state.search_results.total_count),
+        None => (vec![], vec![], 0)
     };
     
     rsx! {
@@ -84,6 +86,24 @@ pub fn CategoryTree() -> Element {
                         level: 0
                     }
                 }
+                
+                // 标签树
+                if !tag_tree.is_empty() {
+                    label {
+                        font_size: "18",
+                        font_weight: "bold",
+                        margin: "15 0 15 0",
+                        "Tags"
+                    }
+                    
+                    for tag in tag_tree {
+                        TagTreeNode {
+                            key: "{tag.id}",
+                            tag: tag.clone(),
+                            level: 0
+                        }
+                    }
+                }
             }
         }
     }
@@ -193,6 +213,94 @@ fn CategoryNode(category: Category, level: u8) -> Element {
     }
 }
 
+#[component]
+fn TagTreeNode(tag: TagNode, level: u8) -> Element {
+    let mut app_state = use_context::<Signal<Option<AppState>>>();
+    let mut expanded = use_signal(|| false);
+    let has_children = !tag.children.is_empty();
+    let indent = level * 20;
+    
+    let is_selected = app_state.read().as_ref()
+        .and_then(|s| s.selected_tag.as_ref())
+        .map(|t| t == &tag.path)
+        .unwrap_or(false);
+    
+    rsx! {
+        rect {
+            width: "100%",
+            direction: "column",
+            
+            // 标签节点
+            rect {
+                width: "100%",
+                padding: "8 10 8 {indent + 10}",
+                direction: "horizontal",
+                content: "center start",
+                spacing: "8",
+                background: if is_selected { "rgb(240, 240, 255)" } else { "transparent" },
+                corner_radius: "4",
+                onclick: move |_| {
+                    if has_children {
+                        expanded.toggle();
+                    }
+                    
+                    // 选择标签，再次点击取消
+                    if let Some(state) = app_state.write().as_mut() {
+                        if state.selected_tag == Some(tag.path.clone()) {
+                            state.selected_tag = None;
+                        } else {
+                            state.selected_tag = Some(tag.path.clone());
+                        }
+                    }
+                },
+                
+                // 展开/折叠图标
+                if has_children {
+                    label {
+                        font_size: "12",
+                        color: "rgb(150, 150, 150)",
+                        width: "16",
+                        if expanded() { "▼" } else { "▶" }
+                    }
+                } else {
+                    rect { width: "16" }
+                }
+                
+                // 标签名称
+                label {
+                    font_size: "14",
+                    color: if is_selected { "rgb(80, 80, 255)" } else { "rgb(50, 50, 50)" },
+                    font_weight: if is_selected { "bold" } else { "normal" },
+                    "#{tag.name}"
+                }
+                
+                // 文件数量（含子标签）
+                label {
+                    font_size: "12",
+                    color: "rgb(150, 150, 150)",
+                    "({tag.total_count})"
+                }
+            }
+            
+            // 子标签
+            if expanded() {
+                rect {
+                    width: "100%",
+                    direction: "column",
+                    
+                    for child in &tag.children {
+                        TagTreeNode {
+                            key: "{child.id}",
+                            tag: child.clone(),
+                            level: level + 1
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn FileNode(file: FileEntry, level: u8) -> Element {
     let mut app_state = use_context::<Signal<Option<AppState>>>();
@@ -9,6 +9,7 @@ mod toast;
 mod breadcrumb;
 mod status_bar;
 mod custom_button;
+mod bulk_edit_panel;
 
 pub use top_bar::TopBar;
 pub use custom_button::{CustomButton, IconButton};
@@ -20,4 +21,5 @@ pub use drag_drop::{DragDropArea, SelectedFileDisplay};
 pub use confirm_dialog::ConfirmDialog;
 pub use toast::{ToastContainer, ToastMessage, ToastType, create_toast};
 pub use breadcrumb::Breadcrumb;
-pub use status_bar::StatusBar;
\ No newline at end of file
+pub use status_bar::StatusBar;
+pub use bulk_edit_panel::BulkEditPanel;
\ No newline at end of file
@@ -60,6 +60,7 @@ pub fn EditPage(file_id: String) -> Element {
                                 .map(|s| s.trim().to_string())
                                 .filter(|s| !s.is_empty())
                                 .collect(),
+                            contributors: Vec::new(),
                             year: year.read().parse::<i32>().ok(),
                             publisher: if publisher.read().is_empty() { None } else { Some(publisher.read().clone()) },
                             source: if source.read().is_empty() { None } else { Some(source.read().clone()) },
@@ -76,6 +77,7 @@ pub fn EditPage(file_id: String) -> Element {
                             additional_info: HashMap::new(),
                             file_metadata: None,
                             type_metadata: None,
+                            mime_type: None,
                         };
                         
                         let result = if let Some(state) = app_state.read().as_ref() {
@@ -692,6 +694,7 @@ mod tests {
         let metadata = ImportMetadata {
             title: title.to_string(),
             authors: authors.clone(),
+            contributors: Vec::new(),
             year: None,
             publisher: None,
             source: None,
@@ -704,6 +707,7 @@ mod tests {
             additional_info: HashMap::new(),
             file_metadata: None,
             type_metadata: None,
+            mime_type: None,
         };
 
         assert_eq!(metadata.title, "Test Title");
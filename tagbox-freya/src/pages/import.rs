@@ -98,6 +98,7 @@ pub fn ImportPage() -> Element {
             let metadata = ImportMetadata {
                 title: title.read().clone(),
                 authors: authors.read().split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect(),
+                contributors: Vec::new(),
                 year: year.read().parse().ok(),
                 publisher: if publisher.read().is_empty() { None } else { Some(publisher.read().clone()) },
                 source: None,
@@ -110,6 +111,7 @@ pub fn ImportPage() -> Element {
                 additional_info: std::collections::HashMap::new(),
                 file_metadata: None,
                 type_metadata: None,
+                mime_type: None,
             };
             
             // 执行导入
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use tagbox_core::{
     config::AppConfig,
     schema::Database,
-    types::{FileEntry, ImportMetadata, SearchOptions, SearchResult},
+    types::{EditBatch, FileEntry, FilePatch, ImportMetadata, SearchOptions, SearchResult, TagNode},
     FileOperation, LinkManager,
 };
 use tokio::sync::Mutex;
@@ -122,14 +122,19 @@ impl TagBoxService {
             self.extract_metadata(path).await?
         };
 
-        tagbox_core::import_file(path, metadata, &self.config).await
-            .map_err(|e| anyhow::anyhow!("Import failed: {}", e))
+        let entry = tagbox_core::import_file(path, metadata, &self.config).await
+            .map_err(|e| anyhow::anyhow!("Import failed: {}", e))?;
+        // 大文件的逐页索引在后台进行
+        tagbox_core::spawn_page_indexing(self.config.clone());
+        Ok(entry)
     }
 
     /// 批量导入文件
     pub async fn import_files(&self, paths: Vec<&Path>) -> Result<Vec<FileEntry>> {
-        tagbox_core::extract_and_import_files(&paths, &self.config).await
-            .map_err(|e| anyhow::anyhow!("Batch import failed: {}", e))
+        let entries = tagbox_core::extract_and_import_files(&paths, &self.config).await
+            .map_err(|e| anyhow::anyhow!("Batch import failed: {}", e))?;
+        tagbox_core::spawn_page_indexing(self.config.clone());
+        Ok(entries)
     }
 
     /// 获取文件信息
@@ -145,6 +150,7 @@ impl TagBoxService {
         let update_request = FileUpdateRequest {
             title: if metadata.title.is_empty() { None } else { Some(metadata.title) },
             authors: if metadata.authors.is_empty() { None } else { Some(metadata.authors) },
+            contributors: None,
             year: metadata.year,
             publisher: metadata.publisher,
             source: metadata.source,
@@ -170,6 +176,7 @@ impl TagBoxService {
         let update_request = FileUpdateRequest {
             title: None,
             authors: None,
+            contributors: None,
             year: None,
             publisher: None,
             source: None,
@@ -188,6 +195,18 @@ impl TagBoxService {
             .map_err(|e| anyhow::anyhow!("Delete file failed: {}", e))
     }
 
+    /// 对多个文件批量应用标签/分类补丁，`dry_run` 时只返回预计的变化
+    pub async fn bulk_edit(&self, file_ids: &[String], patch: &FilePatch, dry_run: bool) -> Result<EditBatch> {
+        tagbox_core::bulk_edit_files(file_ids, patch, dry_run, &self.config).await
+            .map_err(|e| anyhow::anyhow!("Bulk edit failed: {}", e))
+    }
+
+    /// 撤销一次批量编辑
+    pub async fn undo_bulk_edit(&self, batch_id: &str) -> Result<EditBatch> {
+        tagbox_core::undo_edit_batch(batch_id, &self.config).await
+            .map_err(|e| anyhow::anyhow!("Undo bulk edit failed: {}", e))
+    }
+
     /// 获取分类列表
     pub async fn get_categories(&self) -> Result<Vec<String>> {
         // TODO: 实现分类查询
@@ -201,6 +220,11 @@ impl TagBoxService {
         ])
     }
 
+    /// 获取标签树（含每个标签及其子标签的文件数）
+    pub async fn get_tag_tree(&self) -> Result<Vec<TagNode>> {
+        Ok(tagbox_core::get_tag_tree(&self.config).await?)
+    }
+
     /// 获取作者列表
     pub async fn get_authors(&self) -> Result<Vec<String>> {
         // TODO: 实现真实的作者查询
@@ -15,8 +15,12 @@ pub struct AppState {
     
     // UI 状态
     pub selected_file: Option<FileEntry>,
+    pub checked_files: Vec<String>,  // 多选的文件ID，用于批量编辑
+    pub last_edit_batch: Option<String>,  // 最近一次批量编辑，可撤销
     pub selected_category: Option<String>,  // 选中的分类ID
     pub categories: Vec<Category>,
+    pub selected_tag: Option<String>,  // 选中的标签路径，包含子标签
+    pub tag_tree: Vec<types::TagNode>,
     pub is_loading: bool,
     pub error_message: Option<String>,
     pub toast_messages: Vec<ToastMessage>,
@@ -32,16 +36,23 @@ impl AppState {
             total_count: 0,
             offset: 0,
             limit: 50,
+            page_hits: vec![],
+            annotation_hits: vec![],
         });
         let categories = Self::build_category_tree(&search_results);
+        let tag_tree = service.get_tag_tree().await.unwrap_or_default();
         
         Ok(Self {
             service,
             search_query: String::new(),
             search_results,
             selected_file: None,
+            checked_files: Vec::new(),
+            last_edit_batch: None,
             selected_category: None,
             categories,
+            selected_tag: None,
+            tag_tree,
             is_loading: false,
             error_message: None,
             toast_messages: Vec::new(),
@@ -77,9 +88,20 @@ impl AppState {
         } else {
             self.search(&query).await?;
         }
+        // 编辑或导入后标签可能变化
+        self.tag_tree = self.service.get_tag_tree().await.unwrap_or_default();
         Ok(())
     }
     
+    /// 切换文件的多选状态
+    pub fn toggle_checked(&mut self, file_id: &str) {
+        if let Some(pos) = self.checked_files.iter().position(|id| id == file_id) {
+            self.checked_files.remove(pos);
+        } else {
+            self.checked_files.push(file_id.to_string());
+        }
+    }
+    
     /// 显示成功通知
     pub fn show_success(&mut self, message: &str) {
         self.toast_messages.push(create_toast(ToastType::Success, message));
@@ -100,39 +122,36 @@ impl AppState {
         self.toast_messages.push(create_toast(ToastType::Warning, message));
     }
     
-    /// 获取当前显示的文件列表（根据选中的分类过滤）
+    /// 获取当前显示的文件列表（根据选中的分类和标签过滤）
     pub fn get_filtered_files(&self) -> Vec<FileEntry> {
+        let files = self.get_category_files();
+        
+        match &self.selected_tag {
+            Some(tag) => {
+                // 选中父标签时同时显示子标签下的文件
+                let prefix = format!("{}/", tag);
+                files.into_iter()
+                    .filter(|file| file.tags.iter().any(|t| t == tag || t.starts_with(&prefix)))
+                    .collect()
+            }
+            None => files,
+        }
+    }
+    
+    /// 根据选中的分类过滤文件列表
+    fn get_category_files(&self) -> Vec<FileEntry> {
         let all_files: Vec<FileEntry> = self.search_results.entries.iter()
             .map(|e| e.clone().into())
             .collect();
             
         if let Some(category_id) = &self.selected_category {
-            // 根据分类ID过滤文件
+            // 分类ID即完整分类路径
             all_files.into_iter()
                 .filter(|file| {
-                    if let Some(cat) = &file.category {
-                        // 检查是否匹配一级分类
-                        if &cat.level1 == category_id {
-                            return true;
-                        }
-                        // 检查是否匹配二级分类
-                        if let Some(level2) = &cat.level2 {
-                            let cat2_id = format!("{}/{}", cat.level1, level2);
-                            if &cat2_id == category_id {
-                                return true;
-                            }
-                            // 检查是否匹配三级分类
-                            if let Some(level3) = &cat.level3 {
-                                let cat3_id = format!("{}/{}/{}", cat.level1, level2, level3);
-                                if &cat3_id == category_id {
-                                    return true;
-                                }
-                            }
-                        }
-                        false
-                    } else {
+                    match &file.category {
+                        Some(cat) => &cat.full_path() == category_id,
                         // 未分类文件
-                        category_id == "uncategorized"
+                        None => category_id == "uncategorized",
                     }
                 })
                 .collect()
@@ -141,111 +160,50 @@ impl AppState {
         }
     }
     
-    /// 从搜索结果构建分类树
+    /// 从搜索结果构建分类树，分类路径按 "/" 逐级拆分，层级不限
     fn build_category_tree(results: &SearchResult) -> Vec<Category> {
-        use std::collections::HashMap;
-        
-        // 收集所有分类路径
-        let mut category_map: HashMap<String, Vec<FileEntry>> = HashMap::new();
+        let mut root_categories: Vec<Category> = Vec::new();
+        let mut uncategorized_files = Vec::new();
         
         for entry in &results.entries {
             let file_entry: FileEntry = entry.clone().into();
+            let path = match &file_entry.category {
+                Some(cat) => cat.full_path(),
+                None => {
+                    uncategorized_files.push(file_entry);
+                    continue;
+                }
+            };
+            let segments: Vec<&str> = path.split('/').collect();
             
-            // 处理分类路径
-            if let Some(cat) = &file_entry.category {
-                // 一级分类
-                let cat1_key = cat.level1.clone();
-                category_map.entry(cat1_key.clone()).or_insert_with(Vec::new);
-                
-                // 二级分类
-                if let Some(level2) = &cat.level2 {
-                    let cat2_key = format!("{}/{}", cat1_key, level2);
-                    category_map.entry(cat2_key.clone()).or_insert_with(Vec::new);
-                    
-                    // 三级分类
-                    if let Some(level3) = &cat.level3 {
-                        let cat3_key = format!("{}/{}/{}", cat1_key, level2, level3);
-                        category_map.entry(cat3_key.clone()).or_insert_with(Vec::new).push(file_entry.clone());
-                    } else {
-                        category_map.get_mut(&cat2_key).unwrap().push(file_entry.clone());
+            // 逐级查找或创建分类节点，文件挂在最深一级
+            let mut children = &mut root_categories;
+            for depth in 0..segments.len() {
+                let id = segments[..=depth].join("/");
+                let index = match children.iter().position(|c| c.id == id) {
+                    Some(index) => index,
+                    None => {
+                        children.push(Category {
+                            id,
+                            name: segments[depth].to_string(),
+                            level: (depth + 1) as u8,
+                            parent_id: (depth > 0).then(|| segments[..depth].join("/")),
+                            children: vec![],
+                            files: vec![],
+                        });
+                        children.len() - 1
                     }
-                } else {
-                    category_map.get_mut(&cat1_key).unwrap().push(file_entry.clone());
-                }
-            }
-        }
-        
-        // 构建分类树
-        let mut root_categories = Vec::new();
-        let mut processed = std::collections::HashSet::new();
-        
-        // 先处理一级分类
-        for (path, files) in &category_map {
-            if !path.contains('/') && !processed.contains(path) {
-                processed.insert(path.clone());
-                
-                let mut category = Category {
-                    id: path.clone(),
-                    name: path.clone(),
-                    level: 1,
-                    parent_id: None,
-                    children: vec![],
-                    files: files.clone(),
                 };
                 
-                // 查找二级分类
-                for (sub_path, sub_files) in &category_map {
-                    if sub_path.starts_with(&format!("{}/", path)) && sub_path.matches('/').count() == 1 {
-                        let parts: Vec<&str> = sub_path.split('/').collect();
-                        if parts.len() >= 2 {
-                            let mut sub_category = Category {
-                                id: sub_path.clone(),
-                                name: parts[1].to_string(),
-                                level: 2,
-                                parent_id: Some(path.clone()),
-                                children: vec![],
-                                files: sub_files.clone(),
-                            };
-                            
-                            // 查找三级分类
-                            for (sub_sub_path, sub_sub_files) in &category_map {
-                                if sub_sub_path.starts_with(&format!("{}/", sub_path)) && sub_sub_path.matches('/').count() == 2 {
-                                    let parts: Vec<&str> = sub_sub_path.split('/').collect();
-                                    if parts.len() >= 3 {
-                                        let sub_sub_category = Category {
-                                            id: sub_sub_path.clone(),
-                                            name: parts[2].to_string(),
-                                            level: 3,
-                                            parent_id: Some(sub_path.clone()),
-                                            children: vec![],
-                                            files: sub_sub_files.clone(),
-                                        };
-                                        sub_category.children.push(sub_sub_category);
-                                    }
-                                }
-                            }
-                            
-                            category.children.push(sub_category);
-                        }
-                    }
+                let category = &mut children[index];
+                if depth + 1 == segments.len() {
+                    category.files.push(file_entry.clone());
                 }
-                
-                root_categories.push(category);
+                children = &mut category.children;
             }
         }
         
         // 添加未分类的文件
-        let uncategorized_files: Vec<FileEntry> = results.entries.iter()
-            .filter_map(|e| {
-                let file_entry: FileEntry = e.clone().into();
-                if file_entry.category.is_none() {
-                    Some(file_entry)
-                } else {
-                    None
-                }
-            })
-            .collect();
-            
         if !uncategorized_files.is_empty() {
             root_categories.push(Category {
                 id: "uncategorized".to_string(),
@@ -258,10 +216,18 @@ impl AppState {
         }
         
         // 按名称排序
-        root_categories.sort_by(|a, b| a.name.cmp(&b.name));
+        Self::sort_categories(&mut root_categories);
         
         root_categories
     }
+    
+    /// 递归按名称排序各级分类
+    fn sort_categories(categories: &mut [Category]) {
+        categories.sort_by(|a, b| a.name.cmp(&b.name));
+        for category in categories {
+            Self::sort_categories(&mut category.children);
+        }
+    }
 }
 
 impl Default for AppState {
@@ -274,10 +240,16 @@ impl Default for AppState {
                 total_count: 0,
                 offset: 0,
                 limit: 50,
+                page_hits: vec![],
+                annotation_hits: vec![],
             },
             selected_file: None,
+            checked_files: Vec::new(),
+            last_edit_batch: None,
             selected_category: None,
             categories: vec![],
+            selected_tag: None,
+            tag_tree: vec![],
             is_loading: false,
             error_message: None,
             toast_messages: Vec::new(),
@@ -306,6 +278,13 @@ pub struct CategoryPath {
     pub level3: Option<String>,
 }
 
+impl CategoryPath {
+    /// 完整分类路径，`level3` 可能包含更深的层级（如 `Acme/2026/Contracts`）
+    pub fn full_path(&self) -> String {
+        tagbox_core::CategoryPath::from_columns(&self.level1, self.level2.as_deref(), self.level3.as_deref()).to_string()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Category {
     pub id: String,
@@ -133,6 +133,7 @@ impl TagBoxApi {
         Ok(ImportMetadata {
             title: "Example Document".to_string(),
             authors: vec![],
+            contributors: Vec::new(),
             year: None,
             publisher: None,
             source: None,
@@ -145,6 +146,7 @@ impl TagBoxApi {
             additional_info: Default::default(),
             file_metadata: None,
             type_metadata: None,
+            mime_type: None,
         })
     }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 use std::sync::mpsc::Receiver;
+use tagbox_core::bibliography::CitationStyle;
 use tagbox_core::config::AppConfig;
+use tagbox_core::types::{EditBatch, FilePatch};
 use crate::state::AppEvent;
 use crate::components::MainWindow;
 use crate::utils::AsyncBridge;
@@ -247,6 +249,24 @@ impl App {
                     }
                 }
             }
+            AppEvent::CopyCitation(file_ref) => {
+                tracing::info!("Copying citation: {}", file_ref);
+                let file = if file_ref == "current" {
+                    self.main_window.file_preview.get_current_file().cloned()
+                } else {
+                    self.get_file_by_ref(&file_ref)
+                };
+                if let Some(file) = file {
+                    self.async_bridge.spawn_format_citation(file.id, CitationStyle::Apa, self.config.clone());
+                }
+            }
+            AppEvent::CitationReady(citation) => {
+                if let Err(e) = crate::utils::copy_to_clipboard(&citation) {
+                    let _ = self.main_window.event_sender.send(AppEvent::Error(format!("Failed to copy citation: {}", e)));
+                } else {
+                    self.main_window.status_bar.set_temp_status("📋 Citation copied to clipboard", 2000);
+                }
+            }
             AppEvent::ShowInFolder(file_ref) => {
                 tracing::info!("Showing file in folder: {}", file_ref);
                 if let Some(file) = self.get_file_by_ref(&file_ref) {
@@ -410,6 +430,72 @@ impl App {
                     }
                 }
             }
+            AppEvent::BulkEditSelected => {
+                let files = self.main_window.get_selected_files();
+                if files.is_empty() {
+                    self.main_window.status_bar.set_temp_status("⚠️ No file selected", 2000);
+                    return Ok(());
+                }
+                tracing::info!("Bulk editing {} selected files", files.len());
+                
+                // 依次询问要添加/移除的标签和新分类
+                let Some(add_tags) = fltk::dialog::input_default(&format!("Add tags to {} files (comma-separated):", files.len()), "") else {
+                    return Ok(());
+                };
+                let Some(remove_tags) = fltk::dialog::input_default("Remove tags (comma-separated):", "") else {
+                    return Ok(());
+                };
+                let Some(category) = fltk::dialog::input_default("Set category (empty to keep):", "") else {
+                    return Ok(());
+                };
+                let category = category.trim().to_string();
+                let move_files = !category.is_empty()
+                    && fltk::dialog::choice2_default("Move files to match the new category?", "No", "Move", "") == Some(1);
+                
+                let patch = FilePatch {
+                    add_tags: Self::split_tags(&add_tags),
+                    remove_tags: Self::split_tags(&remove_tags),
+                    set_category: (!category.is_empty()).then_some(category),
+                    move_files,
+                };
+                if patch.is_empty() {
+                    self.main_window.status_bar.set_temp_status("⚠️ Nothing to change", 2000);
+                    return Ok(());
+                }
+                
+                // 先预演，确认后再真正执行
+                let file_ids = files.into_iter().map(|f| f.id).collect();
+                self.async_bridge.spawn_bulk_edit(file_ids, patch, true, self.config.clone());
+            }
+            AppEvent::BulkEditPreview(file_ids, patch, preview) => {
+                if preview.changes.is_empty() {
+                    self.main_window.status_bar.set_temp_status("ℹ️ Selected files already match", 2000);
+                    return Ok(());
+                }
+                let message = format!(
+                    "{} of {} files will change:\n\n{}",
+                    preview.changes.len(),
+                    preview.matched,
+                    Self::describe_batch(&preview)
+                );
+                if fltk::dialog::choice2_default(&message, "Cancel", "Apply", "") == Some(1) {
+                    self.async_bridge.spawn_bulk_edit(file_ids, patch, false, self.config.clone());
+                    self.main_window.status_bar.set_temp_status("🏷️ Applying bulk edit...", 2000);
+                }
+            }
+            AppEvent::BulkEditDone(batch) => {
+                tracing::info!("Bulk edit applied: {:?}", batch.batch_id);
+                self.main_window.status_bar.set_temp_status(&format!("✅ Updated {} files", batch.changes.len()), 3000);
+                
+                // 提供一次撤销机会
+                if let Some(batch_id) = batch.batch_id {
+                    let message = format!("Updated {} files.", batch.changes.len());
+                    if fltk::dialog::choice2_default(&message, "OK", "Undo", "") == Some(1) {
+                        self.async_bridge.spawn_undo_bulk_edit(batch_id, self.config.clone());
+                        self.main_window.status_bar.set_temp_status("↩️ Undoing bulk edit...", 2000);
+                    }
+                }
+            }
             _ => {
                 tracing::debug!("Unhandled event: {:?}", event);
             }
@@ -417,6 +503,35 @@ impl App {
         Ok(())
     }
     
+    // 辅助方法：拆分逗号分隔的标签
+    fn split_tags(input: &str) -> Vec<String> {
+        input
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+    
+    // 辅助方法：生成批量编辑的预览文本（最多列出 10 个文件）
+    fn describe_batch(batch: &EditBatch) -> String {
+        let mut lines: Vec<String> = batch
+            .changes
+            .iter()
+            .take(10)
+            .map(|change| {
+                let mut line = format!("• {}", change.title);
+                if change.old_category != change.new_category {
+                    line.push_str(&format!("  [{} → {}]", change.old_category, change.new_category));
+                }
+                line
+            })
+            .collect();
+        if batch.changes.len() > 10 {
+            lines.push(format!("… and {} more", batch.changes.len() - 10));
+        }
+        lines.join("\n")
+    }
+    
     // 辅助方法：根据文件引用获取文件
     fn get_file_by_ref(&self, file_ref: &str) -> Option<tagbox_core::types::FileEntry> {
         if file_ref.starts_with("index:") {
@@ -1,14 +1,19 @@
 use fltk::{
     prelude::*,
-    tree::Tree,
+    tree::{Tree, TreeItem},
     enums::{Color, Event},
     app::MouseButton,
 };
 use std::sync::mpsc::Sender;
 use std::collections::{HashMap, BTreeMap, BTreeSet};
 use tagbox_core::config::AppConfig;
+use tagbox_core::types::TagNode;
+use tagbox_core::CategoryPath;
 use crate::state::{AppEvent, CategoryTreeState};
 
+// 标签树根节点的名称
+const TAGS_ROOT: &str = "Tags";
+
 // 分类层次结构管理器
 #[derive(Debug, Clone)]
 struct CategoryHierarchy {
@@ -80,6 +85,7 @@ pub struct CategoryTree {
     state: CategoryTreeState,
     event_sender: Sender<AppEvent>,
     file_counts: HashMap<String, i32>,
+    tag_tree: Vec<TagNode>,
 }
 
 impl CategoryTree {
@@ -103,6 +109,7 @@ impl CategoryTree {
             },
             event_sender,
             file_counts: HashMap::new(),
+            tag_tree: Vec::new(),
         };
         
         category_tree.setup_callbacks();
@@ -119,21 +126,10 @@ impl CategoryTree {
         
         // 收集所有分类并构建层次结构
         for file in &search_result.entries {
-            let cat1 = &file.category1;
-            category_structure.add_category(&[cat1.clone()]);
-            *self.file_counts.entry(cat1.clone()).or_insert(0) += 1;
-            
-            if let Some(cat2) = &file.category2 {
-                category_structure.add_category(&[cat1.clone(), cat2.clone()]);
-                let path2 = format!("{}/{}", cat1, cat2);
-                *self.file_counts.entry(path2).or_insert(0) += 1;
-                
-                if let Some(cat3) = &file.category3 {
-                    category_structure.add_category(&[cat1.clone(), cat2.clone(), cat3.clone()]);
-                    let path3 = format!("{}/{}/{}", cat1, cat2, cat3);
-                    *self.file_counts.entry(path3).or_insert(0) += 1;
-                }
-            }
+            // 按完整分类路径逐级拆分，第三级以下的每一级都是独立节点
+            let path = CategoryPath::of_file(file);
+            category_structure.add_category(path.segments());
+            self.count_file(path.segments());
         }
         
         // 重建树
@@ -143,7 +139,11 @@ impl CategoryTree {
         self.tree.add(&format!("📁 All Files ({})", search_result.entries.len()));
         
         // 构建分层的分类树
-        self.build_tree_from_hierarchy(&category_structure, "");
+        self.build_tree_from_hierarchy(&category_structure, "", "");
+        
+        // 标签树（含子标签的文件数）
+        self.tag_tree = tagbox_core::get_tag_tree(config).await?;
+        self.add_tag_nodes();
         
         self.tree.redraw();
         println!("Loaded {} categories with {} total files", category_structure.count_categories(), search_result.entries.len());
@@ -197,10 +197,21 @@ impl CategoryTree {
         self.tree.set_callback(move |tree| {
             if let Some(selected_items) = tree.get_selected_items() {
                 if let Some(selected_item) = selected_items.first() {
-                    let label = selected_item.label().unwrap_or_default();
+                    // 标签节点按标签路径搜索（包含子标签）
+                    if let Some(tag_path) = Self::tag_path_of(selected_item) {
+                        if !tag_path.is_empty() {
+                            let query = if tag_path.contains(char::is_whitespace) {
+                                format!("tag:\"{}\"", tag_path)
+                            } else {
+                                format!("tag:{}", tag_path)
+                            };
+                            let _ = sender.send(AppEvent::SearchQuery(query));
+                        }
+                        return;
+                    }
                     
-                    // 解析分类路径
-                    let category_path = Self::parse_category_from_label(&label);
+                    // 解析分类路径（由各级节点拼出完整路径）
+                    let category_path = Self::category_path_of(selected_item);
                     
                     if category_path == "All Files" {
                         // 显示所有文件
@@ -220,8 +231,12 @@ impl CategoryTree {
                     if fltk::app::event_mouse_button() == MouseButton::Right {
                         if let Some(selected_items) = tree.get_selected_items() {
                             if let Some(selected_item) = selected_items.first() {
-                                let label = selected_item.label().unwrap_or_default();
-                                let category_path = Self::parse_category_from_label(&label);
+                                // 标签节点没有分类菜单
+                                if Self::tag_path_of(selected_item).is_some() {
+                                    return true;
+                                }
+                                
+                                let category_path = Self::category_path_of(selected_item);
                                 
                                 // 显示分类右键菜单
                                 Self::show_category_context_menu(&category_path, &sender_menu);
@@ -243,7 +258,8 @@ impl CategoryTree {
         let clean_label = label
             .trim_start_matches("📁 ")
             .trim_start_matches("📄 ")
-            .trim_start_matches("📂 ");
+            .trim_start_matches("📂 ")
+            .trim_start_matches("🏷 ");
             
         if let Some(paren_pos) = clean_label.rfind(" (") {
             clean_label[..paren_pos].to_string()
@@ -252,8 +268,67 @@ impl CategoryTree {
         }
     }
     
+    // 分类节点的完整路径，如 `Work/Clients/Acme`
+    fn category_path_of(item: &TreeItem) -> String {
+        let mut segments = vec![Self::parse_category_from_label(&item.label().unwrap_or_default())];
+        let mut current = item.clone();
+        // 隐藏的根节点不计入路径
+        while let Some(parent) = current.parent() {
+            if parent.parent().is_some() {
+                segments.push(Self::parse_category_from_label(&parent.label().unwrap_or_default()));
+            }
+            current = parent;
+        }
+        segments.reverse();
+        segments.join("/")
+    }
+    
+    // 如果是标签树中的节点，返回标签路径（根节点为空字符串）
+    fn tag_path_of(item: &TreeItem) -> Option<String> {
+        let mut segments = Vec::new();
+        let mut current = item.clone();
+        // 根节点没有父节点，不计入路径
+        while let Some(parent) = current.parent() {
+            segments.push(Self::parse_category_from_label(&current.label().unwrap_or_default()));
+            current = parent;
+        }
+        segments.reverse();
+        
+        if segments.first().map(String::as_str) == Some(TAGS_ROOT) {
+            Some(segments[1..].join("/"))
+        } else {
+            None
+        }
+    }
+    
+    // 在树的末尾添加标签层级
+    fn add_tag_nodes(&mut self) {
+        if self.tag_tree.is_empty() {
+            return;
+        }
+        
+        let root = format!("🏷 {}", TAGS_ROOT);
+        self.tree.add(&root);
+        let nodes = self.tag_tree.clone();
+        for node in &nodes {
+            self.add_tag_node(node, &root);
+        }
+    }
+    
+    // 递归添加标签节点，节点路径使用显示文本以便挂到父节点下
+    fn add_tag_node(&mut self, node: &TagNode, parent_label_path: &str) {
+        let label = format!("🏷 {} ({})", node.name, node.total_count);
+        let label_path = format!("{}/{}", parent_label_path, label);
+        self.tree.add(&label_path);
+        
+        for child in &node.children {
+            self.add_tag_node(child, &label_path);
+        }
+    }
+    
     // 从分类层次结构构建树
-    fn build_tree_from_hierarchy(&mut self, hierarchy: &CategoryHierarchy, parent_path: &str) {
+    // `parent_label_path` 为父节点在树中的显示路径，子节点需挂在它下面
+    fn build_tree_from_hierarchy(&mut self, hierarchy: &CategoryHierarchy, parent_path: &str, parent_label_path: &str) {
         let categories = if parent_path.is_empty() {
             hierarchy.get_root_categories()
         } else {
@@ -273,17 +348,17 @@ impl CategoryTree {
             let icon = if has_subcategories { "📂" } else { "📄" };
             let label = format!("{} {} ({})", icon, category, count);
             
-            let tree_path = if parent_path.is_empty() {
+            let tree_path = if parent_label_path.is_empty() {
                 label
             } else {
-                format!("{}/{}", parent_path, label)
+                format!("{}/{}", parent_label_path, label)
             };
             
             self.tree.add(&tree_path);
             
             // 递归添加子分类
             if has_subcategories {
-                self.build_tree_from_hierarchy(hierarchy, &full_path);
+                self.build_tree_from_hierarchy(hierarchy, &full_path, &tree_path);
             }
         }
     }
@@ -306,24 +381,20 @@ impl CategoryTree {
         
         // 重新计算每个分类的文件数量
         for file in files {
-            let cat1 = &file.category1;
-            *self.file_counts.entry(cat1.clone()).or_insert(0) += 1;
-            
-            if let Some(cat2) = &file.category2 {
-                let path2 = format!("{}/{}", cat1, cat2);
-                *self.file_counts.entry(path2).or_insert(0) += 1;
-                
-                if let Some(cat3) = &file.category3 {
-                    let path3 = format!("{}/{}/{}", cat1, cat2, cat3);
-                    *self.file_counts.entry(path3).or_insert(0) += 1;
-                }
-            }
+            self.count_file(CategoryPath::of_file(file).segments());
         }
         
         // 刷新树显示以更新计数
         self.refresh_tree_display();
     }
     
+    // 文件计入其分类路径上的每一级
+    fn count_file(&mut self, segments: &[String]) {
+        for depth in 1..=segments.len() {
+            *self.file_counts.entry(segments[..depth].join("/")).or_insert(0) += 1;
+        }
+    }
+    
     // 刷新树显示
     fn refresh_tree_display(&mut self) {
         self.tree.clear();
@@ -352,6 +423,9 @@ impl CategoryTree {
             }
         }
         
+        // 保留上次加载的标签树
+        self.add_tag_nodes();
+        
         self.tree.redraw();
     }
     
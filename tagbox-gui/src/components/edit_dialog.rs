@@ -10,7 +10,7 @@ use fltk::{
     text::{TextEditor, TextBuffer},
 };
 use std::sync::mpsc::Sender;
-use tagbox_core::{config::AppConfig, types::{FileEntry, ImportMetadata}};
+use tagbox_core::{config::AppConfig, split_author_list, types::{FileEntry, ImportMetadata}};
 use crate::state::AppEvent;
 
 pub struct EditDialog {
@@ -279,11 +279,7 @@ impl EditDialog {
     }
     
     pub fn collect_form_data(&self) -> ImportMetadata {
-        let authors: Vec<String> = self.authors_input.value()
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect();
+        let authors = split_author_list(&self.authors_input.value());
         
         let tags: Vec<String> = self.tags_input.value()
             .split(',')
@@ -337,6 +333,7 @@ impl EditDialog {
         ImportMetadata {
             title: self.title_input.value(),
             authors,
+            contributors: Vec::new(),
             year,
             publisher,
             source,
@@ -349,6 +346,7 @@ impl EditDialog {
             additional_info: std::collections::HashMap::new(),
             file_metadata: None,
             type_metadata: None,
+            mime_type: None,
         }
     }
     
@@ -360,6 +358,7 @@ impl EditDialog {
             let update_request = tagbox_core::types::FileUpdateRequest {
                 title: Some(metadata.title),
                 authors: Some(metadata.authors),
+                contributors: None,
                 year: metadata.year,
                 publisher: metadata.publisher,
                 source: metadata.source,
@@ -389,6 +388,7 @@ impl EditDialog {
             let delete_request = tagbox_core::types::FileUpdateRequest {
                 title: None,
                 authors: None,
+                contributors: None,
                 year: None,
                 publisher: None,
                 source: None,
@@ -256,6 +256,15 @@ impl FileList {
         self.get_selected_file()
     }
     
+    // 获取所有选中的文件（多选）
+    pub fn get_selected_files(&self) -> Vec<FileEntry> {
+        let files = self.files.lock().unwrap();
+        (3..=self.browser.size())  // 跳过表头
+            .filter(|line| self.browser.selected(*line))
+            .filter_map(|line| files.get((line - 3) as usize).cloned())
+            .collect()
+    }
+    
     // 获取当前文件列表
     pub fn get_current_files(&self) -> Vec<FileEntry> {
         let files = self.files.lock().unwrap();
@@ -306,8 +315,10 @@ impl FileList {
         menu.add_choice("📄 Open File");
         menu.add_choice("✏️ Edit Metadata");
         menu.add_choice("📋 Copy Path");
+        menu.add_choice("📚 Copy Citation");
         menu.add_choice("📁 Show in Folder");
         menu.add_choice("🗑️ Delete");
+        menu.add_choice("🏷️ Bulk Edit Selected...");
         
         let choice = menu.popup().map(|item| item.value() as usize);
         
@@ -321,14 +332,20 @@ impl FileList {
             Some(2) => { // Copy Path
                 let _ = sender.send(AppEvent::CopyFilePath(format!("index:{}", file_index)));
             },
-            Some(3) => { // Show in Folder
+            Some(3) => { // Copy Citation
+                let _ = sender.send(AppEvent::CopyCitation(format!("index:{}", file_index)));
+            },
+            Some(4) => { // Show in Folder
                 let _ = sender.send(AppEvent::ShowInFolder(format!("index:{}", file_index)));
             },
-            Some(4) => { // Delete
+            Some(5) => { // Delete
                 if fltk::dialog::choice2_default("Remove this file from TagBox?", "Cancel", "Remove", "") == Some(1) {
                     let _ = sender.send(AppEvent::DeleteFile(format!("index:{}", file_index)));
                 }
             },
+            Some(6) => { // Bulk Edit Selected
+                let _ = sender.send(AppEvent::BulkEditSelected);
+            },
             _ => {}
         }
     }
@@ -41,6 +41,7 @@ pub struct FilePreview {
     open_btn: Button,
     edit_btn: Button,
     copy_path_btn: Button,
+    cite_btn: Button,
     cd_btn: Button,
     
     // 状态
@@ -208,6 +209,12 @@ impl FilePreview {
         copy_path_btn.set_label_color(Color::White);
         copy_path_btn.set_label_size(12);
         
+        let mut cite_btn = Button::new(0, 0, 0, 35, "Cite");
+        cite_btn.set_color(Color::from_rgb(111, 66, 193));
+        cite_btn.set_label_color(Color::White);
+        cite_btn.set_label_size(12);
+        cite_btn.set_tooltip("Copy citation (APA)");
+        
         let mut cd_btn = Button::new(0, 0, 0, 35, "Folder");
         cd_btn.set_color(Color::from_rgb(23, 162, 184));
         cd_btn.set_label_color(Color::White);
@@ -234,6 +241,11 @@ impl FilePreview {
             // 复制路径功能在 display_file 中实现
         });
         
+        let sender_clone = event_sender.clone();
+        cite_btn.set_callback(move |_| {
+            let _ = sender_clone.send(AppEvent::CopyCitation("current".to_string()));
+        });
+        
         cd_btn.set_callback(move |_| {
             // 打开文件夹功能在 display_file 中实现
         });
@@ -254,6 +266,7 @@ impl FilePreview {
             open_btn,
             edit_btn,
             copy_path_btn,
+            cite_btn,
             cd_btn,
             current_file: None,
             event_sender,
@@ -376,11 +389,13 @@ impl FilePreview {
             self.open_btn.deactivate();
             self.edit_btn.deactivate();
             self.copy_path_btn.deactivate();
+            self.cite_btn.deactivate();
             self.cd_btn.deactivate();
         } else {
             self.open_btn.activate();
             self.edit_btn.activate();
             self.copy_path_btn.activate();
+            self.cite_btn.activate();
             self.cd_btn.activate();
         }
         
@@ -324,6 +324,7 @@ impl ImportDialog {
         ImportMetadata {
             title: self.title_input.value(),
             authors,
+            contributors: Vec::new(),
             year,
             publisher,
             source,
@@ -336,6 +337,7 @@ impl ImportDialog {
             additional_info: std::collections::HashMap::new(),
             file_metadata: None,
             type_metadata: None,
+            mime_type: None,
         }
     }
     
@@ -191,6 +191,8 @@ impl MainWindow {
             total_count: 0,
             offset: 0,
             limit: 0,
+            page_hits: vec![],
+            annotation_hits: vec![],
         };
         
         // 异步加载文件到列表中
@@ -591,6 +593,11 @@ impl MainWindow {
         self.file_list.get_current_files()
     }
     
+    // 获取文件列表中所有选中的文件（多选）
+    pub fn get_selected_files(&self) -> Vec<tagbox_core::types::FileEntry> {
+        self.file_list.get_selected_files()
+    }
+    
     // 更新状态栏（定期调用）
     pub fn update_status_bar(&mut self) {
         self.status_bar.update();
@@ -1,5 +1,5 @@
 use std::path::PathBuf;
-use tagbox_core::types::{SearchOptions, SearchResult, FileEntry};
+use tagbox_core::types::{EditBatch, FilePatch, SearchOptions, SearchResult, FileEntry};
 
 #[derive(Debug, Clone)]
 pub enum AppEvent {
@@ -23,8 +23,15 @@ pub enum AppEvent {
     OpenFile(String), // 打开文件
     EditFile(String), // 编辑文件元数据
     CopyFilePath(String), // 复制文件路径
+    CopyCitation(String), // 复制参考文献条目
+    CitationReady(String), // 参考文献条目生成完成
     ShowInFolder(String), // 在文件夹中显示
     
+    // 批量编辑（多选）
+    BulkEditSelected, // 对选中的文件批量编辑
+    BulkEditPreview(Vec<String>, FilePatch, EditBatch), // 预演完成：文件ID、补丁、预计变化
+    BulkEditDone(EditBatch), // 批量编辑或撤销完成
+    
     // 分类树操作
     CategoryExpand(String),
     CategorySelect(String),
@@ -1,7 +1,8 @@
 use std::sync::mpsc::Sender;
 use std::future::Future;
+use tagbox_core::bibliography::CitationStyle;
 use tagbox_core::config::AppConfig;
-use tagbox_core::types::SearchOptions;
+use tagbox_core::types::{FilePatch, SearchOptions};
 use crate::state::AppEvent;
 use tracing::{info, warn, error};
 
@@ -102,6 +103,8 @@ impl AsyncBridge {
                     info!("Successfully imported file: {} -> {}", file_path.display(), file_entry.id);
                     let _ = sender.send(AppEvent::FileImported(file_entry));
                     let _ = sender.send(AppEvent::RefreshView);
+                    // 大文件的逐页索引在后台进行
+                    tagbox_core::spawn_page_indexing(config);
                 }
                 Err(e) => {
                     error!("Failed to import file {}: {}", file_path.display(), e);
@@ -224,6 +227,65 @@ impl AsyncBridge {
             info!("Batch import completed: {} successful, {} failed", successful_imports, failed_imports);
             let _ = sender.send(AppEvent::RefreshView);
             let _ = sender.send(AppEvent::LoadingEnd);
+            
+            if successful_imports > 0 {
+                tagbox_core::spawn_page_indexing(config);
+            }
+        });
+    }
+    
+    /// 生成文件的参考文献条目，完成后由主线程复制到剪贴板
+    pub fn spawn_format_citation(&self, file_id: String, style: CitationStyle, config: AppConfig) {
+        let sender = self.event_sender.clone();
+        self.runtime.spawn(async move {
+            info!("Formatting citation for: {}", file_id);
+            match tagbox_core::format_citations(&[file_id], style, &config).await {
+                Ok(mut citations) => {
+                    let _ = sender.send(AppEvent::CitationReady(citations.remove(0)));
+                }
+                Err(e) => {
+                    error!("Failed to format citation: {}", e);
+                    let _ = sender.send(AppEvent::Error(format!("Failed to format citation: {}", e)));
+                }
+            }
+        });
+    }
+    
+    /// 批量编辑选中的文件，`dry_run` 时只预演并返回预计的变化
+    pub fn spawn_bulk_edit(&self, file_ids: Vec<String>, patch: FilePatch, dry_run: bool, config: AppConfig) {
+        let sender = self.event_sender.clone();
+        self.runtime.spawn(async move {
+            info!("Bulk editing {} files (dry run: {})", file_ids.len(), dry_run);
+            match tagbox_core::bulk_edit_files(&file_ids, &patch, dry_run, &config).await {
+                Ok(batch) if dry_run => {
+                    let _ = sender.send(AppEvent::BulkEditPreview(file_ids, patch, batch));
+                }
+                Ok(batch) => {
+                    let _ = sender.send(AppEvent::BulkEditDone(batch));
+                    let _ = sender.send(AppEvent::RefreshView);
+                }
+                Err(e) => {
+                    error!("Failed to bulk edit files: {}", e);
+                    let _ = sender.send(AppEvent::Error(format!("Failed to bulk edit files: {}", e)));
+                }
+            }
+        });
+    }
+    
+    /// 撤销一次批量编辑
+    pub fn spawn_undo_bulk_edit(&self, batch_id: String, config: AppConfig) {
+        let sender = self.event_sender.clone();
+        self.runtime.spawn(async move {
+            info!("Undoing bulk edit: {}", batch_id);
+            match tagbox_core::undo_edit_batch(&batch_id, &config).await {
+                Ok(_) => {
+                    let _ = sender.send(AppEvent::RefreshView);
+                }
+                Err(e) => {
+                    error!("Failed to undo bulk edit: {}", e);
+                    let _ = sender.send(AppEvent::Error(format!("Failed to undo bulk edit: {}", e)));
+                }
+            }
         });
     }
     
@@ -258,6 +320,7 @@ impl AsyncBridge {
             let update_request = tagbox_core::types::FileUpdateRequest {
                 title: Some(metadata.title),
                 authors: Some(metadata.authors),
+                contributors: None,
                 year: metadata.year,
                 publisher: metadata.publisher,
                 source: metadata.source,
@@ -295,6 +358,7 @@ impl AsyncBridge {
             let delete_request = tagbox_core::types::FileUpdateRequest {
                 title: None,
                 authors: None,
+                contributors: None,
                 year: None,
                 publisher: None,
                 source: None,
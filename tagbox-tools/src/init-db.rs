@@ -54,6 +54,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             category_id TEXT,   -- 来自 database.md
             source_url TEXT,    -- 来自 database.md
             summary TEXT,     -- 来自 database.md (JSON)
+            original_path TEXT, -- 导入时的源路径
+            size INTEGER,       -- 文件大小（字节）
+            mime_type TEXT,     -- MIME 类型
+            file_mtime TEXT,    -- 源文件修改时间
+            page_index_status TEXT, -- 页级全文索引状态
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
             is_deleted INTEGER NOT NULL DEFAULT 0, -- 对应 BOOLEAN
@@ -110,6 +115,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     )
     .await?;
 
+    execute_sql(
+        &db,
+        "
+        CREATE TABLE IF NOT EXISTS tag_aliases (
+            alias TEXT PRIMARY KEY,
+            tag_id TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+    ",
+        "Create tag_aliases table",
+    )
+    .await?;
+
     execute_sql(
         &db,
         "DROP TABLE IF EXISTS authors;",
@@ -126,6 +145,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             aliases TEXT, -- JSON 数组
             bio TEXT,
             homepage TEXT,
+            orcid TEXT, -- 规范化的 ORCID
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
             is_deleted INTEGER NOT NULL DEFAULT 0 -- 对应 BOOLEAN
@@ -141,7 +161,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         CREATE TABLE IF NOT EXISTS file_authors (
             file_id TEXT NOT NULL,
             author_id TEXT NOT NULL,
-            PRIMARY KEY (file_id, author_id),
+            role TEXT NOT NULL DEFAULT 'author', -- author, editor, translator, illustrator
+            position INTEGER NOT NULL DEFAULT 0, -- 同一角色内的署名顺序
+            PRIMARY KEY (file_id, author_id, role),
             FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE,
             FOREIGN KEY (author_id) REFERENCES authors(id) ON DELETE CASCADE
         );
@@ -204,18 +226,185 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         &db,
         "
         CREATE VIRTUAL TABLE IF NOT EXISTS files_fts USING fts5(
-            title, 
-            tags,
-            summary, 
+            title,
             authors,
-            content=\'files\', 
-            content_rowid=\'id\'
+            summary,
+            tags,
+            full_text
         );
     ",
         "Create files_fts FTS5 table",
     )
     .await?;
 
+    execute_sql(
+        &db,
+        "
+        CREATE TABLE IF NOT EXISTS file_aliases (
+            file_id TEXT NOT NULL,
+            original_path TEXT NOT NULL,
+            filename TEXT NOT NULL,
+            added_at TEXT NOT NULL,
+            PRIMARY KEY (file_id, original_path),
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+        );
+    ",
+        "Create file_aliases table",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TABLE IF NOT EXISTS file_pages (
+            file_id TEXT NOT NULL,
+            page INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            PRIMARY KEY (file_id, page),
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+        );
+    ",
+        "Create file_pages table",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE VIRTUAL TABLE IF NOT EXISTS file_pages_fts USING fts5(
+            text,
+            content='file_pages',
+            content_rowid='rowid'
+        );
+    ",
+        "Create file_pages_fts FTS5 table",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TRIGGER IF NOT EXISTS file_pages_ai AFTER INSERT ON file_pages BEGIN
+            INSERT INTO file_pages_fts(rowid, text) VALUES (new.rowid, new.text);
+        END;
+    ",
+        "Create file_pages_ai trigger",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TRIGGER IF NOT EXISTS file_pages_ad AFTER DELETE ON file_pages BEGIN
+            INSERT INTO file_pages_fts(file_pages_fts, rowid, text)
+            VALUES ('delete', old.rowid, old.text);
+        END;
+    ",
+        "Create file_pages_ad trigger",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TABLE IF NOT EXISTS file_annotations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_id TEXT NOT NULL,
+            page INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            author TEXT,
+            contents TEXT,
+            quoted_text TEXT,
+            modified_at TEXT,
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+        );
+    ",
+        "Create file_annotations table",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "CREATE INDEX IF NOT EXISTS idx_file_annotations_file ON file_annotations(file_id, page);",
+        "Create file_annotations index",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE VIRTUAL TABLE IF NOT EXISTS file_annotations_fts USING fts5(
+            contents,
+            quoted_text,
+            content='file_annotations',
+            content_rowid='id'
+        );
+    ",
+        "Create file_annotations_fts FTS5 table",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TRIGGER IF NOT EXISTS file_annotations_ai AFTER INSERT ON file_annotations BEGIN
+            INSERT INTO file_annotations_fts(rowid, contents, quoted_text)
+            VALUES (new.id, new.contents, new.quoted_text);
+        END;
+    ",
+        "Create file_annotations_ai trigger",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TRIGGER IF NOT EXISTS file_annotations_ad AFTER DELETE ON file_annotations BEGIN
+            INSERT INTO file_annotations_fts(file_annotations_fts, rowid, contents, quoted_text)
+            VALUES ('delete', old.id, old.contents, old.quoted_text);
+        END;
+    ",
+        "Create file_annotations_ad trigger",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TABLE IF NOT EXISTS file_identifiers (
+            file_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            value TEXT NOT NULL,
+            PRIMARY KEY (file_id, kind, value),
+            FOREIGN KEY (file_id) REFERENCES files(id) ON DELETE CASCADE
+        );
+    ",
+        "Create file_identifiers table",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "CREATE INDEX IF NOT EXISTS idx_file_identifiers_value ON file_identifiers(kind, value);",
+        "Create file_identifiers index",
+    )
+    .await?;
+
+    execute_sql(
+        &db,
+        "
+        CREATE TABLE IF NOT EXISTS enrichment_cache (
+            provider TEXT NOT NULL,
+            query_key TEXT NOT NULL,
+            response TEXT,
+            fetched_at TEXT NOT NULL,
+            PRIMARY KEY (provider, query_key)
+        );
+    ",
+        "Create enrichment_cache table",
+    )
+    .await?;
+
     execute_sql(
         &db,
         "
@@ -122,6 +122,7 @@ async fn extract_pdf_direct(
     let mut meta = ImportMetadata {
         title: String::new(),
         authors: Vec::new(),
+        contributors: Vec::new(),
         year: None,
         publisher: None,
         source: None,
@@ -134,6 +135,7 @@ async fn extract_pdf_direct(
         additional_info: HashMap::new(),
         file_metadata: None,
         type_metadata: None,
+        mime_type: None,
     };
 
     // 尝试打开并解析PDF文件